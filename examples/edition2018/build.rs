@@ -0,0 +1,8 @@
+use pyo3_bindgen::{unstable::Edition, Codegen, Config};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    Codegen::new(Config::builder().output_edition(Edition::E2018).build())
+        .module_names(["os", "posixpath", "sys"])?
+        .build(format!("{}/bindings.rs", std::env::var("OUT_DIR")?))?;
+    Ok(())
+}
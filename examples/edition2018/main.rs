@@ -0,0 +1,18 @@
+//! Example demonstrating the use of bindings generated via `Config::output_edition` in a crate
+//! that targets Rust 2018. The bindings are generated for the same modules as in the "os_sys" example.
+//!
+//! See `build.rs` for more details about the generation.
+
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+fn main() -> pyo3::PyResult<()> {
+    pyo3::Python::with_gil(|py| {
+        // Get the current working directory via "os" Python module
+        let current_dir = os::getcwd(py)?;
+        // Get the base name of the current working directory via "posixpath" Python module
+        let basename = posixpath::basename(py, current_dir)?;
+
+        println!("Name of the current working directory: '{}'", basename);
+        Ok(())
+    })
+}
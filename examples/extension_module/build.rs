@@ -0,0 +1,14 @@
+use pyo3_bindgen::Codegen;
+
+/// Python module that is embedded into the extension module and called from Rust via the generated bindings.
+const GREETINGS_PY: &str = r#"
+def greet(name: str) -> str:
+    return f"Hello, {name}!"
+"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    Codegen::default()
+        .module_from_str(GREETINGS_PY, "greetings")?
+        .build(format!("{}/bindings.rs", std::env::var("OUT_DIR")?))?;
+    Ok(())
+}
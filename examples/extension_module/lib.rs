@@ -0,0 +1,30 @@
+//! Example demonstrating the use of generated bindings inside a Python extension module (e.g. built via
+//! `maturin develop`), i.e. Rust code that is called from Python and itself calls back into Python.
+//!
+//! Python usage:
+//!
+//! ```py
+//! import pyo3_bindgen_extension
+//!
+//! assert pyo3_bindgen_extension.greet("World") == "Hello, World!"
+//! ```
+//!
+//! See `build.rs` for more details about the generation.
+
+use pyo3::prelude::*;
+
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+/// Greet the given name via the embedded "greetings" Python module.
+#[pyfunction]
+fn greet(py: Python<'_>, name: &str) -> PyResult<String> {
+    // The interpreter is owned by the host Python process, so the embedded module is not re-executed
+    // if it is already present in `sys.modules` (e.g. from a previous call)
+    greetings::pyo3_embed_python_source_code(py, false)?;
+    greetings::greet(py, name)
+}
+
+#[pymodule]
+fn pyo3_bindgen_extension(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(greet, m)?)
+}
@@ -22,6 +22,8 @@
 //! pyo3 = { version = "0.21", features = ["auto-initialize"] }
 //! ```
 //!
+//! > The generated bindings expect the Python interpreter to be initialized before they are used. Without the `auto-initialize` feature, call `pyo3::prepare_freethreaded_python()` manually or let the generated `pyo3_with_gil()` helpers take care of it via `Config::builder().init_strategy(InitStrategy::Prepare)`.
+//!
 //! Then, create a [`build.rs`](https://doc.rust-lang.org/cargo/reference/build-scripts.html) script in the root of your crate that generates bindings to the selected Python modules. In this example, the bindings are simultaneously generated for the "os", "posixpath", and "sys" Python modules. At the end of the generation process, the Rust bindings are written to `${OUT_DIR}/bindings.rs`.
 //!
 //! > With this approach, you can also customize the generation process via [`pyo3_bindgen::Config`](https://docs.rs/pyo3_bindgen/latest/pyo3_bindgen/struct.Config.html) that can be passed to `Codegen::new` constructor, e.g. `Codegen::new(Config::builder().include_private(true).build())`.
@@ -109,6 +111,33 @@ pub use pyo3;
 // Public API re-exports from engine
 pub use pyo3_bindgen_engine::{Codegen, Config, PyBindgenError, PyBindgenResult};
 
+// Experimental API re-exports from engine (prefer importing these via `unstable` module)
+#[doc(hidden)]
+pub use pyo3_bindgen_engine::{
+    AbstractPolicy, BodyMode, FloatType, GenerationReport, InitStrategy, IntType, NameSanitization,
+    SkippedItem,
+};
+
+/// Stable subset of the public API that follows semantic versioning.
+///
+/// The stable options of [`Config`] are `traverse_submodules`, `generate_preludes`, `generate_imports`,
+/// `generate_classes`, `generate_type_vars`, `generate_functions`, `generate_properties`, `generate_docs`,
+/// `blocklist_names`, `include_private`, `generate_dependencies`, `suppress_python_stdout` and
+/// `suppress_python_stderr`. All other options are considered experimental.
+pub mod prelude {
+    pub use pyo3_bindgen_engine::{Codegen, Config, PyBindgenError, PyBindgenResult};
+}
+
+/// Experimental part of the public API that might change between any releases.
+pub mod unstable {
+    pub use pyo3_bindgen_engine::{
+        AbstractMethodHandling, AbstractPolicy, BodyMode, CollisionPolicy, CollisionReport,
+        Edition, FloatType, GenerationMetrics, GenerationReport, GetterRenaming, InitStrategy,
+        IntType, ItemKind, ItemMetrics, LintAllowMode, ModuleMetrics, NameSanitization,
+        RenamedItem, SkippedItem, StringReturnType, VerifiedVersion, Warning, WarningKind,
+    };
+}
+
 // Public API re-exports from macros
 #[cfg(feature = "macros")]
 pub use pyo3_bindgen_macros::import_python;
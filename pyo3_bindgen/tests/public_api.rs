@@ -0,0 +1,98 @@
+//! Compile-time checks of the stable public API exposed via `pyo3_bindgen::prelude`.
+//!
+//! Any change that breaks these tests is a breaking change of the stable public API. The experimental options are
+//! only checked for their availability via `pyo3_bindgen::unstable` and the hidden root re-exports.
+
+use pyo3_bindgen::prelude::*;
+
+#[test]
+fn public_api_codegen() {
+    let _: fn(Config) -> Codegen = Codegen::new;
+    let _: fn(Codegen, &pyo3::Bound<pyo3::types::PyModule>) -> PyBindgenResult<Codegen> =
+        Codegen::module;
+    let _: fn(Codegen, &str) -> PyBindgenResult<Codegen> = Codegen::module_name;
+    let _: fn(Codegen, &str, &str) -> PyBindgenResult<Codegen> = Codegen::module_from_str;
+    let _: fn(
+        Codegen,
+        Vec<pyo3::Bound<'static, pyo3::types::PyModule>>,
+    ) -> PyBindgenResult<Codegen> = Codegen::modules;
+    let _: fn(Codegen, [&'static str; 2]) -> PyBindgenResult<Codegen> = Codegen::module_names;
+    let _: fn(Codegen) -> PyBindgenResult<_> = Codegen::generate;
+    let _: fn(Codegen, &'static str) -> PyBindgenResult<()> = Codegen::build;
+    let _: Codegen = Codegen::default();
+}
+
+#[test]
+fn public_api_config() {
+    // Arrange
+    let cfg = Config::builder()
+        .traverse_submodules(true)
+        .generate_preludes(true)
+        .generate_imports(true)
+        .generate_classes(true)
+        .generate_type_vars(true)
+        .generate_functions(true)
+        .generate_properties(true)
+        .generate_docs(true)
+        .blocklist_names(vec!["test".to_owned()])
+        .include_private(false)
+        .generate_dependencies(true)
+        .suppress_python_stdout(false)
+        .suppress_python_stderr(false)
+        .build();
+
+    // Act
+    let result = Codegen::new(cfg)
+        .module_from_str("answer = 42", "public_api_config")
+        .and_then(Codegen::generate);
+
+    // Assert
+    assert!(result.is_ok());
+    assert_eq!(Config::default(), Config::builder().build());
+}
+
+#[test]
+fn public_api_error() {
+    fn describe(err: &PyBindgenError) -> &'static str {
+        // The wildcard arm is required because the error is `#[non_exhaustive]`
+        #[allow(unreachable_patterns)]
+        match err {
+            PyBindgenError::IoError(_) => "io",
+            PyBindgenError::PyError(_) => "python",
+            PyBindgenError::PyDowncastError => "downcast",
+            PyBindgenError::SynError(_) => "syn",
+            PyBindgenError::ParseError(_) => "parse",
+            PyBindgenError::CodegenError(_) => "codegen",
+            _ => "other",
+        }
+    }
+
+    // Arrange
+    let err = PyBindgenError::CodegenError("test".to_owned());
+
+    // Act
+    let description = describe(&err);
+
+    // Assert
+    assert_eq!(description, "codegen");
+    let _: &dyn std::error::Error = &err;
+}
+
+#[test]
+fn public_api_unstable() {
+    // Arrange
+    let cfg = Config::builder()
+        .sanitization(pyo3_bindgen::unstable::NameSanitization::Error)
+        .abstract_functions(pyo3_bindgen::unstable::AbstractPolicy::Generate)
+        .build();
+
+    // Act
+    let result = Codegen::new(cfg)
+        .module_from_str("answer = 42", "public_api_unstable")
+        .and_then(Codegen::generate);
+
+    // Assert
+    assert!(result.is_ok());
+    let _: pyo3_bindgen::NameSanitization = pyo3_bindgen::unstable::NameSanitization::Warn;
+    let _: pyo3_bindgen::AbstractPolicy = pyo3_bindgen::unstable::AbstractPolicy::Skip;
+}
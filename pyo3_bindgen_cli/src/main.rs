@@ -7,22 +7,117 @@ fn main() {
     // Parse the CLI arguments
     let args = Args::parse();
 
-    // Generate the bindings for the module specified by the `--module-name` argument
-    let bindings = args
+    // Verify the versions of Python packages specified by the `--require` argument before importing any module
+    let codegen = args.require.iter().fold(
+        pyo3_bindgen::Codegen::default(),
+        |codegen, (package, spec)| {
+            codegen
+                .require_version(package, spec)
+                .unwrap_or_else(|err| panic!("Failed to verify the version of '{package}':\n{err}"))
+        },
+    );
+
+    // Import the modules specified by the `--module-name` argument (each module independently of the others)
+    let mut failures = Vec::new();
+    let codegen = args
         .module_names
         .iter()
-        .fold(pyo3_bindgen::Codegen::default(), |codegen, module_name| {
-            codegen.module_name(module_name).unwrap_or_else(|err| {
-                panic!("Failed to parse the content of '{module_name}' Python module:\n{err}")
-            })
-        })
-        .generate()
-        .unwrap_or_else(|err| panic!("Failed to generate bindings for Python modules:\n{err}"));
+        .fold(codegen, |codegen, module_name| {
+            match codegen.clone().module_name(module_name) {
+                Ok(codegen) => codegen,
+                Err(err) => {
+                    failures.push((module_name.clone(), err));
+                    codegen
+                }
+            }
+        });
+    let is_total_failure = failures.len() == args.module_names.len();
+    if is_total_failure || (args.strict && !failures.is_empty()) {
+        eprint!("{}", format_failures(&failures, args.module_names.len()));
+        std::process::exit(EXIT_FAILURE);
+    }
+
+    // Append the Rust code specified by the `--append` argument
+    let codegen = args
+        .append
+        .iter()
+        .fold(codegen, |codegen, (module_name, snippet_path)| {
+            let rust_code = std::fs::read_to_string(snippet_path)
+                .unwrap_or_else(|_| panic!("Failed to read from file: {}", snippet_path.display()));
+            codegen.append_to_module(module_name, &rust_code)
+        });
+
+    // Override the signatures specified by the `--overrides` argument
+    let codegen = args
+        .overrides
+        .iter()
+        .fold(codegen, |codegen, overrides_path| {
+            let overrides = std::fs::read_to_string(overrides_path).unwrap_or_else(|_| {
+                panic!("Failed to read from file: {}", overrides_path.display())
+            });
+            parse_overrides(&overrides)
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "Failed to parse the signature overrides in '{}':\n{err}",
+                        overrides_path.display()
+                    )
+                })
+                .iter()
+                .fold(codegen, |codegen, (path, signature)| {
+                    codegen.override_signature(path, signature)
+                })
+        });
+
+    // Export the schema of all classes if the `--schema` argument is provided
+    if let Some(schema_path) = &args.schema {
+        let schema = codegen.export_schema().unwrap_or_else(|err| {
+            exit_with_error("Failed to export the schema of Python modules", &err)
+        });
+        std::fs::write(schema_path, format!("{schema:#}\n"))
+            .unwrap_or_else(|_| panic!("Failed to write to file: {}", schema_path.display()));
+    }
+
+    // Generate the bindings incrementally if the `--output-dir` argument is provided
+    if let Some(output_dir) = args.output_dir {
+        codegen
+            .build_incremental(&output_dir, args.force)
+            .unwrap_or_else(|err| {
+                exit_with_error("Failed to generate bindings for Python modules", &err)
+            });
+        exit_with_failures(&failures, args.module_names.len(), args.allow_partial);
+    }
+
+    // Generate the bindings
+    let (bindings, report) = codegen.generate_with_report().unwrap_or_else(|err| {
+        exit_with_error("Failed to generate bindings for Python modules", &err)
+    });
+
+    // Print the size metrics of the bindings to STDERR if the `--stats` argument is provided
+    if args.stats {
+        eprint!("{}", format_stats(&report.metrics));
+    }
 
     // Format the bindings with prettyplease
-    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    let bindings = prettyplease::unparse(
+        &syn::parse2(bindings)
+            .map_err(|err| pyo3_bindgen::PyBindgenError::CodegenSyntaxError {
+                span_context: err.to_string(),
+            })
+            .unwrap_or_else(|err| panic!("Failed to format the generated bindings:\n{err}")),
+    );
 
-    if let Some(output) = args.output {
+    if let (true, Some(output)) = (args.check, &args.output) {
+        // Compare the bindings with the existing file if the `--check` argument is provided (without writing)
+        let existing = std::fs::read_to_string(output).unwrap_or_default();
+        if existing != bindings {
+            eprintln!(
+                "Bindings in '{}' are not up to date:\n{}",
+                output.display(),
+                diff_lines(&existing, &bindings)
+            );
+            std::process::exit(1);
+        }
+    } else if let Some(output) = args.output {
         // Write the bindings to a file if the `--output` argument is provided
         if let Some(parent) = output.parent() {
             std::fs::create_dir_all(parent).unwrap_or_else(|_| {
@@ -35,6 +130,63 @@ fn main() {
         // Otherwise, print the bindings to STDOUT
         std::io::stdout().write_all(bindings.as_bytes()).unwrap();
     }
+
+    exit_with_failures(&failures, args.module_names.len(), args.allow_partial);
+}
+
+/// Exit code if the bindings were generated for all modules
+const EXIT_SUCCESS: i32 = 0;
+/// Exit code if no bindings were generated (e.g. all modules failed, or any module failed with `--strict`)
+const EXIT_FAILURE: i32 = 1;
+/// Exit code if the bindings were generated only for the modules that did not fail
+const EXIT_PARTIAL: i32 = 2;
+
+/// Print the summary of the failed modules to STDERR (if any) and exit with the corresponding exit code
+fn exit_with_failures(
+    failures: &[(String, pyo3_bindgen::PyBindgenError)],
+    n_modules: usize,
+    allow_partial: bool,
+) -> ! {
+    if failures.is_empty() {
+        std::process::exit(EXIT_SUCCESS);
+    }
+    eprint!("{}", format_failures(failures, n_modules));
+    std::process::exit(if allow_partial {
+        EXIT_SUCCESS
+    } else {
+        EXIT_PARTIAL
+    });
+}
+
+/// Print the error that prevented the generation of any bindings to STDERR and exit with `EXIT_FAILURE`
+fn exit_with_error(context: &str, err: &pyo3_bindgen::PyBindgenError) -> ! {
+    eprintln!("{context}:\n{err}");
+    std::process::exit(EXIT_FAILURE);
+}
+
+/// Render the summary of the modules that failed to be parsed, including the chain of errors of each module
+fn format_failures(
+    failures: &[(String, pyo3_bindgen::PyBindgenError)],
+    n_modules: usize,
+) -> String {
+    let mut output = format!(
+        "Failed to parse {} of {n_modules} Python modules:\n",
+        failures.len()
+    );
+    for (module_name, err) in failures {
+        let message = err.to_string();
+        output.push_str(&format!("  - {module_name}: {message}\n"));
+        // Sources that are already part of the message are not repeated
+        let mut source = std::error::Error::source(err);
+        while let Some(err) = source {
+            let cause = err.to_string();
+            if !message.contains(&cause) {
+                output.push_str(&format!("      caused by: {cause}\n"));
+            }
+            source = err.source();
+        }
+    }
+    output
 }
 
 /// Arguments for the CLI tool
@@ -47,6 +199,177 @@ struct Args {
     #[arg(short, long)]
     /// Name of the output file to which to write the bindings [default: STDOUT]
     pub output: Option<std::path::PathBuf>,
+    #[arg(long, requires = "output")]
+    /// Verify that the existing output file matches the generated bindings instead of writing it (requires `--output`)
+    pub check: bool,
+    #[arg(long, conflicts_with = "output")]
+    /// Directory to which to write the bindings of each module incrementally (only changed modules are regenerated)
+    pub output_dir: Option<std::path::PathBuf>,
+    #[arg(long, requires = "output_dir")]
+    /// Regenerate the bindings of all modules, even if their inputs did not change (requires `--output-dir`)
+    pub force: bool,
+    #[arg(long, conflicts_with = "output_dir")]
+    /// Print a summary of the size of the generated bindings per module and their largest items to STDERR
+    pub stats: bool,
+    #[arg(long, conflicts_with = "allow_partial")]
+    /// Fail without generating any bindings if any of the Python modules fails to be parsed
+    pub strict: bool,
+    #[arg(long)]
+    /// Exit with code 0 if the bindings were generated only for the Python modules that did not fail to be parsed
+    /// [default: exit with code 2]
+    pub allow_partial: bool,
+    #[arg(long)]
+    /// Name of the file to which to write a JSON schema of all classes in the Python modules
+    pub schema: Option<std::path::PathBuf>,
+    #[arg(long, value_name = "MODULE=FILE", value_parser = parse_append, num_args=1..)]
+    /// Rust code to append to the bindings of a Python module, e.g. `os.path=snippet.rs`
+    pub append: Vec<(String, std::path::PathBuf)>,
+    #[arg(long, value_name = "REQUIREMENT", value_parser = parse_require, num_args=1..)]
+    /// Required version of a Python package that is verified before importing any module, e.g. `mypkg==1.2.3`
+    pub require: Vec<(String, String)>,
+    #[arg(long, value_name = "FILE", num_args=1..)]
+    /// TOML file that maps the Python paths of functions to their signatures, e.g. `"os.getcwd" = "() -> str"`
+    pub overrides: Vec<std::path::PathBuf>,
+}
+
+/// Parse a `MODULE=FILE` pair of the `--append` argument
+fn parse_append(input: &str) -> Result<(String, std::path::PathBuf), String> {
+    let (module_name, snippet_path) = input
+        .split_once('=')
+        .ok_or_else(|| format!("expected `MODULE=FILE`, but got `{input}`"))?;
+    Ok((module_name.to_owned(), snippet_path.into()))
+}
+
+/// Parse a `PACKAGE<SPEC>` requirement of the `--require` argument, e.g. `mypkg==1.2.3` or `mypkg>=1.2,<2`
+fn parse_require(input: &str) -> Result<(String, String), String> {
+    let (package, spec) = input
+        .find(['=', '<', '>', '!', '~'])
+        .map(|index| input.split_at(index))
+        .ok_or_else(|| format!("expected `PACKAGE==VERSION`, but got `{input}`"))?;
+    let package = package.trim();
+    if package.is_empty() {
+        return Err(format!("expected `PACKAGE==VERSION`, but got `{input}`"));
+    }
+    Ok((package.to_owned(), spec.trim().to_owned()))
+}
+
+/// Parse the signature overrides of the `--overrides` argument, whose (nested) keys are joined into Python paths
+fn parse_overrides(input: &str) -> Result<Vec<(String, String)>, String> {
+    fn flatten(
+        prefix: &str,
+        table: &toml::Table,
+        overrides: &mut Vec<(String, String)>,
+    ) -> Result<(), String> {
+        for (key, value) in table {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match value {
+                toml::Value::String(signature) => overrides.push((path, signature.clone())),
+                toml::Value::Table(table) => flatten(&path, table, overrides)?,
+                _ => return Err(format!("expected a signature string for `{path}`")),
+            }
+        }
+        Ok(())
+    }
+
+    let table = input
+        .parse::<toml::Table>()
+        .map_err(|err| err.to_string())?;
+    let mut overrides = Vec::new();
+    flatten("", &table, &mut overrides)?;
+    Ok(overrides)
+}
+
+/// Number of the largest items that are listed by the `--stats` argument
+const N_LARGEST_ITEMS: usize = 10;
+
+/// Render the size metrics of the generated bindings as a table of all modules followed by the largest items
+fn format_stats(metrics: &pyo3_bindgen::unstable::GenerationMetrics) -> String {
+    let width = metrics
+        .modules
+        .iter()
+        .map(|module| module.path.len())
+        .chain(["module".len(), "total".len()])
+        .max()
+        .unwrap_or_default();
+    let row = |name: &str, counts: [usize; 5]| {
+        let [n_classes, n_functions, n_methods, n_properties, n_tokens] = counts;
+        format!("{name:<width$}  {n_classes:>7}  {n_functions:>9}  {n_methods:>7}  {n_properties:>10}  {n_tokens:>8}\n")
+    };
+    let mut output = format!(
+        "{:<width$}  {:>7}  {:>9}  {:>7}  {:>10}  {:>8}\n",
+        "module", "classes", "functions", "methods", "properties", "tokens"
+    );
+    for module in &metrics.modules {
+        output.push_str(&row(
+            &module.path,
+            [
+                module.n_classes,
+                module.n_functions,
+                module.n_methods,
+                module.n_properties,
+                module.n_tokens,
+            ],
+        ));
+    }
+    output.push_str(&row(
+        "total",
+        [
+            metrics.n_classes(),
+            metrics.n_functions(),
+            metrics.n_methods(),
+            metrics.n_properties(),
+            metrics.n_tokens(),
+        ],
+    ));
+    let largest_items = metrics.largest_items(N_LARGEST_ITEMS);
+    if !largest_items.is_empty() {
+        output.push_str(&format!(
+            "\nlargest items:\n{:>8}  {:<8}  path\n",
+            "tokens", "kind"
+        ));
+        for item in largest_items {
+            output.push_str(&format!(
+                "{:>8}  {:<8}  {}\n",
+                item.n_tokens, item.kind, item.path
+            ));
+        }
+    }
+    output
+}
+
+/// Render the lines that differ between the `existing` and `generated` text (excluding their common prefix and
+/// suffix) in the format of a unified diff
+fn diff_lines(existing: &str, generated: &str) -> String {
+    let existing: Vec<&str> = existing.lines().collect();
+    let generated: Vec<&str> = generated.lines().collect();
+    let n_prefix = existing
+        .iter()
+        .zip(&generated)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let n_suffix = existing[n_prefix..]
+        .iter()
+        .rev()
+        .zip(generated[n_prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let removed = &existing[n_prefix..existing.len() - n_suffix];
+    let added = &generated[n_prefix..generated.len() - n_suffix];
+    std::iter::once(format!(
+        "@@ -{},{} +{},{} @@",
+        n_prefix + 1,
+        removed.len(),
+        n_prefix + 1,
+        added.len()
+    ))
+    .chain(removed.iter().map(|line| format!("-{line}")))
+    .chain(added.iter().map(|line| format!("+{line}")))
+    .collect::<Vec<_>>()
+    .join("\n")
 }
 
 #[cfg(test)]
@@ -66,6 +389,288 @@ mod tests {
         assert_eq!(args.output, Some("bindings.rs".into()));
     }
 
+    #[test]
+    fn test_parser_check() {
+        // Arrange
+        let input = ["", "-m", "os", "--output", "bindings.rs", "--check"];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["os"]);
+        assert_eq!(args.output, Some("bindings.rs".into()));
+        assert!(args.check);
+    }
+
+    #[test]
+    fn test_parser_check_without_output() {
+        // Arrange
+        let input = ["", "-m", "os", "--check"];
+
+        // Act
+        let result = Args::try_parse_from(input);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        // Arrange
+        let existing = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let generated = "fn a() {}\nfn b2() {}\nfn c() {}\n";
+
+        // Act
+        let diff = diff_lines(existing, generated);
+
+        // Assert
+        assert_eq!(diff, "@@ -2,1 +2,1 @@\n-fn b() {}\n+fn b2() {}");
+    }
+
+    #[test]
+    fn test_parser_partial() {
+        // Arrange
+        let input_strict = ["", "-m", "os", "sys", "--strict"];
+        let input_allow_partial = ["", "-m", "os", "sys", "--allow-partial"];
+        let input_both = ["", "-m", "os", "--strict", "--allow-partial"];
+
+        // Act
+        let args_strict = Args::parse_from(input_strict);
+        let args_allow_partial = Args::parse_from(input_allow_partial);
+        let result_both = Args::try_parse_from(input_both);
+
+        // Assert
+        assert!(args_strict.strict && !args_strict.allow_partial);
+        assert!(args_allow_partial.allow_partial && !args_allow_partial.strict);
+        assert!(result_both.is_err());
+    }
+
+    #[test]
+    fn test_format_failures() {
+        // Arrange
+        let failures = [(
+            "mod_a".to_owned(),
+            pyo3_bindgen::PyBindgenError::AttributeParseFailed {
+                path: "mod_a.f".to_owned(),
+                source: Box::new(pyo3_bindgen::PyBindgenError::ParseError(
+                    "invalid signature".to_owned(),
+                )),
+            },
+        )];
+
+        // Act
+        let summary = format_failures(&failures, 3);
+
+        // Assert
+        assert_eq!(
+            summary,
+            [
+                "Failed to parse 1 of 3 Python modules:",
+                "  - mod_a: Failed to parse Python attribute 'mod_a.f': Failed to parse Python code: invalid signature",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_parser_stats() {
+        // Arrange
+        let input = ["", "-m", "os", "--stats"];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert!(args.stats);
+    }
+
+    #[test]
+    fn test_format_stats() {
+        // Arrange
+        let metrics = pyo3_bindgen::unstable::GenerationMetrics {
+            modules: vec![pyo3_bindgen::unstable::ModuleMetrics {
+                path: "mod_a".to_owned(),
+                n_classes: 1,
+                n_functions: 2,
+                n_methods: 3,
+                n_properties: 4,
+                n_tokens: 500,
+            }],
+            items: vec![pyo3_bindgen::unstable::ItemMetrics {
+                path: "mod_a.MyClass".to_owned(),
+                kind: pyo3_bindgen::unstable::ItemKind::Class,
+                n_tokens: 300,
+            }],
+        };
+
+        // Act
+        let stats = format_stats(&metrics);
+
+        // Assert
+        assert_eq!(
+            stats,
+            [
+                "module  classes  functions  methods  properties    tokens",
+                "mod_a         1          2        3           4       500",
+                "total         1          2        3           4       500",
+                "",
+                "largest items:",
+                "  tokens  kind      path",
+                "     300  class     mod_a.MyClass",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_parser_incremental() {
+        // Arrange
+        let input = ["", "-m", "os", "--output-dir", "bindings", "--force"];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["os"]);
+        assert_eq!(args.output_dir, Some("bindings".into()));
+        assert!(args.force);
+    }
+
+    #[test]
+    fn test_parser_schema() {
+        // Arrange
+        let input = ["", "-m", "os", "--schema", "schema.json"];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["os"]);
+        assert_eq!(args.schema, Some("schema.json".into()));
+    }
+
+    #[test]
+    fn test_parser_append() {
+        // Arrange
+        let input = [
+            "",
+            "-m",
+            "os",
+            "--append",
+            "os=os.rs",
+            "os.path=snippets/path.rs",
+        ];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["os"]);
+        assert_eq!(
+            args.append,
+            [
+                ("os".to_owned(), "os.rs".into()),
+                ("os.path".to_owned(), "snippets/path.rs".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_append_invalid() {
+        // Arrange
+        let input = ["", "-m", "os", "--append", "os.rs"];
+
+        // Act
+        let result = Args::try_parse_from(input);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_require() {
+        // Arrange
+        let input = [
+            "",
+            "--require",
+            "mypkg==1.2.3",
+            "other>=1.0,<2",
+            "-m",
+            "mypkg",
+        ];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["mypkg"]);
+        assert_eq!(
+            args.require,
+            [
+                ("mypkg".to_owned(), "==1.2.3".to_owned()),
+                ("other".to_owned(), ">=1.0,<2".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_require_invalid() {
+        // Arrange
+        let input = ["", "-m", "mypkg", "--require", "mypkg"];
+
+        // Act
+        let result = Args::try_parse_from(input);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parser_overrides() {
+        // Arrange
+        let input = ["", "-m", "zlib", "--overrides", "overrides.toml"];
+
+        // Act
+        let args = Args::parse_from(input);
+
+        // Assert
+        assert_eq!(args.module_names, ["zlib"]);
+        assert_eq!(args.overrides, [std::path::PathBuf::from("overrides.toml")]);
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        // Arrange
+        let input = r#"
+            "zlib.compress" = "(data: bytes, /, level: int = -1) -> bytes"
+
+            [zlib.Compress]
+            flush = "(self, mode: int = 4) -> bytes"
+        "#;
+
+        // Act
+        let overrides = parse_overrides(input).unwrap();
+
+        // Assert
+        assert_eq!(
+            overrides,
+            [
+                (
+                    "zlib.Compress.flush".to_owned(),
+                    "(self, mode: int = 4) -> bytes".to_owned()
+                ),
+                (
+                    "zlib.compress".to_owned(),
+                    "(data: bytes, /, level: int = -1) -> bytes".to_owned()
+                ),
+            ]
+        );
+        assert!(parse_overrides("zlib.compress = 1").is_err());
+    }
+
     #[test]
     fn test_parser_short() {
         // Arrange
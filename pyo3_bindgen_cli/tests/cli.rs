@@ -61,4 +61,150 @@ mod test_cli {
         // Assert
         assert.success();
     }
+
+    #[test]
+    fn test_cli_check() {
+        // Arrange
+        let output =
+            std::env::temp_dir().join(format!("{BIN_NAME}_check_{}.rs", std::process::id()));
+        Command::cargo_bin(BIN_NAME)
+            .unwrap()
+            .arg("-m")
+            .arg("os")
+            .arg("-o")
+            .arg(&output)
+            .assert()
+            .success();
+
+        // Act
+        let assert_up_to_date = Command::cargo_bin(BIN_NAME)
+            .unwrap()
+            .arg("-m")
+            .arg("os")
+            .arg("-o")
+            .arg(&output)
+            .arg("--check")
+            .assert();
+        let mut stale = std::fs::read_to_string(&output).unwrap();
+        stale.push_str("// stale\n");
+        std::fs::write(&output, &stale).unwrap();
+        let assert_stale = Command::cargo_bin(BIN_NAME)
+            .unwrap()
+            .arg("-m")
+            .arg("os")
+            .arg("-o")
+            .arg(&output)
+            .arg("--check")
+            .assert();
+        let unchanged = std::fs::read_to_string(&output).unwrap();
+        std::fs::remove_file(&output).unwrap();
+
+        // Assert
+        assert_up_to_date.success();
+        assert_stale
+            .failure()
+            .stderr(predicate::str::contains("-// stale"));
+        assert_eq!(unchanged, stale);
+    }
+
+    #[test]
+    fn test_cli_partial_failure() {
+        // Arrange
+        let output =
+            std::env::temp_dir().join(format!("{BIN_NAME}_partial_{}.rs", std::process::id()));
+        let run = |extra_args: &[&str]| {
+            let _ = std::fs::remove_file(&output);
+            let assert = Command::cargo_bin(BIN_NAME)
+                .unwrap()
+                .arg("-m")
+                .arg("os")
+                .arg("pyo3_bindgen_nonexistent_module")
+                .arg("-o")
+                .arg(&output)
+                .args(extra_args)
+                .assert();
+            (assert, std::fs::read_to_string(&output).ok())
+        };
+
+        // Act
+        let (assert_partial, output_partial) = run(&[]);
+        let (assert_allow_partial, output_allow_partial) = run(&["--allow-partial"]);
+        let (assert_strict, output_strict) = run(&["--strict"]);
+        let _ = std::fs::remove_file(&output);
+
+        // Assert
+        assert_partial.code(2).stderr(
+            predicate::str::contains("Failed to parse 1 of 2 Python modules:").and(
+                predicate::str::contains(
+                    "  - pyo3_bindgen_nonexistent_module: Failed to import Python module",
+                ),
+            ),
+        );
+        assert!(output_partial.is_some_and(|bindings| bindings.contains("pub mod os")));
+        assert_allow_partial
+            .success()
+            .stderr(predicate::str::contains("pyo3_bindgen_nonexistent_module"));
+        assert!(output_allow_partial.is_some());
+        assert_strict
+            .code(1)
+            .stderr(predicate::str::contains("pyo3_bindgen_nonexistent_module"));
+        assert!(output_strict.is_none());
+    }
+
+    #[test]
+    fn test_cli_total_failure() {
+        // Arrange
+        let mut cmd = Command::cargo_bin(BIN_NAME).unwrap();
+
+        // Act
+        let assert = cmd
+            .arg("-m")
+            .arg("pyo3_bindgen_nonexistent_module")
+            .arg("--allow-partial")
+            .assert();
+
+        // Assert
+        assert.code(1).stdout("").stderr(predicate::str::contains(
+            "Failed to parse 1 of 1 Python modules:",
+        ));
+    }
+
+    #[test]
+    fn test_cli_generation_failure() {
+        // Arrange
+        let overrides =
+            std::env::temp_dir().join(format!("{BIN_NAME}_overrides_{}.toml", std::process::id()));
+        let output_dir =
+            std::env::temp_dir().join(format!("{BIN_NAME}_output_dir_{}", std::process::id()));
+        std::fs::write(
+            &overrides,
+            "\"os.pyo3_bindgen_nonexistent_function\" = \"() -> int\"\n",
+        )
+        .unwrap();
+        let run = |extra_args: &[&std::ffi::OsStr]| {
+            Command::cargo_bin(BIN_NAME)
+                .unwrap()
+                .arg("-m")
+                .arg("os")
+                .arg("--overrides")
+                .arg(&overrides)
+                .args(extra_args)
+                .assert()
+        };
+
+        // Act
+        let assert_generate = run(&[]);
+        let assert_incremental = run(&["--output-dir".as_ref(), output_dir.as_os_str()]);
+        std::fs::remove_file(&overrides).unwrap();
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        // Assert
+        for assert in [assert_generate, assert_incremental] {
+            assert.code(1).stdout("").stderr(
+                predicate::str::contains("Failed to generate bindings for Python modules:").and(
+                    predicate::str::contains("os.pyo3_bindgen_nonexistent_function"),
+                ),
+            );
+        }
+    }
 }
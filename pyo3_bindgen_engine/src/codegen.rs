@@ -1,6 +1,8 @@
 use crate::{
-    syntax::{Ident, Import, Module, Path},
-    Config, PyBindgenError, Result,
+    syntax::{Class, Function, Ident, Import, Module, Path, Property},
+    typing::Type,
+    utils::report::ReportSink,
+    Config, GenerationReport, PyBindgenError, Result, VerifiedVersion, Warning,
 };
 use itertools::Itertools;
 use pyo3::prelude::*;
@@ -45,8 +47,25 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 pub struct Codegen {
     cfg: Config,
     modules: Vec<Module>,
+    /// Python modules added by [`Self::module()`], which are parsed only once the bindings are generated, so that
+    /// [`Self::build_incremental()`] can skip the parsing of modules that did not change.
+    imported_modules: Vec<pyo3::Py<pyo3::types::PyModule>>,
     /// Python source code included by [`Self::module_from_str()`] in the generated Rust bindings.
     embedded_source_code: HashMap<String, String>,
+    /// Sink of the skipped items, warnings and metrics recorded while parsing the modules and generating their
+    /// bindings, which are included in the [`GenerationReport`].
+    sink: ReportSink,
+    /// Raw Rust code appended by [`Self::append_to_module()`] to the bindings of modules.
+    appended_module_code: Vec<(Path, String)>,
+    /// Raw Rust code appended by [`Self::append_to_class_impl()`] to the bindings of classes.
+    appended_class_code: Vec<(Path, String)>,
+    /// Signatures in Python syntax set by [`Self::override_signature()`] for functions and methods.
+    signature_overrides: Vec<(Path, String)>,
+    /// Versions of Python packages verified by [`Self::require_version()`], which are included in the
+    /// [`GenerationReport`].
+    verified_versions: Vec<VerifiedVersion>,
+    /// Flag that indicates whether the modules were already parsed and prepared for generating their bindings.
+    is_prepared: bool,
 }
 
 impl Codegen {
@@ -60,16 +79,33 @@ impl Codegen {
     }
 
     /// Add a Python module to the list of modules for which to generate bindings.
+    ///
+    /// # Note
+    ///
+    /// The module is parsed only once the bindings are generated, so errors that occur while parsing its content
+    /// are reported at that point.
     pub fn module(mut self, module: &pyo3::Bound<pyo3::types::PyModule>) -> Result<Self> {
-        crate::io_utils::with_suppressed_python_output(
-            module.py(),
-            self.cfg.suppress_python_stdout,
-            self.cfg.suppress_python_stderr,
-            || {
-                self.modules.push(Module::parse(&self.cfg, module)?);
-                Ok(())
-            },
-        )?;
+        self.imported_modules.push(module.clone().unbind());
+        Ok(self)
+    }
+
+    /// Require the installed version of a Python package to satisfy the given version specifier (e.g. `==1.2.3`
+    /// or `>=1.2,<2`), so that the bindings are not generated against whatever version happens to be installed.
+    ///
+    /// The version is verified immediately, i.e. before any subsequently added module is imported, and it fails
+    /// with [`PyBindgenError::VersionMismatch`] if the requirement is not satisfied. Version specifiers are
+    /// evaluated via the `packaging` Python package if it is available, otherwise only exact versions are
+    /// supported.
+    pub fn require_version(mut self, package: &str, spec: &str) -> Result<Self> {
+        #[cfg(not(PyPy))]
+        pyo3::prepare_freethreaded_python();
+        let found =
+            pyo3::Python::with_gil(|py| crate::utils::version::verify_version(py, package, spec))?;
+        self.verified_versions.push(VerifiedVersion {
+            package: package.to_owned(),
+            required: spec.to_owned(),
+            found,
+        });
         Ok(self)
     }
 
@@ -78,7 +114,12 @@ impl Codegen {
         #[cfg(not(PyPy))]
         pyo3::prepare_freethreaded_python();
         pyo3::Python::with_gil(|py| {
-            let module = py.import_bound(module_name)?;
+            let module = py.import_bound(module_name).map_err(|source| {
+                PyBindgenError::ModuleImportFailed {
+                    module: module_name.to_owned(),
+                    source,
+                }
+            })?;
             self.module(&module)
         })
     }
@@ -90,9 +131,10 @@ impl Codegen {
     /// When including a module in this way, the Python source code must be available also during runtime for
     /// the underlying Python interpreter.
     ///
-    /// For convenience, you can call `module_name::pyo3_embed_python_source_code()` that is automatically
+    /// For convenience, you can call `module_name::pyo3_embed_python_source_code(py, false)` that is automatically
     /// generated in the Rust bindings. This function must be called before attempting to use any functions
-    /// of classes from the module.
+    /// of classes from the module. It is idempotent and keeps an existing module of the same name in
+    /// `sys.modules`, unless its `force` parameter is set.
     pub fn module_from_str(mut self, source_code: &str, module_name: &str) -> Result<Self> {
         self.embedded_source_code
             .insert(module_name.to_owned(), source_code.to_owned());
@@ -109,13 +151,46 @@ impl Codegen {
         })
     }
 
+    /// Add a Python module from its abstract syntax tree (`ast.Module`) and name to the list of modules for which
+    /// to generate bindings. The module is never imported, so its top-level code is not executed.
+    ///
+    /// # Note
+    ///
+    /// Only the statements at the top level of the module body are considered, so the bindings might be less
+    /// complete than those of an imported module (e.g. inherited members, conditional definitions and wildcard
+    /// imports are not included). The parent packages of a dotted `module_name` are still imported in order to
+    /// extract their documentation.
+    pub fn module_from_ast(
+        mut self,
+        ast: &pyo3::Bound<pyo3::types::PyAny>,
+        module_name: &str,
+    ) -> Result<Self> {
+        let py = ast.py();
+        if !ast.is_instance(
+            &py.import_bound(pyo3::intern!(py, "ast"))?
+                .getattr(pyo3::intern!(py, "Module"))?,
+        )? {
+            return Err(PyBindgenError::ParseError(format!(
+                "Expected an `ast.Module` node for module '{module_name}', but got '{}'",
+                ast.get_type()
+            )));
+        }
+        self.modules.push(Module::from_ast(
+            &self.cfg,
+            &self.sink,
+            ast,
+            Path::from_py(module_name),
+        )?);
+        Ok(self)
+    }
+
     /// Add multiple Python modules to the list of modules for which to generate bindings.
     pub fn modules<'py>(
         mut self,
         modules: impl AsRef<[pyo3::Bound<'py, pyo3::types::PyModule>]>,
     ) -> Result<Self> {
         let modules = modules.as_ref();
-        self.modules.reserve(modules.len());
+        self.imported_modules.reserve(modules.len());
         for module in modules {
             self = self.module(module)?;
         }
@@ -125,15 +200,416 @@ impl Codegen {
     /// Add multiple Python modules by their names to the list of modules for which to generate bindings.
     pub fn module_names<'a>(mut self, module_names: impl AsRef<[&'a str]>) -> Result<Self> {
         let module_names = module_names.as_ref();
-        self.modules.reserve(module_names.len());
+        self.imported_modules.reserve(module_names.len());
         for module_name in module_names {
             self = self.module_name(module_name)?;
         }
         Ok(self)
     }
 
+    /// Append raw Rust code to the body of the generated module with the given Python path, e.g. a free function
+    /// or a trait implementation. The code can refer to the generated items via the same relative paths as the
+    /// generated bindings (e.g. `super::...`).
+    ///
+    /// # Note
+    ///
+    /// The code is parsed when the bindings are generated, which fails if it is not valid Rust or if the module
+    /// is not part of the bindings.
+    #[must_use]
+    pub fn append_to_module(mut self, py_path: &str, rust_code: &str) -> Self {
+        self.appended_module_code
+            .push((Path::from_py(py_path), rust_code.to_owned()));
+        self
+    }
+
+    /// Append raw Rust code to the implementation block of the generated struct for the class with the given
+    /// Python path, e.g. an associated function or a method.
+    ///
+    /// # Note
+    ///
+    /// The code is parsed when the bindings are generated, which fails if it is not valid Rust or if the class
+    /// is not part of the bindings.
+    #[must_use]
+    pub fn append_to_class_impl(mut self, py_path: &str, rust_code: &str) -> Self {
+        self.appended_class_code
+            .push((Path::from_py(py_path), rust_code.to_owned()));
+        self
+    }
+
+    /// Override the signature of the function or method with the given Python path by a signature in Python
+    /// syntax, e.g. `(data: bytes, *, level: int = 6) -> bytes`. This is useful if the introspected signature is
+    /// wrong or missing, e.g. for functions of C extensions. The first parameter of methods that are bound to the
+    /// instance or class (`self` or `cls`) can be omitted.
+    ///
+    /// # Note
+    ///
+    /// The signature is parsed when the bindings are generated, which fails if it is not valid or if the function
+    /// is not part of the bindings.
+    #[must_use]
+    pub fn override_signature(mut self, py_path: &str, signature: &str) -> Self {
+        self.signature_overrides
+            .push((Path::from_py(py_path), signature.to_owned()));
+        self
+    }
+
+    /// Parse the modules added to the engine (if not done yet) and get the non-fatal issues encountered so far,
+    /// e.g. skipped members and types that cannot be mapped to Rust types.
+    ///
+    /// # Note
+    ///
+    /// The modules are parsed in place, so the bindings that are generated afterwards reuse them. Modules that are
+    /// added afterwards are not parsed anymore. Issues that are encountered only while generating the bindings
+    /// (e.g. names that collide with other items) are part of the [`GenerationReport`] returned by
+    /// [`Self::generate_with_report()`], which includes all issues.
+    pub fn warnings(&mut self) -> Result<Vec<Warning>> {
+        self.prepare()?;
+        Ok(self.sink.warnings())
+    }
+
     /// Generate the Rust FFI bindings for all modules added to the engine.
-    pub fn generate(mut self) -> Result<proc_macro2::TokenStream> {
+    pub fn generate(self) -> Result<proc_macro2::TokenStream> {
+        self.generate_with_report()
+            .map(|(bindings, _report)| bindings)
+    }
+
+    /// Generate the Rust FFI bindings for all modules added to the engine as a typed syntax tree, which is suitable
+    /// for programmatic post-processing (e.g. adding attributes or adjusting the visibility of items).
+    pub fn generate_ast(self) -> Result<syn::File> {
+        let code = self.generate()?.to_string();
+        syn::parse_file(&code).map_err(|err| PyBindgenError::codegen_syntax_error(&code, &err))
+    }
+
+    /// Generate the Rust FFI bindings for all modules added to the engine after applying the given transformation
+    /// to their syntax tree (see `generate_ast`).
+    pub fn generate_with(
+        self,
+        transform: impl FnOnce(&mut syn::File),
+    ) -> Result<proc_macro2::TokenStream> {
+        let mut file = self.generate_ast()?;
+        transform(&mut file);
+        Ok(quote::ToTokens::into_token_stream(file))
+    }
+
+    /// Generate the Rust FFI bindings for all modules added to the engine alongside a report
+    /// that summarizes the generated bindings, skipped items and types that could not be mapped.
+    pub fn generate_with_report(mut self) -> Result<(proc_macro2::TokenStream, GenerationReport)> {
+        self.prepare()?;
+
+        // Generate the bindings for all modules
+        let all_types = self.get_all_types();
+        let bindings = self
+            .modules
+            .iter()
+            .map(|module| module.generate(&self.cfg, &self.sink, &self.modules, &all_types))
+            .collect::<Result<proc_macro2::TokenStream>>()
+            .and_then(|mut bindings| {
+                // Generate the helpers for typed access to the returned dictionaries of all modules (if needed)
+                if (self.cfg.generate_functions || self.cfg.generate_classes)
+                    && self.cfg.dict_key_accessors
+                    && self.modules.iter().any(Module::returns_str_keyed_dict)
+                {
+                    bindings.extend(Function::generate_dict_key_accessors(&self.cfg));
+                }
+                // Generate the prelude that combines all modules (if enabled)
+                if self.cfg.generate_root_prelude {
+                    bindings.extend(self.generate_root_prelude()?);
+                }
+                Ok(bindings)
+            })?;
+
+        // Summarize the generated bindings
+        let mut report = GenerationReport::new(&self.modules, &all_types, self.sink);
+        report.verified_versions = self.verified_versions;
+
+        Ok((bindings, report))
+    }
+
+    /// Export the structure of all classes within the modules added to the engine as a JSON schema-like
+    /// object, which can be consumed by tooling for other languages. Each property, parameter and return
+    /// value contains its Python annotation alongside a normalized type tag (e.g. `integer`, `array<string>`
+    /// or `ref:module.Class`) and a `nullable` flag for `Optional` annotations.
+    pub fn export_schema(&self) -> Result<serde_json::Value> {
+        let mut codegen = self.clone();
+        codegen.prepare()?;
+        Ok(serde_json::json!({
+            "modules": codegen.modules.iter().map(Module::export_schema).collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Generate the Rust FFI bindings for all modules added to the engine and write them to the given file.
+    /// This is a convenience method that combines `generate` and `std::fs::write`.
+    pub fn build(self, output_path: impl AsRef<std::path::Path>) -> Result<()> {
+        Ok(std::fs::write(output_path, self.generate()?.to_string())?)
+    }
+
+    /// Generate the Rust FFI bindings incrementally and write each top-level module into a separate
+    /// file `<module_name>.rs` within the given output directory.
+    ///
+    /// A state file with the fingerprints of the configuration, of the source files and of the parsed content
+    /// of each module is stored alongside the bindings. On subsequent runs, modules whose source files did not
+    /// change (including the source files of all modules that they transitively depend on) are neither parsed
+    /// nor regenerated. Of the remaining modules, only those whose parsed content changed (or that transitively
+    /// depend on such modules) are regenerated, so changes that do not affect the bindings (e.g. to function
+    /// bodies) do not cause regeneration, while changes of modules without source files (e.g. built-in modules)
+    /// are still detected. The files of modules that are no longer part of the engine are removed.
+    /// All modules are regenerated if `force` is enabled or if the state file is missing or corrupt.
+    ///
+    /// # Note
+    ///
+    /// All modules are parsed if [`Config::generate_dependencies`] or [`Config::collect_all_types_from_stubs`]
+    /// is enabled, as their bindings then depend on the content of all other modules. With
+    /// [`Config::fuzzy_type_resolution`], skipped modules that define or reference types of the same name as
+    /// the parsed modules are parsed as well. Changes of other Python packages that the modules merely import are not detected, which
+    /// requires `force` to be enabled.
+    pub fn build_incremental(
+        mut self,
+        output_dir: impl AsRef<std::path::Path>,
+        force: bool,
+    ) -> Result<()> {
+        use crate::utils::incremental::{
+            combine_hashes, defined_type_names, hash_config, hash_module_content,
+            hash_module_source, module_dependencies, referenced_type_names, write_file,
+            IncrementalState, ModuleState, STATE_FILE_NAME,
+        };
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let output_dir = output_dir.as_ref();
+        std::fs::create_dir_all(output_dir)?;
+        let state_path = output_dir.join(STATE_FILE_NAME);
+        let output_path =
+            |name: &str| output_dir.join(format!("{}.rs", Path::from_py(name).to_rs()));
+
+        // Compare with the previous state (if available and applicable)
+        let config_hash = hash_config(&self.cfg);
+        let loaded_state = IncrementalState::load(&state_path);
+        let previous_state = loaded_state
+            .clone()
+            .filter(|previous_state| !force && previous_state.config_hash == config_hash)
+            .unwrap_or_default();
+
+        // Determine the fingerprints of the source files of all top-level modules before parsing them
+        let mut input_hashes = BTreeMap::<String, Vec<Option<u64>>>::new();
+        for module in &self.modules {
+            let root = module
+                .name
+                .root()
+                .map(|root| root.to_py())
+                .unwrap_or_default();
+            input_hashes.entry(root).or_default().push(None);
+        }
+        let mut imported_modules = pyo3::Python::with_gil(|py| {
+            std::mem::take(&mut self.imported_modules)
+                .into_iter()
+                .map(|module| {
+                    let name = Path::from_py(&module.bind(py).name()?.to_string());
+                    let root = name.root().map(|root| root.to_py()).unwrap_or_default();
+                    input_hashes
+                        .entry(root.clone())
+                        .or_default()
+                        .push(hash_module_source(
+                            module.bind(py),
+                            self.embedded_source_code
+                                .get(&name.to_py())
+                                .map(String::as_str),
+                        ));
+                    Ok((root, module))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let source_hashes = input_hashes
+            .into_iter()
+            .map(|(root, hashes)| (root, combine_hashes(hashes)))
+            .collect::<BTreeMap<_, _>>();
+
+        // Skip modules whose source files (and those of all their transitive dependencies) did not change
+        let is_source_unchanged = |name: &str| {
+            previous_state.modules.get(name).is_some_and(|previous| {
+                previous.source_hash.is_some()
+                    && previous.source_hash == source_hashes.get(name).copied().flatten()
+            }) && output_path(name).exists()
+        };
+        let mut skipped_modules =
+            if self.cfg.generate_dependencies || self.cfg.collect_all_types_from_stubs {
+                BTreeSet::new()
+            } else {
+                source_hashes
+                    .keys()
+                    .filter(|name| {
+                        is_source_unchanged(name)
+                            && previous_state
+                                .dependency_closure(name)
+                                .iter()
+                                .all(|dependency| is_source_unchanged(dependency))
+                    })
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+            };
+
+        // Parse all other modules alongside the skipped modules that are relevant for their bindings
+        let top_level_names = source_hashes.keys().cloned().collect::<Vec<_>>();
+        let mut changed_types = None;
+        loop {
+            let (modules_to_parse, remaining_modules) = imported_modules
+                .into_iter()
+                .partition::<Vec<_>, _>(|(root, _)| !skipped_modules.contains(root));
+            imported_modules = remaining_modules;
+            self.parse_modules(modules_to_parse.into_iter().map(|(_, module)| module))?;
+
+            // Types that are referenced only by their name might be resolved to types of any other module
+            let changed_types: &BTreeSet<String> = changed_types.get_or_insert_with(|| {
+                self.modules
+                    .iter()
+                    .flat_map(defined_type_names)
+                    .chain(
+                        source_hashes
+                            .keys()
+                            .filter(|name| !skipped_modules.contains(*name))
+                            .filter_map(|name| previous_state.modules.get(name))
+                            .flat_map(|previous| previous.defined_types.iter().cloned()),
+                    )
+                    .collect()
+            });
+            let dependencies = self
+                .modules
+                .iter()
+                .flat_map(|module| module_dependencies(module, &top_level_names))
+                .collect::<BTreeSet<_>>();
+            let referenced_types = self
+                .modules
+                .iter()
+                .flat_map(referenced_type_names)
+                .collect::<BTreeSet<_>>();
+            let relevant_modules = skipped_modules
+                .iter()
+                .filter(|name| {
+                    let previous = &previous_state.modules[*name];
+                    dependencies.contains(*name)
+                        || (self.cfg.fuzzy_type_resolution
+                            && (!previous.defined_types.is_disjoint(&referenced_types)
+                                || !previous.referenced_types.is_disjoint(changed_types)))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if relevant_modules.is_empty() {
+                break;
+            }
+            relevant_modules.iter().for_each(|name| {
+                skipped_modules.remove(name);
+            });
+        }
+        let mut referenced_types = BTreeMap::<String, BTreeSet<String>>::new();
+        for module in &self.modules {
+            referenced_types
+                .entry(
+                    module
+                        .name
+                        .root()
+                        .map(|root| root.to_py())
+                        .unwrap_or_default(),
+                )
+                .or_default()
+                .extend(referenced_type_names(module));
+        }
+
+        // The state of skipped modules is carried over from the previous run
+        let mut state = IncrementalState {
+            config_hash,
+            modules: skipped_modules
+                .iter()
+                .map(|name| (name.clone(), previous_state.modules[name].clone()))
+                .collect(),
+        };
+
+        if !self.modules.is_empty() || skipped_modules.is_empty() {
+            self.prepare()?;
+
+            // Determine the current state of all parsed modules
+            let top_level_names = self
+                .modules
+                .iter()
+                .map(|module| module.name.to_py())
+                .chain(skipped_modules.iter().cloned())
+                .collect::<Vec<_>>();
+            for module in &self.modules {
+                let name = module.name.to_py();
+                let module_state = ModuleState {
+                    source_hash: source_hashes.get(&name).copied().flatten(),
+                    content_hash: hash_module_content(module),
+                    dependencies: module_dependencies(module, &top_level_names),
+                    defined_types: defined_type_names(module),
+                    referenced_types: referenced_types.remove(&name).unwrap_or_default(),
+                };
+                state.modules.insert(name, module_state);
+            }
+            let is_changed = |name: &str| {
+                previous_state
+                    .modules
+                    .get(name)
+                    .map(|module| module.content_hash)
+                    != state.modules.get(name).map(|module| module.content_hash)
+            };
+
+            // Generate the bindings for all modules that changed or transitively depend on changed modules
+            let all_types = self.get_all_types();
+            for module in &self.modules {
+                let name = module.name.to_py();
+                let output_path = output_path(&name);
+                if !is_changed(&name)
+                    && !state
+                        .dependency_closure(&name)
+                        .iter()
+                        .any(|dependency| is_changed(dependency))
+                    && output_path.exists()
+                {
+                    continue;
+                }
+                let bindings = module.generate(&self.cfg, &self.sink, &self.modules, &all_types)?;
+                write_file(&output_path, bindings.to_string())?;
+            }
+
+            // The helpers for typed access to the returned dictionaries are shared by all modules
+            let dict_key_accessors_path = output_dir.join("dict_key_accessors.rs");
+            if (self.cfg.generate_functions || self.cfg.generate_classes)
+                && self.cfg.dict_key_accessors
+                && self.modules.iter().any(Module::returns_str_keyed_dict)
+            {
+                write_file(
+                    &dict_key_accessors_path,
+                    Function::generate_dict_key_accessors(&self.cfg).to_string(),
+                )?;
+            } else if skipped_modules.is_empty() && dict_key_accessors_path.exists() {
+                std::fs::remove_file(dict_key_accessors_path)?;
+            }
+        }
+
+        // Remove the bindings of modules that are no longer part of the engine
+        for name in loaded_state
+            .iter()
+            .flat_map(|loaded_state| loaded_state.modules.keys())
+            .filter(|name| !state.modules.contains_key(*name))
+        {
+            let output_path = output_path(name);
+            if output_path.exists() {
+                std::fs::remove_file(output_path)?;
+            }
+        }
+
+        // Store the state for the next run
+        state.save(&state_path)?;
+
+        Ok(())
+    }
+
+    /// Prepare the parsed modules for the generation of bindings.
+    fn prepare(&mut self) -> Result<()> {
+        if self.is_prepared {
+            return Ok(());
+        }
+
+        // Parse the modules added via `Self::module()`
+        let imported_modules = std::mem::take(&mut self.imported_modules);
+        self.parse_modules(imported_modules)?;
+
         if self.modules.is_empty() {
             return Err(PyBindgenError::CodegenError(
                 "There are no modules for which to generate bindings".to_string(),
@@ -145,9 +621,30 @@ impl Codegen {
             self.parse_dependencies()?;
         }
 
+        // Override the signatures of functions and methods before their annotations are processed any further
+        self.override_signatures()?;
+
+        // Parse classes that are referenced in annotations but not reachable otherwise (if enabled)
+        if self.cfg.collect_all_types_from_stubs {
+            self.collect_referenced_types()?;
+        }
+
         // Canonicalize the module tree
         self.canonicalize();
 
+        // Resolve annotations that refer to generated types only by their name (if enabled)
+        if self.cfg.fuzzy_type_resolution {
+            self.resolve_types_by_name();
+        }
+
+        // Relax parameters annotated with callable classes (if enabled)
+        if self.cfg.callable_class_params_accept_closures {
+            self.relax_callable_class_params();
+        }
+
+        // Pass enum members and records by value (if enabled), otherwise records keep their Python representation
+        self.resolve_by_value_types();
+
         // Embed the source code of the modules
         self.modules.iter_mut().for_each(|module| {
             if let Some(source_code) = self.embedded_source_code.get(&module.name.to_rs()) {
@@ -155,17 +652,116 @@ impl Codegen {
             }
         });
 
-        // Generate the bindings for all modules
-        self.modules
-            .iter()
-            .map(|module| module.generate(&self.cfg, &self.modules, &self.get_all_types()))
-            .collect::<Result<_>>()
+        // Append the raw Rust code to the modules and classes
+        self.append_code()?;
+
+        self.is_prepared = true;
+        Ok(())
     }
 
-    /// Generate the Rust FFI bindings for all modules added to the engine and write them to the given file.
-    /// This is a convenience method that combines `generate` and `std::fs::write`.
-    pub fn build(self, output_path: impl AsRef<std::path::Path>) -> Result<()> {
-        Ok(std::fs::write(output_path, self.generate()?.to_string())?)
+    /// Parse the given Python modules and add them to the list of parsed modules.
+    fn parse_modules(
+        &mut self,
+        modules: impl IntoIterator<Item = pyo3::Py<pyo3::types::PyModule>>,
+    ) -> Result<()> {
+        pyo3::Python::with_gil(|py| {
+            modules.into_iter().try_for_each(|module| {
+                crate::io_utils::with_suppressed_python_output(
+                    py,
+                    self.cfg.suppress_python_stdout,
+                    self.cfg.suppress_python_stderr,
+                    || {
+                        self.modules
+                            .push(Module::parse(&self.cfg, &self.sink, module.bind(py))?);
+                        Ok(())
+                    },
+                )
+            })
+        })
+    }
+
+    fn append_code(&mut self) -> Result<()> {
+        fn find_module_recursive<'a>(
+            input: &'a mut [Module],
+            path: &Path,
+        ) -> Option<&'a mut Module> {
+            for module in input {
+                if module.name == *path {
+                    return Some(module);
+                }
+                if let Some(submodule) = find_module_recursive(&mut module.submodules, path) {
+                    return Some(submodule);
+                }
+            }
+            None
+        }
+
+        for (path, rust_code) in &self.appended_module_code {
+            let module = find_module_recursive(&mut self.modules, path).ok_or_else(|| {
+                PyBindgenError::CodegenError(format!(
+                    "Cannot append Rust code to module '{path}' because it is not part of the bindings"
+                ))
+            })?;
+            module.appended_code.push(rust_code.clone());
+        }
+        for (path, rust_code) in &self.appended_class_code {
+            let class = path
+                .parent()
+                .and_then(|module_path| find_module_recursive(&mut self.modules, &module_path))
+                .and_then(|module| module.classes.iter_mut().find(|class| class.name == *path))
+                .ok_or_else(|| {
+                    PyBindgenError::CodegenError(format!(
+                        "Cannot append Rust code to class '{path}' because it is not part of the bindings"
+                    ))
+                })?;
+            class.appended_code.push(rust_code.clone());
+        }
+
+        Ok(())
+    }
+
+    fn override_signatures(&mut self) -> Result<()> {
+        fn find_function_recursive<'a>(
+            input: &'a mut [Module],
+            path: &Path,
+        ) -> Option<&'a mut Function> {
+            for module in input {
+                if let Some(function) = module
+                    .functions
+                    .iter_mut()
+                    .chain(
+                        module
+                            .classes
+                            .iter_mut()
+                            .flat_map(|class| class.methods_mut().iter_mut()),
+                    )
+                    .find(|function| function.name == *path)
+                {
+                    return Some(function);
+                }
+                if let Some(function) = find_function_recursive(&mut module.submodules, path) {
+                    return Some(function);
+                }
+            }
+            None
+        }
+
+        let mut unmatched = Vec::new();
+        for (path, signature) in &self.signature_overrides {
+            if let Some(function) = find_function_recursive(&mut self.modules, path) {
+                function.override_signature(&self.cfg, &self.sink, signature)?;
+            } else {
+                unmatched.push(path.to_py());
+            }
+        }
+        if !unmatched.is_empty() {
+            return Err(PyBindgenError::CodegenError(format!(
+                "Cannot override the signature of '{}' because it is not part of the bindings",
+                unmatched.join("', '")
+            )));
+        }
+
+        Ok(())
     }
 
     fn parse_dependencies(&mut self) -> Result<()> {
@@ -241,7 +837,8 @@ impl Codegen {
                         self.cfg.suppress_python_stdout,
                         self.cfg.suppress_python_stderr,
                         || {
-                            self.modules.push(Module::parse(&self.cfg, &module)?);
+                            self.modules
+                                .push(Module::parse(&self.cfg, &self.sink, &module)?);
                             Ok(())
                         },
                     )
@@ -250,10 +847,190 @@ impl Codegen {
         })
     }
 
+    /// Parse the classes that are referenced in the type annotations of the parsed modules but are not part
+    /// of the module tree. Each class is added to a separate module that is merged into the tree during
+    /// canonicalization. The process is repeated until no new classes are found.
+    fn collect_referenced_types(&mut self) -> Result<()> {
+        fn get_annotations_recursive(input: &[Module]) -> Vec<&Type> {
+            let mut annotations = Vec::new();
+            for module in input {
+                annotations.extend(
+                    module
+                        .functions
+                        .iter()
+                        .flat_map(|function| function.annotations()),
+                );
+                annotations.extend(
+                    module
+                        .properties
+                        .iter()
+                        .flat_map(|property| property.annotations()),
+                );
+                annotations.extend(module.classes.iter().flat_map(|class| {
+                    class
+                        .methods()
+                        .iter()
+                        .flat_map(|method| method.annotations())
+                        .chain(
+                            class
+                                .properties()
+                                .iter()
+                                .flat_map(|property| property.annotations()),
+                        )
+                }));
+                annotations.extend(get_annotations_recursive(&module.submodules));
+            }
+            annotations
+        }
+
+        let mut visited = HashSet::default();
+        loop {
+            let all_types = self.get_all_types();
+            let referenced_types = get_annotations_recursive(&self.modules)
+                .into_iter()
+                .flat_map(|annotation| annotation.unmapped_types(&all_types))
+                .map(|type_name| {
+                    Path::from_py(type_name.split_once('[').map_or(type_name, |s| s.0))
+                })
+                .filter(|path| path.len() > 1 && visited.insert(path.clone()))
+                .collect_vec();
+            if referenced_types.is_empty() {
+                break;
+            }
+
+            pyo3::Python::with_gil(|py| {
+                referenced_types.into_iter().try_for_each(|class_path| {
+                    let module_path = class_path.parent().unwrap_or_else(|| unreachable!());
+                    let Ok(module) = py.import_bound(module_path.to_py().as_str()) else {
+                        return Ok(());
+                    };
+                    let Ok(class) = module
+                        .getattr(class_path.name().as_py())
+                        .and_then(|attr| Ok(attr.downcast_into::<pyo3::types::PyType>()?))
+                    else {
+                        return Ok(());
+                    };
+                    if !self
+                        .cfg
+                        .is_attr_allowed(class_path.name(), &module_path, &class)
+                    {
+                        return Ok(());
+                    }
+                    crate::io_utils::with_suppressed_python_output(
+                        py,
+                        self.cfg.suppress_python_stdout,
+                        self.cfg.suppress_python_stderr,
+                        || {
+                            let mut module = Module::empty(py, module_path)?;
+                            module
+                                .classes
+                                .push(Class::parse(&self.cfg, &self.sink, &class, class_path)?);
+                            self.modules.push(module);
+                            Ok(())
+                        },
+                    )
+                })
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Generate a `prelude` module that re-exports the classes (alongside their method traits) and functions
+    /// of all modules. Names that are defined in multiple modules are skipped with a warning.
+    fn generate_root_prelude(&self) -> Result<proc_macro2::TokenStream> {
+        fn collect_items(cfg: &Config, module: &Module, items: &mut Vec<(Ident, Path)>) {
+            if cfg.generate_classes {
+                for class in &module.classes {
+                    let Ok(struct_ident) = syn::Ident::try_from(class.name.name()) else {
+                        continue;
+                    };
+                    let trait_ident = Ident::from_py(&format!("{struct_ident}Methods"));
+                    let trait_path = module.name.join(&trait_ident.clone().into());
+                    items.push((class.name.name().clone(), class.name.clone()));
+                    items.push((trait_ident, trait_path));
+                }
+            }
+            if cfg.generate_functions && !cfg.module_handle_injection {
+                for function in &module.functions {
+                    if function.is_generated_as_is(cfg) {
+                        items.push((function.name.name().clone(), function.name.clone()));
+                    }
+                }
+            }
+            for submodule in &module.submodules {
+                collect_items(cfg, submodule, items);
+            }
+        }
+
+        // Collect the items of all modules
+        let mut items = Vec::new();
+        self.modules
+            .iter()
+            .for_each(|module| collect_items(&self.cfg, module, &mut items));
+
+        // Skip ambiguous names that are defined in multiple modules
+        let mut occurrences: HashMap<Ident, Vec<Path>> = HashMap::default();
+        items.iter().for_each(|(ident, path)| {
+            occurrences
+                .entry(ident.clone())
+                .or_default()
+                .push(path.clone());
+        });
+        occurrences
+            .iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .sorted()
+            .for_each(|(ident, paths)| {
+                self.sink.warn(
+                    crate::WarningKind::Other,
+                    ident,
+                    format!(
+                        "Name '{ident}' is defined in multiple modules ({}). It is skipped in the combined prelude.",
+                        paths.iter().map(Path::to_py).join(", ")
+                    ),
+                );
+            });
+        let exports = items
+            .iter()
+            .filter(|(ident, _)| occurrences[ident].len() == 1)
+            .map(|(_, path)| {
+                let path: syn::Path = Path::from_rs("super").join(path).try_into()?;
+                Ok(quote::quote! {
+                    pub use #path;
+                })
+            })
+            .collect::<Result<proc_macro2::TokenStream>>()?;
+
+        // Finalize the prelude with its content (avoid clashes with the names of top-level modules)
+        let Some(prelude_ident) = self.cfg.on_collision.resolve(
+            &self.sink,
+            &Path::from_rs("prelude"),
+            Ident::from_py("prelude"),
+            Some(Ident::from_py("prelude_from_modules")),
+            |ident| {
+                self.modules
+                    .iter()
+                    .any(|module| module.name.name() == ident)
+            },
+        )?
+        else {
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let prelude_ident: syn::Ident = prelude_ident.try_into()?;
+        Ok(self.cfg.output_edition.adjust(quote::quote! {
+            /// Prelude that re-exports the classes (alongside their method traits) and functions of all modules.
+            pub mod #prelude_ident {
+                #exports
+            }
+        }))
+    }
+
     fn canonicalize(&mut self) {
         // Canonicalize the module tree, such that no submodules remain at the top-level
         // Example: If `mod.submod.subsubmod` is currently top-level, it will be embedded as submodule into `mod.submod`
         //          and `mod.submod` will be embedded in top-level `mod`
+        // Sibling modules (e.g. `mod.a` and `mod.b`) are merged under their common parent package afterwards
         pyo3::Python::with_gil(|py| {
             self.modules.iter_mut().for_each(|module| {
                 if module.name.len() > 1 {
@@ -262,8 +1039,9 @@ impl Codegen {
                             .rev()
                             .fold(module.clone(), |package, i| {
                                 let name = Path::from(&module.name[0..=i]);
-                                let mut parent_package =
-                                    Module::empty(py, name).unwrap_or_else(|_| unreachable!());
+                                // Parent packages that cannot be imported are synthesized
+                                let mut parent_package = Module::empty(py, name.clone())
+                                    .unwrap_or_else(|_| Module::synthesized_package(name));
                                 parent_package.submodules.push(package);
                                 parent_package
                             });
@@ -296,7 +1074,21 @@ impl Codegen {
         }
 
         fn merge_duplicate_submodules_recursive(input: &[Module]) -> Module {
+            // Prefer the copy with embedded source code, then a package over a non-package and then
+            // a copy with a docstring, so that the result does not depend on the order of the copies
+            let primary = input
+                .iter()
+                .rev()
+                .max_by_key(|module| {
+                    (
+                        module.source_code.is_some(),
+                        module.is_package,
+                        module.docstring.is_some(),
+                    )
+                })
+                .unwrap_or_else(|| unreachable!());
             Module {
+                name: primary.name.clone(),
                 prelude: input
                     .iter()
                     .fold(HashSet::default(), |mut prelude, module| {
@@ -361,7 +1153,21 @@ impl Codegen {
                     })
                     .into_iter()
                     .collect(),
-                ..input[0].clone()
+                docstring: primary
+                    .docstring
+                    .clone()
+                    .or_else(|| input.iter().find_map(|module| module.docstring.clone())),
+                is_package: input.iter().any(|module| module.is_package),
+                source_code: primary.source_code.clone(),
+                source_file: primary
+                    .source_file
+                    .clone()
+                    .or_else(|| input.iter().find_map(|module| module.source_file.clone())),
+                is_dynamic: input.iter().any(|module| module.is_dynamic),
+                appended_code: input
+                    .iter()
+                    .flat_map(|module| module.appended_code.clone())
+                    .collect(),
             }
         }
 
@@ -375,6 +1181,111 @@ impl Codegen {
             });
     }
 
+    fn relax_callable_class_params(&mut self) {
+        fn get_callable_types_recursive(input: &[Module]) -> Vec<Path> {
+            let mut types = Vec::new();
+            for module in input {
+                types.extend(
+                    module
+                        .classes
+                        .iter()
+                        .filter(|class| class.is_callable())
+                        .map(|class| class.name.clone()),
+                );
+                types.extend(get_callable_types_recursive(&module.submodules));
+            }
+            types
+        }
+        fn relax_recursive(input: &mut [Module], callable_types: &[Path]) {
+            for module in input {
+                module
+                    .functions
+                    .iter_mut()
+                    .for_each(|function| function.relax_callable_class_params(callable_types));
+                module
+                    .classes
+                    .iter_mut()
+                    .for_each(|class| class.relax_callable_class_params(callable_types));
+                relax_recursive(&mut module.submodules, callable_types);
+            }
+        }
+
+        let callable_types = get_callable_types_recursive(&self.modules);
+        relax_recursive(&mut self.modules, &callable_types);
+    }
+
+    fn resolve_by_value_types(&mut self) {
+        fn get_by_value_types_recursive(input: &[Module]) -> Vec<(Path, Type)> {
+            let mut types = Vec::new();
+            for module in input {
+                types.extend(module.classes.iter().filter_map(|class| {
+                    class
+                        .by_value_type()
+                        .map(|by_value_type| (class.name.clone(), by_value_type))
+                }));
+                types.extend(get_by_value_types_recursive(&module.submodules));
+            }
+            types
+        }
+        fn resolve_recursive(input: &mut [Module], by_value_types: &HashMap<Path, Type>) {
+            for module in input {
+                module
+                    .functions
+                    .iter_mut()
+                    .for_each(|function| function.resolve_by_value_types(by_value_types));
+                module
+                    .classes
+                    .iter_mut()
+                    .for_each(|class| class.resolve_by_value_types(by_value_types));
+                module
+                    .properties
+                    .iter_mut()
+                    .flat_map(Property::annotations_mut)
+                    .for_each(|annotation| annotation.resolve_by_value_types(by_value_types));
+                resolve_recursive(&mut module.submodules, by_value_types);
+            }
+        }
+
+        let by_value_types = if self.cfg.emit_into_py_object {
+            get_by_value_types_recursive(&self.modules)
+                .into_iter()
+                .collect()
+        } else {
+            HashMap::default()
+        };
+        resolve_recursive(&mut self.modules, &by_value_types);
+    }
+
+    /// Resolve the type annotations that refer to a generated type only by its name, e.g. classes that are imported
+    /// under `if TYPE_CHECKING:` and are thus not available at runtime (see `Config::fuzzy_type_resolution`).
+    fn resolve_types_by_name(&mut self) {
+        fn resolve_recursive(input: &mut [Module], sink: &ReportSink, all_types: &[Path]) {
+            for module in input {
+                module
+                    .classes
+                    .iter_mut()
+                    .flat_map(Class::annotations_mut)
+                    .chain(
+                        module
+                            .functions
+                            .iter_mut()
+                            .flat_map(Function::annotations_mut),
+                    )
+                    .chain(
+                        module
+                            .properties
+                            .iter_mut()
+                            .flat_map(Property::annotations_mut),
+                    )
+                    .for_each(|annotation| annotation.resolve_by_name(all_types, sink));
+                resolve_recursive(&mut module.submodules, sink, all_types);
+            }
+        }
+
+        let all_types = self.get_all_types();
+        resolve_recursive(&mut self.modules, &self.sink, &all_types);
+    }
+
     fn get_all_types(&self) -> Vec<Path> {
         fn get_types_recursive(input: &[Module]) -> Vec<Path> {
             let mut types = Vec::new();
@@ -397,3 +1308,66 @@ impl Codegen {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(name: &str) -> Module {
+        Module {
+            name: Path::from_py(name),
+            prelude: Vec::default(),
+            imports: Vec::default(),
+            submodules: Vec::default(),
+            classes: Vec::default(),
+            type_vars: Vec::default(),
+            functions: Vec::default(),
+            properties: Vec::default(),
+            docstring: None,
+            is_package: false,
+            source_code: None,
+            source_file: None,
+            is_dynamic: false,
+            appended_code: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_duplicate_modules() {
+        for source_first in [true, false] {
+            // Arrange
+            let with_source = Module {
+                source_code: Some("x = 1\n".to_owned()),
+                ..module("mod_merge")
+            };
+            let with_docstring = Module {
+                docstring: Some("Docstring".to_owned()),
+                is_package: true,
+                ..module("mod_merge")
+            };
+            let mut codegen = Codegen {
+                modules: if source_first {
+                    vec![with_source, with_docstring]
+                } else {
+                    vec![with_docstring, with_source]
+                },
+                ..Codegen::default()
+            };
+
+            // Act
+            codegen.merge_duplicate_modules();
+
+            // Assert
+            assert_eq!(codegen.modules.len(), 1);
+            let merged = &codegen.modules[0];
+            assert_eq!(merged.source_code.as_deref(), Some("x = 1\n"));
+            assert_eq!(merged.docstring.as_deref(), Some("Docstring"));
+            assert!(merged.is_package);
+            let bindings = merged
+                .generate(&Config::default(), &codegen.sink, &codegen.modules, &[])
+                .unwrap()
+                .to_string();
+            assert!(bindings.contains("pyo3_embed_python_source_code"));
+        }
+    }
+}
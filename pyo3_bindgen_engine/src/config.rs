@@ -1,4 +1,7 @@
-use crate::syntax::{Ident, Path};
+use crate::{
+    syntax::{Ident, Path},
+    utils::report::ReportSink,
+};
 
 /// Array of forbidden attribute names that are reserved for internal use by derived traits
 pub const FORBIDDEN_FUNCTION_NAMES: [&str; 5] = ["get_type", "obj", "py", "repr", "str"];
@@ -15,17 +18,42 @@ pub const FORBIDDEN_TYPE_NAMES: [&str; 7] = [
 
 /// Default array of blocklisted attribute names
 const DEFAULT_BLOCKLIST_ATTRIBUTE_NAMES: [&str; 4] = ["builtins", "testing", "tests", "test"];
+/// Default maximum nesting depth of type annotations
+pub(crate) const DEFAULT_MAX_TYPE_DEPTH: usize = 32;
 
 /// Configuration for `Codegen` engine.
+///
+/// New options might be added in any release, so the configuration must be constructed via
+/// `Config::builder()` (or `Config::default()`) instead of a struct literal.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, typed_builder::TypedBuilder)]
+#[non_exhaustive]
 pub struct Config {
     /// Flag that determines whether to recursively generate code for all submodules of the target modules.
     #[builder(default = true)]
     pub traverse_submodules: bool,
+    /// List of full names of submodules (e.g. `numpy.testing`) that are skipped entirely while traversing the
+    /// submodules of the target modules, including all of their own submodules. Skipped submodules are not imported.
+    #[builder(default)]
+    pub skip_modules: Vec<String>,
 
     /// Flag that determines whether to generate code for prelude modules (Python `__all__` attribute).
     #[builder(default = true)]
     pub generate_preludes: bool,
+    /// Flag that determines whether to generate a `prelude` module alongside the top-level modules, which re-exports
+    /// the classes (including their method traits) and functions of all generated modules. Names that are defined
+    /// in multiple modules are ambiguous and therefore skipped. Not supported by `Codegen::build_incremental`.
+    #[builder(default = false)]
+    pub generate_root_prelude: bool,
+    /// Number of functions below which leaf modules (without classes, type variables and submodules) are generated
+    /// in a compact form, i.e. without a prelude module (all of their items are public anyway) and without imports
+    /// whose origin is not generated due to the configuration. Disabled by default.
+    #[builder(default, setter(strip_option))]
+    pub compact_leaf_modules: Option<usize>,
+    /// Flag that determines whether submodules that contain exactly one public class or function (and nothing else)
+    /// are flattened into their parent module, i.e. the item is generated directly in the parent module and the
+    /// submodule only re-exports it via `pub use`, which keeps the original path of the item valid.
+    #[builder(default = false)]
+    pub flatten_single_item_modules: bool,
     /// Flag that determines whether to generate code for imports.
     #[builder(default = true)]
     pub generate_imports: bool,
@@ -45,13 +73,255 @@ pub struct Config {
     /// The documentation is based on Python docstrings.
     #[builder(default = true)]
     pub generate_docs: bool,
+    /// Maximum width of the lines in the generated documentation. Longer lines are wrapped at whitespace, while
+    /// code blocks (fenced, doctest and reStructuredText literal blocks) are kept as-is. Unlimited by default.
+    #[builder(default, setter(strip_option))]
+    pub docstring_max_width: Option<usize>,
+    /// Flag that determines whether to note the `repr()` of Python default values in the documentation of functions.
+    /// Default values that are not simple literals (numbers, strings, bools, `None` or empty containers) are noted
+    /// by the name of their type instead.
+    #[builder(default = false)]
+    pub preserve_default_repr_in_docs: bool,
+    /// Flag that determines whether to generate a module `{function}_defaults` next to each module-level function,
+    /// which contains a constant for each parameter whose default value is a `bool`, `int`, `float` or `str`
+    /// literal (e.g. `pub const ENCODING: &str = "utf-8";`), so that the default can be passed explicitly.
+    #[builder(default = false)]
+    pub default_helpers: bool,
+    /// Flag that determines whether to include a `no_run` example in the documentation of module-level functions,
+    /// which shows how to call the function within `Python::with_gil` with placeholder arguments. Examples are
+    /// generated only for functions whose parameters are all `bool`, `int`, `float`, `str` or optional. The
+    /// examples refer to functions via their module path (e.g. `os::getcwd`), so they compile as doctests only
+    /// if the bindings are in scope under this path.
+    #[builder(default = false)]
+    pub emit_doctest_examples: bool,
+    /// Flag that determines whether to append an `ignore` example to the documentation of functions and methods,
+    /// which shows the Rust call corresponding to the Python signature, i.e. the final order and names of the
+    /// parameters with placeholder arguments (e.g. `"..."` for strings, `0` for integers and `None` for optional
+    /// parameters). Functions with a `no_run` example (see `emit_doctest_examples`) keep only that example.
+    #[builder(default = false)]
+    pub generate_usage_examples: bool,
+    /// Flag that determines whether to check `annotated-types` constraints of parameters annotated with
+    /// `Annotated[T, ...]` (i.e. `Ge`, `Le`, `Gt`, `Lt`, `MinLen` and `MaxLen`) via `debug_assert!` before calling
+    /// into Python. Only `int` and `float` parameters (value bounds) and `str` parameters (length bounds) are checked.
+    #[builder(default = false)]
+    pub validate_annotated: bool,
+    /// Flag that determines whether each generated function compares the live signature of its Python counterpart
+    /// (names and kinds of parameters via `inspect.signature`) against the signature at generation time when it is
+    /// first called, and emits a Python `RuntimeWarning` on mismatch. This surfaces drift between the bindings and
+    /// the installed Python package (e.g. renamed or reordered parameters) without failing the call.
+    #[builder(default = false)]
+    pub runtime_signature_guards: bool,
+    /// Mode that determines whether generated functions have their full body or only a stub body that panics.
+    /// Stub bodies keep all signatures, structs, traits and docs identical while substantially reducing the size
+    /// of the bindings, which is sufficient for type-checking of downstream code (e.g. in IDEs).
+    #[builder(default)]
+    pub bodies: BodyMode,
+    /// Policy that determines how to generate abstract functions, i.e. functions whose body consists solely of
+    /// an optional docstring and `raise NotImplementedError`.
+    #[builder(default)]
+    pub abstract_functions: AbstractPolicy,
+    /// Policy that determines how to generate methods that are declared with `@abstractmethod`, which cannot be
+    /// called unless they are overridden by a subclass.
+    #[builder(default)]
+    pub abstract_method_handling: AbstractMethodHandling,
+    /// Strategy that determines how the generated bindings expect the Python interpreter to be initialized.
+    #[builder(default)]
+    pub init_strategy: InitStrategy,
+    /// Flag that determines whether each module gets a `{Module}Handle` struct that wraps the module object and
+    /// provides its functions as methods (e.g. `handle.foo(py, ...)`), instead of generating free functions that
+    /// import the module by name. Constructors and static methods of classes take the handle as their first
+    /// argument and look up the class object through it. A handle can be created via `import()` or from any
+    /// module object via `from_module()`, e.g. a mock module in tests. Builders for constructors are not generated.
+    #[builder(default = false)]
+    pub module_handle_injection: bool,
+    /// Mode that determines how lints triggered by the generated code (e.g. `non_snake_case` for Python names)
+    /// are allowed via `#[allow(...)]` attributes.
+    #[builder(default)]
+    pub lint_allows: LintAllowMode,
+    /// Flag that determines whether to mark the generated property getters and setters with `#[inline]`.
+    #[builder(default = false)]
+    pub inline_accessors: bool,
+    /// Flag that determines whether to generate comparison helpers (`py_eq`, `py_hash`, `py_cmp`)
+    /// for classes that define `__eq__`, `__hash__` or `__lt__`.
+    #[builder(default = false)]
+    pub impl_comparisons: bool,
+    /// Flag that determines whether to generate `get` and `insert` helpers mirroring `HashMap` for dict-like
+    /// classes, i.e. classes whose `__getitem__` or `__setitem__` have annotated key and value types.
+    #[builder(default = false)]
+    pub mapping_helpers: bool,
+    /// Flag that determines whether to generate `len` and `is_empty` helpers returning `usize` and `bool` for
+    /// classes that define `__len__`, instead of only exposing the raw dunder method (if included).
+    #[builder(default = false)]
+    pub emit_len_method: bool,
+    /// Flag that determines whether functions and methods returning `dict[str, V]` return the Python dictionary
+    /// as `StrKeyedDict<V>` (or as `Bound<PyDict>` for `dict[str, Any]`) instead of converting it into a `HashMap`.
+    /// Their `get_str` (via the `DictKeyAccessors` extension trait for `Bound<PyDict>`) extracts the value of a
+    /// single key without converting the rest. Both are generated once alongside the top-level modules (or into
+    /// a separate `dict_key_accessors.rs` file by `Codegen::build_incremental`).
+    #[builder(default = false)]
+    pub dict_key_accessors: bool,
+    /// Flag that determines whether to generate logging helpers (`log_info`, `log_warning`, `log_error`)
+    /// for modules with a `logging.Logger` property, e.g. `logger = logging.getLogger(__name__)`.
+    #[builder(default = false)]
+    pub logging_helpers: bool,
+    /// Flag that determines whether to generate `get_attr` and `set_attr` helpers for all classes, which provide
+    /// access to dynamic attributes that are not discoverable at generation time.
+    #[builder(default = false)]
+    pub emit_getattr_fallback: bool,
+    /// Flag that determines whether to generate `to_dict` and `update_from_dict` helpers for all classes, which
+    /// convert objects from/into their dict representation (`dataclasses.asdict` for dataclasses, `vars()` otherwise).
+    #[builder(default = true)]
+    pub dict_helpers: bool,
+    /// Flag that determines whether to emit a compile-time guard into top-level modules, which fails with an error
+    /// that names the required `pyo3` version if the `Bound` API targeted by the generated bindings is unavailable.
+    #[builder(default = false)]
+    pub emit_pyo3_version_check: bool,
+    /// Flag that determines whether to mark top-level modules with `#[deprecated]`, so that each use of the
+    /// generated bindings warns that they were generated by an experimental code path (e.g. `import_python!`).
+    #[builder(default = false)]
+    pub emit_experimental_warnings: bool,
+    /// Rust edition that the generated bindings target. Identifiers that are reserved as keywords in the selected
+    /// edition (e.g. `gen` in Rust 2024) are emitted as raw identifiers.
+    #[builder(default)]
+    pub output_edition: Edition,
+    /// Policy that determines how name collisions between generated items (e.g. a constructor and a method
+    /// named `new`) are resolved. See [`CollisionPolicy`] for the naming scheme.
+    #[builder(default)]
+    pub on_collision: CollisionPolicy,
+    /// Strategy that determines how property getters that collide with a method of the same name (e.g. a property
+    /// `size` and a method `size()` of a base class) are renamed. See [`GetterRenaming`] for the naming schemes.
+    #[builder(default)]
+    pub rename_conflicting_getters: GetterRenaming,
+    /// Name of the associated functions that are generated for class constructors (`__init__`), e.g. `create`.
+    /// Collisions with other methods of the class are resolved according to `on_collision`.
+    #[builder(default = "new".to_owned(), setter(into))]
+    pub constructor_name: String,
+    /// Policy that determines how Python names that are invalid Rust idents (e.g. `a-b`) are handled. Such names of
+    /// functions and classes are sanitized with an `f_` and `s_` prefix, respectively.
+    #[builder(default)]
+    pub sanitization: NameSanitization,
+    /// Flag that determines whether setters of class properties are named `try_set_x` instead of `set_x`, which
+    /// communicates that setting the property might fail at runtime (e.g. if its setter rejects the value).
+    #[builder(default = false)]
+    pub infallible_setters: bool,
+    /// Flag that determines whether to generate a builder struct (`XBuilder`) for class constructors,
+    /// where required parameters are passed to `X::builder()` and defaulted parameters are set via setters.
+    #[builder(default = false)]
+    pub emit_builder_for_constructors: bool,
+    /// Flag that determines whether to generate a Rust enum `XMember` for each Python enum `X` (subclass of
+    /// `enum.Enum`), whose variants correspond to the members of the enum, and a Rust struct `XRecord` for each
+    /// record `X` (`typing.TypedDict` or named tuple), whose fields correspond to the fields of the record.
+    /// Both implement `FromPyObject` and are fallibly converted into their Python counterpart via `into_bound`.
+    /// Parameters and return values that are annotated with such an enum or record (or its optional variant)
+    /// are passed by value, whereas nested occurrences (e.g. `list[X]`) keep their Python representation.
+    #[builder(default = false)]
+    pub emit_into_py_object: bool,
+    /// Flag that determines whether to generate a `Send` handle (`XHandle`) for each class, which wraps `Py<X>` and
+    /// provides the methods of the class that acquire the GIL internally. Only methods whose parameters and return
+    /// types do not borrow from the GIL are provided (returned `Bound<'py, T>` objects are converted to `Py<T>`).
+    #[builder(default = false)]
+    pub emit_send_wrappers: bool,
+    /// Flag that determines whether to generate an associated function `X::into_py_shared` for each class, which
+    /// unbinds `Bound<'py, X>` into `Py<X>` that can be stored and sent across threads, and an extension trait
+    /// `XSharedMethods` for `Py<X>`, which re-binds the object via `rebind` and forwards the methods of the class
+    /// with an explicit `py` token.
+    #[builder(default = false)]
+    pub emit_shared_methods: bool,
+    /// Flag that determines whether to append a note about the thread safety of the generated bindings to the
+    /// documentation of each class.
+    #[builder(default = false)]
+    pub thread_docs: bool,
+    /// Flag that determines whether parameters annotated with a class that defines `__call__` accept
+    /// any Python callable (e.g. a Rust closure wrapped in `PyCFunction`) instead of only instances of the class.
+    #[builder(default = false)]
+    pub callable_class_params_accept_closures: bool,
+
+    /// Rust type to which Python `float` is mapped. Extraction into `f32` is lossy, i.e. values are rounded
+    /// to the nearest representable `f32` (and values outside its range become infinite).
+    #[builder(default)]
+    pub float_type: FloatType,
+
+    /// Rust type to which Python `int` is mapped. Extraction fails with `OverflowError` if the value
+    /// does not fit into the selected type, which can be avoided via arbitrary-precision `BigInt`.
+    #[builder(default)]
+    pub int_type: IntType,
+    /// Rust type to which Python `str` is mapped when returned from functions and properties (incl. optional
+    /// returns). Nested strings (e.g. in `list[str]`) are always mapped to `String`.
+    #[builder(default)]
+    pub string_return_type: StringReturnType,
+    /// Flag that determines whether parameters of primitive types accept any compatible Rust type, i.e.
+    /// `impl AsRef<str>` for strings, `impl Into<i64>` for integers, `impl Into<f64>` for floats and
+    /// `impl AsRef<Path>` for paths (collections are not affected).
+    #[builder(default = false)]
+    pub flexible_primitives: bool,
+    /// Maximum nesting depth of type annotations, beyond which the remainder of the annotation is mapped to `Any`
+    /// (with a warning). This guards against unbounded recursion, e.g. of deeply nested or self-referential
+    /// annotations such as `JSON = Union[str, list["JSON"], dict[str, "JSON"]]`.
+    #[builder(default = DEFAULT_MAX_TYPE_DEPTH)]
+    pub max_type_depth: usize,
+
+    /// Flag that determines whether module-level properties without a type annotation are accessed as `PyAny`
+    /// instead of the type of their runtime value, which might differ between platforms (e.g. `os.sep`).
+    /// This keeps the generated bindings identical across platforms at the cost of manual extraction,
+    /// while annotated properties keep their concrete types.
+    #[builder(default = false)]
+    pub portable_property_types: bool,
+    /// Minimum Python version that the generated bindings support at runtime. Version requirements of functions
+    /// (see `python_version_requirements`) up to this version are always met, so they are not checked.
+    #[builder(default, setter(strip_option))]
+    pub min_python_version: Option<(u8, u8)>,
+    /// Python versions required by specific functions and methods, which are identified by their full Python path
+    /// (e.g. `pkg.new_function` or `pkg.Class.new_method`). The generated bindings check the version of the Python
+    /// interpreter at runtime and return an error instead of calling into Python if the requirement is not met,
+    /// which keeps a single set of bindings usable across Python versions.
+    #[builder(via_mutators, mutators(
+        /// Require a minimum Python version for the function or method with the given full Python path.
+        pub fn require_python_version(&mut self, path: impl Into<String>, version: (u8, u8)) {
+            self.python_version_requirements.push((path.into(), version));
+        }
+    ))]
+    pub python_version_requirements: Vec<(String, (u8, u8))>,
 
     /// List of blocklisted attribute names that are skipped during the code generation.
     #[builder(default = DEFAULT_BLOCKLIST_ATTRIBUTE_NAMES.iter().map(|&s| s.to_string()).collect())]
     pub blocklist_names: Vec<String>,
-    /// Flag that determines whether private attributes are considered while parsing the Python code.
+    /// Flag that determines whether private attributes are considered while parsing the Python code. This includes
+    /// attributes with a leading underscore (e.g. `_helper`), dunder attributes (e.g. `__len__`) and attributes of
+    /// private modules, but not name-mangled attributes (see `include_name_mangled`).
     #[builder(default = false)]
     pub include_private: bool,
+    /// Flag that determines whether attributes with a single leading underscore (e.g. semi-public `_replace`)
+    /// are considered while parsing the Python code, even if `include_private` is disabled.
+    #[builder(default = false)]
+    pub include_underscore_attrs: bool,
+    /// Flag that determines whether name-mangled attributes of classes (e.g. `_Class__secret` for `__secret`)
+    /// are considered while parsing the Python code. These are skipped even if `include_private` is enabled.
+    #[builder(default = false)]
+    pub include_name_mangled: bool,
+    /// List of dunder attribute names (e.g. `__len__`) that are considered while parsing the Python code, even if
+    /// `include_private` is disabled.
+    #[builder(default)]
+    pub include_dunder: Vec<String>,
+
+    /// Flag that determines whether to generate an empty stub module for submodules that fail to import
+    /// (e.g. platform-specific submodules), instead of skipping them. The import error is preserved in the
+    /// documentation of the stub, which keeps the module structure stable across platforms.
+    #[builder(default = false)]
+    pub platform_stubs: bool,
+
+    /// Flag that determines whether modules that resolve their attributes dynamically via a module-level
+    /// `__getattr__` (e.g. lazily-loaded modules) are also searched for the attributes listed in their `__all__`,
+    /// which might be missing from `dir()`. A `pyo3_getattr()` helper is generated for such modules in order
+    /// to access any of their dynamic attributes by name.
+    #[builder(default = false)]
+    pub dynamic_module_attributes: bool,
+
+    /// Flag that determines whether to generate accessors for the standard metadata of each module, which are
+    /// fetched at runtime, i.e. `version()` (`__version__`), `module_path()` (`__file__`, which is missing for
+    /// namespace packages) and `exported_names()` (`__all__`, falling back to the public names of `dir()`).
+    /// Collisions with other items of the module are resolved according to `on_collision`.
+    #[builder(default = false)]
+    pub generate_metadata_accessors: bool,
 
     /// Flag that determines whether to generate code for all dependencies of the target modules.
     /// The list of dependent modules is derived from the imports of the target modules.
@@ -60,6 +330,18 @@ pub struct Config {
     #[builder(default = false)]
     pub generate_dependencies: bool,
 
+    /// Flag that determines whether to also generate bindings for classes that are referenced in type annotations
+    /// but are not reachable from the target modules, e.g. the return type of a function that is defined in a
+    /// submodule that is not traversed. Without this flag, such types are mapped to `PyAny`.
+    #[builder(default = false)]
+    pub collect_all_types_from_stubs: bool,
+
+    /// Flag that determines whether to resolve type annotations that refer to a class only by a name that is not
+    /// available at runtime (e.g. a class imported under `if TYPE_CHECKING:`) to the generated class with the same
+    /// name. Names that match multiple generated classes are ambiguous and they are mapped to `PyAny`.
+    #[builder(default = true)]
+    pub fuzzy_type_resolution: bool,
+
     /// Flag that suppresses the generation of Python STDOUT while parsing the Python code.
     #[builder(default = true)]
     pub suppress_python_stdout: bool,
@@ -68,6 +350,315 @@ pub struct Config {
     pub suppress_python_stderr: bool,
 }
 
+/// Mode that determines how the bodies of generated functions are emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BodyMode {
+    /// Full bodies that call into Python.
+    #[default]
+    Full,
+    /// Stub bodies that panic via `unimplemented!()` when called.
+    Stub,
+}
+
+impl BodyMode {
+    /// Get the body of a generated function, i.e. either the `full_body` (including its braces) or a stub body.
+    pub(crate) fn body(self, full_body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Full => full_body,
+            Self::Stub => quote::quote! {
+                {
+                    unimplemented!("regenerate with BodyMode::Full")
+                }
+            },
+        }
+    }
+}
+
+/// Policy that determines how abstract functions (that only raise `NotImplementedError`) are generated.
+///
+/// The detection is a best-effort heuristic that requires the Python source code of the function. Functions
+/// without available source code are always considered to be regular functions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AbstractPolicy {
+    /// Generate abstract functions like any other function.
+    #[default]
+    Generate,
+    /// Generate abstract functions with a warning in their documentation and mark them as `#[deprecated]`.
+    GenerateWithWarning,
+    /// Do not generate bindings for abstract functions.
+    Skip,
+}
+
+/// Policy that determines how methods declared with `@abstractmethod` are generated.
+///
+/// Abstract methods are detected via their `__isabstractmethod__` attribute (or their decorators if the module
+/// is parsed from its AST). Unlike [`AbstractPolicy`], the detection does not require the Python source code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AbstractMethodHandling {
+    /// Generate abstract methods like any other method.
+    #[default]
+    Emit,
+    /// Generate abstract methods with a warning in their documentation and mark them as `#[deprecated]`.
+    Mark,
+    /// Do not generate bindings for abstract methods.
+    Skip,
+}
+
+/// Strategy that determines how the generated bindings expect the Python interpreter to be initialized.
+///
+/// All generated functions take a `py: Python<'py>` token, so the interpreter must always be initialized before
+/// they are called. The generated bindings never initialize the interpreter more than once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InitStrategy {
+    /// The interpreter is initialized externally, e.g. via the `auto-initialize` feature of `pyo3` or a manual
+    /// call to `pyo3::prepare_freethreaded_python()`.
+    #[default]
+    External,
+    /// Top-level modules additionally contain a `pyo3_with_gil()` helper that initializes the interpreter via
+    /// `pyo3::prepare_freethreaded_python()` (unless it is already initialized), embeds the Python source code of
+    /// the module (unless it is already embedded) and then acquires the GIL. Not supported on PyPy.
+    Prepare,
+}
+
+/// Mode that determines how lints triggered by the generated code are allowed via `#[allow(...)]` attributes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LintAllowMode {
+    /// All lints that might be triggered by the generated code (including all `clippy` lint groups) are allowed
+    /// for each top-level module as a whole.
+    #[default]
+    Blanket,
+    /// Only the lints that are triggered by specific items are allowed directly on these items, e.g.
+    /// `non_snake_case` on functions whose Python name (or the name of their parameters) is not in snake case.
+    Targeted,
+    /// No lints are allowed.
+    None,
+}
+
+/// Policy that determines how name collisions between generated items are resolved.
+///
+/// Colliding items are renamed based on their Python origin instead of a counter, so that the generated names
+/// remain stable when unrelated items are added to the Python package or when they are reordered:
+///
+/// | Item | Name | Name on collision |
+/// |------|------|-------------------|
+/// | Constructor (`__init__`) | `new` | `new_from_init` |
+/// | Callable instance (`__call__`) | `call` | `call_from_call` |
+/// | Module prelude (`__all__`) | `call` | `call_from_all` |
+/// | Combined prelude of all modules | `prelude` | `prelude_from_modules` |
+/// | Property getter | `x` | `get_x` (see [`GetterRenaming`]) |
+/// | Property setter | `set_x` | — |
+/// | Mapping helpers | `get`, `keys`, `values`, `items` | `get_from_mapping`, ... |
+/// | Sequence helpers | `get_index`, `len`, `to_vec` | `get_index_from_sequence`, ... |
+/// | Item access of dict-like classes | `get`, `insert` | `get_from_getitem`, `insert_from_setitem` |
+/// | Dynamic attribute access | `get_attr`, `set_attr` | `get_attr_from_getattr`, `set_attr_from_getattr` |
+/// | Module metadata | `version`, `module_path`, `exported_names` | `version_from_metadata`, ... |
+///
+/// Items whose name on collision is taken as well are skipped. All renamed items are listed in the
+/// [`CollisionReport`](crate::CollisionReport) of the [`GenerationReport`](crate::GenerationReport).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CollisionPolicy {
+    /// Rename colliding items according to the naming scheme (or skip them if no name is available).
+    #[default]
+    Suffix,
+    /// Fail the generation with an error on the first collision.
+    Error,
+}
+
+impl CollisionPolicy {
+    /// Resolve the name of the item at `path`, i.e. return the `intended` name if it is not taken, otherwise
+    /// the `alternative` name (if any and not taken). Returns `None` if the item is skipped.
+    pub(crate) fn resolve(
+        self,
+        sink: &ReportSink,
+        path: &Path,
+        intended: Ident,
+        alternative: Option<Ident>,
+        is_taken: impl Fn(&Ident) -> bool,
+    ) -> crate::Result<Option<Ident>> {
+        if !is_taken(&intended) {
+            return Ok(Some(intended));
+        }
+        match self {
+            Self::Suffix => {
+                if let Some(alternative) = alternative.filter(|alternative| !is_taken(alternative))
+                {
+                    sink.rename(path, &intended, &alternative);
+                    Ok(Some(alternative))
+                } else {
+                    sink.skip(
+                        path,
+                        format!("Name '{intended}' collides with another item"),
+                    );
+                    Ok(None)
+                }
+            }
+            Self::Error => Err(crate::PyBindgenError::CodegenError(format!(
+                "Name '{intended}' of '{path}' collides with another item"
+            ))),
+        }
+    }
+}
+
+/// Strategy that determines how property getters that collide with another item of the class (e.g. a method) are
+/// renamed. The renamed getter is subject to the [`CollisionPolicy`], i.e. it is skipped (or fails the generation)
+/// if its new name is taken as well.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GetterRenaming {
+    /// Rename the getter of property `x` to `get_x`.
+    #[default]
+    Prefix,
+    /// Rename the getter of property `x` to `x_prop`.
+    Suffix,
+    /// Fail the generation with an error, regardless of the [`CollisionPolicy`].
+    Error,
+}
+
+impl GetterRenaming {
+    /// Get the name on collision of the getter of the property at `path` with the given `name`.
+    pub(crate) fn rename(self, path: &Path, name: &Ident) -> crate::Result<Ident> {
+        match self {
+            Self::Prefix => Ok(name.with_prefix("get_")),
+            Self::Suffix => Ok(name.with_suffix("_prop")),
+            Self::Error => Err(crate::PyBindgenError::CodegenError(format!(
+                "Getter '{name}' of property '{path}' collides with another item"
+            ))),
+        }
+    }
+}
+
+/// Policy that determines how names of functions and classes that are invalid Rust idents are handled, which
+/// are sanitized with an `f_` (functions) or `s_` (structs) prefix. Names that cannot be sanitized are skipped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NameSanitization {
+    /// Sanitize the name and print a warning.
+    #[default]
+    Warn,
+    /// Fail the generation with an error, so that an explicit rename can be added instead.
+    Error,
+    /// Sanitize the name without a warning.
+    Silent,
+}
+
+impl NameSanitization {
+    /// Handle the `kind` of item (e.g. `Function`) at `path` whose name is an invalid Rust ident, which is renamed
+    /// to `sanitized` (or skipped if `None`).
+    pub(crate) fn handle(
+        self,
+        sink: &ReportSink,
+        path: &Path,
+        kind: &str,
+        sanitized: Option<&Path>,
+    ) -> crate::Result<()> {
+        let kind_lowercase = kind.to_lowercase();
+        match self {
+            Self::Warn => {
+                let message = if let Some(sanitized) = sanitized {
+                    format!("{kind} '{path}' is an invalid Rust ident for a {kind_lowercase} name. Renamed to '{sanitized}'.")
+                } else {
+                    format!("{kind} '{path}' is an invalid Rust ident for a {kind_lowercase} name. Renaming failed. Bindings will not be generated.")
+                };
+                sink.warn(crate::WarningKind::UnnameableSymbol, path, message);
+                Ok(())
+            }
+            Self::Error => Err(crate::PyBindgenError::CodegenError(format!(
+                "{kind} '{path}' is an invalid Rust ident for a {kind_lowercase} name"
+            ))),
+            Self::Silent => Ok(()),
+        }
+    }
+}
+
+/// Rust edition that the generated bindings target.
+///
+/// The generated bindings are compatible with all supported editions, except for identifiers that are reserved as
+/// keywords only in some of them. Editions before Rust 2018 are not supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Edition {
+    /// Rust 2018.
+    E2018,
+    /// Rust 2021.
+    #[default]
+    E2021,
+    /// Rust 2024, which reserves `gen` as a keyword.
+    E2024,
+}
+
+impl Edition {
+    /// Get the identifiers that are reserved as keywords in this edition but are still accepted by `syn`.
+    fn reserved_keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::E2018 | Self::E2021 => &[],
+            Self::E2024 => &["gen"],
+        }
+    }
+
+    /// Adjust the generated code to this edition, i.e. emit all identifiers that are reserved in this edition
+    /// as raw identifiers (lifetimes are left unchanged).
+    pub(crate) fn adjust(self, tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let reserved_keywords = self.reserved_keywords();
+        if reserved_keywords.is_empty() {
+            return tokens;
+        }
+        let mut is_lifetime = false;
+        tokens
+            .into_iter()
+            .map(|token| {
+                let token = match token {
+                    proc_macro2::TokenTree::Ident(ident)
+                        if !is_lifetime && reserved_keywords.contains(&ident.to_string().as_str()) =>
+                    {
+                        proc_macro2::Ident::new_raw(&ident.to_string(), ident.span()).into()
+                    }
+                    proc_macro2::TokenTree::Group(group) => {
+                        let mut adjusted_group =
+                            proc_macro2::Group::new(group.delimiter(), self.adjust(group.stream()));
+                        adjusted_group.set_span(group.span());
+                        adjusted_group.into()
+                    }
+                    token => token,
+                };
+                is_lifetime = matches!(&token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'');
+                token
+            })
+            .collect()
+    }
+}
+
+/// Rust type to which Python `float` is mapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FloatType {
+    /// Single-precision `f32` (lossy conversion from Python `float`).
+    F32,
+    /// Double-precision `f64` that matches the precision of Python `float`.
+    #[default]
+    F64,
+}
+
+/// Rust type to which Python `int` is mapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntType {
+    /// 64-bit signed integer `i64`.
+    #[default]
+    I64,
+    /// 128-bit signed integer `i128`.
+    I128,
+    /// Arbitrary-precision `num_bigint::BigInt` (requires the `num-bigint` feature of `pyo3`).
+    #[cfg(feature = "num-bigint")]
+    BigInt,
+}
+
+/// Rust type to which returned Python `str` is mapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StringReturnType {
+    /// Growable `String`.
+    #[default]
+    String,
+    /// Boxed `Box<str>` that saves the capacity word of `String`.
+    BoxStr,
+    /// Copy-on-write `Cow<'static, str>` (always owned when returned).
+    CowStr,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::builder().build()
@@ -84,10 +675,11 @@ impl Config {
         if
         // Skip always forbidden attribute names
         FORBIDDEN_FUNCTION_NAMES.contains(&attr_name.as_py()) ||
-        // Skip private attributes if `include_private` is disabled
+        // Skip private attributes unless their tier is included
+        !self.is_private_attr_included(attr_name.as_py()) ||
+        // Skip attributes of private modules if `include_private` is disabled
         (!self.include_private &&
-            (attr_name.as_py().starts_with('_') ||
-             attr_module.iter().any(|segment| segment.as_py().starts_with('_')))) ||
+             attr_module.iter().any(|segment| segment.as_py().starts_with('_'))) ||
         // Skip blocklisted attributes
         self.blocklist_names.iter().any(|blocklist_match| {
             attr_name.as_py() == blocklist_match
@@ -102,4 +694,45 @@ impl Config {
             true
         }
     }
+
+    /// Get the Python version required by the item with the given path, unless the requirement is always met
+    /// because of `min_python_version`.
+    pub(crate) fn required_python_version(&self, path: &Path) -> Option<(u8, u8)> {
+        let path = path.to_py();
+        self.python_version_requirements
+            .iter()
+            .rev()
+            .find(|(requirement_path, _)| *requirement_path == path)
+            .map(|(_, version)| *version)
+            .filter(|version| {
+                self.min_python_version
+                    .map_or(true, |min_version| *version > min_version)
+            })
+    }
+
+    /// Check whether an attribute is included based on its visibility tier, i.e. dunder (`__len__`), name-mangled
+    /// (`_Class__secret`) or leading underscore (`_helper`). Public attributes are always included.
+    fn is_private_attr_included(&self, attr_name: &str) -> bool {
+        let is_dunder =
+            attr_name.len() > 4 && attr_name.starts_with("__") && attr_name.ends_with("__");
+        let is_name_mangled = !is_dunder
+            && attr_name
+                .strip_prefix('_')
+                .and_then(|name| name.split_once("__"))
+                .is_some_and(|(class_name, name)| {
+                    !class_name.is_empty()
+                        && !class_name.starts_with('_')
+                        && !name.is_empty()
+                        && !name.ends_with("__")
+                });
+        if is_dunder {
+            self.include_private || self.include_dunder.iter().any(|name| name == attr_name)
+        } else if is_name_mangled {
+            self.include_name_mangled
+        } else if attr_name.starts_with('_') {
+            self.include_private || self.include_underscore_attrs
+        } else {
+            true
+        }
+    }
 }
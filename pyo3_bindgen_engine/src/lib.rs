@@ -12,5 +12,15 @@ use utils::result::Result;
 
 // Public API re-exports
 pub use codegen::Codegen;
-pub use config::Config;
-pub use utils::{error::PyBindgenError, result::PyBindgenResult};
+pub use config::{
+    AbstractMethodHandling, AbstractPolicy, BodyMode, CollisionPolicy, Config, Edition, FloatType,
+    GetterRenaming, InitStrategy, IntType, LintAllowMode, NameSanitization, StringReturnType,
+};
+pub use utils::{
+    error::PyBindgenError,
+    report::{
+        CollisionReport, GenerationMetrics, GenerationReport, ItemKind, ItemMetrics, ModuleMetrics,
+        RenamedItem, SkippedItem, VerifiedVersion, Warning, WarningKind,
+    },
+    result::PyBindgenResult,
+};
@@ -2,7 +2,11 @@ use super::{
     AttributeVariant, Function, FunctionImplementation, FunctionType, Ident, MethodType, Path,
     Property, PropertyOwner, TraitMethod,
 };
-use crate::{Config, Result};
+use crate::{
+    typing::{Type, TypeQualifier},
+    utils::report::ReportSink,
+    Config, Result,
+};
 use itertools::Itertools;
 use pyo3::prelude::*;
 use rustc_hash::FxHashMap as HashMap;
@@ -14,11 +18,73 @@ pub struct Class {
     methods: Vec<Function>,
     properties: Vec<Property>,
     docstring: Option<String>,
+    defines_eq: bool,
+    defines_ord: bool,
+    is_hashable: bool,
+    is_sized: bool,
+    collection: Option<CollectionAbc>,
+    item_access: Option<ItemAccess>,
+    is_dataclass: bool,
+    enum_members: Option<Vec<Ident>>,
+    record: Option<Record>,
+    pub appended_code: Vec<String>,
+}
+
+/// Abstract base class of `collections.abc` to which a class conforms, alongside the types of its items.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CollectionAbc {
+    Mapping { key_type: Type, value_type: Type },
+    Sequence { item_type: Type },
+}
+
+/// Key and value types of the item access of a dict-like class via `__getitem__` and `__setitem__` (only
+/// dunder methods with annotated key and value types are considered).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ItemAccess {
+    get: Option<(Type, Type)>,
+    insert: Option<(Type, Type)>,
+}
+
+/// Fields of a record, i.e. a `typing.TypedDict` or a named tuple, which can be converted by value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Record {
+    is_typed_dict: bool,
+    fields: Vec<RecordField>,
+}
+
+/// Field of a record alongside its type and whether it is required (only keys of a `TypedDict` can be optional).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RecordField {
+    name: Ident,
+    annotation: Type,
+    is_required: bool,
+}
+
+impl CollectionAbc {
+    /// Get the abstract base class from `collections.abc`.
+    fn abc_class<'py>(&self, py: pyo3::Python<'py>) -> Result<Bound<'py, pyo3::types::PyAny>> {
+        let abc_name = match self {
+            Self::Mapping { .. } => pyo3::intern!(py, "Mapping"),
+            Self::Sequence { .. } => pyo3::intern!(py, "Sequence"),
+        };
+        Ok(py
+            .import_bound(pyo3::intern!(py, "collections.abc"))?
+            .getattr(abc_name)?)
+    }
+
+    /// Names of the generated typed helpers.
+    fn helper_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mapping { .. } => &["get", "keys", "values", "items"],
+            Self::Sequence { .. } => &["get_index", "len", "to_vec"],
+        }
+    }
 }
 
 impl Class {
     pub fn parse(
         cfg: &Config,
+        sink: &ReportSink,
         class: &pyo3::Bound<pyo3::types::PyType>,
         name: Path,
     ) -> Result<Self> {
@@ -29,10 +95,29 @@ impl Class {
         let mut methods = Vec::new();
         let mut properties = Vec::new();
 
-        // Extract the list of all attribute names in the module
-        class
-            .dir()
-            .iter()
+        // Extract the list of all attribute names in the class
+        // Note: Metaclasses can customize `dir()` in a way that fails, in which case only the attributes that are
+        //       defined by the class and its bases are considered
+        let builtins = py.import_bound(pyo3::intern!(py, "builtins"))?;
+        let attr_names = builtins
+            .call_method1(pyo3::intern!(py, "dir"), (class,))
+            .or_else(|err| {
+                sink.warn(
+                    crate::WarningKind::Other,
+                    &name,
+                    format!("Cannot list the attributes of '{name}' via `dir()` ({err}). Only the attributes defined by the class and its bases are considered."),
+                );
+                builtins.call_method1(
+                    pyo3::intern!(py, "sorted"),
+                    (py
+                        .get_type_bound::<pyo3::types::PyType>()
+                        .call_method1(pyo3::intern!(py, "__dir__"), (class,))?,),
+                )
+            })?;
+        attr_names
+            .iter()?
+            .collect::<PyResult<Vec<_>>>()?
+            .into_iter()
             // Convert each attribute name to an identifier
             .map(|attr_name| Ident::from_py(&attr_name.to_string()))
             .unique()
@@ -42,19 +127,19 @@ impl Class {
             // Expand each attribute to a tuple of (attr, attr_name, attr_module, attr_type)
             .filter_map(|attr_name| {
                 if let Ok(attr) = class.getattr(attr_name.as_py()) {
-
                     let attr_module = Path::from_py(
                         &attr
-                        .getattr(pyo3::intern!(py, "__module__"))
-                        .map(|a|a.to_string())
-                        .unwrap_or_default(),
+                            .getattr(pyo3::intern!(py, "__module__"))
+                            .map(|a| a.to_string())
+                            .unwrap_or_default(),
                     );
                     let attr_type = attr.get_type();
 
                     Some((attr, attr_name, attr_module, attr_type))
                 } else {
-                    eprintln!(
-                        "WARN: Cannot get attribute '{attr_name}' of '{name}' even though it is listed in its `__dir__`. Bindings will not be generated.",
+                    sink.skip(
+                        name.join(&attr_name.into()),
+                        "Cannot get attribute even though it is listed in `__dir__`",
                     );
                     None
                 }
@@ -67,62 +152,103 @@ impl Class {
             // Iterate over the remaining attributes and parse them
             .try_for_each(|(attr, attr_name, attr_module, attr_type)| {
                 let attr_name_full = name.join(&attr_name.clone().into());
-                match AttributeVariant::determine(py, &attr, &attr_type, &attr_module, &name, false)
-                    ?
-                {
+                if Self::is_provided_by_metaclass(class, &attr_name) {
+                    sink.skip(
+                        &attr_name_full,
+                        "Attributes provided dynamically by the metaclass are not supported",
+                    );
+                    return Ok(());
+                }
+                match AttributeVariant::determine(
+                    py,
+                    &attr,
+                    &attr_type,
+                    &attr_module,
+                    &name,
+                    false,
+                )? {
                     AttributeVariant::Import => {
-                        eprintln!("WARN: Imports in classes are not supported: '{name}.{attr_name}'. Bindings will not be generated.");
+                        sink.skip(&attr_name_full, "Imports in classes are not supported");
                     }
                     AttributeVariant::Module => {
-                        eprintln!(
-                            "WARN: Submodules in classes are not supported: '{name}.{attr_name}'. Bindings will not be generated.",
-                        );
+                        sink.skip(&attr_name_full, "Submodules in classes are not supported");
                     }
                     AttributeVariant::Class => {
                         // let subclass =
-                        //     Self::parse(cfg, attr.downcast()?, attr_name_full)?;
+                        //     Self::parse(cfg, sink, attr.downcast()?, attr_name_full)?;
                         // subclasses.push(subclass);
-                        eprintln!(
-                            "WARN: Subclasses in classes are not supported: '{name}.{attr_name}'. Bindings will not be generated.",
-                        );
+                        sink.skip(&attr_name_full, "Subclasses in classes are not supported");
                     }
                     AttributeVariant::Function | AttributeVariant::Method => {
                         let method = Function::parse(
                             cfg,
+                            sink,
                             &attr,
-                            attr_name_full,
+                            attr_name_full.clone(),
                             FunctionType::Method {
                                 class_path: name.clone(),
                                 typ: match attr_name.as_py() {
                                     "__init__" => MethodType::Constructor,
                                     "__call__" => MethodType::Callable,
-                                    _ => MethodType::Unknown,
+                                    _ => Self::determine_method_type(class, &attr_name),
                                 },
                             },
                         )
-                        ?;
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         methods.push(method);
                     }
                     AttributeVariant::Closure => {
-                        eprintln!("WARN: Closures are not supported in classes: '{attr_name}'. Bindings will not be generated.");
+                        sink.skip(&attr_name_full, "Closures in classes are not supported");
                     }
                     AttributeVariant::TypeVar => {
-                        eprintln!("WARN: TypesVars are not supported in classes: '{attr_name}'. Bindings will not be generated.");
+                        sink.skip(&attr_name_full, "TypeVars in classes are not supported");
                     }
                     AttributeVariant::Property => {
                         let property = Property::parse(
                             cfg,
+                            sink,
                             &attr,
-                            attr_name_full,
+                            attr_name_full.clone(),
                             PropertyOwner::Class,
+                            Self::class_annotation(class, &attr_name)?,
                         )
-                        ?;
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         properties.push(property);
                     }
                 }
                 Result::Ok(())
             })?;
 
+        // Instance attributes that are only declared via annotations in the class body (incl. its bases) have no
+        // class attribute, so they are not listed in `__dir__` and are accessed via the instance instead
+        let mut annotated_names = Vec::new();
+        for base in class.getattr(pyo3::intern!(py, "__mro__"))?.iter()? {
+            let Ok(annotations) = base?.getattr(pyo3::intern!(py, "__annotations__")) else {
+                continue;
+            };
+            let Ok(annotations) = annotations.downcast_into::<pyo3::types::PyDict>() else {
+                continue;
+            };
+            for (attr_name, annotation) in annotations.iter() {
+                let attr_name = Ident::from_py(&attr_name.to_string());
+                if annotated_names.contains(&attr_name)
+                    || class.hasattr(attr_name.as_py())?
+                    || !cfg.is_attr_allowed(&attr_name, &name, &annotation.get_type())
+                    // Class variables and init-only variables of dataclasses are not instance attributes
+                    || Type::strip_qualifier(annotation.clone())?.1 == Some(TypeQualifier::ClassVar)
+                    || annotation.to_string().contains("InitVar")
+                {
+                    continue;
+                }
+                let attr_name_full = name.join(&attr_name.clone().into());
+                let property =
+                    Property::from_annotation(cfg, sink, annotation, attr_name_full.clone())
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
+                properties.push(property);
+                annotated_names.push(attr_name);
+            }
+        }
+
         // Extract the docstring of the class
         let docstring = {
             let docstring = class.getattr(pyo3::intern!(py, "__doc__"))?.to_string();
@@ -133,18 +259,801 @@ impl Class {
             }
         };
 
+        // Determine which comparison operators are overridden by the class
+        let object = py
+            .import_bound(pyo3::intern!(py, "builtins"))?
+            .getattr(pyo3::intern!(py, "object"))?;
+        let overrides_object = |dunder: &Bound<pyo3::types::PyString>| {
+            class
+                .getattr(dunder)
+                .and_then(|attr| Ok(!attr.is(&object.getattr(dunder)?)))
+                .unwrap_or(false)
+        };
+        let defines_eq = overrides_object(pyo3::intern!(py, "__eq__"));
+        let defines_ord = overrides_object(pyo3::intern!(py, "__lt__"));
+        // Note: Python sets `__hash__` to `None` for classes that define `__eq__` without `__hash__`
+        let is_hashable = class
+            .getattr(pyo3::intern!(py, "__hash__"))
+            .is_ok_and(|hash| !hash.is_none());
+        let is_sized = class.hasattr(pyo3::intern!(py, "__len__"))?;
+
+        // Determine whether the class is a mapping or a sequence
+        let collection = Self::parse_collection_abc(class)?;
+
+        // Determine whether the class is a dataclass (affects the conversion from/into its dict representation)
+        let is_dataclass = py
+            .import_bound(pyo3::intern!(py, "dataclasses"))?
+            .call_method1(pyo3::intern!(py, "is_dataclass"), (class,))?
+            .is_truthy()?;
+
+        // The constructor of a dataclass subclass that chains to the generated `__init__` via `*args` and `**kwargs`
+        // takes the parameters of the generated `__init__`
+        if is_dataclass {
+            for method in &mut methods {
+                if let FunctionType::Method {
+                    typ: MethodType::Constructor,
+                    ..
+                } = method.typ
+                {
+                    method
+                        .infer_dataclass_parameters(cfg, sink, class)
+                        .map_err(|err| err.in_attribute(&method.name))?;
+                }
+            }
+        }
+
+        // Determine the members of enums and the fields of records (affects the conversion from/into Python by value)
+        let enum_members = if crate::typing::from_py::is_enum(class)? {
+            Some(
+                class
+                    .iter()?
+                    .map(|member| {
+                        Ok(Ident::from_py(
+                            &member?.getattr(pyo3::intern!(py, "name"))?.to_string(),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        } else {
+            None
+        };
+        let record = Self::parse_record(cfg, sink, class)?;
+
+        // Determine the typed item access of dict-like classes (if enabled)
+        let item_access =
+            if cfg.mapping_helpers && !matches!(collection, Some(CollectionAbc::Sequence { .. })) {
+                Self::parse_item_access(class)?
+            } else {
+                None
+            };
+
+        // Untyped mixin methods inherited from the ABC unchanged are superseded by the typed collection helpers
+        if let Some(collection) = &collection {
+            let abc = collection.abc_class(py)?;
+            methods.retain(|method| {
+                let method_name = method.name.name().as_py();
+                !(collection.helper_names().contains(&method_name)
+                    && class
+                        .getattr(method_name)
+                        .and_then(|attr| Ok(attr.is(&abc.getattr(method_name)?)))
+                        .unwrap_or(false))
+            });
+        }
+
+        // Resolve collisions of transliterated names among the methods and properties of the class
+        super::common::disambiguate(
+            methods
+                .iter_mut()
+                .map(|method| method.name.name_mut())
+                .chain(
+                    properties
+                        .iter_mut()
+                        .map(|property| property.name.name_mut()),
+                ),
+        );
+
+        Ok(Self {
+            name,
+            // subclasses,
+            methods,
+            properties,
+            docstring,
+            defines_eq,
+            defines_ord,
+            is_hashable,
+            is_sized,
+            collection,
+            item_access,
+            is_dataclass,
+            enum_members,
+            record,
+            appended_code: Vec::new(),
+        })
+    }
+
+    /// Parse the class from its `ast.ClassDef` node without evaluating it. The type of each method is determined
+    /// from its decorators, and inherited members are not considered.
+    pub fn from_ast(
+        cfg: &Config,
+        sink: &ReportSink,
+        node: &pyo3::Bound<pyo3::types::PyAny>,
+        name: Path,
+        scope: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let py = node.py();
+        let class_module = name.parent().unwrap_or_default();
+
+        // Initialize lists for all members of the class
+        let mut methods: Vec<Function> = Vec::new();
+        let mut properties: Vec<Property> = Vec::new();
+
+        // Extract the name and decorators of all functions defined in the class body
+        let body = super::common::ast::children(node, pyo3::intern!(py, "body"))?;
+        let function_defs = body
+            .iter()
+            .filter(|stmt| {
+                super::common::ast::is_node(stmt, &["FunctionDef", "AsyncFunctionDef"])
+                    .unwrap_or(false)
+            })
+            .map(|stmt| {
+                let attr_name =
+                    Ident::from_py(&stmt.getattr(pyo3::intern!(py, "name"))?.to_string());
+                let decorators =
+                    super::common::ast::children(stmt, pyo3::intern!(py, "decorator_list"))?
+                        .iter()
+                        .map(super::common::ast::unparse)
+                        .collect::<Result<Vec<_>>>()?;
+                Result::Ok((stmt, attr_name, decorators))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for stmt in &body {
+            if super::common::ast::is_node(stmt, &["FunctionDef", "AsyncFunctionDef"])? {
+                let (_, attr_name, decorators) = function_defs
+                    .iter()
+                    .find(|(function_def, ..)| function_def.is(stmt))
+                    .unwrap_or_else(|| unreachable!());
+                let attr_name_full = name.join(&attr_name.clone().into());
+                let has_decorator = |decorator: &str| decorators.iter().any(|d| d == decorator);
+
+                // Skip attributes based on various configurable conditions
+                if !(cfg.is_attr_allowed(attr_name, &class_module, &stmt.get_type())
+                    || ["__init__", "__call__"].contains(&attr_name.as_py()))
+                {
+                    continue;
+                }
+
+                // Skip overload signatures and property setters (parsed together with their getter)
+                if has_decorator("overload")
+                    || has_decorator("typing.overload")
+                    || has_decorator(&format!("{attr_name}.setter"))
+                    || has_decorator(&format!("{attr_name}.deleter"))
+                {
+                    continue;
+                }
+
+                if has_decorator("property") {
+                    let setter = function_defs
+                        .iter()
+                        .find(|(_, setter_name, setter_decorators)| {
+                            setter_name == attr_name
+                                && setter_decorators.contains(&format!("{attr_name}.setter"))
+                        })
+                        .map(|(setter, ..)| *setter);
+                    let property = Property::from_ast_accessors(
+                        cfg,
+                        sink,
+                        stmt,
+                        setter,
+                        attr_name_full.clone(),
+                        scope,
+                    )
+                    .map_err(|err| err.in_attribute(&attr_name_full))?;
+                    properties.retain(|property| property.name != attr_name_full);
+                    properties.push(property);
+                } else {
+                    let method = Function::from_ast(
+                        cfg,
+                        sink,
+                        stmt,
+                        attr_name_full.clone(),
+                        FunctionType::Method {
+                            class_path: name.clone(),
+                            typ: match attr_name.as_py() {
+                                "__init__" => MethodType::Constructor,
+                                "__call__" => MethodType::Callable,
+                                _ if has_decorator("classmethod") => MethodType::ClassMethod,
+                                _ if has_decorator("staticmethod") => MethodType::StaticMethod,
+                                _ => MethodType::InstanceMethod,
+                            },
+                        },
+                        scope,
+                    )
+                    .map_err(|err| err.in_attribute(&attr_name_full))?;
+                    // Later definitions replace earlier ones, like during the evaluation of the class body
+                    methods.retain(|method| method.name != attr_name_full);
+                    methods.push(method);
+                }
+            } else if super::common::ast::is_node(stmt, &["AnnAssign", "Assign"])? {
+                let (targets, annotation) = if super::common::ast::is_node(stmt, &["AnnAssign"])? {
+                    (
+                        vec![stmt.getattr(pyo3::intern!(py, "target"))?],
+                        Some(stmt.getattr(pyo3::intern!(py, "annotation"))?),
+                    )
+                } else {
+                    (
+                        super::common::ast::children(stmt, pyo3::intern!(py, "targets"))?,
+                        None,
+                    )
+                };
+                let value = Some(stmt.getattr(pyo3::intern!(py, "value"))?)
+                    .filter(|value| !value.is_none());
+                for target in targets {
+                    // Only assignments to plain names define class attributes
+                    if !super::common::ast::is_node(&target, &["Name"])? {
+                        continue;
+                    }
+                    let attr_name =
+                        Ident::from_py(&target.getattr(pyo3::intern!(py, "id"))?.to_string());
+                    if !cfg.is_attr_allowed(&attr_name, &class_module, &target.get_type()) {
+                        continue;
+                    }
+                    let attr_name_full = name.join(&attr_name.into());
+                    let property = Property::from_ast_variable(
+                        cfg,
+                        sink,
+                        annotation.as_ref(),
+                        value.as_ref(),
+                        attr_name_full.clone(),
+                        PropertyOwner::Class,
+                        scope,
+                    )
+                    .map_err(|err| err.in_attribute(&attr_name_full))?;
+                    properties.retain(|property| property.name != attr_name_full);
+                    properties.push(property);
+                }
+            } else if super::common::ast::is_node(stmt, &["ClassDef"])? {
+                let attr_name = stmt.getattr(pyo3::intern!(py, "name"))?.to_string();
+                sink.skip(
+                    name.join(&Path::from_py(&attr_name)),
+                    "Subclasses in classes are not supported",
+                );
+            }
+        }
+
+        // Extract the docstring of the class
+        let docstring = super::common::ast::docstring(node)?;
+
+        // Determine whether the class is decorated as a dataclass (with or without arguments)
+        let is_dataclass = super::common::ast::children(node, pyo3::intern!(py, "decorator_list"))?
+            .iter()
+            .map(super::common::ast::unparse)
+            .collect::<Result<Vec<_>>>()?
+            .iter()
+            .any(|decorator| {
+                let decorator = decorator.split('(').next().unwrap_or_default();
+                decorator == "dataclass" || decorator.ends_with(".dataclass")
+            });
+
+        // Determine which comparison operators are defined in the class body
+        let defines = |dunder: &str| {
+            function_defs
+                .iter()
+                .any(|(_, attr_name, _)| attr_name.as_py() == dunder)
+        };
+        let defines_eq = defines("__eq__");
+        let defines_ord = defines("__lt__");
+        // Note: Python sets `__hash__` to `None` for classes that define `__eq__` without `__hash__`
+        let is_hashable = defines("__hash__") || !defines_eq;
+        let is_sized = defines("__len__");
+
+        // Resolve collisions of transliterated names among the methods and properties of the class
+        super::common::disambiguate(
+            methods
+                .iter_mut()
+                .map(|method| method.name.name_mut())
+                .chain(
+                    properties
+                        .iter_mut()
+                        .map(|property| property.name.name_mut()),
+                ),
+        );
+
         Ok(Self {
             name,
             // subclasses,
             methods,
             properties,
             docstring,
+            defines_eq,
+            defines_ord,
+            is_hashable,
+            is_sized,
+            collection: None,
+            item_access: None,
+            is_dataclass,
+            enum_members: None,
+            record: None,
+            appended_code: Vec::new(),
+        })
+    }
+
+    /// Determine whether the class conforms to `collections.abc.Mapping` or `collections.abc.Sequence`. The types
+    /// of keys, values and items are derived from the annotations of `__getitem__` and `__iter__` (if available).
+    fn parse_collection_abc(
+        class: &pyo3::Bound<pyo3::types::PyType>,
+    ) -> Result<Option<CollectionAbc>> {
+        let py = class.py();
+        let abc = py.import_bound(pyo3::intern!(py, "collections.abc"))?;
+        let is_subclass = |abc_name: &Bound<pyo3::types::PyString>| {
+            abc.getattr(abc_name)
+                .and_then(|abc_class| class.is_subclass(&abc_class))
+                .unwrap_or(false)
+        };
+        let is_mapping = is_subclass(pyo3::intern!(py, "Mapping"));
+        if !is_mapping && !is_subclass(pyo3::intern!(py, "Sequence")) {
+            return Ok(None);
+        }
+
+        // Extract the annotations of the dunder methods (unannotated types are unknown)
+        let (getitem_param_type, getitem_return_type) =
+            Self::dunder_annotations(class, pyo3::intern!(py, "__getitem__"))
+                .map(|(mut param_types, return_type)| {
+                    (
+                        param_types.drain(..).next().unwrap_or(Type::Unknown),
+                        return_type,
+                    )
+                })
+                .unwrap_or((Type::Unknown, Type::Unknown));
+        let iter_item_type = match Self::dunder_annotations(class, pyo3::intern!(py, "__iter__")) {
+            Some((_, Type::PyList(item_type))) => *item_type,
+            _ => Type::Unknown,
+        };
+        let or_fallback = |typ: Type, fallback: Type| {
+            if typ == Type::Unknown {
+                fallback
+            } else {
+                typ
+            }
+        };
+
+        Ok(Some(if is_mapping {
+            CollectionAbc::Mapping {
+                key_type: or_fallback(getitem_param_type, iter_item_type),
+                value_type: getitem_return_type,
+            }
+        } else {
+            CollectionAbc::Sequence {
+                item_type: or_fallback(getitem_return_type, iter_item_type),
+            }
+        }))
+    }
+
+    /// Determine the key and value types of `__getitem__` and `__setitem__` of a dict-like class. Returns `None`
+    /// if neither of the dunder methods has annotated key and value types.
+    fn parse_item_access(class: &pyo3::Bound<pyo3::types::PyType>) -> Result<Option<ItemAccess>> {
+        let py = class.py();
+        let is_known = |typ: &Type| *typ != Type::Unknown;
+        let get = Self::dunder_annotations(class, pyo3::intern!(py, "__getitem__")).and_then(
+            |(param_types, return_type)| match param_types.as_slice() {
+                [key_type] if is_known(key_type) && is_known(&return_type) => {
+                    Some((key_type.clone(), return_type))
+                }
+                _ => None,
+            },
+        );
+        let insert = Self::dunder_annotations(class, pyo3::intern!(py, "__setitem__")).and_then(
+            |(param_types, _)| match param_types.as_slice() {
+                [key_type, value_type] if is_known(key_type) && is_known(value_type) => {
+                    Some((key_type.clone(), value_type.clone()))
+                }
+                _ => None,
+            },
+        );
+        Ok((get.is_some() || insert.is_some()).then_some(ItemAccess { get, insert }))
+    }
+
+    /// Parse the fields of a record, i.e. the keys of a `typing.TypedDict` or the fields of a named tuple
+    /// (untyped fields of `collections.namedtuple` are unknown).
+    fn parse_record(
+        cfg: &Config,
+        sink: &ReportSink,
+        class: &pyo3::Bound<pyo3::types::PyType>,
+    ) -> Result<Option<Record>> {
+        let py = class.py();
+        let is_typed_dict = crate::typing::from_py::is_typed_dict(class)?;
+        if !is_typed_dict && !crate::typing::from_py::is_named_tuple(class)? {
+            return Ok(None);
+        }
+
+        let annotations = class
+            .getattr(pyo3::intern!(py, "__annotations__"))
+            .ok()
+            .and_then(|annotations| annotations.downcast_into::<pyo3::types::PyDict>().ok());
+        let field_names: Vec<String> = if is_typed_dict {
+            annotations
+                .iter()
+                .flat_map(|annotations| annotations.keys())
+                .map(|key| key.to_string())
+                .collect()
+        } else {
+            class.getattr(pyo3::intern!(py, "_fields"))?.extract()?
+        };
+        let required_keys = if is_typed_dict {
+            Some(class.getattr(pyo3::intern!(py, "__required_keys__"))?)
+        } else {
+            None
+        };
+
+        let fields = field_names
+            .into_iter()
+            .map(|field_name| {
+                let annotation = match annotations
+                    .as_ref()
+                    .map(|annotations| annotations.get_item(&field_name))
+                    .transpose()?
+                    .flatten()
+                {
+                    Some(annotation) => {
+                        Type::from_py_with_max_depth(annotation, cfg.max_type_depth, sink)?
+                    }
+                    None => Type::Unknown,
+                };
+                let is_required = match &required_keys {
+                    Some(required_keys) => required_keys.contains(&field_name)?,
+                    None => true,
+                };
+                Ok(RecordField {
+                    name: Ident::from_py(&field_name),
+                    annotation,
+                    is_required,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Record {
+            is_typed_dict,
+            fields,
+        }))
+    }
+
+    /// Get the annotation of the attribute in the class body of the class or any of its bases (if any).
+    fn class_annotation<'py>(
+        class: &pyo3::Bound<'py, pyo3::types::PyType>,
+        attr_name: &Ident,
+    ) -> Result<Option<pyo3::Bound<'py, pyo3::types::PyAny>>> {
+        let py = class.py();
+        for base in class.getattr(pyo3::intern!(py, "__mro__"))?.iter()? {
+            let Ok(annotations) = base?.getattr(pyo3::intern!(py, "__annotations__")) else {
+                continue;
+            };
+            let Ok(annotations) = annotations.downcast_into::<pyo3::types::PyDict>() else {
+                continue;
+            };
+            if let Some(annotation) = annotations.get_item(attr_name.as_py())? {
+                return Ok(Some(annotation));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Extract the annotations of the parameters (excluding `self`) and the return type of a dunder method of
+    /// the class. Unannotated types are unknown. Returns `None` if the signature of the method is unavailable.
+    fn dunder_annotations(
+        class: &pyo3::Bound<pyo3::types::PyType>,
+        dunder: &Bound<pyo3::types::PyString>,
+    ) -> Option<(Vec<Type>, Type)> {
+        let py = class.py();
+        (|| -> Result<(Vec<Type>, Type)> {
+            let inspect = py.import_bound(pyo3::intern!(py, "inspect"))?;
+            let signature =
+                inspect.call_method1(pyo3::intern!(py, "signature"), (class.getattr(dunder)?,))?;
+            let empty = inspect
+                .getattr(pyo3::intern!(py, "Signature"))?
+                .getattr(pyo3::intern!(py, "empty"))?;
+            let into_type = |annotation: Bound<pyo3::types::PyAny>| -> Type {
+                if annotation.is(&empty) {
+                    Type::Unknown
+                } else {
+                    annotation.try_into().unwrap_or(Type::Unknown)
+                }
+            };
+            let param_types = signature
+                .getattr(pyo3::intern!(py, "parameters"))?
+                .call_method0(pyo3::intern!(py, "values"))?
+                .iter()?
+                .skip(1)
+                .map(|parameter| {
+                    Ok(into_type(
+                        parameter?.getattr(pyo3::intern!(py, "annotation"))?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let return_type = into_type(signature.getattr(pyo3::intern!(py, "return_annotation"))?);
+            Ok((param_types, return_type))
+        })()
+        .ok()
+    }
+
+    /// Determine whether the attribute is provided dynamically by a custom metaclass (e.g. via its `__getattr__`
+    /// or by listing it in its `__dir__`), i.e. it is not defined by the class or any of its bases. Such attributes
+    /// are only accessible from the class object, but not from its instances.
+    fn is_provided_by_metaclass(
+        class: &pyo3::Bound<pyo3::types::PyType>,
+        attr_name: &Ident,
+    ) -> bool {
+        let py = class.py();
+        if class
+            .get_type()
+            .is(&py.get_type_bound::<pyo3::types::PyType>())
+        {
+            return false;
+        }
+        class
+            .getattr(pyo3::intern!(py, "__mro__"))
+            .and_then(|mro| {
+                for base in mro.iter()? {
+                    if base?
+                        .getattr(pyo3::intern!(py, "__dict__"))?
+                        .contains(attr_name.as_py())?
+                    {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Determine the type of a method from its static attribute in the class, i.e. before Python's
+    /// descriptor protocol is applied. Returns `MethodType::Unknown` if the type cannot be determined.
+    ///
+    /// Note: A plain function stored in the class is bound to the instance upon attribute access, so it is
+    ///       an instance method regardless of the name of its first parameter. This also covers functions that
+    ///       are created outside of the class body, e.g. `method = make_method()`.
+    fn determine_method_type(
+        class: &pyo3::Bound<pyo3::types::PyType>,
+        attr_name: &Ident,
+    ) -> MethodType {
+        let py = class.py();
+        let Ok(static_attr) = py
+            .import_bound(pyo3::intern!(py, "inspect"))
+            .and_then(|inspect| {
+                inspect.call_method1(
+                    pyo3::intern!(py, "getattr_static"),
+                    (class, attr_name.as_py()),
+                )
+            })
+        else {
+            return MethodType::Unknown;
+        };
+        let is_instance_of = |module_name: &str, type_name: &str| {
+            py.import_bound(module_name)
+                .and_then(|module| module.getattr(type_name))
+                .and_then(|typ| static_attr.is_instance(&typ))
+                .unwrap_or(false)
+        };
+        if is_instance_of("builtins", "classmethod") {
+            MethodType::ClassMethod
+        } else if is_instance_of("builtins", "staticmethod") {
+            MethodType::StaticMethod
+        } else if is_instance_of("types", "FunctionType") {
+            MethodType::InstanceMethod
+        } else {
+            MethodType::Unknown
+        }
+    }
+
+    pub(crate) fn methods(&self) -> &[Function] {
+        &self.methods
+    }
+
+    pub(crate) fn methods_mut(&mut self) -> &mut [Function] {
+        &mut self.methods
+    }
+
+    pub(crate) fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    /// Iterate mutably over the type annotations of all methods, properties, collection items and item access.
+    pub(crate) fn annotations_mut(&mut self) -> impl Iterator<Item = &mut Type> {
+        let collection_types = match &mut self.collection {
+            Some(CollectionAbc::Mapping {
+                key_type,
+                value_type,
+            }) => vec![key_type, value_type],
+            Some(CollectionAbc::Sequence { item_type }) => vec![item_type],
+            None => Vec::new(),
+        };
+        let item_access_types = self
+            .item_access
+            .iter_mut()
+            .flat_map(|item_access| item_access.get.iter_mut().chain(&mut item_access.insert))
+            .flat_map(|(key_type, value_type)| [key_type, value_type]);
+        self.methods
+            .iter_mut()
+            .flat_map(Function::annotations_mut)
+            .chain(
+                self.properties
+                    .iter_mut()
+                    .flat_map(Property::annotations_mut),
+            )
+            .chain(collection_types)
+            .chain(item_access_types)
+    }
+
+    /// Check whether the class defines `__call__`.
+    pub fn is_callable(&self) -> bool {
+        self.methods.iter().any(|method| {
+            matches!(
+                method.typ,
+                FunctionType::Method {
+                    typ: MethodType::Callable,
+                    ..
+                }
+            )
+        })
+    }
+
+    /// Relax the parameters of all methods that are annotated with one of the `callable_types`.
+    pub fn relax_callable_class_params(&mut self, callable_types: &[Path]) {
+        self.methods
+            .iter_mut()
+            .for_each(|method| method.relax_callable_class_params(callable_types));
+    }
+
+    /// Get the type that the class is converted to by value (if any), i.e. the members of enums and records
+    /// whose members and fields are valid Rust idents (see `Config::emit_into_py_object`).
+    pub(crate) fn by_value_type(&self) -> Option<Type> {
+        let is_valid = |ident: &Ident| syn::Ident::try_from(ident.clone()).is_ok();
+        if let Some(enum_members) = &self.enum_members {
+            (!enum_members.is_empty() && enum_members.iter().all(is_valid))
+                .then(|| Type::EnumMember(self.name.to_py()))
+        } else if let Some(record) = &self.record {
+            record
+                .fields
+                .iter()
+                .all(|field| is_valid(&field.name))
+                .then(|| {
+                    if record.is_typed_dict {
+                        Type::TypedDict(self.name.to_py())
+                    } else {
+                        Type::NamedTuple(self.name.to_py())
+                    }
+                })
+        } else {
+            None
+        }
+    }
+
+    /// Pass the annotations of all methods and properties of the `by_value_types` by value, whereas the types of
+    /// collection items and item access keep the Python representation of records.
+    pub(crate) fn resolve_by_value_types(&mut self, by_value_types: &HashMap<Path, Type>) {
+        self.methods
+            .iter_mut()
+            .for_each(|method| method.resolve_by_value_types(by_value_types));
+        self.properties
+            .iter_mut()
+            .flat_map(Property::annotations_mut)
+            .for_each(|annotation| annotation.resolve_by_value_types(by_value_types));
+        let collection_types = match &mut self.collection {
+            Some(CollectionAbc::Mapping {
+                key_type,
+                value_type,
+            }) => vec![key_type, value_type],
+            Some(CollectionAbc::Sequence { item_type }) => vec![item_type],
+            None => Vec::new(),
+        };
+        collection_types
+            .into_iter()
+            .chain(
+                self.item_access
+                    .iter_mut()
+                    .flat_map(|item_access| {
+                        item_access.get.iter_mut().chain(&mut item_access.insert)
+                    })
+                    .flat_map(|(key_type, value_type)| [key_type, value_type]),
+            )
+            .for_each(|annotation| annotation.resolve_by_value_types(&HashMap::default()));
+    }
+
+    /// Export the structure of the class as a schema object.
+    pub fn export_schema(&self) -> serde_json::Value {
+        let properties = self
+            .properties
+            .iter()
+            .map(|property| {
+                (
+                    property.name.name().as_py().to_owned(),
+                    property.export_schema(),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| {
+                (
+                    method.name.name().as_py().to_owned(),
+                    method.export_schema(),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "type": "object",
+            "docstring": self.docstring,
+            "properties": properties,
+            "methods": methods,
+        })
+    }
+
+    /// Render everything about the class that the generated bindings depend on as a stable snapshot.
+    pub(crate) fn snapshot(&self) -> serde_json::Value {
+        let collection = self.collection.as_ref().map(|collection| match collection {
+            CollectionAbc::Mapping {
+                key_type,
+                value_type,
+            } => {
+                serde_json::json!({ "Mapping": [key_type.to_snapshot(), value_type.to_snapshot()] })
+            }
+            CollectionAbc::Sequence { item_type } => {
+                serde_json::json!({ "Sequence": item_type.to_snapshot() })
+            }
+        });
+        let item_access = self.item_access.as_ref().map(|item_access| {
+            let snapshot = |types: &Option<(Type, Type)>| {
+                types.as_ref().map(|(key_type, value_type)| {
+                    [key_type.to_snapshot(), value_type.to_snapshot()]
+                })
+            };
+            serde_json::json!({
+                "get": snapshot(&item_access.get),
+                "insert": snapshot(&item_access.insert),
+            })
+        });
+        let record = self.record.as_ref().map(|record| {
+            let fields = record
+                .fields
+                .iter()
+                .map(|field| {
+                    serde_json::json!({
+                        "name": field.name.as_py(),
+                        "annotation": field.annotation.to_snapshot(),
+                        "is_required": field.is_required,
+                    })
+                })
+                .collect::<Vec<_>>();
+            serde_json::json!({
+                "is_typed_dict": record.is_typed_dict,
+                "fields": fields,
+            })
+        });
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "methods": self.methods.iter().map(Function::snapshot).collect::<Vec<_>>(),
+            "properties": self.properties.iter().map(Property::snapshot).collect::<Vec<_>>(),
+            "docstring": self.docstring,
+            "defines_eq": self.defines_eq,
+            "defines_ord": self.defines_ord,
+            "is_hashable": self.is_hashable,
+            "is_sized": self.is_sized,
+            "collection": collection,
+            "item_access": item_access,
+            "is_dataclass": self.is_dataclass,
+            "enum_members": self
+                .enum_members
+                .as_ref()
+                .map(|members| members.iter().map(Ident::as_py).collect::<Vec<_>>()),
+            "record": record,
+            "appended_code": self.appended_code,
         })
     }
 
     pub fn generate(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         local_types: &HashMap<Path, Path>,
     ) -> Result<proc_macro2::TokenStream> {
         let mut output = proc_macro2::TokenStream::new();
@@ -152,11 +1061,23 @@ impl Class {
         // Documentation
         if cfg.generate_docs {
             if let Some(mut docstring) = self.docstring.clone() {
-                crate::utils::text::format_docstring(&mut docstring);
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
                 output.extend(quote::quote! {
                     #[doc = #docstring]
                 });
             }
+            if cfg.thread_docs {
+                if self.docstring.is_some() {
+                    output.extend(quote::quote! {
+                        #[doc = ""]
+                    });
+                }
+                let thread_note = Self::thread_note(cfg, self.name.name());
+                let thread_note = thread_note.split('\n');
+                output.extend(quote::quote! {
+                    #(#[doc = #thread_note])*
+                });
+            }
         }
 
         // Generate the struct
@@ -171,22 +1092,30 @@ impl Class {
                     name.as_py().replace(|c: char| !c.is_alphanumeric(), "_")
                 ));
                 if let Ok(sanitized_ident) = new_name.clone().try_into() {
-                    eprintln!(
-                        "WARN: Struct '{}' is an invalid Rust ident for a struct name. Renamed to '{}'.",
-                        self.name, self.name.parent().unwrap_or_default().join(&new_name.into())
-                    );
+                    cfg.sanitization.handle(
+                        sink,
+                        &self.name,
+                        "Struct",
+                        Some(
+                            &self
+                                .name
+                                .parent()
+                                .unwrap_or_default()
+                                .join(&new_name.into()),
+                        ),
+                    )?;
                     sanitized_ident
                 } else {
-                    eprintln!(
-                        "WARN: Struct '{}' is an invalid Rust ident for a struct name. Renaming failed. Bindings will not be generated.",
-                        self.name
-                    );
+                    cfg.sanitization.handle(sink, &self.name, "Struct", None)?;
+                    sink.skip(&self.name, "Invalid Rust ident for a struct name");
                     return Ok(proc_macro2::TokenStream::new());
                 }
             }
         };
+        let allow = crate::utils::lint::allow_struct(cfg, &struct_ident, []);
         output.extend(quote::quote! {
             #[repr(transparent)]
+            #allow
             pub struct #struct_ident(::pyo3::PyAny);
         });
 
@@ -214,21 +1143,24 @@ impl Class {
         let mut method_defs = proc_macro2::TokenStream::new();
         let mut method_impls = proc_macro2::TokenStream::new();
         // Methods
-        self.methods
-            .iter()
-            .map(|method| method.generate(cfg, &scoped_function_idents, local_types))
-            .try_for_each(|def| {
-                match def? {
-                    FunctionImplementation::Function(impl_fn) => {
-                        struct_impl.extend(impl_fn);
-                    }
-                    FunctionImplementation::Method(TraitMethod { trait_fn, impl_fn }) => {
-                        method_defs.extend(trait_fn);
-                        method_impls.extend(impl_fn);
-                    }
+        self.methods.iter().try_for_each(|method| {
+            let def = method.generate(cfg, sink, &scoped_function_idents, local_types)?;
+            let record = |tokens: &proc_macro2::TokenStream| {
+                sink.record_item(&method.name, crate::utils::report::ItemKind::Method, tokens);
+            };
+            match def {
+                FunctionImplementation::Function(impl_fn) => {
+                    record(&impl_fn);
+                    struct_impl.extend(impl_fn);
                 }
-                Result::Ok(())
-            })?;
+                FunctionImplementation::Method(TraitMethod { trait_fn, impl_fn }) => {
+                    record(&quote::quote! { #trait_fn #impl_fn });
+                    method_defs.extend(trait_fn);
+                    method_impls.extend(impl_fn);
+                }
+            }
+            Result::Ok(())
+        })?;
         // Properties
         {
             let mut scoped_function_idents_extra = Vec::with_capacity(2);
@@ -241,7 +1173,7 @@ impl Class {
                     }
                 )
             }) {
-                scoped_function_idents_extra.push(Ident::from_py("new"));
+                scoped_function_idents_extra.push(Ident::from_py(&cfg.constructor_name));
             }
             if self.methods.iter().any(|method| {
                 matches!(
@@ -257,7 +1189,7 @@ impl Class {
             scoped_function_idents.extend(scoped_function_idents_extra.iter());
             self.properties
                 .iter()
-                .map(|property| property.generate(cfg, &scoped_function_idents, local_types))
+                .map(|property| property.generate(cfg, sink, &scoped_function_idents, local_types))
                 .try_for_each(|def| {
                     match def? {
                         FunctionImplementation::Function(impl_fn) => {
@@ -270,26 +1202,145 @@ impl Class {
                     }
                     Result::Ok(())
                 })?;
+            // Plain class attributes are also readable from the class itself
+            for property in &self.properties {
+                struct_impl.extend(property.generate_class_attribute_getter(
+                    cfg,
+                    sink,
+                    &scoped_function_idents,
+                    local_types,
+                )?);
+            }
+        }
+
+        // Comparisons
+        if cfg.impl_comparisons {
+            let comparisons = self.generate_comparisons(cfg, &struct_ident);
+            method_defs.extend(comparisons.trait_fn);
+            method_impls.extend(comparisons.impl_fn);
+        }
+
+        // Collection helpers
+        if let Some(collection) = &self.collection {
+            let helpers = self.generate_collection_helpers(cfg, sink, collection, local_types)?;
+            method_defs.extend(helpers.trait_fn);
+            method_impls.extend(helpers.impl_fn);
+        }
+
+        // Item access helpers
+        if let Some(item_access) = &self.item_access {
+            let helpers = self.generate_item_access_helpers(cfg, sink, item_access, local_types)?;
+            method_defs.extend(helpers.trait_fn);
+            method_impls.extend(helpers.impl_fn);
+        }
+
+        // Size of containers
+        if cfg.emit_len_method && self.is_sized {
+            let helpers = self.generate_len_method(cfg, sink)?;
+            method_defs.extend(helpers.trait_fn);
+            method_impls.extend(helpers.impl_fn);
+        }
+
+        // Conversion from/into the dict representation
+        if cfg.dict_helpers {
+            let helpers = self.generate_dict_helpers(cfg, sink)?;
+            method_defs.extend(helpers.trait_fn);
+            method_impls.extend(helpers.impl_fn);
+        }
+
+        // Dynamic attribute access
+        if cfg.emit_getattr_fallback {
+            let fallback = self.generate_getattr_fallback(cfg, sink)?;
+            method_defs.extend(fallback.trait_fn);
+            method_impls.extend(fallback.impl_fn);
+        }
+
+        // Conversion into a `Py<T>` that is not bound to the GIL lifetime
+        if cfg.emit_shared_methods
+            && !self
+                .methods
+                .iter()
+                .map(|method| &method.name)
+                .chain(self.properties.iter().map(|property| &property.name))
+                .any(|path| path.name().as_rs() == "into_py_shared")
+        {
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::Bound::unbind(self_)
+                }
+            });
+            struct_impl.extend(quote::quote! {
+                /// Unbind the object from the lifetime of the GIL, so that it can be stored and sent across threads.
+                pub fn into_py_shared(self_: ::pyo3::Bound<'_, Self>) -> ::pyo3::Py<Self> #body
+            });
+        }
+
+        // Appended code
+        for rust_code in &self.appended_code {
+            struct_impl.extend(super::common::appended_code::parse_impl_items(
+                &self.name, rust_code,
+            )?);
         }
 
         // Add the implementation block for the struct
         output.extend(quote::quote! {
-            #[automatically_derived]
             impl #struct_ident {
                 #struct_impl
             }
         });
 
+        // Builder for the constructor (not available for classes that are looked up through a module handle)
+        if cfg.emit_builder_for_constructors && !cfg.module_handle_injection {
+            let scoped_function_idents = self
+                .methods
+                .iter()
+                .map(|method| method.name.name())
+                .chain(self.properties.iter().map(|property| property.name.name()))
+                .collect::<Vec<_>>();
+            self.methods.iter().try_for_each(|method| {
+                output.extend(method.generate_builder(
+                    cfg,
+                    sink,
+                    &struct_ident,
+                    &scoped_function_idents,
+                    local_types,
+                )?);
+                Result::Ok(())
+            })?;
+        }
+
         // Add the trait and implementation block for bounded struct
         let trait_ident: syn::Ident =
             Ident::from_py(&format!("{struct_ident}Methods")).try_into()?;
+
+        // Send handle that acquires the GIL internally
+        if cfg.emit_send_wrappers {
+            output.extend(Self::generate_send_handle(
+                cfg,
+                &struct_ident,
+                &trait_ident,
+                &method_defs,
+            )?);
+        }
+
+        // Extension trait for `Py<T>` with an explicit `py` token
+        if cfg.emit_shared_methods {
+            output.extend(Self::generate_shared_methods(
+                cfg,
+                &struct_ident,
+                &trait_ident,
+                &method_defs,
+            )?);
+        }
+
         let struct_ident_str = struct_ident.to_string();
+        let allow = crate::utils::lint::allow_type(cfg, &trait_ident, []);
         output.extend(quote::quote! {
             /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
             /// method call syntax these methods are separated into a trait, because stable
             /// Rust does not yet support `arbitrary_self_types`.
             #[doc(alias = #struct_ident_str)]
-            #[automatically_derived]
+            #allow
             pub trait #trait_ident {
                 #method_defs
             }
@@ -299,6 +1350,1096 @@ impl Class {
             }
         });
 
+        // By-value conversions of enum members and records
+        if cfg.emit_into_py_object {
+            output.extend(self.generate_enum_members(cfg, sink, &struct_ident)?);
+            output.extend(self.generate_record(cfg, sink, &struct_ident, local_types)?);
+        }
+
+        sink.record_item(&self.name, crate::utils::report::ItemKind::Class, &output);
         Ok(output)
     }
+
+    /// Generate a Rust enum `XMember` with the members of a Python enum, which implements `FromPyObject` and is
+    /// converted into the Python member via `into_bound`, so that the members can be passed to and returned from
+    /// Python by value.
+    fn generate_enum_members(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        struct_ident: &syn::Ident,
+    ) -> Result<proc_macro2::TokenStream> {
+        let Some(enum_members) = self
+            .enum_members
+            .as_ref()
+            .filter(|members| !members.is_empty())
+        else {
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let Ok(variant_idents) = enum_members
+            .iter()
+            .map(|member| syn::Ident::try_from(member.clone()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+        else {
+            sink.warn(
+                crate::WarningKind::Other,
+                &self.name,
+                format!(
+                    "Enum '{}' has members that are invalid Rust idents for enum variants. By-value conversions will not be generated.",
+                    self.name
+                ),
+            );
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let member_names = enum_members.iter().map(Ident::as_py).collect_vec();
+        let enum_ident: syn::Ident = Ident::from_py(&format!("{struct_ident}Member")).try_into()?;
+
+        let object_name = self.name.to_py();
+        let unknown_member_msg = format!("`{{name}}` is not a member of `{object_name}`");
+        let foreign_member_msg = format!("the object is not a member of `{object_name}`");
+        let class_import = pyo3::Python::with_gil(|py| self.name.import_quote(py));
+        let into_bound_body = cfg.bodies.body(quote::quote! {
+            {
+                let name = match self {
+                    #(Self::#variant_idents => #member_names,)*
+                };
+                ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::getattr(
+                    #class_import.as_any(),
+                    name,
+                )?)
+            }
+        });
+        let extract_body = cfg.bodies.body(quote::quote! {
+            {
+                let py = object.py();
+                let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(object, ::pyo3::intern!(py, "name"))?,
+                )?;
+                let member = match name.as_str() {
+                    #(#member_names => Self::#variant_idents,)*
+                    _ => {
+                        return ::std::result::Result::Err(::pyo3::exceptions::PyValueError::new_err(
+                            ::std::format!(#unknown_member_msg),
+                        ))
+                    }
+                };
+                if !::pyo3::types::PyAnyMethods::is(member.into_bound(py)?.as_any(), object) {
+                    return ::std::result::Result::Err(::pyo3::exceptions::PyValueError::new_err(
+                        #foreign_member_msg,
+                    ));
+                }
+                ::std::result::Result::Ok(member)
+            }
+        });
+
+        let doc = if cfg.generate_docs {
+            let docstring = format!(
+                " Members of the Python enum [`{struct_ident}`], which can be passed to and returned from Python by value."
+            );
+            quote::quote! { #[doc = #docstring] }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        let allow = crate::utils::lint::allow_type(cfg, &enum_ident, &variant_idents);
+        Ok(quote::quote! {
+            #doc
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #allow
+            pub enum #enum_ident {
+                #(#variant_idents,)*
+            }
+            impl #enum_ident {
+                /// Get the corresponding member of the Python enum.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, #struct_ident>> #into_bound_body
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for #enum_ident {
+                fn extract_bound(object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>) -> ::pyo3::PyResult<Self> #extract_body
+            }
+        })
+    }
+
+    /// Generate a Rust struct `XRecord` with the fields of a record (`typing.TypedDict` or named tuple), which
+    /// implements `FromPyObject` and is converted into the Python record via `into_bound`, so that records can be
+    /// passed to and returned from Python by value. Fields of types that borrow from the GIL are kept as `PyObject`.
+    fn generate_record(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        struct_ident: &syn::Ident,
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        let Some(record) = &self.record else {
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let Ok(field_idents) = record
+            .fields
+            .iter()
+            .map(|field| syn::Ident::try_from(field.name.clone()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+        else {
+            sink.warn(
+                crate::WarningKind::Other,
+                &self.name,
+                format!(
+                    "Record '{}' has fields that are invalid Rust idents for struct fields. By-value conversions will not be generated.",
+                    self.name
+                ),
+            );
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let field_names = record
+            .fields
+            .iter()
+            .map(|field| field.name.as_py())
+            .collect_vec();
+        let record_ident: syn::Ident =
+            Ident::from_py(&format!("{struct_ident}Record")).try_into()?;
+
+        // Nested records keep their Python representation
+        let field_types = record
+            .fields
+            .iter()
+            .map(|field| {
+                let mut annotation = field.annotation.clone();
+                annotation.resolve_by_value_types(&HashMap::default());
+                let is_optional = matches!(annotation, Type::Optional(..));
+                let field_type = if annotation.is_converted() {
+                    quote::quote!(::pyo3::PyObject)
+                } else {
+                    let field_type = annotation.into_rs_owned(cfg, local_types);
+                    if field_type.to_string().contains("'py") {
+                        quote::quote!(::pyo3::PyObject)
+                    } else {
+                        field_type
+                    }
+                };
+                if field.is_required || is_optional {
+                    field_type
+                } else {
+                    quote::quote!(::std::option::Option<#field_type>)
+                }
+            })
+            .collect_vec();
+
+        let (return_type, into_bound_body, extract_body) = if record.is_typed_dict {
+            let set_items = record.fields.iter().zip(&field_idents).zip(&field_names).map(
+                |((field, field_ident), field_name)| {
+                    if field.is_required {
+                        quote::quote! {
+                            ::pyo3::types::PyDictMethods::set_item(&dict, ::pyo3::intern!(py, #field_name), self.#field_ident)?;
+                        }
+                    } else {
+                        quote::quote! {
+                            if let ::std::option::Option::Some(value) = self.#field_ident {
+                                ::pyo3::types::PyDictMethods::set_item(&dict, ::pyo3::intern!(py, #field_name), value)?;
+                            }
+                        }
+                    }
+                },
+            );
+            let extract_items = record.fields.iter().zip(&field_idents).zip(&field_names).map(
+                |((field, field_ident), field_name)| {
+                    if field.is_required {
+                        quote::quote! {
+                            #field_ident: ::pyo3::types::PyAnyMethods::extract(
+                                &::pyo3::types::PyAnyMethods::get_item(object, ::pyo3::intern!(py, #field_name))?,
+                            )?
+                        }
+                    } else {
+                        quote::quote! {
+                            #field_ident: if ::pyo3::types::PyAnyMethods::contains(object, ::pyo3::intern!(py, #field_name))? {
+                                ::pyo3::types::PyAnyMethods::extract(
+                                    &::pyo3::types::PyAnyMethods::get_item(object, ::pyo3::intern!(py, #field_name))?,
+                                )?
+                            } else {
+                                ::std::option::Option::None
+                            }
+                        }
+                    }
+                },
+            );
+            (
+                quote::quote!(::pyo3::types::PyDict),
+                quote::quote! {
+                    {
+                        let dict = ::pyo3::types::PyDict::new_bound(py);
+                        #(#set_items)*
+                        ::std::result::Result::Ok(dict)
+                    }
+                },
+                quote::quote! {
+                    {
+                        let py = object.py();
+                        ::std::result::Result::Ok(Self {
+                            #(#extract_items,)*
+                        })
+                    }
+                },
+            )
+        } else {
+            let class_import = pyo3::Python::with_gil(|py| self.name.import_quote(py));
+            (
+                quote::quote!(#struct_ident),
+                quote::quote! {
+                    {
+                        let kwargs = ::pyo3::types::PyDict::new_bound(py);
+                        #(::pyo3::types::PyDictMethods::set_item(&kwargs, ::pyo3::intern!(py, #field_names), self.#field_idents)?;)*
+                        ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::call(
+                            #class_import.as_any(),
+                            (),
+                            ::std::option::Option::Some(&kwargs),
+                        )?)
+                    }
+                },
+                quote::quote! {
+                    {
+                        let py = object.py();
+                        ::std::result::Result::Ok(Self {
+                            #(#field_idents: ::pyo3::types::PyAnyMethods::extract(
+                                &::pyo3::types::PyAnyMethods::getattr(object, ::pyo3::intern!(py, #field_names))?,
+                            )?,)*
+                        })
+                    }
+                },
+            )
+        };
+        let into_bound_body = cfg.bodies.body(into_bound_body);
+        let extract_body = cfg.bodies.body(extract_body);
+
+        let doc = if cfg.generate_docs {
+            let docstring = format!(
+                " Record of the Python class [`{struct_ident}`], which can be passed to and returned from Python by value."
+            );
+            quote::quote! { #[doc = #docstring] }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        let allow = crate::utils::lint::allow_struct(cfg, &record_ident, &field_idents);
+        Ok(quote::quote! {
+            #doc
+            #[derive(Debug, Clone)]
+            #allow
+            pub struct #record_ident {
+                #(pub #field_idents: #field_types,)*
+            }
+            impl #record_ident {
+                /// Convert the record into its Python representation.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, #return_type>> #into_bound_body
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for #record_ident {
+                fn extract_bound(object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>) -> ::pyo3::PyResult<Self> #extract_body
+            }
+        })
+    }
+
+    /// Generate a `Send` handle that wraps `Py<X>` and forwards to the methods of the `XMethods` trait while holding
+    /// the GIL. Methods with parameters or return types that borrow from the GIL (other than a returned `Bound`,
+    /// which is unbound into `Py`) cannot outlive `Python::with_gil`, so they are only available via `bind`.
+    fn generate_send_handle(
+        cfg: &Config,
+        struct_ident: &syn::Ident,
+        trait_ident: &syn::Ident,
+        method_defs: &proc_macro2::TokenStream,
+    ) -> Result<proc_macro2::TokenStream> {
+        let has_lifetime = |tokens: &dyn quote::ToTokens| {
+            fn visit(stream: proc_macro2::TokenStream) -> bool {
+                stream.into_iter().any(|token| match token {
+                    proc_macro2::TokenTree::Punct(punct) => punct.as_char() == '\'',
+                    proc_macro2::TokenTree::Group(group) => visit(group.stream()),
+                    _ => false,
+                })
+            }
+            visit(tokens.to_token_stream())
+        };
+
+        let handle_ident: syn::Ident =
+            Ident::from_py(&format!("{struct_ident}Handle")).try_into()?;
+        let mut handle_fns = proc_macro2::TokenStream::new();
+        let method_defs: syn::ItemTrait = syn::parse2(quote::quote! {
+            trait #trait_ident {
+                #method_defs
+            }
+        })?;
+        for item in method_defs.items {
+            let syn::TraitItem::Fn(syn::TraitItemFn { sig, .. }) = item else {
+                continue;
+            };
+            if sig.receiver().is_none()
+                || sig.generics.where_clause.is_some()
+                || sig
+                    .generics
+                    .params
+                    .iter()
+                    .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+            {
+                continue;
+            }
+
+            // Collect the parameters, which must not borrow from the GIL
+            let params = sig
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    syn::FnArg::Typed(pat_type) => Some(pat_type),
+                    syn::FnArg::Receiver(_) => None,
+                })
+                .collect::<Vec<_>>();
+            if params.iter().any(|param| has_lifetime(&param.ty)) {
+                continue;
+            }
+            let param_idents = params
+                .iter()
+                .map(|param| match param.pat.as_ref() {
+                    syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            let Some(param_idents) = param_idents else {
+                continue;
+            };
+            let param_types = params.iter().map(|param| &param.ty);
+
+            // Convert a returned `Bound<'py, T>` into `Py<T>`, otherwise the return type must not borrow from the GIL
+            let (output, unbind) = match &sig.output {
+                syn::ReturnType::Type(_, ty) if has_lifetime(ty) => {
+                    let Some(bound_type) = Self::bound_result_type(ty) else {
+                        continue;
+                    };
+                    if has_lifetime(bound_type) {
+                        continue;
+                    }
+                    (
+                        quote::quote! { -> ::pyo3::PyResult<::pyo3::Py<#bound_type>> },
+                        quote::quote! { .map(::pyo3::Bound::unbind) },
+                    )
+                }
+                output => (quote::quote! { #output }, proc_macro2::TokenStream::new()),
+            };
+
+            let fn_ident = &sig.ident;
+            let doc = format!(" Call `{trait_ident}::{fn_ident}` while holding the GIL.");
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::Python::with_gil(|py| {
+                        #trait_ident::#fn_ident(self.0.bind(py), #(#param_idents),*)#unbind
+                    })
+                }
+            });
+            let signature = quote::quote! {
+                pub fn #fn_ident(&self, #(#param_idents: #param_types),*) #output
+            };
+            let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+            handle_fns.extend(quote::quote! {
+                #[doc = #doc]
+                #allow
+                #signature #body
+            });
+        }
+
+        let doc = format!(
+            " `Send` handle to an object of `{struct_ident}`, which can be moved across threads. Its methods acquire the GIL internally."
+        );
+        let allow = crate::utils::lint::allow_struct(cfg, &handle_ident, []);
+        Ok(quote::quote! {
+            #[doc = #doc]
+            #[derive(Debug)]
+            #[repr(transparent)]
+            #allow
+            pub struct #handle_ident(pub ::pyo3::Py<#struct_ident>);
+            impl #handle_ident {
+                /// Create a handle to the given object.
+                pub fn new(object: &::pyo3::Bound<'_, #struct_ident>) -> Self {
+                    Self(::pyo3::Bound::clone(object).unbind())
+                }
+                /// Borrow the object for the lifetime of the GIL.
+                pub fn bind<'py>(&self, py: ::pyo3::marker::Python<'py>) -> &::pyo3::Bound<'py, #struct_ident> {
+                    self.0.bind(py)
+                }
+                #handle_fns
+            }
+            #[automatically_derived]
+            impl ::std::convert::From<::pyo3::Bound<'_, #struct_ident>> for #handle_ident {
+                fn from(object: ::pyo3::Bound<'_, #struct_ident>) -> Self {
+                    Self(object.unbind())
+                }
+            }
+        })
+    }
+
+    /// Generate the note about the thread safety of the bindings that is appended to the documentation of classes.
+    fn thread_note(cfg: &Config, class_name: &Ident) -> String {
+        let mut note = format!(
+            " # Threading\n\n `Bound<'py, {class_name}>` is tied to the lifetime of the GIL, so it cannot be stored beyond \
+            `Python::with_gil` or sent to other threads. Unbind it into `Py<{class_name}>` (`Send + Sync`) to store it \
+            and re-bind it with `Py::bind` to use it."
+        );
+        if cfg.emit_shared_methods {
+            note.push_str(&format!(
+                " `{class_name}::into_py_shared` and `{class_name}SharedMethods` provide typed helpers for both steps."
+            ));
+        }
+        note.push_str(
+            " Note that all calls into Python still acquire the GIL, so they are serialized across threads.",
+        );
+        note
+    }
+
+    /// Generate an extension trait `XSharedMethods` for `Py<X>` that re-binds the object via `rebind` and forwards
+    /// the methods of the `XMethods` trait with an explicit `py` token. Methods that are generic, but not over `'py`,
+    /// cannot be forwarded, as the object can only be bound for the lifetime of the token. Methods without generics
+    /// (e.g. with only primitive types) are made generic over `'py`.
+    fn generate_shared_methods(
+        cfg: &Config,
+        struct_ident: &syn::Ident,
+        trait_ident: &syn::Ident,
+        method_defs: &proc_macro2::TokenStream,
+    ) -> Result<proc_macro2::TokenStream> {
+        let shared_trait_ident: syn::Ident =
+            Ident::from_py(&format!("{struct_ident}SharedMethods")).try_into()?;
+        let mut shared_fn_defs = proc_macro2::TokenStream::new();
+        let mut shared_fn_impls = proc_macro2::TokenStream::new();
+        let method_defs: syn::ItemTrait = syn::parse2(quote::quote! {
+            trait #trait_ident {
+                #method_defs
+            }
+        })?;
+        for item in method_defs.items {
+            let syn::TraitItem::Fn(syn::TraitItemFn { mut sig, .. }) = item else {
+                continue;
+            };
+            if sig.receiver().is_none() || sig.ident == "rebind" {
+                continue;
+            }
+            if sig.generics.params.is_empty() {
+                sig.generics.params.push(syn::parse_quote!('py));
+            } else if !sig
+                .generics
+                .lifetimes()
+                .any(|param| param.lifetime.ident == "py")
+            {
+                continue;
+            }
+
+            // Pass the `py` token after the receiver, which is bound for the lifetime of the token
+            let params = sig
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    syn::FnArg::Typed(pat_type) => Some(pat_type.clone()),
+                    syn::FnArg::Receiver(_) => None,
+                })
+                .collect::<Vec<_>>();
+            let param_idents = params
+                .iter()
+                .map(|param| match param.pat.as_ref() {
+                    syn::Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            let Some(param_idents) = param_idents else {
+                continue;
+            };
+            sig.inputs = syn::parse_quote! {
+                &'py self, py: ::pyo3::marker::Python<'py>, #(#params),*
+            };
+
+            let fn_ident = &sig.ident;
+            let doc = format!(
+                " Call `{trait_ident}::{fn_ident}` on the object bound to the GIL token `py`."
+            );
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    #trait_ident::#fn_ident(self.bind(py), #(#param_idents),*)
+                }
+            });
+            let allow = crate::utils::lint::allow_fn(cfg, &quote::quote!(#sig))?;
+            shared_fn_defs.extend(quote::quote! {
+                #allow
+                #sig;
+            });
+            shared_fn_impls.extend(quote::quote! {
+                #[doc = #doc]
+                #sig #body
+            });
+        }
+
+        let doc = format!(
+            " Methods of `{struct_ident}` for the `Py<T>` smart pointer, which can be stored and sent across threads. Each method binds the object to the GIL token `py`."
+        );
+        let rebind_body = cfg.bodies.body(quote::quote! {
+            {
+                ::pyo3::Bound::clone(self.bind(py))
+            }
+        });
+        let allow = crate::utils::lint::allow_type(cfg, &shared_trait_ident, []);
+        Ok(quote::quote! {
+            #[doc = #doc]
+            #allow
+            pub trait #shared_trait_ident {
+                fn rebind<'py>(&self, py: ::pyo3::marker::Python<'py>) -> ::pyo3::Bound<'py, #struct_ident>;
+                #shared_fn_defs
+            }
+            #[automatically_derived]
+            impl #shared_trait_ident for ::pyo3::Py<#struct_ident> {
+                /// Bind the object to the lifetime of the GIL token `py`.
+                fn rebind<'py>(&self, py: ::pyo3::marker::Python<'py>) -> ::pyo3::Bound<'py, #struct_ident> #rebind_body
+                #shared_fn_impls
+            }
+        })
+    }
+
+    /// Extract `T` from a return type of the form `PyResult<Bound<'py, T>>`.
+    fn bound_result_type(ty: &syn::Type) -> Option<&syn::Type> {
+        fn generic_arg<'a>(ty: &'a syn::Type, ident: &str) -> Option<&'a syn::Type> {
+            let syn::Type::Path(type_path) = ty else {
+                return None;
+            };
+            let segment = type_path.path.segments.last()?;
+            if segment.ident != ident {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+        }
+        generic_arg(ty, "PyResult").and_then(|ty| generic_arg(ty, "Bound"))
+    }
+
+    fn generate_comparisons(&self, cfg: &Config, struct_ident: &syn::Ident) -> TraitMethod {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+
+        // Skip helpers that would clash with the name of a method or property
+        let is_available = |name: &str| {
+            !self
+                .methods
+                .iter()
+                .map(|method| &method.name)
+                .chain(self.properties.iter().map(|property| &property.name))
+                .any(|path| path.name().as_rs() == name)
+        };
+
+        // Note: `PartialEq`/`PartialOrd` cannot be implemented for `Bound<'_, T>` outside of `pyo3`
+        //       due to the orphan rule, so fallible helpers are provided instead
+        if self.defines_eq && is_available("py_eq") {
+            trait_fn.extend(quote::quote! {
+                fn py_eq<'py>(&'py self, other: &::pyo3::Bound<'py, #struct_ident>) -> ::pyo3::PyResult<bool>;
+            });
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::eq(self.as_any(), other)
+                }
+            });
+            impl_fn.extend(quote::quote! {
+                /// Compare with `other` for equality via the Python `==` operator (`__eq__`).
+                fn py_eq<'py>(&'py self, other: &::pyo3::Bound<'py, #struct_ident>) -> ::pyo3::PyResult<bool> #body
+            });
+        }
+        if self.defines_eq && self.is_hashable && is_available("py_hash") {
+            trait_fn.extend(quote::quote! {
+                fn py_hash(&self) -> ::pyo3::PyResult<isize>;
+            });
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::hash(self.as_any())
+                }
+            });
+            impl_fn.extend(quote::quote! {
+                /// Compute the hash via the Python `hash()` built-in function (`__hash__`).
+                fn py_hash(&self) -> ::pyo3::PyResult<isize> #body
+            });
+        }
+        if self.defines_ord && is_available("py_cmp") {
+            trait_fn.extend(quote::quote! {
+                fn py_cmp<'py>(&'py self, other: &::pyo3::Bound<'py, #struct_ident>) -> ::pyo3::PyResult<::std::cmp::Ordering>;
+            });
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::compare(self.as_any(), other)
+                }
+            });
+            impl_fn.extend(quote::quote! {
+                /// Compare with `other` via the Python `==`, `<` and `>` operators.
+                fn py_cmp<'py>(&'py self, other: &::pyo3::Bound<'py, #struct_ident>) -> ::pyo3::PyResult<::std::cmp::Ordering> #body
+            });
+        }
+
+        TraitMethod { trait_fn, impl_fn }
+    }
+
+    /// Generate typed helpers for classes that conform to `collections.abc.Mapping` (`get`, `keys`, `values`
+    /// and `items`) or `collections.abc.Sequence` (`get_index`, `len` and `to_vec`).
+    fn generate_collection_helpers(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        collection: &CollectionAbc,
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<TraitMethod> {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+
+        let abc_name = match collection {
+            CollectionAbc::Mapping { .. } => "mapping",
+            CollectionAbc::Sequence { .. } => "sequence",
+        };
+        let resolve = |intended: &str| self.resolve_helper_ident(cfg, sink, intended, abc_name);
+        let mut add_helper =
+            |signature: proc_macro2::TokenStream, doc: &str, body: proc_macro2::TokenStream| {
+                let body = cfg.bodies.body(body);
+                let doc = format!(" {doc}");
+                let trait_signature =
+                    crate::utils::lint::omit_unused_py_lifetime(signature.clone(), None);
+                let signature = crate::utils::lint::omit_unused_py_lifetime(signature, Some(&body));
+                trait_fn.extend(quote::quote! {
+                    #trait_signature;
+                });
+                impl_fn.extend(quote::quote! {
+                    #[doc = #doc]
+                    #signature #body
+                });
+            };
+
+        match collection {
+            CollectionAbc::Mapping {
+                key_type,
+                value_type,
+            } => {
+                let key_type_borrowed = key_type.clone().into_rs_borrowed(cfg, local_types);
+                let key_preprocessing = key_type.clone().preprocess_borrowed(
+                    &syn::Ident::new("p_key", proc_macro2::Span::call_site()),
+                    cfg,
+                    local_types,
+                );
+                let key_type = key_type.clone().into_rs_owned(cfg, local_types);
+                let value_type = value_type.clone().into_rs_owned(cfg, local_types);
+                if let Some(ident) = resolve("get")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self, p_key: #key_type_borrowed) -> ::pyo3::PyResult<::std::option::Option<#value_type>>
+                        },
+                        "Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).",
+                        Self::get_item_body(&key_preprocessing),
+                    );
+                }
+                if let Some(ident) = resolve("keys")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self) -> ::pyo3::PyResult<::std::vec::Vec<#key_type>>
+                        },
+                        "Collect all keys of the mapping via `keys()`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::iter(&::pyo3::types::PyAnyMethods::call_method0(self.as_any(), ::pyo3::intern!(self.py(), "keys"))?)?
+                                    .map(|key| ::pyo3::types::PyAnyMethods::extract(&key?))
+                                    .collect()
+                            }
+                        },
+                    );
+                }
+                if let Some(ident) = resolve("values")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self) -> ::pyo3::PyResult<::std::vec::Vec<#value_type>>
+                        },
+                        "Collect all values of the mapping via `values()`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::iter(&::pyo3::types::PyAnyMethods::call_method0(self.as_any(), ::pyo3::intern!(self.py(), "values"))?)?
+                                    .map(|value| ::pyo3::types::PyAnyMethods::extract(&value?))
+                                    .collect()
+                            }
+                        },
+                    );
+                }
+                if let Some(ident) = resolve("items")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self) -> ::pyo3::PyResult<::std::vec::Vec<(#key_type, #value_type)>>
+                        },
+                        "Collect all key-value pairs of the mapping via `items()`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::iter(&::pyo3::types::PyAnyMethods::call_method0(self.as_any(), ::pyo3::intern!(self.py(), "items"))?)?
+                                    .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                                    .collect()
+                            }
+                        },
+                    );
+                }
+            }
+            CollectionAbc::Sequence { item_type } => {
+                let item_type = item_type.clone().into_rs_owned(cfg, local_types);
+                if let Some(ident) = resolve("get_index")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self, index: usize) -> ::pyo3::PyResult<#item_type>
+                        },
+                        "Get the item at the given index via `__getitem__`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::get_item(self.as_any(), index)?)
+                            }
+                        },
+                    );
+                }
+                if let Some(ident) = resolve("len")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident(&self) -> ::pyo3::PyResult<usize>
+                        },
+                        "Get the number of items via `__len__`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::len(self.as_any())
+                            }
+                        },
+                    );
+                }
+                if let Some(ident) = resolve("to_vec")? {
+                    add_helper(
+                        quote::quote! {
+                            fn #ident<'py>(&'py self) -> ::pyo3::PyResult<::std::vec::Vec<#item_type>>
+                        },
+                        "Collect all items of the sequence via `__iter__`.",
+                        quote::quote! {
+                            {
+                                ::pyo3::types::PyAnyMethods::iter(self.as_any())?
+                                    .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                                    .collect()
+                            }
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(TraitMethod { trait_fn, impl_fn })
+    }
+
+    /// Generate the body of a `get` helper that maps a missing key (`KeyError`) to `None`.
+    fn get_item_body(key_preprocessing: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        quote::quote! {
+            {
+                let py = self.py();
+                #key_preprocessing
+                match ::pyo3::types::PyAnyMethods::get_item(self.as_any(), p_key) {
+                    ::std::result::Result::Ok(value) => ::pyo3::types::PyAnyMethods::extract(&value).map(::std::option::Option::Some),
+                    ::std::result::Result::Err(err) if err.is_instance_of::<::pyo3::exceptions::PyKeyError>(py) => ::std::result::Result::Ok(::std::option::Option::None),
+                    ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                }
+            }
+        }
+    }
+
+    /// Generate `get` and `insert` helpers mirroring `HashMap` for dict-like classes with typed `__getitem__` and
+    /// `__setitem__`. The `get` helper is omitted for mappings that already have it as a collection helper.
+    fn generate_item_access_helpers(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        item_access: &ItemAccess,
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<TraitMethod> {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+        let p_key = syn::Ident::new("p_key", proc_macro2::Span::call_site());
+        let p_value = syn::Ident::new("p_value", proc_macro2::Span::call_site());
+
+        if let Some((key_type, value_type)) = &item_access.get {
+            if !matches!(self.collection, Some(CollectionAbc::Mapping { .. })) {
+                if let Some(ident) = self.resolve_helper_ident(cfg, sink, "get", "getitem")? {
+                    let key_type_borrowed = key_type.clone().into_rs_borrowed(cfg, local_types);
+                    let key_preprocessing =
+                        key_type
+                            .clone()
+                            .preprocess_borrowed(&p_key, cfg, local_types);
+                    let value_type = value_type.clone().into_rs_owned(cfg, local_types);
+                    let signature = quote::quote! {
+                        fn #ident<'py>(&'py self, p_key: #key_type_borrowed) -> ::pyo3::PyResult<::std::option::Option<#value_type>>
+                    };
+                    let body = cfg.bodies.body(Self::get_item_body(&key_preprocessing));
+                    let trait_signature =
+                        crate::utils::lint::omit_unused_py_lifetime(signature.clone(), None);
+                    let signature =
+                        crate::utils::lint::omit_unused_py_lifetime(signature, Some(&body));
+                    trait_fn.extend(quote::quote! {
+                        #trait_signature;
+                    });
+                    impl_fn.extend(quote::quote! {
+                        /// Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).
+                        #signature #body
+                    });
+                }
+            }
+        }
+        if let Some((key_type, value_type)) = &item_access.insert {
+            if let Some(ident) = self.resolve_helper_ident(cfg, sink, "insert", "setitem")? {
+                let key_type_borrowed = key_type.clone().into_rs_borrowed(cfg, local_types);
+                let key_preprocessing =
+                    key_type
+                        .clone()
+                        .preprocess_borrowed(&p_key, cfg, local_types);
+                let value_type_borrowed = value_type.clone().into_rs_borrowed(cfg, local_types);
+                let value_preprocessing =
+                    value_type
+                        .clone()
+                        .preprocess_borrowed(&p_value, cfg, local_types);
+                let signature = quote::quote! {
+                    fn #ident<'py>(&'py self, p_key: #key_type_borrowed, p_value: #value_type_borrowed) -> ::pyo3::PyResult<()>
+                };
+                let body = cfg.bodies.body(quote::quote! {
+                    {
+                        let py = self.py();
+                        #key_preprocessing
+                        #value_preprocessing
+                        ::pyo3::types::PyAnyMethods::set_item(self.as_any(), p_key, p_value)
+                    }
+                });
+                let trait_signature =
+                    crate::utils::lint::omit_unused_py_lifetime(signature.clone(), None);
+                let signature = crate::utils::lint::omit_unused_py_lifetime(signature, Some(&body));
+                trait_fn.extend(quote::quote! {
+                    #trait_signature;
+                });
+                impl_fn.extend(quote::quote! {
+                    /// Insert the value for the given key via `__setitem__`.
+                    #signature #body
+                });
+            }
+        }
+
+        Ok(TraitMethod { trait_fn, impl_fn })
+    }
+
+    /// Resolve the name of a generated helper that collides with a method or property of the class, where the
+    /// name on collision is suffixed with the origin of the helper (e.g. `get_from_mapping`).
+    fn resolve_helper_ident(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        intended: &str,
+        origin: &str,
+    ) -> Result<Option<syn::Ident>> {
+        cfg.on_collision
+            .resolve(
+                sink,
+                &self.name.join(&Path::from_py(intended)),
+                Ident::from_py(intended),
+                Some(Ident::from_py(&format!("{intended}_from_{origin}"))),
+                |ident| {
+                    self.methods
+                        .iter()
+                        .map(|method| method.name.name())
+                        .chain(self.properties.iter().map(|property| property.name.name()))
+                        .any(|name| name == ident)
+                        || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&ident.as_py())
+                },
+            )?
+            .map(syn::Ident::try_from)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Generate `len` and `is_empty` helpers for classes that define `__len__`. The `len` helper is omitted for
+    /// sequences that already have it as a collection helper.
+    fn generate_len_method(&self, cfg: &Config, sink: &ReportSink) -> Result<TraitMethod> {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+
+        let is_sequence = matches!(self.collection, Some(CollectionAbc::Sequence { .. }));
+        if !is_sequence {
+            if let Some(ident) = self.resolve_helper_ident(cfg, sink, "len", "len")? {
+                let signature = quote::quote! {
+                    fn #ident(&self) -> ::pyo3::PyResult<usize>
+                };
+                let body = cfg.bodies.body(quote::quote! {
+                    {
+                        ::pyo3::types::PyAnyMethods::len(self.as_any())
+                    }
+                });
+                trait_fn.extend(quote::quote! {
+                    #signature;
+                });
+                impl_fn.extend(quote::quote! {
+                    /// Get the number of items via `__len__`.
+                    #signature #body
+                });
+            }
+        }
+        if let Some(ident) = self.resolve_helper_ident(cfg, sink, "is_empty", "len")? {
+            let signature = quote::quote! {
+                fn #ident(&self) -> ::pyo3::PyResult<bool>
+            };
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::is_empty(self.as_any())
+                }
+            });
+            trait_fn.extend(quote::quote! {
+                #signature;
+            });
+            impl_fn.extend(quote::quote! {
+                /// Determine whether the object contains no items via `__len__`.
+                #signature #body
+            });
+        }
+
+        Ok(TraitMethod { trait_fn, impl_fn })
+    }
+
+    /// Generate `get_attr` and `set_attr` helpers for dynamic access of attributes that are not discoverable
+    /// at generation time.
+    fn generate_getattr_fallback(&self, cfg: &Config, sink: &ReportSink) -> Result<TraitMethod> {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+
+        if let Some(ident) = self.resolve_helper_ident(cfg, sink, "get_attr", "getattr")? {
+            let signature = quote::quote! {
+                fn #ident<'py>(&'py self, p_name: &str) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>
+            };
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::getattr(self.as_any(), p_name)
+                }
+            });
+            trait_fn.extend(quote::quote! {
+                #signature;
+            });
+            impl_fn.extend(quote::quote! {
+                /// Get an attribute of the object by its name, including attributes that are not bound explicitly.
+                #signature #body
+            });
+        }
+        if let Some(ident) = self.resolve_helper_ident(cfg, sink, "set_attr", "getattr")? {
+            let signature = quote::quote! {
+                fn #ident(&self, p_name: &str, p_value: impl ::pyo3::ToPyObject) -> ::pyo3::PyResult<()>
+            };
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::setattr(self.as_any(), p_name, p_value)
+                }
+            });
+            trait_fn.extend(quote::quote! {
+                #signature;
+            });
+            impl_fn.extend(quote::quote! {
+                /// Set an attribute of the object by its name, including attributes that are not bound explicitly.
+                #signature #body
+            });
+        }
+
+        Ok(TraitMethod { trait_fn, impl_fn })
+    }
+
+    /// Generate `to_dict` and `update_from_dict` helpers for the conversion from/into the dict representation of
+    /// objects, which is based on `dataclasses.asdict` and `dataclasses.fields` for dataclasses and on the instance
+    /// attributes (`vars()`) for all other classes.
+    fn generate_dict_helpers(&self, cfg: &Config, sink: &ReportSink) -> Result<TraitMethod> {
+        let mut trait_fn = proc_macro2::TokenStream::new();
+        let mut impl_fn = proc_macro2::TokenStream::new();
+
+        if let Some(ident) = self.resolve_helper_ident(cfg, sink, "to_dict", "vars")? {
+            let signature = quote::quote! {
+                fn #ident<'py>(&'py self) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>
+            };
+            let (doc, body) = if self.is_dataclass {
+                (
+                    " Convert the dataclass into a dict via `dataclasses.asdict()`, which also converts nested dataclasses.",
+                    quote::quote! {
+                        {
+                            let py = self.py();
+                            let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                                py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                                ::pyo3::intern!(py, "asdict"),
+                                (self.as_any(),),
+                            )?;
+                            ::std::result::Result::Ok(::pyo3::types::PyAnyMethods::downcast_into::<::pyo3::types::PyDict>(dict)?)
+                        }
+                    },
+                )
+            } else {
+                (
+                    " Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.",
+                    quote::quote! {
+                        {
+                            let py = self.py();
+                            let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                            let dict = ::pyo3::types::PyAnyMethods::call_method1(builtins.as_any(), ::pyo3::intern!(py, "vars"), (self.as_any(),))
+                                .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(builtins.as_any(), ::pyo3::intern!(py, "dict"), (self.as_any(),)))
+                                .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(self.as_any(), ::pyo3::intern!(py, "__dict__")))?;
+                            ::std::result::Result::Ok(::pyo3::types::PyAnyMethods::downcast_into::<::pyo3::types::PyDict>(dict)?)
+                        }
+                    },
+                )
+            };
+            let body = cfg.bodies.body(body);
+            trait_fn.extend(quote::quote! {
+                #signature;
+            });
+            impl_fn.extend(quote::quote! {
+                #[doc = #doc]
+                #signature #body
+            });
+        }
+        if let Some(ident) = self.resolve_helper_ident(cfg, sink, "update_from_dict", "vars")? {
+            let signature = quote::quote! {
+                fn #ident<'py>(&'py self, p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>) -> ::pyo3::PyResult<()>
+            };
+            let (doc, body) = if self.is_dataclass {
+                (
+                    " Set the fields of the dataclass (`dataclasses.fields()`) to the values of the given dict. Keys that are not fields are ignored.",
+                    quote::quote! {
+                        {
+                            let py = self.py();
+                            let fields = ::pyo3::types::PyAnyMethods::call_method1(
+                                py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                                ::pyo3::intern!(py, "fields"),
+                                (self.as_any(),),
+                            )?;
+                            for field in ::pyo3::types::PyAnyMethods::iter(&fields)? {
+                                let name = ::pyo3::types::PyAnyMethods::getattr(&field?, ::pyo3::intern!(py, "name"))?;
+                                if let ::std::option::Option::Some(value) = ::pyo3::types::PyDictMethods::get_item(p_dict, &name)? {
+                                    ::pyo3::types::PyAnyMethods::setattr(self.as_any(), ::pyo3::types::PyAnyMethods::downcast_into::<::pyo3::types::PyString>(name)?, value)?;
+                                }
+                            }
+                            ::std::result::Result::Ok(())
+                        }
+                    },
+                )
+            } else {
+                (
+                    " Set the attributes of the object to the values of the given dict via `setattr()`.",
+                    quote::quote! {
+                        {
+                            for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                                ::pyo3::types::PyAnyMethods::setattr(self.as_any(), ::pyo3::types::PyAnyMethods::downcast_into::<::pyo3::types::PyString>(name)?, value)?;
+                            }
+                            ::std::result::Result::Ok(())
+                        }
+                    },
+                )
+            };
+            let body = cfg.bodies.body(body);
+            trait_fn.extend(quote::quote! {
+                #signature;
+            });
+            impl_fn.extend(quote::quote! {
+                #[doc = #doc]
+                #signature #body
+            });
+        }
+
+        Ok(TraitMethod { trait_fn, impl_fn })
+    }
 }
@@ -0,0 +1,37 @@
+//! Parsing of raw Rust code that is appended to the generated bindings of modules and classes.
+
+use super::Path;
+use crate::{PyBindgenError, Result};
+use syn::parse::Parser;
+
+/// Parse the Rust code appended to the body of a module, which must consist of items (e.g. `fn`, `impl` or `use`).
+pub(crate) fn parse_module_items(path: &Path, rust_code: &str) -> Result<proc_macro2::TokenStream> {
+    let file = syn::parse_file(rust_code).map_err(|err| invalid_code(path, rust_code, &err))?;
+    let items = file.items;
+    Ok(quote::quote! {
+        #(#items)*
+    })
+}
+
+/// Parse the Rust code appended to the implementation block of a class struct, which must consist of
+/// associated items (e.g. `fn` or `const`).
+pub(crate) fn parse_impl_items(path: &Path, rust_code: &str) -> Result<proc_macro2::TokenStream> {
+    let items = (|input: syn::parse::ParseStream| {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse::<syn::ImplItem>()?);
+        }
+        Ok(items)
+    })
+    .parse_str(rust_code)
+    .map_err(|err| invalid_code(path, rust_code, &err))?;
+    Ok(quote::quote! {
+        #(#items)*
+    })
+}
+
+fn invalid_code(path: &Path, rust_code: &str, err: &syn::Error) -> PyBindgenError {
+    PyBindgenError::CodegenSyntaxError {
+        span_context: format!("Rust code appended to '{path}' is invalid ({err}):\n{rust_code}"),
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for extracting information from the nodes of Python's `ast` module.
+
+use crate::Result;
+use pyo3::prelude::*;
+
+/// Check whether the node is an instance of any of the given `ast` node types, e.g. `FunctionDef`.
+pub(crate) fn is_node(node: &pyo3::Bound<pyo3::types::PyAny>, node_types: &[&str]) -> Result<bool> {
+    let node_type = node.get_type();
+    let node_type = node_type.name()?;
+    Ok(node_types.iter().any(|typ| *typ == node_type))
+}
+
+/// Extract the docstring of a module, class or function node. The docstring is not cleaned, so that it matches
+/// the `__doc__` attribute of the evaluated object.
+pub(crate) fn docstring(node: &pyo3::Bound<pyo3::types::PyAny>) -> Result<Option<String>> {
+    let py = node.py();
+    let kwargs = pyo3::types::PyDict::new_bound(py);
+    kwargs.set_item(pyo3::intern!(py, "clean"), false)?;
+    let docstring = py.import_bound(pyo3::intern!(py, "ast"))?.call_method(
+        pyo3::intern!(py, "get_docstring"),
+        (node,),
+        Some(&kwargs),
+    )?;
+    Ok(if docstring.is_none() {
+        None
+    } else {
+        Some(docstring.to_string())
+    })
+}
+
+/// Unparse the node back to its source code, e.g. `value.setter` for a decorator.
+pub(crate) fn unparse(node: &pyo3::Bound<pyo3::types::PyAny>) -> Result<String> {
+    let py = node.py();
+    Ok(py
+        .import_bound(pyo3::intern!(py, "ast"))?
+        .call_method1(pyo3::intern!(py, "unparse"), (node,))?
+        .to_string())
+}
+
+/// Evaluate the node if it is a literal (e.g. `42` or `["a", "b"]`), otherwise return `None`.
+pub(crate) fn literal_eval<'py>(
+    node: &pyo3::Bound<'py, pyo3::types::PyAny>,
+) -> Option<pyo3::Bound<'py, pyo3::types::PyAny>> {
+    let py = node.py();
+    py.import_bound(pyo3::intern!(py, "ast"))
+        .and_then(|ast| ast.call_method1(pyo3::intern!(py, "literal_eval"), (node,)))
+        .ok()
+}
+
+/// Get the list of child nodes stored in the given attribute of the node, e.g. `body`.
+pub(crate) fn children<'py>(
+    node: &pyo3::Bound<'py, pyo3::types::PyAny>,
+    attr_name: &pyo3::Bound<'py, pyo3::types::PyString>,
+) -> Result<Vec<pyo3::Bound<'py, pyo3::types::PyAny>>> {
+    Ok(node
+        .getattr(attr_name)?
+        .iter()?
+        .collect::<PyResult<Vec<_>>>()?)
+}
+
+/// Check whether the body of a function node consists solely of an optional docstring and
+/// `raise NotImplementedError` (with or without arguments).
+pub(crate) fn is_raising_only(node: &pyo3::Bound<pyo3::types::PyAny>) -> Result<bool> {
+    let py = node.py();
+    let mut body = children(node, pyo3::intern!(py, "body"))?;
+
+    // Skip the docstring
+    if docstring(node)?.is_some() {
+        body.remove(0);
+    }
+
+    // The only remaining statement must raise `NotImplementedError`
+    let [statement] = body.as_slice() else {
+        return Ok(false);
+    };
+    if !is_node(statement, &["Raise"])? {
+        return Ok(false);
+    }
+    let mut exception = statement.getattr(pyo3::intern!(py, "exc"))?;
+    if is_node(&exception, &["Call"])? {
+        exception = exception.getattr(pyo3::intern!(py, "func"))?;
+    }
+    Ok(is_node(&exception, &["Name"])?
+        && exception.getattr(pyo3::intern!(py, "id"))?.to_string() == "NotImplementedError")
+}
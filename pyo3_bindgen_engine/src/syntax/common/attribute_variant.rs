@@ -60,7 +60,15 @@ impl AttributeVariant {
 
         // Determine if the attribute is imported
         let is_external = !is_in_locals && (attr_module != owner_name);
-        let is_imported = is_external && (is_submodule || is_class || is_function || is_method);
+        // Objects that are re-exported from another module of the same package (e.g. via `from .submodule import *`)
+        // are identified by their `__module__` and `__qualname__`, even if they are neither classes nor functions
+        // (e.g. builtin functions of extension modules or wrappers created by `functools.lru_cache`)
+        let is_reexported = is_external
+            && attr_module.root().is_some()
+            && attr_module.root() == owner_name.root()
+            && Self::is_defined_in(py, attr, attr_module);
+        let is_imported =
+            is_external && (is_submodule || is_class || is_function || is_method || is_reexported);
 
         Ok(if consider_import && is_imported {
             AttributeVariant::Import
@@ -80,4 +88,29 @@ impl AttributeVariant {
             AttributeVariant::Property
         })
     }
+
+    /// Check whether the attribute is defined in the given module, i.e. its `__qualname__` resolves to the
+    /// same object within the module.
+    fn is_defined_in(
+        py: pyo3::prelude::Python,
+        attr: &pyo3::Bound<pyo3::prelude::PyAny>,
+        module: &Path,
+    ) -> bool {
+        let Ok(qualname) = attr
+            .getattr(pyo3::intern!(py, "__qualname__"))
+            .map(|qualname| qualname.to_string())
+        else {
+            return false;
+        };
+        if qualname.contains("<locals>") {
+            return false;
+        }
+        py.import_bound(module.to_py().as_str())
+            .and_then(|module| {
+                qualname
+                    .split('.')
+                    .try_fold(module.into_any(), |parent, name| parent.getattr(name))
+            })
+            .is_ok_and(|origin| origin.is(attr))
+    }
 }
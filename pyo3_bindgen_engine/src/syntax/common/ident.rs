@@ -1,28 +1,72 @@
-#[repr(transparent)]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Ident(String);
+/// Identifier that keeps its original Python name alongside the corresponding Rust name.
+///
+/// Python names that are valid Rust identifiers are used as-is (keywords are escaped as raw identifiers).
+/// All other names are transliterated deterministically (see `Ident::from_py`). Identifiers are compared and
+/// hashed by their Python name, so that references to a disambiguated identifier resolve to its Rust name.
+#[derive(Debug, Clone)]
+pub struct Ident {
+    py: String,
+    rs: String,
+}
 
 impl Ident {
     pub fn from_rs(value: &str) -> Self {
         debug_assert!(!value.is_empty());
-        Self(value.to_owned())
+        Self {
+            py: Self::rs_as_py(value).to_owned(),
+            rs: value.to_owned(),
+        }
     }
 
+    /// Create an identifier from a Python name. The Rust name is first normalized to NFKC (like identifiers in
+    /// Python source code), e.g. `e\u{301}` (combining accent) becomes `é` and `ﬁle` becomes `file`. Normalized
+    /// names that consist only of characters with the Unicode property `XID_Start`, underscores and ASCII digits
+    /// (not at the start) are kept unchanged in Rust, e.g. `población` or `数据`. In all other names, each character
+    /// that is not one of these is replaced with `_u{XXXX}_` based on its hexadecimal code point, e.g. `a😀` becomes
+    /// `a_u1F600_` and `x\u{301}` (without a composed form) becomes `x_u301_`. This never fails, and transliterated
+    /// names cannot be confused by the NFC normalization of identifiers in Rust. Collisions of transliterated
+    /// names within a scope are resolved by `disambiguate`.
     pub fn from_py(value: &str) -> Self {
         debug_assert!(!value.is_empty());
-        Self(Self::py_to_rs(value))
+        Self {
+            py: value.to_owned(),
+            rs: Self::py_to_rs(value),
+        }
     }
 
     pub fn into_rs(self) -> String {
-        self.0
+        self.rs
     }
 
     pub fn as_rs(&self) -> &str {
-        &self.0
+        &self.rs
     }
 
     pub fn as_py(&self) -> &str {
-        Self::rs_as_py(&self.0)
+        &self.py
+    }
+
+    /// Create an identifier by prefixing both the Python and Rust name, which keeps any disambiguation suffix.
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        debug_assert!(!prefix.is_empty());
+        Self {
+            py: format!("{prefix}{}", self.py),
+            rs: format!("{prefix}{}", Self::rs_as_py(&self.rs)),
+        }
+    }
+
+    /// Create an identifier by suffixing both the Python and Rust name.
+    pub fn with_suffix(&self, suffix: &str) -> Self {
+        debug_assert!(!suffix.is_empty());
+        Self {
+            py: format!("{}{suffix}", self.py),
+            rs: format!("{}{suffix}", Self::rs_as_py(&self.rs)),
+        }
+    }
+
+    /// Check whether the Rust name differs from the Python name beyond escaping as a raw identifier.
+    pub fn is_transliterated(&self) -> bool {
+        Self::rs_as_py(&self.rs) != self.py
     }
 
     fn rs_as_py(value: &str) -> &str {
@@ -30,10 +74,50 @@ impl Ident {
     }
 
     fn py_to_rs(value: &str) -> String {
-        if syn::parse_str::<syn::Ident>(value).is_ok() {
-            value.to_owned()
+        let value = &unicode_normalization::UnicodeNormalization::nfkc(value).collect::<String>();
+        let is_kept = |(i, c): (usize, char)| {
+            unicode_ident::is_xid_start(c) || c == '_' || (i > 0 && c.is_ascii_digit())
+        };
+        if !value.is_empty() && value.chars().enumerate().all(is_kept) {
+            if syn::parse_str::<syn::Ident>(value).is_ok() {
+                value.to_owned()
+            } else {
+                format!("r#{value}")
+            }
         } else {
-            format!("r#{value}")
+            value
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if is_kept((i, c)) {
+                        c.to_string()
+                    } else {
+                        format!("_u{:X}_", u32::from(c))
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// Resolve collisions of transliterated identifiers within a single scope (see `Ident::from_py`) by suffixing
+/// them with `_2`, `_3`, etc. Identifiers that are not transliterated keep their names, and the order of the
+/// identifiers determines the suffixes, which makes the result deterministic.
+pub fn disambiguate<'a>(idents: impl IntoIterator<Item = &'a mut Ident>) {
+    let mut idents: Vec<&mut Ident> = idents.into_iter().collect();
+    let mut taken: rustc_hash::FxHashSet<String> = idents
+        .iter()
+        .filter(|ident| !ident.is_transliterated())
+        .map(|ident| ident.rs.clone())
+        .collect();
+    for ident in idents.iter_mut().filter(|ident| ident.is_transliterated()) {
+        if !taken.insert(ident.rs.clone()) {
+            let rs = (2..)
+                .map(|n| format!("{}_{n}", ident.rs))
+                .find(|rs| !taken.contains(rs))
+                .unwrap_or_else(|| unreachable!());
+            taken.insert(rs.clone());
+            ident.rs = rs;
         }
     }
 }
@@ -52,6 +136,20 @@ impl TryFrom<&Ident> for syn::Ident {
     }
 }
 
+impl std::cmp::PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_py() == other.as_py()
+    }
+}
+
+impl std::cmp::Eq for Ident {}
+
+impl std::hash::Hash for Ident {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_py().hash(state);
+    }
+}
+
 impl std::cmp::PartialOrd for Ident {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -67,7 +165,7 @@ impl std::cmp::Ord for Ident {
 impl std::ops::Deref for Ident {
     type Target = str;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.rs
     }
 }
 
@@ -103,6 +201,49 @@ mod tests {
         assert_eq!(ident.as_py(), "struct");
     }
 
+    #[test]
+    fn test_from_py_non_ascii() {
+        for (py, rs) in [
+            ("población", "población"),
+            ("数据", "数据"),
+            ("クラス2", "クラス2"),
+            ("a😀", "a_u1F600_"),
+            ("e\u{301}", "é"),
+            ("ﬁle", "file"),
+            ("x\u{301}", "x_u301_"),
+            ("\u{301}e", "_u301_e"),
+            ("2d", "_u32_d"),
+            ("a-b", "a_u2D_b"),
+        ] {
+            let ident = Ident::from_py(py);
+            assert_eq!(ident.as_rs(), rs, "{py}");
+            assert_eq!(ident.as_py(), py, "{py}");
+            assert_eq!(ident.is_transliterated(), py != rs, "{py}");
+            let _syn_ident: syn::Ident = (&ident).try_into().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_disambiguate() {
+        // Arrange
+        let mut idents = [
+            Ident::from_py("a😀"),
+            Ident::from_py("a_u1F600_"),
+            Ident::from_py("a😀"),
+            Ident::from_py("b"),
+        ];
+
+        // Act
+        disambiguate(&mut idents);
+
+        // Assert
+        assert_eq!(
+            idents.iter().map(Ident::as_rs).collect::<Vec<_>>(),
+            ["a_u1F600__2", "a_u1F600_", "a_u1F600__3", "b"]
+        );
+        assert_eq!(idents[0].as_py(), "a😀");
+    }
+
     #[test]
     fn test_into_syn() {
         let ident = Ident::from_rs("ident");
@@ -1,3 +1,5 @@
+pub(crate) mod appended_code;
+pub(crate) mod ast;
 pub(crate) mod attribute_variant;
 pub(crate) mod function_definition;
 pub(crate) mod ident;
@@ -5,5 +7,5 @@ pub(crate) mod path;
 
 pub use attribute_variant::AttributeVariant;
 pub use function_definition::{FunctionImplementation, TraitMethod};
-pub use ident::Ident;
+pub use ident::{disambiguate, Ident};
 pub use path::Path;
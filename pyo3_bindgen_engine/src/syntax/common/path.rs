@@ -103,6 +103,10 @@ impl Path {
         self.segments.last().unwrap()
     }
 
+    pub fn name_mut(&mut self) -> &mut Ident {
+        self.segments.last_mut().unwrap()
+    }
+
     pub fn root(&self) -> Option<Self> {
         if self.segments.is_empty() {
             None
@@ -114,6 +118,14 @@ impl Path {
         }
     }
 
+    /// Check whether the path originates from a module that cannot be imported as a regular dependency,
+    /// i.e. `__main__` or `builtins` (objects created via `exec` or in an interactive session tend to end up there).
+    pub fn is_unresolvable_origin(&self) -> bool {
+        self.segments
+            .first()
+            .is_some_and(|root| ["__main__", "builtins"].contains(&root.as_py()))
+    }
+
     pub fn parent(&self) -> Option<Self> {
         if self.segments.len() > 1 {
             Some(Self {
@@ -342,6 +354,14 @@ mod tests {
         assert_eq!(path.root().unwrap().to_rs(), "long");
     }
 
+    #[test]
+    fn test_is_unresolvable_origin() {
+        assert!(Path::from_py("__main__.Foo").is_unresolvable_origin());
+        assert!(Path::from_py("builtins").is_unresolvable_origin());
+        assert!(!Path::from_py("os.path").is_unresolvable_origin());
+        assert!(!Path::default().is_unresolvable_origin());
+    }
+
     #[test]
     fn test_parent() {
         let path = Path::from_rs("long::path::to");
@@ -1,8 +1,12 @@
 use super::{FunctionImplementation, Ident, Path, TraitMethod};
-use crate::{typing::Type, Config, Result};
+use crate::{
+    typing::{from_py::split_top_level, well_known::WellKnownType, Type},
+    utils::report::ReportSink,
+    AbstractMethodHandling, AbstractPolicy, Config, FloatType, IntType, Result,
+};
 use itertools::Itertools;
 use proc_macro2::TokenStream;
-use pyo3::{prelude::*, types::IntoPyDict, ToPyObject};
+use pyo3::{prelude::*, types::IntoPyDict};
 use rustc_hash::FxHashMap as HashMap;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -12,13 +16,22 @@ pub struct Function {
     parameters: Vec<Parameter>,
     return_annotation: Type,
     docstring: Option<String>,
+    is_abstract: bool,
+    is_abstract_method: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FunctionType {
     Function,
-    Method { class_path: Path, typ: MethodType },
+    Method {
+        class_path: Path,
+        typ: MethodType,
+    },
     Closure,
+    /// Module-level instance of a class with `__call__`, which is bound as a free function.
+    CallableInstance {
+        instance_path: Path,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,7 +46,8 @@ pub enum MethodType {
 
 impl Function {
     pub fn parse(
-        _cfg: &Config,
+        cfg: &Config,
+        sink: &ReportSink,
         function: &pyo3::Bound<pyo3::types::PyAny>,
         name: Path,
         mut typ: FunctionType,
@@ -50,16 +64,55 @@ impl Function {
             }
         };
 
+        // Determine whether the function is abstract (best-effort), which requires parsing its source code and is
+        // therefore only done if abstract functions are handled differently
+        let is_abstract =
+            cfg.abstract_functions != AbstractPolicy::Generate && Self::is_abstract(function);
+
+        // Determine whether the function is declared with `@abstractmethod`
+        let is_abstract_method = function
+            .getattr(pyo3::intern!(py, "__isabstractmethod__"))
+            .and_then(|is_abstract_method| is_abstract_method.is_truthy())
+            .unwrap_or(false);
+
         // Extract the signature of the function
-        if let Ok(function_signature) = py
-            .import_bound(pyo3::intern!(py, "inspect"))?
+        let inspect = py.import_bound(pyo3::intern!(py, "inspect"))?;
+        let function_signature = inspect
             .call_method1(pyo3::intern!(py, "signature"), (function,))
-        {
+            .map(|function_signature| {
+                // Recover the real signature of decorated functions (`functools.wraps`) via `__wrapped__`
+                // if the signature of the wrapper is generic, e.g. because it overrides `__signature__`
+                if function
+                    .hasattr(pyo3::intern!(py, "__wrapped__"))
+                    .unwrap_or(false)
+                    && Self::is_generic_signature(&function_signature)
+                {
+                    inspect
+                        .call_method1(pyo3::intern!(py, "unwrap"), (function,))
+                        .and_then(|wrapped| {
+                            inspect.call_method1(pyo3::intern!(py, "signature"), (wrapped,))
+                        })
+                        .unwrap_or(function_signature)
+                } else {
+                    function_signature
+                }
+            });
+        if let Ok(function_signature) = function_signature {
             // Extract the parameters of the function
             let mut parameters = function_signature
                 .getattr(pyo3::intern!(py, "parameters"))?
                 .call_method0(pyo3::intern!(py, "values"))?
                 .iter()?
+                // Skip unnamed `*` and `/` separators (e.g. of a bare `*`), which are not parameters themselves
+                .filter(|param| {
+                    param.as_ref().map_or(true, |param| {
+                        param
+                            .getattr(pyo3::intern!(py, "name"))
+                            .map_or(true, |name| {
+                                !["", "*", "/"].contains(&name.to_string().as_str())
+                            })
+                    })
+                })
                 .map(|param| {
                     let param = param?;
 
@@ -68,18 +121,31 @@ impl Function {
                     let kind = ParameterKind::from(
                         param.getattr(pyo3::intern!(py, "kind"))?.extract::<u8>()?,
                     );
-                    let annotation = match kind {
-                        ParameterKind::VarPositional => Type::PyTuple(vec![Type::Unknown]),
-                        ParameterKind::VarKeyword => Type::Optional(Box::new(Type::PyDict {
-                            key_type: Box::new(Type::Unknown),
-                            value_type: Box::new(Type::Unknown),
-                        })),
+                    let (annotation, metadata) = match kind {
+                        ParameterKind::VarPositional => {
+                            (Type::PyTuple(vec![Type::Unknown]), Vec::new())
+                        }
+                        ParameterKind::VarKeyword => (
+                            Type::Optional(Box::new(Type::PyDict {
+                                key_type: Box::new(Type::Unknown),
+                                value_type: Box::new(Type::Unknown),
+                            })),
+                            Vec::new(),
+                        ),
                         _ => {
                             let annotation = param.getattr(pyo3::intern!(py, "annotation"))?;
                             if annotation.is(&param.getattr(pyo3::intern!(py, "empty"))?) {
-                                Type::Unknown
+                                (Type::Unknown, Vec::new())
                             } else {
-                                annotation.try_into()?
+                                let metadata = Type::annotated_metadata(&annotation)?;
+                                (
+                                    Type::from_py_with_max_depth(
+                                        annotation,
+                                        cfg.max_type_depth,
+                                        sink,
+                                    )?,
+                                    metadata,
+                                )
                             }
                         }
                     };
@@ -89,7 +155,7 @@ impl Function {
                         if default.is(&param.getattr(pyo3::intern!(py, "empty"))?) {
                             None
                         } else {
-                            Some(default.to_object(py))
+                            Some(DefaultValue::classify(&default)?)
                         }
                     };
 
@@ -98,6 +164,7 @@ impl Function {
                         kind,
                         annotation,
                         default,
+                        metadata,
                     })
                 })
                 .collect::<Result<Vec<_>>>()?;
@@ -112,7 +179,7 @@ impl Function {
                 if return_annotation.is(&function_signature.getattr(pyo3::intern!(py, "empty"))?) {
                     Type::Unknown
                 } else {
-                    return_annotation.try_into()?
+                    Type::from_py_with_max_depth(return_annotation, cfg.max_type_depth, sink)?
                 }
             };
 
@@ -189,7 +256,9 @@ impl Function {
                         };
                     }
                 }
-                FunctionType::Function | FunctionType::Closure => {
+                FunctionType::Function
+                | FunctionType::Closure
+                | FunctionType::CallableInstance { .. } => {
                     if parameters.first().map(|p| p.name.as_rs()) == Some("r#self") {
                         if [
                             ParameterKind::PositionalOnly,
@@ -197,14 +266,18 @@ impl Function {
                         ]
                         .contains(&parameters[0].kind)
                         {
-                            eprintln!(
-                                "WARN: Function '{name}' has the first parameter named 'self', but is not marked as a method. The parameter is renamed to '__unknown_self__'."
+                            sink.warn(
+                                crate::WarningKind::Other,
+                                &name,
+                                format!("Function '{name}' has the first parameter named 'self', but is not marked as a method. The parameter is renamed to '__unknown_self__'."),
                             );
                             parameters[0].name = Ident::from_rs("__unknown_self__");
                             parameters[0].annotation = Type::Unknown;
                         } else {
-                            eprintln!(
-                                "WARN: Function '{name}' has the first parameter named 'self', but is not marked as a method. All parameters are replaced with '*args' and '**kwargs'."
+                            sink.warn(
+                                crate::WarningKind::Other,
+                                &name,
+                                format!("Function '{name}' has the first parameter named 'self', but is not marked as a method. All parameters are replaced with '*args' and '**kwargs'."),
                             );
                             parameters = vec![
                                 Parameter {
@@ -212,6 +285,7 @@ impl Function {
                                     kind: ParameterKind::VarPositional,
                                     annotation: Type::PyTuple(vec![Type::Unknown]),
                                     default: None,
+                                    metadata: Vec::new(),
                                 },
                                 Parameter {
                                     name: Ident::from_rs("kwargs"),
@@ -221,6 +295,7 @@ impl Function {
                                         value_type: Box::new(Type::Unknown),
                                     })),
                                     default: None,
+                                    metadata: Vec::new(),
                                 },
                             ];
                         }
@@ -236,9 +311,11 @@ impl Function {
             } = &typ
             {
                 if parameters.is_empty() {
-                    eprintln!(
-                            "WARN: Method '{name}' is marked as an instance method, but has no parameters. Changed to static method.",
-                        );
+                    sink.warn(
+                        crate::WarningKind::Other,
+                        &name,
+                        format!("Method '{name}' is marked as an instance method, but has no parameters. Changed to static method."),
+                    );
                     typ = FunctionType::Method {
                         class_path: name.clone(),
                         typ: MethodType::StaticMethod,
@@ -255,14 +332,18 @@ impl Function {
                 parameters.remove(0);
             };
 
+            // Resolve collisions of transliterated parameter names
+            super::common::disambiguate(parameters.iter_mut().map(|param| &mut param.name));
+
             // If any of the parameters is still called 'self', do not handle the parameters
             if parameters
                 .iter()
                 .any(|param| param.name.as_rs() == "r#self")
             {
-                eprintln!(
-                    "WARN: Method '{name}' has a non-first parameter named 'self'. All parameters are replaced with '*args' and '**kwargs'.",
-
+                sink.warn(
+                    crate::WarningKind::Other,
+                    &name,
+                    format!("Method '{name}' has a non-first parameter named 'self'. All parameters are replaced with '*args' and '**kwargs'."),
                 );
                 parameters = vec![
                     Parameter {
@@ -270,6 +351,7 @@ impl Function {
                         kind: ParameterKind::VarPositional,
                         annotation: Type::PyTuple(vec![Type::Unknown]),
                         default: None,
+                        metadata: Vec::new(),
                     },
                     Parameter {
                         name: Ident::from_rs("kwargs"),
@@ -279,6 +361,7 @@ impl Function {
                             value_type: Box::new(Type::Unknown),
                         })),
                         default: None,
+                        metadata: Vec::new(),
                     },
                 ];
             }
@@ -289,6 +372,8 @@ impl Function {
                 parameters,
                 return_annotation,
                 docstring,
+                is_abstract,
+                is_abstract_method,
             })
         } else {
             Ok(Self {
@@ -300,6 +385,7 @@ impl Function {
                         kind: ParameterKind::VarPositional,
                         annotation: Type::PyTuple(vec![Type::Unknown]),
                         default: None,
+                        metadata: Vec::new(),
                     },
                     Parameter {
                         name: Ident::from_rs("kwargs"),
@@ -309,26 +395,823 @@ impl Function {
                             value_type: Box::new(Type::Unknown),
                         })),
                         default: None,
+                        metadata: Vec::new(),
                     },
                 ],
                 return_annotation: Type::Unknown,
                 docstring,
+                is_abstract,
+                is_abstract_method,
+            })
+        }
+    }
+
+    /// Replace the generic parameters (`*args` and `**kwargs`) of the constructor of a dataclass with the parameters
+    /// of the `__init__` generated by `dataclasses`, e.g. if a subclass defines `__init__(self, *args, **kwargs)` that
+    /// chains to it. Fields excluded via `field(init=False)` and `ClassVar` fields are not parameters, `InitVar` fields
+    /// are, and keyword-only fields follow all other fields (as in the generated `__init__`).
+    pub(crate) fn infer_dataclass_parameters(
+        &mut self,
+        cfg: &Config,
+        sink: &ReportSink,
+        class: &pyo3::Bound<pyo3::types::PyType>,
+    ) -> Result<()> {
+        let is_generic = !self.parameters.is_empty()
+            && self.parameters.iter().all(|param| {
+                [ParameterKind::VarPositional, ParameterKind::VarKeyword].contains(&param.kind)
+            });
+        if !is_generic {
+            return Ok(());
+        }
+
+        let py = class.py();
+        let missing = py
+            .import_bound(pyo3::intern!(py, "dataclasses"))?
+            .getattr(pyo3::intern!(py, "MISSING"))?;
+        let mut parameters = Vec::new();
+        for field in class
+            .getattr(pyo3::intern!(py, "__dataclass_fields__"))?
+            .call_method0(pyo3::intern!(py, "values"))?
+            .iter()?
+        {
+            let field = field?;
+            let annotation = field.getattr(pyo3::intern!(py, "type"))?;
+            if !field.getattr(pyo3::intern!(py, "init"))?.is_truthy()?
+                || annotation.to_string().contains("ClassVar")
+            {
+                continue;
+            }
+            let kind = if field
+                .getattr(pyo3::intern!(py, "kw_only"))
+                .and_then(|kw_only| kw_only.extract::<bool>())
+                .unwrap_or(false)
+            {
+                ParameterKind::KeywordOnly
+            } else {
+                ParameterKind::PositionalOrKeyword
+            };
+            let default = field.getattr(pyo3::intern!(py, "default"))?;
+            let default_factory = field.getattr(pyo3::intern!(py, "default_factory"))?;
+            let default = if !default.is(&missing) {
+                Some(DefaultValue::classify(&default)?)
+            } else if !default_factory.is(&missing) {
+                Some(DefaultValue::Expression {
+                    source: format!(
+                        "{}()",
+                        default_factory.getattr(pyo3::intern!(py, "__qualname__"))?
+                    ),
+                })
+            } else {
+                None
+            };
+            parameters.push(Parameter {
+                name: Ident::from_py(&field.getattr(pyo3::intern!(py, "name"))?.to_string()),
+                kind,
+                metadata: Type::annotated_metadata(&annotation)?,
+                annotation: Type::from_py_with_max_depth(annotation, cfg.max_type_depth, sink)?,
+                default,
+            });
+        }
+        parameters.sort_by_key(|param| param.kind == ParameterKind::KeywordOnly);
+        super::common::disambiguate(parameters.iter_mut().map(|param| &mut param.name));
+        self.parameters = parameters;
+        Ok(())
+    }
+
+    /// Check whether the signature only consists of variadic parameters (`*args` and `**kwargs`).
+    fn is_generic_signature(function_signature: &pyo3::Bound<pyo3::types::PyAny>) -> bool {
+        let py = function_signature.py();
+        function_signature
+            .getattr(pyo3::intern!(py, "parameters"))
+            .and_then(|parameters| parameters.call_method0(pyo3::intern!(py, "values")))
+            .and_then(|parameters| {
+                parameters
+                    .iter()?
+                    .map(|param| {
+                        param?
+                            .getattr(pyo3::intern!(py, "kind"))?
+                            .extract::<u8>()
+                            .map(ParameterKind::from)
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .is_ok_and(|kinds| {
+                !kinds.is_empty()
+                    && kinds.iter().all(|kind| {
+                        [ParameterKind::VarPositional, ParameterKind::VarKeyword].contains(kind)
+                    })
+            })
+    }
+
+    /// Check whether the source code of the function consists solely of an optional docstring and
+    /// `raise NotImplementedError`. Functions without available source code are not considered abstract.
+    fn is_abstract(function: &pyo3::Bound<pyo3::types::PyAny>) -> bool {
+        let py = function.py();
+        (|| -> Result<bool> {
+            let inspect = py.import_bound(pyo3::intern!(py, "inspect"))?;
+            let source_code = inspect.call_method1(
+                pyo3::intern!(py, "getsource"),
+                (inspect.call_method1(pyo3::intern!(py, "unwrap"), (function,))?,),
+            )?;
+            let source_code = py
+                .import_bound(pyo3::intern!(py, "textwrap"))?
+                .call_method1(pyo3::intern!(py, "dedent"), (source_code,))?;
+            let module_node = py
+                .import_bound(pyo3::intern!(py, "ast"))?
+                .call_method1(pyo3::intern!(py, "parse"), (source_code,))?;
+            match super::common::ast::children(&module_node, pyo3::intern!(py, "body"))?.as_slice()
+            {
+                [node]
+                    if super::common::ast::is_node(node, &["FunctionDef", "AsyncFunctionDef"])? =>
+                {
+                    super::common::ast::is_raising_only(node)
+                }
+                _ => Ok(false),
+            }
+        })()
+        .unwrap_or(false)
+    }
+
+    /// Parse the function from its `ast.FunctionDef` (or `ast.AsyncFunctionDef`) node without evaluating it.
+    /// The type of methods must already be determined from their decorators, and annotations are resolved
+    /// with the names bound in the `scope` of the module.
+    pub fn from_ast(
+        cfg: &Config,
+        sink: &ReportSink,
+        node: &pyo3::Bound<pyo3::types::PyAny>,
+        name: Path,
+        mut typ: FunctionType,
+        scope: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let py = node.py();
+
+        // Extract the docstring of the function
+        let docstring = super::common::ast::docstring(node)?;
+
+        // Extract the parameters of the function
+        let arguments = node.getattr(pyo3::intern!(py, "args"))?;
+        let parse_param =
+            |arg: &pyo3::Bound<pyo3::types::PyAny>,
+             kind: ParameterKind,
+             default: Option<&pyo3::Bound<pyo3::types::PyAny>>| {
+                let name = Ident::from_py(&arg.getattr(pyo3::intern!(py, "arg"))?.to_string());
+                let (annotation, metadata) = match kind {
+                    ParameterKind::VarPositional => {
+                        (Type::PyTuple(vec![Type::Unknown]), Vec::new())
+                    }
+                    ParameterKind::VarKeyword => (
+                        Type::Optional(Box::new(Type::PyDict {
+                            key_type: Box::new(Type::Unknown),
+                            value_type: Box::new(Type::Unknown),
+                        })),
+                        Vec::new(),
+                    ),
+                    _ => {
+                        let annotation = arg.getattr(pyo3::intern!(py, "annotation"))?;
+                        let metadata = if annotation.is_none() {
+                            Vec::new()
+                        } else {
+                            Type::annotated_metadata_from_str(&super::common::ast::unparse(
+                                &annotation,
+                            )?)
+                        };
+                        (
+                            Type::from_ast(&annotation, scope, cfg.max_type_depth, sink)?,
+                            metadata,
+                        )
+                    }
+                };
+                // Literal defaults are evaluated, while other expressions are kept as their AST node
+                let default = default
+                    .map(|default| {
+                        DefaultValue::classify(
+                            &super::common::ast::literal_eval(default)
+                                .unwrap_or_else(|| default.clone()),
+                        )
+                    })
+                    .transpose()?;
+                Result::Ok(Parameter {
+                    name,
+                    kind,
+                    annotation,
+                    default,
+                    metadata,
+                })
+            };
+
+        // Positional parameters (the trailing ones are matched with the default values)
+        let positional =
+            super::common::ast::children(&arguments, pyo3::intern!(py, "posonlyargs"))?
+                .into_iter()
+                .map(|arg| (arg, ParameterKind::PositionalOnly))
+                .chain(
+                    super::common::ast::children(&arguments, pyo3::intern!(py, "args"))?
+                        .into_iter()
+                        .map(|arg| (arg, ParameterKind::PositionalOrKeyword)),
+                )
+                .collect_vec();
+        let defaults = super::common::ast::children(&arguments, pyo3::intern!(py, "defaults"))?;
+        let n_required = positional.len().saturating_sub(defaults.len());
+        let mut parameters = positional
+            .iter()
+            .enumerate()
+            .map(|(i, (arg, kind))| {
+                parse_param(arg, *kind, i.checked_sub(n_required).map(|i| &defaults[i]))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Variadic and keyword-only parameters
+        let vararg = arguments.getattr(pyo3::intern!(py, "vararg"))?;
+        if !vararg.is_none() {
+            parameters.push(parse_param(&vararg, ParameterKind::VarPositional, None)?);
+        }
+        let kw_defaults =
+            super::common::ast::children(&arguments, pyo3::intern!(py, "kw_defaults"))?;
+        for (arg, default) in
+            super::common::ast::children(&arguments, pyo3::intern!(py, "kwonlyargs"))?
+                .iter()
+                .zip(&kw_defaults)
+        {
+            parameters.push(parse_param(
+                arg,
+                ParameterKind::KeywordOnly,
+                (!default.is_none()).then_some(default),
+            )?);
+        }
+        let kwarg = arguments.getattr(pyo3::intern!(py, "kwarg"))?;
+        if !kwarg.is_none() {
+            parameters.push(parse_param(&kwarg, ParameterKind::VarKeyword, None)?);
+        }
+
+        // Extract the return annotation of the function
+        let return_annotation = Type::from_ast(
+            &node.getattr(pyo3::intern!(py, "returns"))?,
+            scope,
+            cfg.max_type_depth,
+            sink,
+        )?;
+
+        // Skip the first parameter of methods that are bound to the instance or class
+        if let FunctionType::Method {
+            class_path,
+            typ: method_typ,
+        } = &typ
+        {
+            match method_typ {
+                MethodType::InstanceMethod
+                | MethodType::ClassMethod
+                | MethodType::Constructor
+                | MethodType::Callable
+                    if !parameters.is_empty() =>
+                {
+                    parameters.remove(0);
+                }
+                MethodType::InstanceMethod => {
+                    sink.warn(
+                        crate::WarningKind::Other,
+                        &name,
+                        format!("Method '{name}' is marked as an instance method, but has no parameters. Changed to static method."),
+                    );
+                    typ = FunctionType::Method {
+                        class_path: class_path.clone(),
+                        typ: MethodType::StaticMethod,
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        // Retain only used parameters (discard unused `_` parameters)
+        parameters.retain(|param| param.name.as_rs() != "r#_");
+
+        // Determine whether the function is declared with `@abstractmethod`
+        let is_abstract_method =
+            super::common::ast::children(node, pyo3::intern!(py, "decorator_list"))?
+                .iter()
+                .map(super::common::ast::unparse)
+                .collect::<Result<Vec<_>>>()?
+                .iter()
+                .any(|decorator| {
+                    ["abstractmethod", "abc.abstractmethod"].contains(&decorator.as_str())
+                });
+
+        // Resolve collisions of transliterated parameter names
+        super::common::disambiguate(parameters.iter_mut().map(|param| &mut param.name));
+
+        Ok(Self {
+            name,
+            typ,
+            parameters,
+            return_annotation,
+            docstring,
+            is_abstract: super::common::ast::is_raising_only(node).unwrap_or(false),
+            is_abstract_method,
+        })
+    }
+
+    /// Replace the parameters and the return annotation of the function with those of the given signature in
+    /// Python syntax, e.g. `(data: bytes, *, level: int = 6) -> bytes`. The first parameter of methods that are
+    /// bound to the instance or class can be omitted.
+    pub(crate) fn override_signature(
+        &mut self,
+        cfg: &Config,
+        sink: &ReportSink,
+        signature: &str,
+    ) -> Result<()> {
+        let invalid_signature = |reason: &str| {
+            crate::PyBindgenError::ParseError(format!(
+                "Invalid signature '{signature}' for function '{}': {reason}",
+                self.name
+            ))
+        };
+
+        // Split the signature into the parameter list and the return annotation (`>` only occurs at the top level
+        // of a signature as part of `->`, since it is nested within the parameter list or quotes otherwise)
+        let (parameter_list, return_annotation) =
+            match split_top_level(signature, '>').as_slice() {
+                [parameter_list] => Some((*parameter_list, None)),
+                [parameter_list, return_annotation] => parameter_list
+                    .strip_suffix('-')
+                    .map(|parameter_list| (parameter_list.trim_end(), Some(*return_annotation))),
+                _ => None,
+            }
+            .and_then(|(parameter_list, return_annotation)| {
+                parameter_list
+                    .strip_prefix('(')
+                    .and_then(|parameter_list| parameter_list.strip_suffix(')'))
+                    .map(|parameter_list| (parameter_list, return_annotation))
+            })
+            .ok_or_else(|| invalid_signature("expected `(<parameters>) -> <return type>`"))?;
+        let return_annotation = match return_annotation {
+            Some(return_annotation) => {
+                Type::from_str_with_max_depth(return_annotation, cfg.max_type_depth, sink)?
+            }
+            None => Type::Unknown,
+        };
+
+        // Parse the parameters, whose kind is determined by the `/`, `*`, `*args` and `**kwargs` separators
+        let mut parameters = Vec::new();
+        let mut kind = ParameterKind::PositionalOrKeyword;
+        for param in split_top_level(parameter_list, ',')
+            .into_iter()
+            .filter(|param| !param.is_empty())
+        {
+            match param {
+                "/" => parameters
+                    .iter_mut()
+                    .for_each(|param: &mut Parameter| param.kind = ParameterKind::PositionalOnly),
+                "*" => kind = ParameterKind::KeywordOnly,
+                param => {
+                    let (param, default) = match split_top_level(param, '=').as_slice() {
+                        [param] => (*param, None),
+                        [param, default] => (*param, Some(*default)),
+                        _ => {
+                            return Err(invalid_signature(&format!(
+                                "cannot parse parameter `{param}`"
+                            )))
+                        }
+                    };
+                    let (name, annotation) = match split_top_level(param, ':').as_slice() {
+                        [name] => (*name, None),
+                        [name, annotation] => (*name, Some(*annotation)),
+                        _ => {
+                            return Err(invalid_signature(&format!(
+                                "cannot parse parameter `{param}`"
+                            )))
+                        }
+                    };
+                    let (name, param_kind) = if let Some(name) = name.strip_prefix("**") {
+                        (name, ParameterKind::VarKeyword)
+                    } else if let Some(name) = name.strip_prefix('*') {
+                        kind = ParameterKind::KeywordOnly;
+                        (name, ParameterKind::VarPositional)
+                    } else {
+                        (name, kind)
+                    };
+                    let (annotation, metadata) = match param_kind {
+                        ParameterKind::VarPositional => {
+                            (Type::PyTuple(vec![Type::Unknown]), Vec::new())
+                        }
+                        ParameterKind::VarKeyword => (
+                            Type::Optional(Box::new(Type::PyDict {
+                                key_type: Box::new(Type::Unknown),
+                                value_type: Box::new(Type::Unknown),
+                            })),
+                            Vec::new(),
+                        ),
+                        _ => match annotation {
+                            Some(annotation) => (
+                                Type::from_str_with_max_depth(
+                                    annotation,
+                                    cfg.max_type_depth,
+                                    sink,
+                                )?,
+                                Type::annotated_metadata_from_str(annotation),
+                            ),
+                            None => (Type::Unknown, Vec::new()),
+                        },
+                    };
+                    // Literal defaults are evaluated, while other expressions are kept as their source
+                    let default = default
+                        .map(|default| {
+                            pyo3::Python::with_gil(|py| {
+                                match py
+                                    .import_bound(pyo3::intern!(py, "ast"))?
+                                    .call_method1(pyo3::intern!(py, "literal_eval"), (default,))
+                                {
+                                    Ok(default) => DefaultValue::classify(&default),
+                                    Err(_) => Ok(DefaultValue::Expression {
+                                        source: default.to_owned(),
+                                    }),
+                                }
+                            })
+                        })
+                        .transpose()?;
+                    parameters.push(Parameter {
+                        name: Ident::from_py(name),
+                        kind: param_kind,
+                        annotation,
+                        default,
+                        metadata,
+                    });
+                }
+            }
+        }
+
+        // Skip the first parameter of methods that are bound to the instance or class
+        if let FunctionType::Method {
+            typ:
+                MethodType::InstanceMethod
+                | MethodType::ClassMethod
+                | MethodType::Constructor
+                | MethodType::Callable,
+            ..
+        } = self.typ
+        {
+            if parameters
+                .first()
+                .is_some_and(|param| ["r#self", "cls"].contains(&param.name.as_rs()))
+            {
+                parameters.remove(0);
+            }
+        }
+
+        // Retain only used parameters (discard unused `_` parameters)
+        parameters.retain(|param| param.name.as_rs() != "r#_");
+
+        // Resolve collisions of transliterated parameter names
+        super::common::disambiguate(parameters.iter_mut().map(|param| &mut param.name));
+
+        self.parameters = parameters;
+        self.return_annotation = return_annotation;
+        Ok(())
+    }
+
+    /// Check whether bindings are generated for the function under its original name, i.e. it is neither
+    /// skipped nor renamed during the generation.
+    pub(crate) fn is_generated_as_is(&self, cfg: &Config) -> bool {
+        let name = self.name.name();
+        let is_skipped = (self.is_abstract && cfg.abstract_functions == AbstractPolicy::Skip)
+            || (self.is_abstract_method
+                && cfg.abstract_method_handling == AbstractMethodHandling::Skip)
+            || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&name.as_py());
+        !is_skipped && syn::Ident::try_from(name).is_ok()
+    }
+
+    /// Resolve the name of a method that is generated under a fixed `intended` name (e.g. `new` for `__init__`).
+    /// On collision, the name is suffixed with the name of the Python method (e.g. `new_from_init`).
+    fn resolve_dunder_name(
+        &self,
+        intended: &str,
+        cfg: &Config,
+        sink: &ReportSink,
+        scoped_function_idents: &[&Ident],
+    ) -> Result<Option<syn::Ident>> {
+        let alternative = format!(
+            "{intended}_from_{}",
+            self.name.name().as_py().trim_matches('_')
+        );
+        cfg.on_collision
+            .resolve(
+                sink,
+                &self.name,
+                Ident::from_py(intended),
+                Some(Ident::from_py(&alternative)),
+                |ident| scoped_function_idents.contains(&ident),
+            )?
+            .map(syn::Ident::try_from)
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Generate a module `{function}_defaults` with a constant for each parameter whose default value is a literal
+    /// that can be represented in Rust, e.g. `pub const ENCODING: &str = "utf-8";`.
+    fn generate_default_helpers(
+        &self,
+        cfg: &Config,
+        function_ident: &syn::Ident,
+    ) -> Result<TokenStream> {
+        let (const_idents, (const_types, const_values)): (Vec<syn::Ident>, (Vec<_>, Vec<_>)) = self
+            .parameters
+            .iter()
+            .filter_map(|param| {
+                let (const_type, const_value) = param.default.as_ref()?.rs_const(cfg)?;
+                Some(
+                    syn::Ident::try_from(Ident::from_py(&param.name.as_py().to_uppercase()))
+                        .map(|const_ident| (const_ident, (const_type, const_value))),
+                )
             })
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+        if const_idents.is_empty() {
+            return Ok(TokenStream::new());
+        }
+
+        let function_name = function_ident.to_string();
+        let function_name = function_name.trim_start_matches("r#");
+        let mod_ident: syn::Ident =
+            Ident::from_py(&format!("{function_name}_defaults")).try_into()?;
+        let doc = if cfg.generate_docs {
+            let docstring = format!(" Default values of the parameters of [`{function_name}`].");
+            quote::quote! { #[doc = #docstring] }
+        } else {
+            TokenStream::new()
+        };
+        let mod_allow = crate::utils::lint::allow_mod(cfg, &mod_ident, []);
+        let const_allows = const_idents
+            .iter()
+            .map(|const_ident| crate::utils::lint::allow_const(cfg, const_ident));
+        Ok(quote::quote! {
+            #doc
+            #mod_allow
+            pub mod #mod_ident {
+                #(
+                    #const_allows
+                    pub const #const_idents: #const_types = #const_values;
+                )*
+            }
+        })
+    }
+
+    /// Generate a `no_run` example of how to call a module-level function with placeholder arguments. Returns
+    /// `None` for methods, for functions of module handles and for functions with parameters for which no simple
+    /// placeholder exists.
+    fn doctest_example(&self, cfg: &Config, function_ident: &syn::Ident) -> Option<Vec<String>> {
+        if self.typ != FunctionType::Function || cfg.module_handle_injection {
+            return None;
         }
+        let args = self
+            .parameters
+            .iter()
+            .map(|param| match &param.annotation {
+                Type::PyBool => Some("false"),
+                Type::PyLong if matches!(cfg.int_type, IntType::I64 | IntType::I128) => Some("0"),
+                Type::PyFloat => Some("0.0"),
+                Type::PyString => Some("\"\""),
+                Type::Optional(_) => Some("None"),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let function_path = self
+            .name
+            .parent()
+            .unwrap_or_default()
+            .join(&Path::from_rs(&function_ident.to_string()))
+            .to_rs();
+        let args = std::iter::once("py").chain(args).join(", ");
+        Some(
+            [
+                " # Example".to_owned(),
+                String::new(),
+                " ```no_run".to_owned(),
+                " pyo3::prepare_freethreaded_python();".to_owned(),
+                " pyo3::Python::with_gil(|py| {".to_owned(),
+                format!("     let result = {function_path}({args});"),
+                " });".to_owned(),
+                " ```".to_owned(),
+            ]
+            .into(),
+        )
+    }
+
+    /// Example of how to call the function from Rust with placeholder arguments, which are labelled with the names
+    /// of the parameters. The example is not compiled (`ignore`), as placeholders cannot be derived for all types.
+    fn usage_example(&self, cfg: &Config, function_ident: &syn::Ident) -> Vec<String> {
+        let handle_path = |module_path: Option<Path>| {
+            let module_path = module_path.unwrap_or_default();
+            module_path
+                .join(&super::Module::handle_ident(&module_path).into())
+                .to_rs()
+        };
+        let (receiver, call) = match &self.typ {
+            FunctionType::Method {
+                class_path,
+                typ: MethodType::InstanceMethod | MethodType::Callable,
+            } => (
+                Some(format!(
+                    "     let instance: &pyo3::Bound<'_, {}> = todo!();",
+                    class_path.to_rs()
+                )),
+                format!("instance.{function_ident}("),
+            ),
+            FunctionType::Method { class_path, .. } if cfg.module_handle_injection => (
+                Some(format!(
+                    "     let handle = {}::import(py)?;",
+                    handle_path(class_path.parent())
+                )),
+                format!("{}::{function_ident}(&handle, py", class_path.to_rs()),
+            ),
+            FunctionType::Method { class_path, .. } => {
+                (None, format!("{}::{function_ident}(py", class_path.to_rs()))
+            }
+            _ if cfg.module_handle_injection => (
+                Some(format!(
+                    "     let handle = {}::import(py)?;",
+                    handle_path(self.name.parent())
+                )),
+                format!("handle.{function_ident}(py"),
+            ),
+            _ => (
+                None,
+                format!(
+                    "{}(py",
+                    self.name
+                        .parent()
+                        .unwrap_or_default()
+                        .join(&Path::from_rs(&function_ident.to_string()))
+                        .to_rs()
+                ),
+            ),
+        };
+        let args = self
+            .parameters
+            .iter()
+            .map(|param| {
+                let placeholder = match &param.annotation {
+                    Type::PyBool => "false",
+                    Type::PyLong => "0",
+                    Type::PyFloat => "0.0",
+                    Type::PyString => "\"...\"",
+                    Type::Optional(_) => "None",
+                    _ => "todo!()",
+                };
+                format!(
+                    "         {}: {placeholder},",
+                    param.name.with_prefix("p_").as_rs()
+                )
+            })
+            .collect_vec();
+        let call = if args.is_empty() {
+            vec![format!("     let result = {call});")]
+        } else {
+            let call = if call.ends_with('(') {
+                format!("     let result = {call}")
+            } else {
+                format!("     let result = {call},")
+            };
+            std::iter::once(call)
+                .chain(args)
+                .chain(std::iter::once("     );".to_owned()))
+                .collect()
+        };
+        [
+            " # Example".to_owned(),
+            String::new(),
+            " ```rust,ignore".to_owned(),
+            " pyo3::Python::with_gil(|py| {".to_owned(),
+        ]
+        .into_iter()
+        .chain(receiver)
+        .chain(call)
+        .chain([" });".to_owned(), " ```".to_owned()])
+        .collect()
     }
 
     pub fn generate(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         scoped_function_idents: &[&Ident],
         local_types: &HashMap<Path, Path>,
     ) -> Result<FunctionImplementation> {
         let mut impl_fn = proc_macro2::TokenStream::new();
 
+        // Abstract functions
+        let is_abstract_with_warning = self.is_abstract
+            && match cfg.abstract_functions {
+                AbstractPolicy::Generate => false,
+                AbstractPolicy::GenerateWithWarning => true,
+                AbstractPolicy::Skip => {
+                    sink.skip(
+                        &self.name,
+                        "Abstract function that only raises `NotImplementedError`",
+                    );
+                    return Ok(FunctionImplementation::empty_function());
+                }
+            };
+        let is_abstract_method_marked = self.is_abstract_method
+            && match cfg.abstract_method_handling {
+                AbstractMethodHandling::Emit => false,
+                AbstractMethodHandling::Mark => true,
+                AbstractMethodHandling::Skip => {
+                    sink.skip(
+                        &self.name,
+                        "Abstract method declared with `@abstractmethod`",
+                    );
+                    return Ok(FunctionImplementation::empty_function());
+                }
+            };
+
+        // Python version required at runtime
+        let required_python_version = cfg.required_python_version(&self.name);
+
         // Documentation
         if cfg.generate_docs {
-            if let Some(mut docstring) = self.docstring.clone() {
-                crate::utils::text::format_docstring(&mut docstring);
+            let mut docstring = self.docstring.clone();
+            if is_abstract_with_warning {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(
+                    "Warning: This function is abstract in Python, i.e. it only raises `NotImplementedError`.",
+                );
+            }
+            if is_abstract_method_marked {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(
+                    "Warning: This method is declared with `@abstractmethod` in Python, i.e. it must be overridden by a subclass before it can be called.",
+                );
+            }
+            if cfg.preserve_default_repr_in_docs {
+                for param in &self.parameters {
+                    if let Some(default) = &param.default {
+                        let docstring = docstring.get_or_insert_with(String::new);
+                        if !docstring.is_empty() {
+                            docstring.push_str("\n\n");
+                        }
+                        docstring.push_str(&format!(
+                            "Parameter `{}` default: {}",
+                            param.name.as_py(),
+                            default.doc()
+                        ));
+                    }
+                }
+            }
+            for param in &self.parameters {
+                if !param.metadata.is_empty() {
+                    let docstring = docstring.get_or_insert_with(String::new);
+                    if !docstring.is_empty() {
+                        docstring.push_str("\n\n");
+                    }
+                    docstring.push_str(&format!(
+                        "Parameter `{}` annotated with: {}",
+                        param.name.as_py(),
+                        param
+                            .metadata
+                            .iter()
+                            .map(|metadata| format!("`{metadata}`"))
+                            .join(", ")
+                    ));
+                }
+            }
+            if cfg.dict_key_accessors && self.returns_str_keyed_dict() {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(if self.str_keyed_dict_value_type().is_some() {
+                    "The values of the returned dictionary can be extracted by key via `StrKeyedDict::get_str`."
+                } else {
+                    "The values of the returned dictionary can be extracted by key via `DictKeyAccessors::get_str`."
+                });
+            }
+            if let Some(note) = self
+                .return_annotation
+                .well_known()
+                .and_then(WellKnownType::doc_note)
+            {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(note);
+            }
+            if let Some((major, minor)) = required_python_version {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(&format!(
+                    "Note: Requires Python {major}.{minor}+, which is checked at runtime."
+                ));
+            }
+            if let Some(mut docstring) = docstring {
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
                 impl_fn.extend(quote::quote! {
                     #[doc = #docstring]
                 });
@@ -351,24 +1234,47 @@ impl Function {
                     name.as_py().replace(|c: char| !c.is_alphanumeric(), "_")
                 ));
                 if let Ok(sanitized_ident) = new_name.clone().try_into() {
-                    eprintln!(
-                        "WARN: Function '{}' is an invalid Rust ident for a function name. Renamed to '{}'.",
-                        self.name, self.name.parent().unwrap_or_default().join(&new_name.into())
-                    );
+                    cfg.sanitization.handle(
+                        sink,
+                        &self.name,
+                        "Function",
+                        Some(
+                            &self
+                                .name
+                                .parent()
+                                .unwrap_or_default()
+                                .join(&new_name.into()),
+                        ),
+                    )?;
                     sanitized_ident
                 } else {
-                    eprintln!(
-                        "WARN: Function '{}' is an invalid Rust ident for a function name. Renaming failed. Bindings will not be generated.",
-                        self.name
-                    );
+                    cfg.sanitization
+                        .handle(sink, &self.name, "Function", None)?;
+                    sink.skip(&self.name, "Invalid Rust ident for a function name");
                     return Ok(FunctionImplementation::empty_function());
                 }
             }
         };
+
+        // Example of how to call the function
+        let mut has_doctest_example = false;
+        if cfg.emit_doctest_examples {
+            if let Some(mut example) = self.doctest_example(cfg, &function_ident) {
+                has_doctest_example = true;
+                // Separate the example from the docstring (if any)
+                if !impl_fn.is_empty() {
+                    example.insert(0, String::new());
+                }
+                impl_fn.extend(quote::quote! {
+                    #(#[doc = #example])*
+                });
+            }
+        }
+
         let param_idents: Vec<syn::Ident> = self
             .parameters
             .iter()
-            .map(|param| Ok(Ident::from_py(&format!("p_{}", param.name)).try_into()?))
+            .map(|param| Ok(param.name.with_prefix("p_").try_into()?))
             .collect::<Result<Vec<_>>>()?;
         // Pre-process parameters that require it
         let param_preprocessing: proc_macro2::TokenStream = self
@@ -378,15 +1284,51 @@ impl Function {
             .map(|(param, param_ident)| {
                 param
                     .annotation
-                    .preprocess_borrowed(param_ident, local_types)
+                    .preprocess_borrowed(param_ident, cfg, local_types)
             })
             .collect();
         let param_types: Vec<proc_macro2::TokenStream> = self
             .parameters
             .iter()
-            .map(|param| Result::Ok(param.annotation.clone().into_rs_borrowed(local_types)))
+            .map(|param| Result::Ok(param.annotation.clone().into_rs_borrowed(cfg, local_types)))
             .collect::<Result<Vec<_>>>()?;
-        let return_type = self.return_annotation.clone().into_rs_owned(local_types);
+        let return_type = if cfg.dict_key_accessors && self.returns_str_keyed_dict() {
+            if let Some(value_type) = self.str_keyed_dict_value_type() {
+                let str_keyed_dict: syn::Path = local_types
+                    .get(&Path::from_rs(Self::STR_KEYED_DICT))
+                    .cloned()
+                    .unwrap_or_else(|| Path::from_rs(Self::STR_KEYED_DICT))
+                    .try_into()?;
+                let value_type = value_type.clone().into_rs_owned(cfg, local_types);
+                quote::quote!(#str_keyed_dict<'py, #value_type>)
+            } else {
+                quote::quote!(::pyo3::Bound<'py, ::pyo3::types::PyDict>)
+            }
+        } else {
+            self.return_annotation
+                .clone()
+                .into_rs_returned(cfg, local_types)
+        };
+        // Handle of the module through which the Python objects are looked up (see `Config::module_handle_injection`)
+        let handle_ident: Option<syn::Ident> = if cfg.module_handle_injection {
+            let module_path = match &self.typ {
+                FunctionType::Method { class_path, .. } => class_path.parent(),
+                _ => self.name.parent(),
+            }
+            .unwrap_or_default();
+            Some(super::Module::handle_ident(&module_path).try_into()?)
+        } else {
+            None
+        };
+        let handle_param = match (&handle_ident, &self.typ) {
+            (None, _) => TokenStream::new(),
+            (Some(handle_ident), FunctionType::Method { .. }) => {
+                quote::quote! { handle: &#handle_ident, }
+            }
+            (Some(_), _) => quote::quote! { &self, },
+        };
+        // Ident under which the function is called (differs from the function ident for constructors and callables)
+        let mut called_ident = function_ident.clone();
         let fn_contract = match &self.typ {
             FunctionType::Method {
                 typ: MethodType::InstanceMethod,
@@ -403,20 +1345,12 @@ impl Function {
                 typ: MethodType::Callable,
                 ..
             } => {
-                let call_fn_ident: syn::Ident = {
-                    let mut i = 0;
-                    loop {
-                        let ident = Ident::from_py(&format!(
-                            "call{}",
-                            (i > 0).then(|| i.to_string()).unwrap_or_default()
-                        ));
-                        if !scoped_function_idents.contains(&&ident) {
-                            break ident;
-                        }
-                        i += 1;
-                    }
-                }
-                .try_into()?;
+                let Some(call_fn_ident) =
+                    self.resolve_dunder_name("call", cfg, sink, scoped_function_idents)?
+                else {
+                    return Ok(FunctionImplementation::empty_method());
+                };
+                called_ident = call_fn_ident.clone();
                 quote::quote! {
                     fn #call_fn_ident<'py>(
                         &'py self,
@@ -428,22 +1362,22 @@ impl Function {
                 typ: MethodType::Constructor,
                 ..
             } => {
-                let new_fn_ident: syn::Ident = {
-                    let mut i = 0;
-                    loop {
-                        let ident = Ident::from_py(&format!(
-                            "new{}",
-                            (i > 0).then(|| i.to_string()).unwrap_or_default()
-                        ));
-                        if !scoped_function_idents.contains(&&ident) {
-                            break ident;
-                        }
-                        i += 1;
-                    }
-                }
-                .try_into()?;
+                let Some(new_fn_ident) = self.resolve_dunder_name(
+                    &cfg.constructor_name,
+                    cfg,
+                    sink,
+                    scoped_function_idents,
+                )?
+                else {
+                    return Ok(FunctionImplementation::empty_function());
+                };
+                called_ident = new_fn_ident.clone();
+                let handle_param = handle_ident
+                    .as_ref()
+                    .map(|handle_ident| quote::quote! { handle: &#handle_ident, });
                 quote::quote! {
                     pub fn #new_fn_ident<'py>(
+                        #handle_param
                         py: ::pyo3::marker::Python<'py>,
                         #(#param_idents: #param_types),*
                     ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>>
@@ -452,13 +1386,41 @@ impl Function {
             _ => {
                 quote::quote! {
                     pub fn #function_ident<'py>(
+                        #handle_param
                         py: ::pyo3::marker::Python<'py>,
                         #(#param_idents: #param_types),*
                     ) -> ::pyo3::PyResult<#return_type>
                 }
             }
         };
-        impl_fn.extend(fn_contract.clone());
+
+        // Example of how to call the function from Rust (unless a doctest example was already added)
+        if cfg.generate_usage_examples && !has_doctest_example {
+            let mut example = self.usage_example(cfg, &called_ident);
+            // Separate the example from the docstring (if any)
+            if !impl_fn.is_empty() {
+                example.insert(0, String::new());
+            }
+            impl_fn.extend(quote::quote! {
+                #(#[doc = #example])*
+            });
+        }
+
+        let is_trait_method = matches!(
+            self.typ,
+            FunctionType::Method {
+                typ: MethodType::InstanceMethod | MethodType::Callable,
+                ..
+            }
+        );
+        let deprecated_attr = if is_abstract_with_warning || is_abstract_method_marked {
+            quote::quote! { #[deprecated(note = "abstract in Python")] }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+        if !is_trait_method {
+            impl_fn.extend(deprecated_attr.clone());
+        }
 
         // If the function is a method with `self` as a parameter, extract the Python marker from `self`
         let maybe_extract_py = match &self.typ {
@@ -473,8 +1435,29 @@ impl Function {
 
         // Function body (function dispatcher)
         let function_dispatcher = match &self.typ {
-            FunctionType::Function | FunctionType::Closure => pyo3::Python::with_gil(|py| {
-                self.name
+            FunctionType::Function | FunctionType::Closure if handle_ident.is_some() => {
+                quote::quote! {
+                    self.0.bind(py)
+                }
+            }
+            FunctionType::Method {
+                class_path,
+                typ: MethodType::ClassMethod | MethodType::StaticMethod | MethodType::Constructor,
+            } if handle_ident.is_some() => {
+                let accessor_ident: syn::Ident =
+                    super::Module::class_accessor_ident(class_path.name()).try_into()?;
+                quote::quote! {
+                    handle.#accessor_ident(py)?
+                }
+            }
+            FunctionType::CallableInstance { instance_path } if handle_ident.is_some() => {
+                let instance_name = instance_path.name().as_py();
+                quote::quote! {
+                    ::pyo3::types::PyAnyMethods::getattr(self.0.bind(py).as_any(), ::pyo3::intern!(py, #instance_name))?
+                }
+            }
+            FunctionType::Function | FunctionType::Closure => pyo3::Python::with_gil(|py| {
+                self.name
                     .parent()
                     .unwrap_or_else(|| unreachable!())
                     .import_quote(py)
@@ -483,6 +1466,9 @@ impl Function {
                 class_path,
                 typ: MethodType::ClassMethod | MethodType::StaticMethod | MethodType::Constructor,
             } => pyo3::Python::with_gil(|py| class_path.import_quote(py)),
+            FunctionType::CallableInstance { instance_path } => {
+                pyo3::Python::with_gil(|py| instance_path.import_quote(py))
+            }
             FunctionType::Method {
                 typ: MethodType::InstanceMethod | MethodType::Callable,
                 ..
@@ -495,15 +1481,25 @@ impl Function {
                 typ: MethodType::Unknown,
                 ..
             } => {
-                eprintln!(
-                    "WARN: Method '{}' has an unknown type. Bindings will not be generated.",
-                    self.name
-                );
+                sink.skip(&self.name, "Unknown method type");
                 return Ok(FunctionImplementation::empty_method());
             }
         };
 
         // Function body: positional args
+        // Note: Positional-or-keyword parameters with a default value are passed as keyword args (unless the function
+        //       also accepts variadic positional args), so that their Python default is preserved when omitted
+        let has_var_positional_args = self
+            .parameters
+            .iter()
+            .any(|param| param.kind == ParameterKind::VarPositional);
+        let is_passed_as_keyword = |param: &Parameter| match param.kind {
+            ParameterKind::PositionalOrKeyword => {
+                param.default.is_some() && !has_var_positional_args
+            }
+            ParameterKind::KeywordOnly => true,
+            _ => false,
+        };
         let positional_args_idents: Vec<syn::Ident> = self
             .parameters
             .iter()
@@ -513,14 +1509,15 @@ impl Function {
                     ParameterKind::PositionalOrKeyword,
                 ]
                 .contains(&param.kind)
+                    && !is_passed_as_keyword(param)
             })
-            .map(|param| Ok(Ident::from_py(&format!("p_{}", param.name)).try_into()?))
+            .map(|param| Ok(param.name.with_prefix("p_").try_into()?))
             .collect::<Result<_>>()?;
         let var_positional_args_ident: Option<syn::Ident> = self
             .parameters
             .iter()
             .find(|param| param.kind == ParameterKind::VarPositional)
-            .and_then(|param| Ident::from_py(&format!("p_{}", param.name)).try_into().ok());
+            .and_then(|param| param.name.with_prefix("p_").try_into().ok());
         let has_positional_args =
             !positional_args_idents.is_empty() || var_positional_args_ident.is_some();
         let positional_args = if let Some(var_positional_args_ident) = var_positional_args_ident {
@@ -532,12 +1529,12 @@ impl Function {
                 let n_args_fixed = positional_args_idents.len();
                 quote::quote! {
                     {
-                        let mut __internal__args = Vec::with_capacity(#n_args_fixed + ::pyo3::types::PyTupleMethods::len(#var_positional_args_ident));
-                        __internal__args.extend([#(::pyo3::ToPyObject::to_object(&#positional_args_idents, py),)*]);
-                        __internal__args.extend(::pyo3::types::PyTupleMethods::iter(#var_positional_args_ident).map(|__internal__arg| ::pyo3::ToPyObject::to_object(&__internal__arg, py)));
+                        let mut __internal_args = Vec::with_capacity(#n_args_fixed + ::pyo3::types::PyTupleMethods::len(#var_positional_args_ident));
+                        __internal_args.extend([#(::pyo3::ToPyObject::to_object(&#positional_args_idents, py),)*]);
+                        __internal_args.extend(::pyo3::types::PyTupleMethods::iter(#var_positional_args_ident).map(|__internal_arg| ::pyo3::ToPyObject::to_object(&__internal_arg, py)));
                         ::pyo3::types::PyTuple::new_bound(
                             py,
-                            __internal__args,
+                            __internal_args,
                         )
                     }
                 }
@@ -555,53 +1552,65 @@ impl Function {
             }
         };
         // Function body: keyword args
+        // Note: Keyword args with a default value are omitted if `None` is passed, so that their Python default is preserved
         let keyword_args: Vec<&Parameter> = self
             .parameters
             .iter()
-            .filter(|param| [ParameterKind::KeywordOnly].contains(&param.kind))
+            .filter(|param| is_passed_as_keyword(param))
             .collect_vec();
-        let keyword_args_names: Vec<&str> = keyword_args
+        let keyword_args_set_items: Vec<proc_macro2::TokenStream> = keyword_args
             .iter()
-            .map(|param| param.name.as_py())
-            .collect();
-        let keyword_args_idents: Vec<syn::Ident> = keyword_args
-            .iter()
-            .map(|param| Ok(Ident::from_py(&format!("p_{}", param.name)).try_into()?))
+            .map(|param| {
+                let keyword_arg_name = param.name.as_py();
+                let keyword_arg_ident: syn::Ident =
+                    param.name.with_prefix("p_").try_into()?;
+                Ok(if param.default.is_some() {
+                    quote::quote! {
+                        {
+                            let __internal_value = ::pyo3::ToPyObject::to_object(&#keyword_arg_ident, py);
+                            if !__internal_value.is_none(py) {
+                                ::pyo3::types::PyDictMethods::set_item(&__internal_kwargs, ::pyo3::intern!(py, #keyword_arg_name), __internal_value)?;
+                            }
+                        }
+                    }
+                } else {
+                    quote::quote! {
+                        ::pyo3::types::PyDictMethods::set_item(&__internal_kwargs, ::pyo3::intern!(py, #keyword_arg_name), #keyword_arg_ident)?;
+                    }
+                })
+            })
             .collect::<Result<_>>()?;
         let var_keyword_args_ident: Option<syn::Ident> = self
             .parameters
             .iter()
             .find(|param| param.kind == ParameterKind::VarKeyword)
-            .and_then(|param| Ident::from_py(&format!("p_{}", param.name)).try_into().ok());
-        let has_keyword_args = !keyword_args_idents.is_empty() || var_keyword_args_ident.is_some();
+            .and_then(|param| param.name.with_prefix("p_").try_into().ok());
+        let has_keyword_args =
+            !keyword_args_set_items.is_empty() || var_keyword_args_ident.is_some();
         let keyword_args = if let Some(var_keyword_args_ident) = var_keyword_args_ident {
-            if keyword_args_idents.is_empty() {
+            if keyword_args_set_items.is_empty() {
                 quote::quote! {
                     #var_keyword_args_ident
                 }
             } else {
                 quote::quote! {
                     {
-                        let __internal__kwargs = #var_keyword_args_ident;
-                        #(
-                            ::pyo3::types::PyDictMethods::set_item(&__internal__kwargs, ::pyo3::intern!(py, #keyword_args_names), #keyword_args_idents);
-                        )*
-                        __internal__kwargs
+                        let __internal_kwargs = #var_keyword_args_ident;
+                        #(#keyword_args_set_items)*
+                        __internal_kwargs
                     }
                 }
             }
-        } else if keyword_args_idents.is_empty() {
+        } else if keyword_args_set_items.is_empty() {
             quote::quote! {
                 ::pyo3::types::PyDict::new_bound(py)
             }
         } else {
             quote::quote! {
                 {
-                    let __internal__kwargs = ::pyo3::types::PyDict::new_bound(py);
-                    #(
-                        ::pyo3::types::PyDictMethods::set_item(&__internal__kwargs, ::pyo3::intern!(py, #keyword_args_names), #keyword_args_idents);
-                    )*
-                    __internal__kwargs
+                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                    #(#keyword_args_set_items)*
+                    __internal_kwargs
                 }
             }
         };
@@ -609,7 +1618,8 @@ impl Function {
         let call = if let FunctionType::Method {
             typ: MethodType::Constructor | MethodType::Callable,
             ..
-        } = &self.typ
+        }
+        | FunctionType::CallableInstance { .. } = &self.typ
         {
             if has_keyword_args {
                 quote::quote! {
@@ -641,26 +1651,602 @@ impl Function {
             }
         };
 
+        // Function body: checks of annotated constraints
+        let param_validation: proc_macro2::TokenStream = if cfg.validate_annotated {
+            self.parameters
+                .iter()
+                .zip(param_idents.iter())
+                .map(|(param, param_ident)| param.validation(cfg, param_ident))
+                .collect()
+        } else {
+            TokenStream::new()
+        };
+
+        // Function body: check of the required Python version (cached for each function)
+        let python_version_check = if let Some((major, minor)) = required_python_version {
+            let error_message = format!("{} requires Python {major}.{minor}+", self.name);
+            quote::quote! {
+                static IS_PYTHON_VERSION_SUPPORTED: ::pyo3::sync::GILOnceCell<bool> = ::pyo3::sync::GILOnceCell::new();
+                if !*IS_PYTHON_VERSION_SUPPORTED.get_or_init(py, || py.version_info() >= (#major, #minor)) {
+                    return ::std::result::Result::Err(::pyo3::exceptions::PyRuntimeError::new_err(#error_message));
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        // Function body: check of the live Python signature against the generated one (performed at the first call)
+        let signature_guard = if cfg.runtime_signature_guards {
+            let live_function = match &self.typ {
+                FunctionType::Method {
+                    typ: MethodType::Constructor | MethodType::Callable,
+                    ..
+                }
+                | FunctionType::CallableInstance { .. } => quote::quote! {
+                    #function_dispatcher.as_any().clone()
+                },
+                _ => {
+                    let function_name = self.name.name().as_py();
+                    quote::quote! {
+                        ::pyo3::types::PyAnyMethods::getattr(#function_dispatcher.as_any(), ::pyo3::intern!(py, #function_name))?
+                    }
+                }
+            };
+            let expected_signature = self.compact_signature();
+            let function_name = self.name.to_py();
+            quote::quote! {
+                const EXPECTED_SIGNATURE: &str = #expected_signature;
+                static SIGNATURE_GUARD: ::pyo3::sync::GILOnceCell<()> = ::pyo3::sync::GILOnceCell::new();
+                SIGNATURE_GUARD.get_or_init(py, || {
+                    let _ = (|| -> ::pyo3::PyResult<()> {
+                        let __internal_signature = ::pyo3::types::PyAnyMethods::call_method1(
+                            py.import_bound(::pyo3::intern!(py, "inspect"))?.as_any(),
+                            ::pyo3::intern!(py, "signature"),
+                            (#live_function,),
+                        )?;
+                        let mut __internal_params = ::std::vec::Vec::new();
+                        for __internal_param in ::pyo3::types::PyAnyMethods::iter(&::pyo3::types::PyAnyMethods::call_method0(
+                            &::pyo3::types::PyAnyMethods::getattr(&__internal_signature, ::pyo3::intern!(py, "parameters"))?,
+                            ::pyo3::intern!(py, "values"),
+                        )?)? {
+                            let __internal_param = __internal_param?;
+                            let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::getattr(&__internal_param, ::pyo3::intern!(py, "name"))?)?;
+                            let kind: u8 = ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::getattr(&__internal_param, ::pyo3::intern!(py, "kind"))?)?;
+                            match (name.as_str(), kind) {
+                                ("_", _) => {}
+                                (_, 2) => __internal_params.push(::std::format!("*{name}")),
+                                (_, 4) => __internal_params.push(::std::format!("**{name}")),
+                                _ => __internal_params.push(::std::format!("{name}:{kind}")),
+                            }
+                        }
+                        let __internal_live_signature = __internal_params.join(",");
+                        if __internal_live_signature != EXPECTED_SIGNATURE {
+                            ::pyo3::types::PyAnyMethods::call_method1(
+                                py.import_bound(::pyo3::intern!(py, "warnings"))?.as_any(),
+                                ::pyo3::intern!(py, "warn"),
+                                (
+                                    ::std::format!(
+                                        "Signature of '{}' changed since its bindings were generated (expected '{}', found '{}')",
+                                        #function_name,
+                                        EXPECTED_SIGNATURE,
+                                        __internal_live_signature,
+                                    ),
+                                    py.get_type_bound::<::pyo3::exceptions::PyRuntimeWarning>(),
+                                ),
+                            )?;
+                        }
+                        Ok(())
+                    })();
+                });
+            }
+        } else {
+            TokenStream::new()
+        };
+
         // Function body
-        impl_fn.extend(quote::quote! {
+        let extract_return = self
+            .return_annotation
+            .extract_returned(cfg, quote::quote!(#call?));
+        let body = cfg.bodies.body(quote::quote! {
             {
                 #maybe_extract_py
+                #python_version_check
+                #signature_guard
                 #param_preprocessing
-                ::pyo3::types::PyAnyMethods::extract(
-                    &#call?
-                )
+                #param_validation
+                #extract_return
             }
         });
 
-        Ok(match &self.typ {
+        // Omit the `'py` lifetime if it is not needed beyond the `py` parameter or the `self` receiver (e.g. for
+        // primitive types), where the declaration of trait methods only depends on their signature
+        let trait_fn_contract =
+            crate::utils::lint::omit_unused_py_lifetime(fn_contract.clone(), None);
+        let fn_contract = crate::utils::lint::omit_unused_py_lifetime(fn_contract, Some(&body));
+        let allow = crate::utils::lint::allow_fn(cfg, &fn_contract)?;
+        if !is_trait_method {
+            impl_fn.extend(allow.clone());
+        }
+        impl_fn.extend(fn_contract);
+        impl_fn.extend(body);
+
+        // Constants with the default values of parameters (module-level functions only)
+        if cfg.default_helpers && self.typ == FunctionType::Function {
+            impl_fn.extend(self.generate_default_helpers(cfg, &function_ident)?);
+        }
+
+        Ok(if is_trait_method {
+            FunctionImplementation::Method(TraitMethod {
+                trait_fn: quote::quote! { #deprecated_attr #allow #trait_fn_contract ; },
+                impl_fn,
+            })
+        } else {
+            FunctionImplementation::Function(impl_fn)
+        })
+    }
+}
+
+impl Function {
+    /// Name of the wrapper of dictionaries returned for `dict[str, V]` (see `Config::dict_key_accessors`).
+    pub(crate) const STR_KEYED_DICT: &'static str = "StrKeyedDict";
+
+    /// Check whether the function or method (other than a constructor) returns `dict[str, V]`, which is returned
+    /// as `StrKeyedDict<V>` (or `Bound<PyDict>`) if `Config::dict_key_accessors` is enabled.
+    pub(crate) fn returns_str_keyed_dict(&self) -> bool {
+        !matches!(
+            self.typ,
             FunctionType::Method {
-                typ: MethodType::InstanceMethod | MethodType::Callable,
+                typ: MethodType::Constructor,
                 ..
-            } => FunctionImplementation::Method(TraitMethod {
-                trait_fn: quote::quote! { #fn_contract ; },
-                impl_fn,
+            }
+        ) && matches!(&self.return_annotation, Type::PyDict { key_type, .. } if **key_type == Type::PyString)
+    }
+
+    /// Get the value type `V` of a function that returns `dict[str, V]`, unless the values are of any type (`Any`).
+    fn str_keyed_dict_value_type(&self) -> Option<&Type> {
+        match &self.return_annotation {
+            Type::PyDict { value_type, .. }
+                if !matches!(**value_type, Type::PyAny | Type::Unknown) =>
+            {
+                Some(value_type)
+            }
+            _ => None,
+        }
+    }
+
+    /// Generate the `DictKeyAccessors` extension trait and the `StrKeyedDict` wrapper, which provide typed access to
+    /// single keys of the dictionaries returned by the functions of all modules (see `Config::dict_key_accessors`).
+    /// These are generated once alongside the top-level modules.
+    pub(crate) fn generate_dict_key_accessors(cfg: &Config) -> TokenStream {
+        let (trait_doc, struct_doc, fn_doc) = if cfg.generate_docs {
+            (
+                quote::quote! {
+                    #[doc = " Typed access to single keys of the dictionaries returned for `dict[str, Any]`, without"]
+                    #[doc = " converting the whole dictionary."]
+                },
+                quote::quote! {
+                    #[doc = " Dictionary returned for `dict[str, V]`, whose values can be extracted by key as `V` without"]
+                    #[doc = " converting the whole dictionary."]
+                },
+                quote::quote! {
+                    #[doc = " Extract the value of the given key (or `None` if the key is missing)."]
+                },
+            )
+        } else {
+            (TokenStream::new(), TokenStream::new(), TokenStream::new())
+        };
+        let str_keyed_dict = syn::Ident::new(Self::STR_KEYED_DICT, proc_macro2::Span::call_site());
+        let get_str_body = cfg.bodies.body(quote::quote! {
+            {
+                ::pyo3::types::PyDictMethods::get_item(self, key)?
+                    .map(|value| ::pyo3::types::PyAnyMethods::extract(&value))
+                    .transpose()
+            }
+        });
+        let typed_get_str_body = cfg.bodies.body(quote::quote! {
+            {
+                DictKeyAccessors::get_str(&self.0, key)
+            }
+        });
+        let extract_body = cfg.bodies.body(quote::quote! {
+            {
+                ::std::result::Result::Ok(Self(
+                    ::pyo3::types::PyAnyMethods::downcast::<::pyo3::types::PyDict>(ob)?.clone(),
+                    ::std::marker::PhantomData,
+                ))
+            }
+        });
+        quote::quote! {
+            #trait_doc
+            pub trait DictKeyAccessors<'py> {
+                #fn_doc
+                fn get_str<T>(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<T>>
+                where
+                    T: ::pyo3::FromPyObject<'py>;
+            }
+            #[automatically_derived]
+            impl<'py> DictKeyAccessors<'py> for ::pyo3::Bound<'py, ::pyo3::types::PyDict> {
+                fn get_str<T>(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<T>>
+                where
+                    T: ::pyo3::FromPyObject<'py>,
+                #get_str_body
+            }
+            #struct_doc
+            #[repr(transparent)]
+            pub struct #str_keyed_dict<'py, V>(
+                ::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ::std::marker::PhantomData<V>,
+            );
+            impl<'py, V> #str_keyed_dict<'py, V>
+            where
+                V: ::pyo3::FromPyObject<'py>,
+            {
+                #fn_doc
+                pub fn get_str(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<V>> #typed_get_str_body
+            }
+            impl<'py, V> #str_keyed_dict<'py, V> {
+                /// Get the underlying Python dictionary.
+                pub fn into_inner(self) -> ::pyo3::Bound<'py, ::pyo3::types::PyDict> {
+                    self.0
+                }
+            }
+            #[automatically_derived]
+            impl<'py, V> ::std::ops::Deref for #str_keyed_dict<'py, V> {
+                type Target = ::pyo3::Bound<'py, ::pyo3::types::PyDict>;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+            #[automatically_derived]
+            impl<'py, V> ::pyo3::FromPyObject<'py> for #str_keyed_dict<'py, V> {
+                fn extract_bound(ob: &::pyo3::Bound<'py, ::pyo3::types::PyAny>) -> ::pyo3::PyResult<Self> #extract_body
+            }
+        }
+    }
+
+    /// Compact representation of the parameters of the function that is embedded into the bindings for
+    /// `Config::runtime_signature_guards`, e.g. `a:1,b:3,**kwargs` (`name:kind` of each parameter).
+    fn compact_signature(&self) -> String {
+        self.parameters
+            .iter()
+            .map(|param| {
+                let name = param.name.as_py();
+                match param.kind {
+                    ParameterKind::VarPositional => format!("*{name}"),
+                    ParameterKind::VarKeyword => format!("**{name}"),
+                    kind => format!("{name}:{}", kind as u8),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Iterate over the type annotations of all parameters and the return type.
+    pub(crate) fn annotations(&self) -> impl Iterator<Item = &Type> {
+        self.parameters
+            .iter()
+            .map(|param| &param.annotation)
+            .chain(std::iter::once(&self.return_annotation))
+    }
+
+    /// Iterate mutably over the type annotations of all parameters and the return type.
+    pub(crate) fn annotations_mut(&mut self) -> impl Iterator<Item = &mut Type> {
+        self.parameters
+            .iter_mut()
+            .map(|param| &mut param.annotation)
+            .chain(std::iter::once(&mut self.return_annotation))
+    }
+
+    /// Export the signature of the function as a schema object.
+    pub fn export_schema(&self) -> serde_json::Value {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|param| {
+                let mut schema = param.annotation.to_schema();
+                schema["name"] = param.name.as_py().into();
+                schema["required"] = (param.default.is_none()
+                    && ![ParameterKind::VarPositional, ParameterKind::VarKeyword]
+                        .contains(&param.kind))
+                .into();
+                schema
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "name": self.name.name().as_py(),
+            "docstring": self.docstring,
+            "parameters": parameters,
+            "return": self.return_annotation.to_schema(),
+        })
+    }
+
+    /// Render everything about the function that the generated bindings depend on as a stable snapshot.
+    pub(crate) fn snapshot(&self) -> serde_json::Value {
+        let typ = match &self.typ {
+            FunctionType::Function => serde_json::json!("Function"),
+            FunctionType::Method { class_path, typ } => serde_json::json!({
+                "Method": [class_path.to_py(), match typ {
+                    MethodType::InstanceMethod => "InstanceMethod",
+                    MethodType::ClassMethod => "ClassMethod",
+                    MethodType::StaticMethod => "StaticMethod",
+                    MethodType::Constructor => "Constructor",
+                    MethodType::Callable => "Callable",
+                    MethodType::Unknown => "Unknown",
+                }],
             }),
-            _ => FunctionImplementation::Function(impl_fn),
+            FunctionType::Closure => serde_json::json!("Closure"),
+            FunctionType::CallableInstance { instance_path } => {
+                serde_json::json!({ "CallableInstance": instance_path.to_py() })
+            }
+        };
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "type": typ,
+            "parameters": self.parameters.iter().map(Parameter::snapshot).collect::<Vec<_>>(),
+            "return": self.return_annotation.to_snapshot(),
+            "docstring": self.docstring,
+            "is_abstract": self.is_abstract,
+            "is_abstract_method": self.is_abstract_method,
+        })
+    }
+
+    /// Pass the parameters and the return value of the `by_value_types` by value (see
+    /// `Type::resolve_by_value_types`), whereas variadic parameters keep the Python representation of records.
+    pub fn resolve_by_value_types(&mut self, by_value_types: &HashMap<Path, Type>) {
+        for param in &mut self.parameters {
+            if matches!(
+                param.kind,
+                ParameterKind::VarPositional | ParameterKind::VarKeyword
+            ) {
+                param.annotation.resolve_by_value_types(&HashMap::default());
+            } else {
+                param.annotation.resolve_by_value_types(by_value_types);
+            }
+        }
+        self.return_annotation
+            .resolve_by_value_types(by_value_types);
+    }
+
+    /// Relax parameters annotated with one of the `callable_types` (classes that define `__call__`),
+    /// such that they accept any Python callable instead of only instances of the class.
+    pub fn relax_callable_class_params(&mut self, callable_types: &[Path]) {
+        let is_callable_type = |typ: &Type| matches!(typ, Type::Other(type_name) if callable_types.contains(&Path::from_py(type_name)));
+
+        let mut relaxed_params = Vec::new();
+        for param in &mut self.parameters {
+            match &mut param.annotation {
+                Type::Optional(inner_type) if is_callable_type(inner_type) => {
+                    **inner_type = Type::PyAny;
+                }
+                annotation if is_callable_type(annotation) => {
+                    *annotation = Type::PyAny;
+                }
+                _ => continue,
+            }
+            relaxed_params.push(param.name.as_py().to_owned());
+        }
+
+        // Note the relaxed parameters in the documentation
+        if !relaxed_params.is_empty() {
+            let mut docstring = self.docstring.take().unwrap_or_default();
+            for param_name in relaxed_params {
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(&format!(
+                    "Note: Parameter `{param_name}` accepts any Python callable, e.g. a Rust closure wrapped via `PyCFunction::new_closure_bound`."
+                ));
+            }
+            self.docstring = Some(docstring);
+        }
+    }
+
+    /// Generate a builder struct for the constructor of a class, where required parameters are passed
+    /// to `X::builder()` and parameters with a default value can be optionally set via setters.
+    pub fn generate_builder(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        struct_ident: &syn::Ident,
+        scoped_function_idents: &[&Ident],
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        let FunctionType::Method {
+            class_path,
+            typ: MethodType::Constructor,
+        } = &self.typ
+        else {
+            return Ok(proc_macro2::TokenStream::new());
+        };
+
+        // Builders pass all parameters as keyword arguments, which is not possible for all parameter kinds
+        if self.parameters.iter().any(|param| {
+            ![
+                ParameterKind::PositionalOrKeyword,
+                ParameterKind::KeywordOnly,
+            ]
+            .contains(&param.kind)
+        }) {
+            sink.skip(
+                class_path.join(&Ident::from_py("builder").into()),
+                "Builder of a constructor with positional-only or variadic parameters",
+            );
+            return Ok(proc_macro2::TokenStream::new());
+        }
+        if scoped_function_idents.contains(&&Ident::from_py("builder"))
+            || self
+                .parameters
+                .iter()
+                .any(|param| ["build", "py"].contains(&param.name.as_py()))
+        {
+            sink.skip(
+                class_path.join(&Ident::from_py("builder").into()),
+                "Builder whose name or parameters would clash with the generated items",
+            );
+            return Ok(proc_macro2::TokenStream::new());
+        }
+
+        let builder_ident: syn::Ident =
+            Ident::from_py(&format!("{struct_ident}Builder")).try_into()?;
+        let (required_params, optional_params): (Vec<&Parameter>, Vec<&Parameter>) = self
+            .parameters
+            .iter()
+            .partition(|param| param.default.is_none());
+
+        // Required parameters
+        let required_idents: Vec<syn::Ident> = required_params
+            .iter()
+            .map(|param| Ok(param.name.with_prefix("p_").try_into()?))
+            .collect::<Result<_>>()?;
+        let required_names: Vec<&str> = required_params
+            .iter()
+            .map(|param| param.name.as_py())
+            .collect();
+        let required_types: Vec<proc_macro2::TokenStream> = required_params
+            .iter()
+            .map(|param| param.annotation.clone().into_rs_borrowed(cfg, local_types))
+            .collect();
+        let required_preprocessing: proc_macro2::TokenStream = required_params
+            .iter()
+            .zip(required_idents.iter())
+            .map(|(param, param_ident)| {
+                param
+                    .annotation
+                    .preprocess_borrowed(param_ident, cfg, local_types)
+            })
+            .collect();
+
+        // Optional parameters
+        let optional_idents: Vec<syn::Ident> = optional_params
+            .iter()
+            .map(|param| Ok(param.name.with_prefix("p_").try_into()?))
+            .collect::<Result<_>>()?;
+        let optional_setter_idents: Vec<syn::Ident> = optional_params
+            .iter()
+            .map(|param| Ok(param.name.clone().try_into()?))
+            .collect::<Result<_>>()?;
+        let optional_names: Vec<&str> = optional_params
+            .iter()
+            .map(|param| param.name.as_py())
+            .collect();
+        let optional_types: Vec<proc_macro2::TokenStream> = optional_params
+            .iter()
+            .map(|param| param.annotation.clone().into_rs_borrowed(cfg, local_types))
+            .collect();
+        let optional_preprocessing: Vec<proc_macro2::TokenStream> = optional_params
+            .iter()
+            .zip(optional_idents.iter())
+            .map(|(param, param_ident)| {
+                param
+                    .annotation
+                    .preprocess_borrowed(param_ident, cfg, local_types)
+            })
+            .collect();
+
+        // Documentation
+        let mut builder_doc = proc_macro2::TokenStream::new();
+        if cfg.generate_docs {
+            let docstring = format!(
+                " Builder for [`{struct_ident}`] that calls its constructor (`__init__`) once all desired parameters are set."
+            );
+            builder_doc.extend(quote::quote! {
+                #[doc = #docstring]
+            });
+        }
+
+        // Bodies of the builder functions
+        let builder_body = cfg.bodies.body(quote::quote! {
+            {
+                #required_preprocessing
+                #builder_ident {
+                    py,
+                    #(#required_idents: ::pyo3::ToPyObject::to_object(&#required_idents, py).into_bound(py),)*
+                    #(#optional_idents: ::std::option::Option::None,)*
+                }
+            }
+        });
+        let setter_bodies: Vec<proc_macro2::TokenStream> = optional_idents
+            .iter()
+            .zip(optional_preprocessing.iter())
+            .map(|(optional_ident, optional_preprocessing)| {
+                cfg.bodies.body(quote::quote! {
+                    {
+                        let py = self.py;
+                        #optional_preprocessing
+                        self.#optional_ident = ::std::option::Option::Some(::pyo3::ToPyObject::to_object(&#optional_ident, py).into_bound(py));
+                        self
+                    }
+                })
+            })
+            .collect();
+        let import = pyo3::Python::with_gil(|py| class_path.import_quote(py));
+        let build_body = cfg.bodies.body(quote::quote! {
+            {
+                let py = self.py;
+                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                #(
+                    ::pyo3::types::PyDictMethods::set_item(&__internal_kwargs, ::pyo3::intern!(py, #required_names), self.#required_idents)?;
+                )*
+                #(
+                    if let ::std::option::Option::Some(__internal_value) = self.#optional_idents {
+                        ::pyo3::types::PyDictMethods::set_item(&__internal_kwargs, ::pyo3::intern!(py, #optional_names), __internal_value)?;
+                    }
+                )*
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call(#import.as_any(), (), ::std::option::Option::Some(&__internal_kwargs))?
+                )
+            }
+        });
+
+        // Lints of the builder functions and the builder itself
+        let builder_signature = quote::quote! {
+            pub fn builder<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                #(#required_idents: #required_types),*
+            ) -> #builder_ident<'py>
+        };
+        let builder_allow = crate::utils::lint::allow_fn(cfg, &builder_signature)?;
+        let setter_allows = optional_setter_idents
+            .iter()
+            .zip(optional_idents.iter())
+            .zip(optional_types.iter())
+            .map(|((setter_ident, optional_ident), optional_type)| {
+                crate::utils::lint::allow_fn(
+                    cfg,
+                    &quote::quote! {
+                        pub fn #setter_ident(mut self, #optional_ident: #optional_type) -> Self
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let struct_allow = crate::utils::lint::allow_struct(
+            cfg,
+            &builder_ident,
+            required_idents.iter().chain(optional_idents.iter()),
+        );
+
+        Ok(quote::quote! {
+            impl #struct_ident {
+                #builder_allow
+                #builder_signature #builder_body
+            }
+            #builder_doc
+            #struct_allow
+            pub struct #builder_ident<'py> {
+                py: ::pyo3::marker::Python<'py>,
+                #(#required_idents: ::pyo3::Bound<'py, ::pyo3::types::PyAny>,)*
+                #(#optional_idents: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,)*
+            }
+            impl<'py> #builder_ident<'py> {
+                #(
+                    #setter_allows
+                    pub fn #optional_setter_idents(
+                        mut self,
+                        #optional_idents: #optional_types,
+                    ) -> Self #setter_bodies
+                )*
+                pub fn build(self) -> ::pyo3::PyResult<::pyo3::Bound<'py, #struct_ident>> #build_body
+            }
         })
     }
 }
@@ -670,7 +2256,238 @@ struct Parameter {
     name: Ident,
     kind: ParameterKind,
     annotation: Type,
-    default: Option<pyo3::Py<pyo3::types::PyAny>>,
+    default: Option<DefaultValue>,
+    /// Metadata of an `Annotated[T, ...]` annotation (e.g. `Ge(ge=0)`), which is not part of the type itself.
+    metadata: Vec<String>,
+}
+
+/// Default value of a parameter that is classified while parsing.
+#[derive(Debug, Clone, PartialEq)]
+enum DefaultValue {
+    /// Simple literal (number, string, bool, `None` or empty container) alongside its `repr()`.
+    Literal { value: LiteralValue, repr: String },
+    /// Any other value (e.g. an object instance) alongside the name of its type.
+    Instance { type_name: String },
+    /// Expression that is not evaluated because the function is parsed from its AST.
+    Expression { source: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum LiteralValue {
+    Bool(bool),
+    /// Integer (`None` if it does not fit into `i128`).
+    Int(Option<i128>),
+    Float(f64),
+    Str(String),
+    None,
+    EmptyContainer,
+}
+
+impl DefaultValue {
+    /// Classify a default value (or an `ast` node of an expression that could not be evaluated).
+    fn classify(default: &pyo3::Bound<pyo3::types::PyAny>) -> Result<Self> {
+        let py = default.py();
+        let ast = py.import_bound(pyo3::intern!(py, "ast"))?;
+        if default.is_instance(&ast.getattr(pyo3::intern!(py, "AST"))?)? {
+            return Ok(Self::Expression {
+                source: super::common::ast::unparse(default)?,
+            });
+        }
+
+        let value = if let Ok(value) = default.downcast::<pyo3::types::PyBool>() {
+            Some(LiteralValue::Bool(value.is_true()))
+        } else if default.is_instance_of::<pyo3::types::PyLong>() {
+            Some(LiteralValue::Int(default.extract().ok()))
+        } else if let Ok(value) = default.downcast::<pyo3::types::PyFloat>() {
+            Some(LiteralValue::Float(value.value()))
+        } else if let Ok(value) = default.downcast::<pyo3::types::PyString>() {
+            Some(LiteralValue::Str(value.to_str()?.to_owned()))
+        } else if default.is_none() {
+            Some(LiteralValue::None)
+        } else if (default.is_instance_of::<pyo3::types::PyList>()
+            || default.is_instance_of::<pyo3::types::PyTuple>()
+            || default.is_instance_of::<pyo3::types::PyDict>()
+            || default.is_instance_of::<pyo3::types::PySet>()
+            || default.is_instance_of::<pyo3::types::PyFrozenSet>())
+            && default.len()? == 0
+        {
+            Some(LiteralValue::EmptyContainer)
+        } else {
+            None
+        };
+        Ok(match value {
+            Some(value) => Self::Literal {
+                value,
+                repr: default.repr()?.to_string(),
+            },
+            None => Self::Instance {
+                type_name: default.get_type().qualname()?.to_string(),
+            },
+        })
+    }
+
+    /// Describe the default value in the documentation.
+    fn doc(&self) -> String {
+        match self {
+            Self::Literal { repr, .. } => format!("`{repr}`"),
+            Self::Instance { type_name } => {
+                format!("instance of `{type_name}` (not a literal, so its value is not shown)")
+            }
+            Self::Expression { source } => {
+                format!("`{source}` (not a literal, so it is evaluated by Python)")
+            }
+        }
+    }
+
+    /// Get the Rust type and value of a constant that holds the default value, if it can be represented as such.
+    fn rs_const(&self, cfg: &Config) -> Option<(TokenStream, TokenStream)> {
+        let Self::Literal { value, .. } = self else {
+            return None;
+        };
+        match value {
+            LiteralValue::Bool(value) => Some((quote::quote!(bool), quote::quote!(#value))),
+            LiteralValue::Int(Some(value)) => match cfg.int_type {
+                IntType::I64 => {
+                    let value = proc_macro2::Literal::i64_unsuffixed(i64::try_from(*value).ok()?);
+                    Some((quote::quote!(i64), quote::quote!(#value)))
+                }
+                IntType::I128 => {
+                    let value = proc_macro2::Literal::i128_unsuffixed(*value);
+                    Some((quote::quote!(i128), quote::quote!(#value)))
+                }
+                #[cfg(feature = "num-bigint")]
+                IntType::BigInt => None,
+            },
+            LiteralValue::Float(value) if value.is_finite() => match cfg.float_type {
+                FloatType::F32 => {
+                    let value = proc_macro2::Literal::f32_unsuffixed(*value as f32);
+                    Some((quote::quote!(f32), quote::quote!(#value)))
+                }
+                FloatType::F64 => {
+                    let value = proc_macro2::Literal::f64_unsuffixed(*value);
+                    Some((quote::quote!(f64), quote::quote!(#value)))
+                }
+            },
+            LiteralValue::Str(value) => Some((quote::quote!(&str), quote::quote!(#value))),
+            _ => None,
+        }
+    }
+}
+
+impl Parameter {
+    /// Generate `debug_assert!` checks for the `annotated-types` constraints in the metadata of the parameter.
+    /// Constraints that cannot be checked for the Rust type of the parameter are ignored.
+    fn validation(&self, cfg: &Config, param_ident: &syn::Ident) -> TokenStream {
+        let param_name = self.name.as_py();
+        self.metadata
+            .iter()
+            .filter_map(|metadata| {
+                let (constraint, bound) = AnnotatedConstraint::parse(metadata)?;
+                let (value, op) = match (&self.annotation, constraint) {
+                    (Type::PyLong, AnnotatedConstraint::Value(op))
+                        if matches!(cfg.int_type, IntType::I64 | IntType::I128)
+                            && bound.fract() == 0.0
+                            && bound.abs() < i64::MAX as f64 =>
+                    {
+                        let bound = proc_macro2::Literal::i64_unsuffixed(bound as i64);
+                        (quote::quote!(#param_ident), quote::quote!(#op #bound))
+                    }
+                    (Type::PyFloat, AnnotatedConstraint::Value(op)) if bound.is_finite() => {
+                        let bound = proc_macro2::Literal::f64_unsuffixed(bound);
+                        (quote::quote!(#param_ident), quote::quote!(#op #bound))
+                    }
+                    (Type::PyString, AnnotatedConstraint::Length(op))
+                        if bound.fract() == 0.0 && bound >= 0.0 =>
+                    {
+                        let bound = proc_macro2::Literal::usize_unsuffixed(bound as usize);
+                        (
+                            quote::quote!(#param_ident.chars().count()),
+                            quote::quote!(#op #bound),
+                        )
+                    }
+                    _ => return None,
+                };
+                let message = format!("Parameter `{param_name}` must satisfy `{metadata}`");
+                Some(quote::quote! {
+                    debug_assert!(#value #op, "{}", #message);
+                })
+            })
+            .collect()
+    }
+}
+
+/// Constraint of an `annotated-types` marker (e.g. `Ge(ge=0)` or `MaxLen(max_length=8)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnnotatedConstraint {
+    /// Bound of the value of a number (`Ge`, `Le`, `Gt` or `Lt`).
+    Value(ComparisonOp),
+    /// Bound of the length of a string (`MinLen` or `MaxLen`).
+    Length(ComparisonOp),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparisonOp {
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+impl quote::ToTokens for ComparisonOp {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Ge => quote::quote!(>=),
+            Self::Le => quote::quote!(<=),
+            Self::Gt => quote::quote!(>),
+            Self::Lt => quote::quote!(<),
+        });
+    }
+}
+
+impl AnnotatedConstraint {
+    /// Parse the `repr()` or source of a marker into its constraint and bound, e.g. `Ge(ge=0)` or
+    /// `annotated_types.Ge(0)`. Returns `None` for any other metadata.
+    fn parse(metadata: &str) -> Option<(Self, f64)> {
+        let (marker, argument) = metadata.strip_suffix(')')?.split_once('(')?;
+        let constraint = match marker.rsplit('.').next()?.trim() {
+            "Ge" => Self::Value(ComparisonOp::Ge),
+            "Le" => Self::Value(ComparisonOp::Le),
+            "Gt" => Self::Value(ComparisonOp::Gt),
+            "Lt" => Self::Value(ComparisonOp::Lt),
+            "MinLen" => Self::Length(ComparisonOp::Ge),
+            "MaxLen" => Self::Length(ComparisonOp::Le),
+            _ => return None,
+        };
+        // The bound may be given as a keyword argument (e.g. `ge=0`)
+        let bound = argument
+            .split_once('=')
+            .map_or(argument, |(_keyword, bound)| bound);
+        Some((constraint, bound.trim().parse().ok()?))
+    }
+}
+
+impl Parameter {
+    /// Render the parameter as a stable snapshot, which (unlike `Hash`) includes its default value and metadata.
+    fn snapshot(&self) -> serde_json::Value {
+        let default = self.default.as_ref().map(|default| match default {
+            DefaultValue::Literal { repr, .. } => serde_json::json!({ "Literal": repr }),
+            DefaultValue::Instance { type_name } => serde_json::json!({ "Instance": type_name }),
+            DefaultValue::Expression { source } => serde_json::json!({ "Expression": source }),
+        });
+        serde_json::json!({
+            "name": self.name.as_py(),
+            "kind": match self.kind {
+                ParameterKind::PositionalOnly => "PositionalOnly",
+                ParameterKind::PositionalOrKeyword => "PositionalOrKeyword",
+                ParameterKind::VarPositional => "VarPositional",
+                ParameterKind::KeywordOnly => "KeywordOnly",
+                ParameterKind::VarKeyword => "VarKeyword",
+            },
+            "annotation": self.annotation.to_snapshot(),
+            "default": default,
+            "metadata": self.metadata,
+        })
+    }
 }
 
 impl PartialEq for Parameter {
@@ -22,6 +22,14 @@ impl Import {
         self.import_type == ImportType::ExternalImport
     }
 
+    /// Render the import as a stable snapshot (its type is determined by the paths).
+    pub(crate) fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "origin": self.origin.to_py(),
+            "target": self.target.to_py(),
+        })
+    }
+
     pub fn generate(&self, _cfg: &Config) -> Result<proc_macro2::TokenStream> {
         // For now, we only generate imports for submodule reexports
         if self.import_type != ImportType::SubmoduleReexport {
@@ -2,10 +2,10 @@ use super::{
     AttributeVariant, Class, Function, FunctionImplementation, FunctionType, Ident, Import, Path,
     Property, PropertyOwner, TypeVar,
 };
-use crate::{Config, Result};
+use crate::{utils::report::ReportSink, Config, InitStrategy, LintAllowMode, Result};
 use itertools::Itertools;
 use pyo3::prelude::*;
-use rustc_hash::FxHashSet as HashSet;
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Module {
@@ -20,11 +20,19 @@ pub struct Module {
     pub docstring: Option<String>,
     pub is_package: bool,
     pub source_code: Option<String>,
+    pub source_file: Option<std::path::PathBuf>,
+    pub is_dynamic: bool,
+    pub appended_code: Vec<String>,
 }
 
 impl Module {
     pub fn empty(py: pyo3::Python, name: Path) -> Result<Self> {
-        let module = py.import_bound(name.to_py().as_str())?;
+        let module = py.import_bound(name.to_py().as_str()).map_err(|source| {
+            crate::PyBindgenError::ModuleImportFailed {
+                module: name.to_py(),
+                source,
+            }
+        })?;
 
         // Extract the docstring of the module
         let docstring = {
@@ -48,10 +56,60 @@ impl Module {
             docstring,
             is_package: true,
             source_code: None,
+            source_file: None,
+            is_dynamic: false,
+            appended_code: Vec::new(),
         })
     }
 
-    pub fn parse(cfg: &Config, module: &pyo3::Bound<pyo3::types::PyModule>) -> Result<Self> {
+    /// Create an empty package without documentation, which only serves as the parent of its submodules
+    /// (e.g. if the package cannot be imported because its submodules were added from source code).
+    pub fn synthesized_package(name: Path) -> Self {
+        Self {
+            name,
+            prelude: Vec::default(),
+            imports: Vec::default(),
+            submodules: Vec::default(),
+            classes: Vec::default(),
+            type_vars: Vec::default(),
+            functions: Vec::default(),
+            properties: Vec::default(),
+            docstring: None,
+            is_package: true,
+            source_code: None,
+            source_file: None,
+            is_dynamic: false,
+            appended_code: Vec::new(),
+        }
+    }
+
+    /// Create an empty stub for a module that failed to import, preserving the error in its documentation.
+    pub fn stub(name: Path, err: &pyo3::PyErr) -> Self {
+        Self {
+            name,
+            prelude: Vec::default(),
+            imports: Vec::default(),
+            submodules: Vec::default(),
+            classes: Vec::default(),
+            type_vars: Vec::default(),
+            functions: Vec::default(),
+            properties: Vec::default(),
+            docstring: Some(format!(
+                "Bindings for this module are not available because it failed to import while generating the bindings:\n\n{err}"
+            )),
+            is_package: false,
+            source_code: None,
+            source_file: None,
+            is_dynamic: false,
+            appended_code: Vec::new(),
+        }
+    }
+
+    pub fn parse(
+        cfg: &Config,
+        sink: &ReportSink,
+        module: &pyo3::Bound<pyo3::types::PyModule>,
+    ) -> Result<Self> {
         let py = module.py();
 
         // Extract the name of the module
@@ -69,7 +127,7 @@ impl Module {
 
         // Extract the list of all submodules for packages
         let mut submodules_to_process = if is_package {
-            Self::extract_submodules(cfg, module)?
+            Self::extract_submodules(cfg, sink, module)?
         } else {
             HashSet::default()
         };
@@ -82,10 +140,35 @@ impl Module {
         let mut functions = Vec::new();
         let mut properties = Vec::new();
 
-        // Extract the list of all attribute names in the module
+        // Keep track of the types that claim to originate from another module (e.g. `__main__`) but are defined in this module
+        let mut anchored_types: HashMap<String, String> = HashMap::default();
+        // Keep track of the anchored objects, so that aliases of the same object (e.g. `types.FunctionType` and
+        // `types.LambdaType`) are re-exported instead of being bound as separate items
+        let mut anchored_objects: Vec<(pyo3::Bound<PyAny>, Path)> = Vec::new();
+
+        // Determine if the module resolves its attributes dynamically via `__getattr__` (e.g. lazily-loaded modules)
+        let is_dynamic = module.dict().contains(pyo3::intern!(py, "__getattr__"))?;
+        if is_dynamic && !cfg.dynamic_module_attributes {
+            sink.warn(
+                crate::WarningKind::Other,
+                &name,
+                format!("Module '{name}' resolves its attributes dynamically via `__getattr__`, so its bindings might be incomplete. Enable `Config::dynamic_module_attributes` to also consider the attributes listed in its `__all__`."),
+            );
+        }
+
+        // Extract the list of all attribute names in the module (extended by `__all__` for dynamic modules)
+        let dynamic_attr_names = if is_dynamic && cfg.dynamic_module_attributes {
+            module
+                .getattr(pyo3::intern!(py, "__all__"))
+                .and_then(|index| index.iter()?.collect::<PyResult<Vec<_>>>())
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
         module
             .dir()
             .iter()
+            .chain(dynamic_attr_names)
             // Convert each attribute name to an identifier
             .map(|attr_name| Ident::from_py(&attr_name.to_string()))
             // Remove duplicates
@@ -96,32 +179,69 @@ impl Module {
             // Expand each attribute to a tuple of (attr, attr_name, attr_module, attr_type)
             .filter_map(|attr_name| {
                 if let Ok(attr) = module.getattr(attr_name.as_py()) {
-
-                    let attr_module = Path::from_py(
+                    let mut attr_module = Path::from_py(
                         &attr
-                        .getattr(pyo3::intern!(py, "__module__"))
-                        .map(|a| a.to_string())
-                        .unwrap_or_default(),
+                            .getattr(pyo3::intern!(py, "__module__"))
+                            .map(|a| a.to_string())
+                            .unwrap_or_default(),
                     );
                     let attr_type = attr.get_type();
 
-                    Some((attr, attr_name, attr_module, attr_type))
+                    // Anchor attributes that claim to originate from a module that does not provide them to this module
+                    let mut alias_of = None;
+                    if Self::is_anchored_to_module(&attr, &attr_module) {
+                        let qualname = attr
+                            .getattr(pyo3::intern!(py, "__qualname__"))
+                            .map(|a| a.to_string())
+                            .unwrap_or(attr_name.as_py().to_owned());
+                        // The object is anchored under the name of its `__qualname__` (if exposed), otherwise under the first name
+                        let canonical_name =
+                            Ident::from_py(qualname.rsplit('.').next().unwrap_or_default());
+                        let is_exposed_as_canonical = canonical_name != attr_name
+                            && cfg.is_attr_allowed(&canonical_name, &name, &attr_type)
+                            && module
+                                .getattr(canonical_name.as_py())
+                                .is_ok_and(|canonical| canonical.is(&attr));
+                        if is_exposed_as_canonical {
+                            alias_of = Some(name.join(&canonical_name.into()));
+                        } else if let Some((_, anchor)) =
+                            anchored_objects.iter().find(|(object, _)| object.is(&attr))
+                        {
+                            alias_of = Some(anchor.clone());
+                        } else if cfg.is_attr_allowed(&attr_name, &name, &attr_type) {
+                            let anchor = name.join(&attr_name.clone().into());
+                            anchored_types.insert(
+                                format!("{}.{qualname}", attr_module.to_py()),
+                                anchor.to_py(),
+                            );
+                            anchored_objects.push((attr.clone(), anchor));
+                        }
+                        attr_module = name.clone();
+                    }
+
+                    Some((attr, attr_name, attr_module, attr_type, alias_of))
                 } else {
-                    eprintln!(
-                        "WARN: Cannot get attribute '{attr_name}' of '{name}' even though it is listed in its `__dir__`. Bindings will not be generated.",
+                    sink.skip(
+                        name.join(&attr_name.into()),
+                        "Cannot get attribute even though it is listed in `__dir__`",
                     );
                     None
                 }
             })
             // Filter attributes based on various configurable conditions
-            .filter(|(_attr, attr_name, attr_module, attr_type)| {
+            .filter(|(_attr, attr_name, attr_module, attr_type, _alias_of)| {
                 cfg.is_attr_allowed(attr_name, attr_module, attr_type)
             })
             // Iterate over the remaining attributes and parse them
-            .try_for_each(|(attr, attr_name, attr_module, attr_type)| {
+            .try_for_each(|(attr, attr_name, attr_module, attr_type, alias_of)| {
                 let attr_name_full = name.join(&attr_name.clone().into());
-                match AttributeVariant::determine(py, &attr, &attr_type, &attr_module, &name, true)
-                    ?
+
+                // Re-export the aliases of anchored objects
+                if let Some(anchor) = alias_of {
+                    imports.push(Import::new(anchor, attr_name_full));
+                    return Ok(());
+                }
+                match AttributeVariant::determine(py, &attr, &attr_type, &attr_module, &name, true)?
                 {
                     AttributeVariant::Import => {
                         let origin = attr_module.join(&Path::from_py(
@@ -136,6 +256,12 @@ impl Module {
                             return Ok(());
                         }
 
+                        // Skip if the origin cannot be imported as a regular dependency
+                        if origin.is_unresolvable_origin() {
+                            sink.skip(&attr_name_full, "Imported from an unresolvable module");
+                            return Ok(());
+                        }
+
                         // Make sure the origin attribute is allowed (each segment of the path)
                         let is_origin_attr_allowed = (0..origin.len()).all(|i| {
                             let attr_name = &origin[i];
@@ -152,7 +278,8 @@ impl Module {
                         }
 
                         // Determine if the import overwrites a submodule
-                        let import_overwrites_submodule = submodules_to_process.contains(&attr_name);
+                        let import_overwrites_submodule =
+                            submodules_to_process.contains(&attr_name);
 
                         // Generate the import
                         let import = Import::new(origin, attr_name_full);
@@ -169,10 +296,15 @@ impl Module {
                         submodules_to_process.insert(attr_name.clone());
                     }
                     AttributeVariant::Class => {
-                        let class =
-                            Class::parse(cfg, attr.downcast().unwrap_or_else(|_| unreachable!(
-                                "The attribute is known to be a class at this point"
-                            )), attr_name_full)?;
+                        let class = Class::parse(
+                            cfg,
+                            sink,
+                            attr.downcast().unwrap_or_else(|_| {
+                                unreachable!("The attribute is known to be a class at this point")
+                            }),
+                            attr_name_full.clone(),
+                        )
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         classes.push(class);
                     }
                     AttributeVariant::TypeVar => {
@@ -180,29 +312,62 @@ impl Module {
                         type_vars.push(type_var);
                     }
                     AttributeVariant::Function => {
-                        let function =
-                            Function::parse(cfg, &attr, attr_name_full, FunctionType::Function)
-                                ?;
+                        let function = Function::parse(
+                            cfg,
+                            sink,
+                            &attr,
+                            attr_name_full.clone(),
+                            FunctionType::Function,
+                        )
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         functions.push(function);
                     }
                     AttributeVariant::Method => {
-                        eprintln!("WARN: Methods in modules are not supported: '{name}.{attr_name}'. Bindings will not be generated.");
+                        sink.skip(&attr_name_full, "Methods in modules are not supported");
                     }
                     AttributeVariant::Closure => {
-                        let function =
-                            Function::parse(cfg, &attr, attr_name_full, FunctionType::Closure)
-                                ?;
+                        let function = Function::parse(
+                            cfg,
+                            sink,
+                            &attr,
+                            attr_name_full.clone(),
+                            FunctionType::Closure,
+                        )
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         functions.push(function);
                     }
                     AttributeVariant::Property => {
                         let property = Property::parse(
                             cfg,
+                            sink,
                             &attr,
-                            attr_name_full,
+                            attr_name_full.clone(),
                             PropertyOwner::Module,
+                            None,
                         )
-                        ?;
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
                         properties.push(property);
+
+                        // Instances of callable classes are also bound as a free function `<name>_call`
+                        let is_callable_instance = attr.is_callable()
+                            && attr_type
+                                .getattr(pyo3::intern!(py, "__module__"))
+                                .is_ok_and(|module| module.to_string() != "builtins");
+                        if is_callable_instance {
+                            let function_name =
+                                name.join(&Path::from_py(&format!("{}_call", attr_name.as_py())));
+                            let function = Function::parse(
+                                cfg,
+                                sink,
+                                &attr.getattr(pyo3::intern!(py, "__call__"))?,
+                                function_name,
+                                FunctionType::CallableInstance {
+                                    instance_path: attr_name_full.clone(),
+                                },
+                            )
+                            .map_err(|err| err.in_attribute(&attr_name_full))?;
+                            functions.push(function);
+                        }
                     }
                 }
                 Result::Ok(())
@@ -228,7 +393,7 @@ impl Module {
                                     .downcast_into::<pyo3::types::PyModule>()
                                     .unwrap()
                             })
-                            .and_then(|module| Self::parse(cfg, &module))
+                            .and_then(|module| Self::parse(cfg, sink, &module))
                         {
                             // It could be any attribute, so all of them need to be checked
                             if let Some(mut import) = submodule
@@ -275,16 +440,33 @@ impl Module {
                         return None;
                     }
 
+                    // Skip submodules that are excluded entirely (without importing them)
+                    if cfg.skip_modules.contains(&full_submodule_name.to_py()) {
+                        return None;
+                    }
+
                     // Try to import both as a package and as a attribute of the current module
-                    py.import_bound(full_submodule_name.to_py().as_str())
-                        .or_else(|_| {
-                            module
-                                .getattr(submodule_name.as_py())
-                                .and_then(|attr| Ok(attr.downcast_into::<pyo3::types::PyModule>()?))
-                        })
-                        .ok()
+                    match py.import_bound(full_submodule_name.to_py().as_str()).or_else(|err| {
+                        module
+                            .getattr(submodule_name.as_py())
+                            .and_then(|attr| Ok(attr.downcast_into::<pyo3::types::PyModule>()?))
+                            .map_err(|_| err)
+                    }) {
+                        Ok(submodule) => Some(Self::parse(cfg, sink, &submodule)),
+                        Err(err) if cfg.platform_stubs => {
+                            sink.warn(
+                                crate::WarningKind::Other,
+                                &full_submodule_name,
+                                format!("Failed to import submodule '{full_submodule_name}' ({err}). An empty stub module is generated instead."),
+                            );
+                            Some(Ok(Self::stub(full_submodule_name, &err)))
+                        }
+                        Err(err) => {
+                            sink.skip(&full_submodule_name, format!("Failed to import submodule ({err})"));
+                            None
+                        }
+                    }
                 })
-                .map(|submodule| Self::parse(cfg, &submodule))
                 .collect::<Result<_>>()?
         } else {
             Vec::default()
@@ -300,6 +482,50 @@ impl Module {
             }
         };
 
+        // Resolve the aliases of imported modules in all type annotations (e.g. `import numpy as np`)
+        let module_aliases = Self::extract_module_aliases(module)?;
+        if !module_aliases.is_empty() {
+            classes
+                .iter_mut()
+                .flat_map(Class::annotations_mut)
+                .chain(functions.iter_mut().flat_map(Function::annotations_mut))
+                .chain(properties.iter_mut().flat_map(Property::annotations_mut))
+                .for_each(|annotation| annotation.resolve_module_aliases(&module_aliases));
+        }
+
+        // Anchor the types that originate from an unresolvable module (e.g. `__main__`) in all type annotations
+        classes
+            .iter_mut()
+            .flat_map(Class::annotations_mut)
+            .chain(functions.iter_mut().flat_map(Function::annotations_mut))
+            .chain(properties.iter_mut().flat_map(Property::annotations_mut))
+            .for_each(|annotation| annotation.anchor_unresolvable_origins(&anchored_types, sink));
+
+        // Extract the path to the source file of the module (if available)
+        let source_file = module
+            .getattr(pyo3::intern!(py, "__file__"))
+            .and_then(|file| file.extract::<std::path::PathBuf>())
+            .ok();
+
+        // Resolve collisions of transliterated names within the value and type namespaces of the module
+        super::common::disambiguate(
+            functions
+                .iter_mut()
+                .map(|function| function.name.name_mut())
+                .chain(
+                    properties
+                        .iter_mut()
+                        .map(|property| property.name.name_mut()),
+                ),
+        );
+        super::common::disambiguate(
+            classes.iter_mut().map(|class| class.name.name_mut()).chain(
+                type_vars
+                    .iter_mut()
+                    .map(|type_var| type_var.name.name_mut()),
+            ),
+        );
+
         Ok(Self {
             name,
             prelude,
@@ -312,12 +538,273 @@ impl Module {
             docstring,
             is_package,
             source_code: None,
+            source_file,
+            is_dynamic,
+            appended_code: Vec::new(),
+        })
+    }
+
+    /// Parse the module from its `ast.Module` node without importing it. Only the statements at the top level of
+    /// the module body are considered, so conditional definitions, wildcard imports and preludes are not supported.
+    pub fn from_ast(
+        cfg: &Config,
+        sink: &ReportSink,
+        node: &pyo3::Bound<pyo3::types::PyAny>,
+        name: Path,
+    ) -> Result<Self> {
+        let py = node.py();
+
+        // Initialize lists for all members of the module
+        let mut imports: Vec<Import> = Vec::new();
+        let mut classes: Vec<Class> = Vec::new();
+        let mut type_vars: Vec<TypeVar> = Vec::new();
+        let mut functions: Vec<Function> = Vec::new();
+        let mut properties: Vec<Property> = Vec::new();
+        let mut is_dynamic = false;
+
+        // Extract the imports and all names bound by them or by class definitions, which are used to resolve annotations
+        let body = super::common::ast::children(node, pyo3::intern!(py, "body"))?;
+        let mut scope = HashMap::default();
+        for stmt in &body {
+            if super::common::ast::is_node(stmt, &["ClassDef"])? {
+                let attr_name =
+                    Path::from_py(&stmt.getattr(pyo3::intern!(py, "name"))?.to_string());
+                scope.insert(attr_name.to_py(), name.join(&attr_name).to_py());
+            } else if super::common::ast::is_node(stmt, &["Import", "ImportFrom"])? {
+                // Resolve the origin of relative imports with respect to the module
+                let from_module = if super::common::ast::is_node(stmt, &["ImportFrom"])? {
+                    let level = stmt
+                        .getattr(pyo3::intern!(py, "level"))?
+                        .extract::<usize>()?;
+                    let mut origin_module = name.clone();
+                    for _ in 0..level {
+                        origin_module = origin_module.parent().unwrap_or_default();
+                    }
+                    let module = stmt.getattr(pyo3::intern!(py, "module"))?;
+                    if !module.is_none() {
+                        origin_module = origin_module.join(&Path::from_py(&module.to_string()));
+                    }
+                    Some(origin_module)
+                } else {
+                    None
+                };
+
+                for alias in super::common::ast::children(stmt, pyo3::intern!(py, "names"))? {
+                    let alias_name = alias.getattr(pyo3::intern!(py, "name"))?.to_string();
+                    let asname = alias.getattr(pyo3::intern!(py, "asname"))?;
+                    if alias_name == "*" {
+                        sink.skip(
+                            &name,
+                            "Wildcard imports are not supported when parsing the AST",
+                        );
+                        continue;
+                    }
+
+                    // Note: `import a.b` binds only the root package `a` in the module
+                    let origin = match &from_module {
+                        Some(from_module) => from_module.join(&Path::from_py(&alias_name)),
+                        None if asname.is_none() => Path::from_py(&alias_name)
+                            .root()
+                            .unwrap_or_else(|| unreachable!()),
+                        None => Path::from_py(&alias_name),
+                    };
+                    let attr_name = if asname.is_none() {
+                        origin.name().clone()
+                    } else {
+                        Ident::from_py(&asname.to_string())
+                    };
+                    scope.insert(attr_name.as_py().to_owned(), origin.to_py());
+                    if !cfg.is_attr_allowed(
+                        &attr_name,
+                        &origin.parent().unwrap_or_default(),
+                        &alias.get_type(),
+                    ) {
+                        continue;
+                    }
+                    imports.push(Import::new(origin, name.join(&attr_name.into())));
+                }
+            }
+        }
+
+        for stmt in body {
+            if super::common::ast::is_node(&stmt, &["ClassDef", "FunctionDef", "AsyncFunctionDef"])?
+            {
+                let attr_name =
+                    Ident::from_py(&stmt.getattr(pyo3::intern!(py, "name"))?.to_string());
+                if attr_name.as_py() == "__getattr__" {
+                    is_dynamic = true;
+                }
+                if !cfg.is_attr_allowed(&attr_name, &name, &stmt.get_type()) {
+                    continue;
+                }
+                let attr_name_full = name.join(&attr_name.into());
+
+                // Later definitions replace earlier ones, like during the execution of the module
+                if super::common::ast::is_node(&stmt, &["ClassDef"])? {
+                    let class = Class::from_ast(cfg, sink, &stmt, attr_name_full.clone(), &scope)
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
+                    classes.retain(|class| class.name != attr_name_full);
+                    classes.push(class);
+                } else {
+                    let function = Function::from_ast(
+                        cfg,
+                        sink,
+                        &stmt,
+                        attr_name_full.clone(),
+                        FunctionType::Function,
+                        &scope,
+                    )
+                    .map_err(|err| err.in_attribute(&attr_name_full))?;
+                    functions.retain(|function| function.name != attr_name_full);
+                    functions.push(function);
+                }
+            } else if super::common::ast::is_node(&stmt, &["AnnAssign", "Assign"])? {
+                let (targets, annotation) = if super::common::ast::is_node(&stmt, &["AnnAssign"])? {
+                    (
+                        vec![stmt.getattr(pyo3::intern!(py, "target"))?],
+                        Some(stmt.getattr(pyo3::intern!(py, "annotation"))?),
+                    )
+                } else {
+                    (
+                        super::common::ast::children(&stmt, pyo3::intern!(py, "targets"))?,
+                        None,
+                    )
+                };
+                let value = Some(stmt.getattr(pyo3::intern!(py, "value"))?)
+                    .filter(|value| !value.is_none());
+                let is_type_var = value
+                    .as_ref()
+                    .filter(|value| super::common::ast::is_node(value, &["Call"]).unwrap_or(false))
+                    .map(|value| {
+                        let func = super::common::ast::unparse(
+                            &value.getattr(pyo3::intern!(py, "func"))?,
+                        )?;
+                        Result::Ok(["TypeVar", "typing.TypeVar"].contains(&func.as_str()))
+                    })
+                    .transpose()?
+                    .unwrap_or(false);
+
+                for target in targets {
+                    // Only assignments to plain names define module attributes
+                    if !super::common::ast::is_node(&target, &["Name"])? {
+                        continue;
+                    }
+                    let attr_name =
+                        Ident::from_py(&target.getattr(pyo3::intern!(py, "id"))?.to_string());
+                    if !cfg.is_attr_allowed(&attr_name, &name, &target.get_type()) {
+                        continue;
+                    }
+                    let attr_name_full = name.join(&attr_name.into());
+                    if is_type_var {
+                        type_vars.push(TypeVar::new(attr_name_full));
+                    } else {
+                        let property = Property::from_ast_variable(
+                            cfg,
+                            sink,
+                            annotation.as_ref(),
+                            value.as_ref(),
+                            attr_name_full.clone(),
+                            PropertyOwner::Module,
+                            &scope,
+                        )
+                        .map_err(|err| err.in_attribute(&attr_name_full))?;
+                        properties.retain(|property| property.name != attr_name_full);
+                        properties.push(property);
+                    }
+                }
+            }
+        }
+
+        // Extract the docstring of the module
+        let docstring = super::common::ast::docstring(node)?;
+
+        // Resolve collisions of transliterated names within the value and type namespaces of the module
+        super::common::disambiguate(
+            functions
+                .iter_mut()
+                .map(|function| function.name.name_mut())
+                .chain(
+                    properties
+                        .iter_mut()
+                        .map(|property| property.name.name_mut()),
+                ),
+        );
+        super::common::disambiguate(
+            classes.iter_mut().map(|class| class.name.name_mut()).chain(
+                type_vars
+                    .iter_mut()
+                    .map(|type_var| type_var.name.name_mut()),
+            ),
+        );
+
+        Ok(Self {
+            name,
+            prelude: Vec::default(),
+            imports,
+            submodules: Vec::default(),
+            classes,
+            type_vars,
+            functions,
+            properties,
+            docstring,
+            is_package: false,
+            source_code: None,
+            source_file: None,
+            is_dynamic,
+            appended_code: Vec::new(),
         })
     }
 
+    /// Export the structure of all classes within the module hierarchy as a schema object.
+    pub fn export_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "docstring": self.docstring,
+            "classes": self.classes.iter().map(Class::export_schema).collect::<Vec<_>>(),
+            "submodules": self.submodules.iter().map(Self::export_schema).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Render everything about the module and its submodules that the generated bindings depend on as a stable
+    /// snapshot, which serves as the basis of its fingerprint in incremental code generation.
+    pub(crate) fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "prelude": self.prelude.iter().map(Ident::as_py).collect::<Vec<_>>(),
+            "imports": self.imports.iter().map(Import::snapshot).collect::<Vec<_>>(),
+            "submodules": self.submodules.iter().map(Self::snapshot).collect::<Vec<_>>(),
+            "classes": self.classes.iter().map(Class::snapshot).collect::<Vec<_>>(),
+            "type_vars": self
+                .type_vars
+                .iter()
+                .map(|type_var| type_var.name.to_py())
+                .collect::<Vec<_>>(),
+            "functions": self.functions.iter().map(Function::snapshot).collect::<Vec<_>>(),
+            "properties": self.properties.iter().map(Property::snapshot).collect::<Vec<_>>(),
+            "docstring": self.docstring,
+            "is_package": self.is_package,
+            "source_code": self.source_code,
+            "source_file": self.source_file.as_ref().map(|path| path.to_string_lossy()),
+            "is_dynamic": self.is_dynamic,
+            "appended_code": self.appended_code,
+        })
+    }
+
+    /// Check whether the module or any of its submodules has a function or method that returns `dict[str, V]`.
+    pub(crate) fn returns_str_keyed_dict(&self) -> bool {
+        self.functions.iter().any(Function::returns_str_keyed_dict)
+            || self
+                .classes
+                .iter()
+                .flat_map(Class::methods)
+                .any(Function::returns_str_keyed_dict)
+            || self.submodules.iter().any(Self::returns_str_keyed_dict)
+    }
+
     pub fn generate(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         top_level_modules: &[Self],
         all_types: &[Path],
     ) -> Result<proc_macro2::TokenStream> {
@@ -325,7 +812,7 @@ impl Module {
 
         // Extra configuration for top-level modules
         let is_top_level = top_level_modules.contains(self);
-        if is_top_level {
+        if is_top_level && cfg.lint_allows == LintAllowMode::Blanket {
             output.extend(quote::quote! {
                 #[allow(
                     clippy::all,
@@ -338,11 +825,23 @@ impl Module {
                 )]
             });
         }
+        if is_top_level {
+            output.extend(crate::utils::lint::allow_top_level(cfg));
+        }
+
+        // Warn about the use of bindings from an experimental code path
+        if is_top_level && cfg.emit_experimental_warnings {
+            output.extend(quote::quote! {
+                #[deprecated(
+                    note = "These bindings are generated by the experimental `import_python!` macro. Consider generating them in a build script via `pyo3_bindgen::Codegen` instead."
+                )]
+            });
+        }
 
         // Documentation
         if cfg.generate_docs {
             if let Some(mut docstring) = self.docstring.clone() {
-                crate::utils::text::format_docstring(&mut docstring);
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
                 output.extend(quote::quote! {
                     #[doc = #docstring]
                 });
@@ -368,27 +867,34 @@ impl Module {
                 all_types
                     .iter()
                     .filter(|&path| path.starts_with(&import.origin))
-                    .cloned()
                     .map(|path| {
                         let imported_path = {
                             if let Some(stripped_path) = path
                                 .to_py()
                                 .strip_prefix(&format!("{}.", import.origin.to_py()))
                             {
-                                let mut path = Path::from_py(stripped_path);
-                                // Overwrite the first segment with the target name to support aliasing
-                                import.target.name().clone_into(&mut path[0]);
-                                path
+                                // Prefix the remainder of the path with the target name to support aliasing
+                                Path::from(import.target.name().to_owned())
+                                    .join(&Path::from_py(stripped_path))
                             } else {
                                 import.target.name().to_owned().into()
                             }
                         };
-                        let relative_path = self.name.relative_to(&path, false);
+                        let relative_path = self.name.relative_to(path, false);
                         (imported_path, relative_path)
                     })
             }))
+            .chain(cfg.dict_key_accessors.then(|| {
+                // The wrapper of returned dictionaries is generated alongside the top-level modules
+                let path = Path::from_rs(Function::STR_KEYED_DICT);
+                let relative_path = self.name.relative_to(&path, false);
+                (path, relative_path)
+            }))
             .collect();
 
+        // Leaf modules with only a few functions are generated in a compact form
+        let is_compact_leaf = self.is_compact_leaf(cfg);
+
         // Generate the module content
         let mut module_content = proc_macro2::TokenStream::new();
         // Imports
@@ -397,17 +903,21 @@ impl Module {
                 self.imports
                     .iter()
                     .filter(|import| {
-                        top_level_modules
-                            .iter()
-                            .any(|module| module.check_path_exists_recursive(&import.origin, false))
+                        top_level_modules.iter().any(|module| {
+                            if is_compact_leaf || cfg.module_handle_injection {
+                                module.check_path_generated_recursive(cfg, &import.origin)
+                            } else {
+                                module.check_path_exists_recursive(&import.origin, false)
+                            }
+                        })
                     })
                     .map(|import| import.generate(cfg))
                     .collect::<Result<proc_macro2::TokenStream>>()?,
             );
         }
-        // Prelude
-        if cfg.generate_preludes {
-            module_content.extend(self.generate_prelude());
+        // Prelude (all items of compact leaf modules are public, so their prelude would be redundant)
+        if cfg.generate_preludes && !is_compact_leaf {
+            module_content.extend(self.generate_prelude(cfg, sink));
         }
         // Type variables
         if cfg.generate_type_vars {
@@ -423,25 +933,30 @@ impl Module {
             module_content.extend(
                 self.classes
                     .iter()
-                    .map(|class| class.generate(cfg, &local_types))
+                    .map(|class| class.generate(cfg, sink, &local_types))
                     .collect::<Result<proc_macro2::TokenStream>>()?,
             );
         }
         // Functions
-        if cfg.generate_functions {
+        if cfg.module_handle_injection {
+            module_content.extend(self.generate_handle(
+                cfg,
+                sink,
+                &scoped_function_idents,
+                &local_types,
+            )?);
+        } else if cfg.generate_functions {
             module_content.extend(
                 self.functions
                     .iter()
                     .map(|function| {
-                        function
-                            .generate(cfg, &scoped_function_idents, &local_types)
-                            .map(|def| {
-                                if let FunctionImplementation::Function(impl_fn) = def {
-                                    impl_fn
-                                } else {
-                                    unreachable!("Methods in modules are not possible")
-                                }
-                            })
+                        Self::generate_function(
+                            cfg,
+                            sink,
+                            function,
+                            &scoped_function_idents,
+                            &local_types,
+                        )
                     })
                     .collect::<Result<proc_macro2::TokenStream>>()?,
             );
@@ -453,7 +968,7 @@ impl Module {
                     .iter()
                     .map(|property| {
                         property
-                            .generate(cfg, &scoped_function_idents, &local_types)
+                            .generate(cfg, sink, &scoped_function_idents, &local_types)
                             .map(|def| {
                                 if let FunctionImplementation::Function(impl_fn) = def {
                                     impl_fn
@@ -466,29 +981,76 @@ impl Module {
             );
         }
         // Submodules
+        let mut n_submodule_tokens = 0;
         if cfg.traverse_submodules {
-            module_content.extend(
-                self.submodules
-                    .iter()
-                    .map(|module| module.generate(cfg, top_level_modules, all_types))
-                    .collect::<Result<proc_macro2::TokenStream>>()?,
-            );
+            // The traits with the methods of classes are generated alongside them
+            let mut hoisted_idents = self
+                .classes
+                .iter()
+                .map(|class| Self::methods_trait_ident(class.name.name()))
+                .collect::<HashSet<_>>();
+            let submodules = self
+                .submodules
+                .iter()
+                .map(|module| {
+                    // Flatten submodules with a single item into this module (unless the name of the item or of
+                    // the trait with its methods is taken)
+                    let item_idents = module.single_item_idents(cfg);
+                    if item_idents.as_ref().is_some_and(|item_idents| {
+                        item_idents.iter().all(|ident| {
+                            !self.check_ident_exists_immediate(ident, true)
+                                && !hoisted_idents.contains(ident)
+                        })
+                    }) {
+                        hoisted_idents.extend(item_idents.unwrap_or_default());
+                        module.generate_hoisted(cfg, sink, &scoped_function_idents, &local_types)
+                    } else {
+                        module.generate(cfg, sink, top_level_modules, all_types)
+                    }
+                })
+                .collect::<Result<proc_macro2::TokenStream>>()?;
+            n_submodule_tokens = crate::utils::report::count_tokens(&submodules);
+            module_content.extend(submodules);
+        }
+        // Appended code
+        for rust_code in &self.appended_code {
+            module_content.extend(super::common::appended_code::parse_module_items(
+                &self.name, rust_code,
+            )?);
         }
 
+        // Guard against incompatible versions of `pyo3` for top-level modules
+        let pyo3_version_check = if is_top_level && cfg.emit_pyo3_version_check {
+            quote::quote! {
+                /// Compile-time guard that fails if the used version of `pyo3` does not provide the `Bound` API
+                /// targeted by the generated bindings.
+                const _: () = {
+                    #[allow(non_upper_case_globals)]
+                    const pyo3_bindgen_requires_pyo3_v0_21: for<'py> fn(
+                        ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::Bound<'py, ::pyo3::types::PyDict> = ::pyo3::types::PyDict::new_bound;
+                };
+            }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
         // Embed the source code if the module was parsed directly from source code
         let embed_source_code_fn = if let Some(source_code) = &self.source_code {
             let module_name = self.name.to_rs();
             let file_name = format!("{module_name}/__init__.py");
-            quote::quote! {
-                /// Embed the Python source code of the module into the Python interpreter
-                /// in order to enable the use of the generated Rust bindings.
-                pub fn pyo3_embed_python_source_code<'py>(py: ::pyo3::marker::Python<'py>) -> ::pyo3::PyResult<()> {
+            let body = cfg.bodies.body(quote::quote! {
+                {
                     const SOURCE_CODE: &str = #source_code;
+                    let modules = pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                        pyo3::intern!(py, "modules"),
+                    )?;
+                    if !force && pyo3::types::PyAnyMethods::contains(&modules, #module_name)? {
+                        return Ok(());
+                    }
                     pyo3::types::PyAnyMethods::set_item(
-                        &pyo3::types::PyAnyMethods::getattr(
-                            py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
-                            pyo3::intern!(py, "modules"),
-                        )?,
+                        &modules,
                         #module_name,
                         pyo3::types::PyModule::from_code_bound(
                             py,
@@ -498,64 +1060,730 @@ impl Module {
                         )?,
                     )
                 }
+            });
+            quote::quote! {
+                /// Embed the Python source code of the module into the Python interpreter
+                /// in order to enable the use of the generated Rust bindings.
+                ///
+                /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+                /// the host application of an extension module) is kept, unless `force` is set.
+                pub fn pyo3_embed_python_source_code(py: ::pyo3::marker::Python<'_>, force: bool) -> ::pyo3::PyResult<()> #body
             }
         } else {
             proc_macro2::TokenStream::new()
         };
 
+        // Initialize the interpreter (if needed) and acquire the GIL for top-level modules
+        let with_gil_fn = if is_top_level && cfg.init_strategy == InitStrategy::Prepare {
+            let maybe_embed_source_code = if self.source_code.is_some() {
+                quote::quote! {
+                    pyo3_embed_python_source_code(py, false)?;
+                }
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::prepare_freethreaded_python();
+                    ::pyo3::marker::Python::with_gil(|py| {
+                        #maybe_embed_source_code
+                        f(py)
+                    })
+                }
+            });
+            quote::quote! {
+                /// Initialize the Python interpreter (unless it is already initialized) and call the given closure
+                /// while holding the GIL, so that the generated bindings can be used.
+                pub fn pyo3_with_gil<F, R>(f: F) -> ::pyo3::PyResult<R>
+                where
+                    F: for<'py> FnOnce(::pyo3::marker::Python<'py>) -> ::pyo3::PyResult<R>,
+                #body
+            }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
+        // Provide access to the dynamic attributes of the module by their name
+        let getattr_fn = if self.is_dynamic && cfg.dynamic_module_attributes {
+            let import = pyo3::Python::with_gil(|py| self.name.import_quote(py));
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    ::pyo3::types::PyAnyMethods::getattr(#import.as_any(), name)
+                }
+            });
+            quote::quote! {
+                /// Get an attribute of the module by its name, including attributes that are resolved
+                /// dynamically via the `__getattr__` of the module.
+                pub fn pyo3_getattr<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    name: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> #body
+            }
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
+        // Provide access to the standard metadata of the module
+        let metadata_fns = if cfg.generate_metadata_accessors {
+            self.generate_metadata_accessors(cfg, sink)?
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
         // Finalize the module with its content
         let module_ident: syn::Ident = self.name.name().try_into().map_err(|err| {
-            crate::PyBindgenError::CodegenError(format!(
-                "Failed to convert module name `{}` to identifier: {}",
-                self.name.name(),
-                err
-            ))
+            crate::PyBindgenError::CodegenSyntaxError {
+                span_context: format!(
+                    "pub mod {} (module name `{}` is not a valid identifier: {})",
+                    self.name.name().as_rs(),
+                    self.name,
+                    err
+                ),
+            }
         })?;
+        let allow = crate::utils::lint::allow_mod(cfg, &module_ident, &self.submodule_idents());
         output.extend(quote::quote! {
+            #allow
             pub mod #module_ident {
+                #pyo3_version_check
                 #embed_source_code_fn
+                #with_gil_fn
+                #getattr_fn
+                #metadata_fns
                 #module_content
             }
         });
 
+        // Adjust the complete bindings to the target edition (submodules are included in top-level modules)
+        if is_top_level {
+            output = cfg.output_edition.adjust(output);
+        }
+
+        // Report the size of the module (excluding its submodules)
+        let (n_classes, n_methods, n_class_properties) = if cfg.generate_classes {
+            (
+                self.classes.len(),
+                self.classes.iter().map(|class| class.methods().len()).sum(),
+                self.classes
+                    .iter()
+                    .map(|class| class.properties().len())
+                    .sum(),
+            )
+        } else {
+            (0, 0, 0)
+        };
+        sink.record_module(crate::utils::report::ModuleMetrics {
+            path: self.name.to_py(),
+            n_classes,
+            n_functions: if cfg.generate_functions {
+                self.functions.len()
+            } else {
+                0
+            },
+            n_methods,
+            n_properties: if cfg.generate_properties {
+                self.properties.len() + n_class_properties
+            } else {
+                0
+            },
+            n_tokens: crate::utils::report::count_tokens(&output)
+                .saturating_sub(n_submodule_tokens),
+        });
+
+        Ok(output)
+    }
+
+    fn generate_function(
+        cfg: &Config,
+        sink: &ReportSink,
+        function: &Function,
+        scoped_function_idents: &[&Ident],
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        function
+            .generate(cfg, sink, scoped_function_idents, local_types)
+            .map(|def| {
+                if let FunctionImplementation::Function(impl_fn) = def {
+                    sink.record_item(
+                        &function.name,
+                        crate::utils::report::ItemKind::Function,
+                        &impl_fn,
+                    );
+                    impl_fn
+                } else {
+                    unreachable!("Methods in modules are not possible")
+                }
+            })
+    }
+
+    /// Generate the handle that wraps the module object (see `Config::module_handle_injection`). Functions of the
+    /// module are generated as its methods, alongside accessors for the class objects of the module.
+    fn generate_handle(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        scoped_function_idents: &[&Ident],
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        let handle_ident: syn::Ident = Self::handle_ident(&self.name).try_into()?;
+        let module_name = self.name.to_py();
+
+        // Constructors
+        let import_body = cfg.bodies.body(quote::quote! {
+            {
+                ::std::result::Result::Ok(Self(py.import_bound(::pyo3::intern!(py, #module_name))?.unbind()))
+            }
+        });
+        let from_module_body = cfg.bodies.body(quote::quote! {
+            {
+                Self(module)
+            }
+        });
+
+        // Accessors for the class objects
+        let class_accessors = if cfg.generate_classes {
+            self.classes
+                .iter()
+                .filter(|class| syn::Ident::try_from(class.name.name()).is_ok())
+                .map(|class| {
+                    let class_name = class.name.name().as_py();
+                    let accessor_ident: syn::Ident =
+                        Self::class_accessor_ident(class.name.name()).try_into()?;
+                    let doc = format!(" Get the class object of `{class_name}` from the module.");
+                    let body = cfg.bodies.body(quote::quote! {
+                        {
+                            ::pyo3::types::PyAnyMethods::getattr(self.0.bind(py).as_any(), ::pyo3::intern!(py, #class_name))
+                        }
+                    });
+                    let signature = quote::quote! {
+                        pub fn #accessor_ident<'py>(
+                            &self,
+                            py: ::pyo3::marker::Python<'py>,
+                        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>
+                    };
+                    let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+                    Ok(quote::quote! {
+                        #[doc = #doc]
+                        #allow
+                        #signature #body
+                    })
+                })
+                .collect::<Result<proc_macro2::TokenStream>>()?
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
+        // Functions (the names of the constructors of the handle are reserved)
+        let functions = if cfg.generate_functions {
+            self.functions
+                .iter()
+                .filter(|function| {
+                    let is_reserved = function.name.name().as_py() == "from_module";
+                    if is_reserved {
+                        sink.skip(
+                            &function.name,
+                            "Name is reserved for the constructor of the module handle",
+                        );
+                    }
+                    !is_reserved
+                })
+                .map(|function| {
+                    Self::generate_function(
+                        cfg,
+                        sink,
+                        function,
+                        scoped_function_idents,
+                        local_types,
+                    )
+                })
+                .collect::<Result<proc_macro2::TokenStream>>()?
+        } else {
+            proc_macro2::TokenStream::new()
+        };
+
+        let struct_doc = format!(
+            " Handle of the `{module_name}` module, through which its functions are called and its classes are looked up."
+        );
+        let import_doc = format!(" Import the `{module_name}` module by its name.");
+        let from_module_doc = format!(
+            " Wrap the given module object, which is used in place of the `{module_name}` module (e.g. a mock in tests)."
+        );
+        let allow = crate::utils::lint::allow_struct(cfg, &handle_ident, []);
+        Ok(quote::quote! {
+            #[doc = #struct_doc]
+            #allow
+            pub struct #handle_ident(::pyo3::Py<::pyo3::types::PyModule>);
+            impl #handle_ident {
+                #[doc = #import_doc]
+                pub fn import(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<Self> #import_body
+                #[doc = #from_module_doc]
+                pub fn from_module(module: ::pyo3::Py<::pyo3::types::PyModule>) -> Self #from_module_body
+                #class_accessors
+                #functions
+            }
+        })
+    }
+
+    /// Get the name of the handle of the module at the given path, i.e. its name in `PascalCase` suffixed
+    /// with `Handle` (see `Config::module_handle_injection`).
+    pub(crate) fn handle_ident(module_path: &Path) -> Ident {
+        let pascal_case: String = module_path
+            .name()
+            .as_py()
+            .split('_')
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect();
+        Ident::from_py(&format!("{pascal_case}Handle"))
+    }
+
+    /// Get the name of the accessor for the class object with the given name on the handle of its module.
+    pub(crate) fn class_accessor_ident(class_name: &Ident) -> Ident {
+        class_name.with_prefix("pyo3_class_")
+    }
+
+    /// Get the idents of the submodules (invalid idents are skipped, as they are reported by the submodules).
+    fn submodule_idents(&self) -> Vec<syn::Ident> {
+        self.submodules
+            .iter()
+            .filter_map(|submodule| submodule.name.name().try_into().ok())
+            .collect()
+    }
+
+    /// Generate the single item of the module directly within the parent module, whose scope is described by the
+    /// given function idents and local types. The module itself only re-exports the items generated for it.
+    fn generate_hoisted(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        scoped_function_idents: &[&Ident],
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        let mut output = proc_macro2::TokenStream::new();
+
+        // The item itself (including any of its auxiliary items, e.g. the trait with the methods of a class)
+        let item = if let Some(class) = self.classes.first() {
+            class.generate(cfg, sink, local_types)?
+        } else {
+            let mut scoped_function_idents = scoped_function_idents.to_vec();
+            scoped_function_idents
+                .extend(self.functions.iter().map(|function| function.name.name()));
+            self.functions
+                .iter()
+                .map(|function| {
+                    Self::generate_function(
+                        cfg,
+                        sink,
+                        function,
+                        &scoped_function_idents,
+                        local_types,
+                    )
+                })
+                .collect::<Result<proc_macro2::TokenStream>>()?
+        };
+        let mut item = syn::parse2::<syn::File>(item).map_err(|err| {
+            crate::PyBindgenError::CodegenSyntaxError {
+                span_context: format!("module `{}` flattened into its parent: {err}", self.name),
+            }
+        })?;
+        let exports = item
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Const(item) => Some(item.ident.clone()),
+                syn::Item::Enum(item) => Some(item.ident.clone()),
+                syn::Item::Fn(item) => Some(item.sig.ident.clone()),
+                syn::Item::Mod(item) => Some(item.ident.clone()),
+                syn::Item::Struct(item) => Some(item.ident.clone()),
+                syn::Item::Trait(item) => Some(item.ident.clone()),
+                syn::Item::Type(item) => Some(item.ident.clone()),
+                _ => None,
+            })
+            .collect_vec();
+
+        // Documentation of the module, which is merged into the documentation of the item (as a separate paragraph)
+        if cfg.generate_docs {
+            let attrs = match item.items.first_mut() {
+                Some(syn::Item::Fn(item)) => Some(&mut item.attrs),
+                Some(syn::Item::Struct(item)) => Some(&mut item.attrs),
+                _ => None,
+            };
+            if let (Some(attrs), Some(mut docstring)) = (attrs, self.docstring.clone()) {
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
+                let position = attrs
+                    .iter()
+                    .rposition(|attr| attr.path().is_ident("doc"))
+                    .map_or(0, |i| i + 1);
+                let mut doc_attrs: Vec<syn::Attribute> = syn::parse_quote! { #[doc = #docstring] };
+                if position > 0 {
+                    doc_attrs.insert(0, syn::parse_quote! { #[doc = ""] });
+                }
+                attrs.splice(position..position, doc_attrs);
+            }
+        }
+        output.extend(quote::ToTokens::to_token_stream(&item));
+
+        // The module that keeps the original path of the item valid
+        let module_ident: syn::Ident = self.name.name().try_into()?;
+        let reexport = if exports.is_empty() {
+            proc_macro2::TokenStream::new()
+        } else {
+            quote::quote! {
+                pub use super::{#(#exports),*};
+            }
+        };
+        let allow = crate::utils::lint::allow_mod(cfg, &module_ident, []);
+        output.extend(quote::quote! {
+            #allow
+            pub mod #module_ident {
+                #reexport
+            }
+        });
+
+        Ok(output)
+    }
+
+    /// Generate the accessors for the standard metadata of the module (see `Config::generate_metadata_accessors`).
+    /// The metadata is fetched at runtime, as dunder attributes are not parsed.
+    fn generate_metadata_accessors(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+    ) -> Result<proc_macro2::TokenStream> {
+        let import = pyo3::Python::with_gil(|py| self.name.import_quote(py));
+        let mut output = proc_macro2::TokenStream::new();
+
+        // Resolve the names of the accessors (against the items of the module)
+        let resolve_ident = |name: &str, attr_name: &str| {
+            cfg.on_collision.resolve(
+                sink,
+                &self.name.join(&Path::from_py(attr_name)),
+                Ident::from_py(name),
+                Some(Ident::from_py(&format!("{name}_from_metadata"))),
+                |ident| self.check_ident_exists_immediate(ident, true),
+            )
+        };
+
+        // Version (`__version__`)
+        if let Some(ident) = resolve_ident("version", "__version__")? {
+            let ident: syn::Ident = ident.try_into()?;
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    let module = #import;
+                    if !::pyo3::types::PyAnyMethods::hasattr(module.as_any(), ::pyo3::intern!(py, "__version__"))? {
+                        return ::std::result::Result::Ok(::std::option::Option::None);
+                    }
+                    let version = ::pyo3::types::PyAnyMethods::getattr(module.as_any(), ::pyo3::intern!(py, "__version__"))?;
+                    let version = ::pyo3::types::PyAnyMethods::str(&version)?;
+                    ::std::result::Result::Ok(::std::option::Option::Some(
+                        ::pyo3::types::PyStringMethods::to_cow(&version)?.into_owned(),
+                    ))
+                }
+            });
+            output.extend(quote::quote! {
+                /// Get the version of the module from its `__version__` attribute (if any).
+                pub fn #ident(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<::std::option::Option<::std::string::String>> #body
+            });
+        }
+
+        // Path (`__file__`)
+        if let Some(ident) = resolve_ident("module_path", "__file__")? {
+            let ident: syn::Ident = ident.try_into()?;
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    let module = #import;
+                    if !::pyo3::types::PyAnyMethods::hasattr(module.as_any(), ::pyo3::intern!(py, "__file__"))? {
+                        return ::std::result::Result::Ok(::std::option::Option::None);
+                    }
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(module.as_any(), ::pyo3::intern!(py, "__file__"))?,
+                    )
+                }
+            });
+            output.extend(quote::quote! {
+                /// Get the path of the file from which the module was loaded via its `__file__` attribute, which is
+                /// missing (or `None`) for namespace packages and built-in modules.
+                pub fn #ident(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<::std::option::Option<::std::path::PathBuf>> #body
+            });
+        }
+
+        // Exported names (`__all__` or `dir()`)
+        if let Some(ident) = resolve_ident("exported_names", "__all__")? {
+            let ident: syn::Ident = ident.try_into()?;
+            let body = cfg.bodies.body(quote::quote! {
+                {
+                    let module = #import;
+                    if ::pyo3::types::PyAnyMethods::hasattr(module.as_any(), ::pyo3::intern!(py, "__all__"))? {
+                        return ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(module.as_any(), ::pyo3::intern!(py, "__all__"))?,
+                        );
+                    }
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::extract::<::std::vec::Vec<::std::string::String>>(
+                            ::pyo3::types::PyAnyMethods::dir(module.as_any()).as_any(),
+                        )?
+                        .into_iter()
+                        .filter(|name| !name.starts_with('_'))
+                        .collect(),
+                    )
+                }
+            });
+            output.extend(quote::quote! {
+                /// Get the names exported by the module from its `__all__` attribute, falling back to the public
+                /// names (without a leading underscore) listed by `dir()`.
+                pub fn #ident(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>> #body
+            });
+        }
+
         Ok(output)
     }
 
+    /// Determine whether the module is a leaf module with only a few functions that is generated in a compact form
+    /// (see `Config::compact_leaf_modules`).
+    fn is_compact_leaf(&self, cfg: &Config) -> bool {
+        cfg.compact_leaf_modules.is_some_and(|max_functions| {
+            self.submodules.is_empty()
+                && self.classes.is_empty()
+                && self.type_vars.is_empty()
+                && self.functions.len() < max_functions
+        })
+    }
+
+    /// Get the name of the only item of the module if the module can be flattened into its parent
+    /// (see `Config::flatten_single_item_modules`), i.e. it contains exactly one generated class or function
+    /// and nothing else that would need to be preserved.
+    fn single_item_ident(&self, cfg: &Config) -> Option<&Ident> {
+        if !cfg.flatten_single_item_modules
+            || cfg.module_handle_injection
+            || !self.submodules.is_empty()
+            || !self.type_vars.is_empty()
+            || !self.properties.is_empty()
+            || (cfg.generate_imports && !self.imports.is_empty())
+            || (cfg.generate_preludes && !self.prelude.is_empty())
+            || self.source_code.is_some()
+            || !self.appended_code.is_empty()
+            || self.is_dynamic
+            || cfg.generate_metadata_accessors
+            // The path to the shared wrapper of returned dictionaries is relative to the module of the item
+            || (cfg.dict_key_accessors && self.returns_str_keyed_dict())
+        {
+            return None;
+        }
+        let item_name = match (self.classes.as_slice(), self.functions.as_slice()) {
+            ([class], []) if cfg.generate_classes => class.name.name(),
+            ([], [function]) if cfg.generate_functions => function.name.name(),
+            _ => return None,
+        };
+        (!item_name.as_py().starts_with('_')).then_some(item_name)
+    }
+
+    /// Get the idents of the items that are generated in the parent if the module is flattened into it (see
+    /// `Self::single_item_ident`), i.e. the item itself and the trait with the methods of a class.
+    fn single_item_idents(&self, cfg: &Config) -> Option<Vec<Ident>> {
+        let item_ident = self.single_item_ident(cfg)?;
+        Some(if self.classes.is_empty() {
+            vec![item_ident.clone()]
+        } else {
+            vec![item_ident.clone(), Self::methods_trait_ident(item_ident)]
+        })
+    }
+
+    /// Get the ident of the trait with the methods of the class with the given name.
+    fn methods_trait_ident(class_ident: &Ident) -> Ident {
+        Ident::from_py(&format!("{}Methods", class_ident.as_py()))
+    }
+
+    /// Determine whether the attribute claims to originate from a module that does not actually provide it, i.e. it was
+    /// defined in the module being parsed. This covers attributes from an unresolvable module (e.g. `__main__` due to
+    /// `exec` quirks) as well as classes whose `__module__` was rewritten to a module that does not expose them under
+    /// their `__qualname__` (e.g. C-extension types that report a different module than where they are exposed).
+    fn is_anchored_to_module(attr: &pyo3::Bound<pyo3::types::PyAny>, attr_module: &Path) -> bool {
+        let is_unresolvable = attr_module.is_unresolvable_origin();
+        if !is_unresolvable && !attr.is_instance_of::<pyo3::types::PyType>() {
+            return false;
+        }
+        let py = attr.py();
+        let Ok(qualname) = attr.getattr(pyo3::intern!(py, "__qualname__")) else {
+            return false;
+        };
+        // Resolvable modules that are not loaded cannot be inspected without importing them, so they are trusted
+        let Ok(origin_module) = py
+            .import_bound(pyo3::intern!(py, "sys"))
+            .and_then(|sys| sys.getattr(pyo3::intern!(py, "modules")))
+            .and_then(|modules| modules.get_item(attr_module.to_py()))
+        else {
+            return is_unresolvable;
+        };
+        let origin_attr = qualname
+            .to_string()
+            .split('.')
+            .try_fold(origin_module, |owner, segment| owner.getattr(segment));
+        !origin_attr.is_ok_and(|origin_attr| origin_attr.is(attr))
+    }
+
+    /// Extract the aliases of modules imported into the module under a different name, e.g. `np` -> `numpy`.
+    fn extract_module_aliases(
+        module: &pyo3::Bound<pyo3::types::PyModule>,
+    ) -> Result<HashMap<String, String>> {
+        let py = module.py();
+        let mut module_aliases = HashMap::default();
+        for (attr_name, attr) in module.dict().iter() {
+            if !attr.is_instance_of::<pyo3::types::PyModule>() {
+                continue;
+            }
+            let attr_name = attr_name.to_string();
+            let module_name = attr.getattr(pyo3::intern!(py, "__name__"))?.to_string();
+            if attr_name != module_name {
+                module_aliases.insert(attr_name, module_name);
+            }
+        }
+        Ok(module_aliases)
+    }
+
     fn extract_submodules(
         cfg: &Config,
+        sink: &ReportSink,
         module: &pyo3::Bound<pyo3::types::PyModule>,
     ) -> Result<HashSet<Ident>> {
         let py = module.py();
-        let pkgutil = py.import_bound(pyo3::intern!(py, "pkgutil"))?;
+        let module_name = Path::from_py(&module.name().unwrap().to_string());
 
-        // Extract the paths of the module
-        let module_paths = module
-            .getattr(pyo3::intern!(py, "__path__"))?
+        // Extract the names of all submodules via `pkgutil.walk_packages` (also handles `zipimport` via its importers)
+        let mut submodule_names = Self::walk_submodule_names(module).unwrap_or_else(|err| {
+            sink.warn(
+                crate::WarningKind::Other,
+                &module_name,
+                format!("Submodules of '{module_name}' cannot be listed via `pkgutil` ({err}). Only the submodules found otherwise are generated."),
+            );
+            HashSet::default()
+        });
+
+        // Fall back to the resources of the package and its already loaded submodules if the loader does not expose
+        // any search path that `pkgutil` understands (e.g. embedded or frozen packages)
+        if submodule_names.is_empty() {
+            submodule_names.extend(Self::resource_submodule_names(module).unwrap_or_else(|err| {
+                sink.warn(
+                    crate::WarningKind::Other,
+                    &module_name,
+                    format!("Submodules of '{module_name}' cannot be listed via `importlib.resources` ({err}). Only the already loaded submodules are generated."),
+                );
+                HashSet::default()
+            }));
+            submodule_names.extend(Self::loaded_submodule_names(module)?);
+        }
+
+        // Filter based on various configurable conditions
+        Ok(submodule_names
+            .into_iter()
+            .map(|submodule_name| Ident::from_py(&submodule_name))
+            .filter(|submodule_name| {
+                cfg.is_attr_allowed(
+                    submodule_name,
+                    &module_name,
+                    &py.get_type_bound::<pyo3::types::PyModule>(),
+                )
+            })
+            .collect())
+    }
+
+    /// Extract the names of the direct submodules that `pkgutil.walk_packages` finds in the search paths of the
+    /// package, whose names are qualified by the name of the package.
+    fn walk_submodule_names(
+        module: &pyo3::Bound<pyo3::types::PyModule>,
+    ) -> Result<HashSet<String>> {
+        let py = module.py();
+        let prefix = format!("{}.", module.name()?);
+        let kwargs = pyo3::types::PyDict::new_bound(py);
+        kwargs.set_item(pyo3::intern!(py, "prefix"), &prefix)?;
+        // Subpackages that fail to import are still listed, and their errors are reported once they are parsed
+        kwargs.set_item(
+            pyo3::intern!(py, "onerror"),
+            pyo3::types::PyList::empty_bound(py).getattr(pyo3::intern!(py, "append"))?,
+        )?;
+        let module_paths = module.getattr(pyo3::intern!(py, "__path__"))?;
+        let mut submodule_names = HashSet::default();
+        for submodule in py
+            .import_bound(pyo3::intern!(py, "pkgutil"))?
+            .call_method(
+                pyo3::intern!(py, "walk_packages"),
+                (module_paths,),
+                Some(&kwargs),
+            )?
             .iter()?
-            .map(|x| Ok(std::path::PathBuf::from(x?.to_string())))
-            .collect::<Result<Vec<_>>>()?;
+        {
+            let name = submodule?.getattr(pyo3::intern!(py, "name"))?.to_string();
+            if let Some(submodule_name) = name
+                .strip_prefix(&prefix)
+                .filter(|submodule_name| !submodule_name.contains('.'))
+            {
+                submodule_names.insert(submodule_name.to_owned());
+            }
+        }
+        Ok(submodule_names)
+    }
 
-        // Extract the names of all submodules via `pkgutil.iter_modules`
-        let module_name = Path::from_py(&module.name().unwrap().to_string());
-        pkgutil
-            .call_method1(pyo3::intern!(py, "iter_modules"), (module_paths,))?
+    /// Extract the names of submodules from the resources of the package via `importlib.resources`, which is supported
+    /// by loaders that do not store the package on the filesystem.
+    fn resource_submodule_names(
+        module: &pyo3::Bound<pyo3::types::PyModule>,
+    ) -> Result<HashSet<String>> {
+        let py = module.py();
+        let suffixes = py
+            .import_bound(pyo3::intern!(py, "importlib.machinery"))?
+            .call_method0(pyo3::intern!(py, "all_suffixes"))?
+            .extract::<Vec<String>>()?;
+        let mut submodule_names = HashSet::default();
+        for entry in py
+            .import_bound(pyo3::intern!(py, "importlib.resources"))?
+            .call_method1(pyo3::intern!(py, "files"), (module,))?
+            .call_method0(pyo3::intern!(py, "iterdir"))?
             .iter()?
-            .map(|submodule| {
-                Ok(Ident::from_py(
-                    &submodule?.getattr(pyo3::intern!(py, "name"))?.to_string(),
-                ))
-            })
-            // Filter based on various configurable conditions
-            .filter(|submodule_name| {
-                submodule_name.as_ref().is_ok_and(|submodule_name| {
-                    cfg.is_attr_allowed(
-                        submodule_name,
-                        &module_name,
-                        &py.get_type_bound::<pyo3::types::PyModule>(),
-                    )
-                })
+        {
+            let entry = entry?;
+            let entry_name = entry.getattr(pyo3::intern!(py, "name"))?.to_string();
+            let is_subpackage = entry
+                .call_method0(pyo3::intern!(py, "is_dir"))?
+                .is_truthy()?
+                && entry
+                    .call_method1(pyo3::intern!(py, "joinpath"), ("__init__.py",))?
+                    .call_method0(pyo3::intern!(py, "is_file"))?
+                    .is_truthy()?;
+            if is_subpackage {
+                submodule_names.insert(entry_name);
+            } else if let Some(submodule_name) = suffixes
+                .iter()
+                .find_map(|suffix| entry_name.strip_suffix(suffix.as_str()))
+            {
+                if !submodule_name.contains('.') && submodule_name != "__init__" {
+                    submodule_names.insert(submodule_name.to_owned());
+                }
+            }
+        }
+        Ok(submodule_names)
+    }
+
+    /// Extract the names of submodules of the package that are already loaded in `sys.modules`.
+    fn loaded_submodule_names(
+        module: &pyo3::Bound<pyo3::types::PyModule>,
+    ) -> Result<HashSet<String>> {
+        let py = module.py();
+        let prefix = format!("{}.", module.name()?);
+        Ok(py
+            .import_bound(pyo3::intern!(py, "sys"))?
+            .getattr(pyo3::intern!(py, "modules"))?
+            .call_method0(pyo3::intern!(py, "copy"))?
+            .iter()?
+            .filter_map(|name| {
+                let name = name.ok()?.to_string();
+                let submodule_name = name.strip_prefix(&prefix)?;
+                (!submodule_name.contains('.')).then(|| submodule_name.to_owned())
             })
-            .collect()
+            .collect())
     }
 
     fn extract_prelude(
@@ -563,16 +1791,18 @@ impl Module {
         module: &pyo3::Bound<pyo3::types::PyModule>,
         module_name: &Path,
     ) -> Vec<Ident> {
-        // Extract the index (__all__) of the module if it exists
-        let mut index_attr_names = if let Ok(index) = module.index() {
-            index
-                .iter()
-                .map(|x| Ident::from_py(&x.to_string()))
-                .unique()
-                .collect()
-        } else {
-            Vec::default()
-        };
+        // Extract the index (__all__) of the module if it exists (`PyModule::index()` would create an empty one)
+        let mut index_attr_names: Vec<Ident> = module
+            .getattr(pyo3::intern!(module.py(), "__all__"))
+            .and_then(|index| index.iter()?.collect::<PyResult<Vec<_>>>())
+            .map(|index| {
+                index
+                    .iter()
+                    .map(|x| Ident::from_py(&x.to_string()))
+                    .unique()
+                    .collect()
+            })
+            .unwrap_or_default();
 
         // Compare the index with public attrs of the module
         // Return an empty vector if they are identical (no need to generate a prelude)
@@ -602,6 +1832,7 @@ impl Module {
                                 .unwrap_or_default(),
                         );
                         attr_module.starts_with(&root_module)
+                            || Self::is_anchored_to_module(&attr, &attr_module)
                     });
                     is_reexport
                 } else {
@@ -623,7 +1854,11 @@ impl Module {
         index_attr_names
     }
 
-    fn generate_prelude(&self) -> Result<proc_macro2::TokenStream> {
+    fn generate_prelude(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+    ) -> Result<proc_macro2::TokenStream> {
         // Skip if the prelude is empty
         if self.prelude.is_empty() {
             return Ok(proc_macro2::TokenStream::new());
@@ -635,6 +1870,14 @@ impl Module {
             .iter()
             // Retain only attributes that are within self.modules, self.classes, self.functions, self.type_vars, self.properties
             .filter(|&ident| self.check_ident_exists_immediate(ident, false))
+            // Functions of modules with a handle are only available as its methods
+            .filter(|&ident| {
+                !cfg.module_handle_injection
+                    || !self
+                        .functions
+                        .iter()
+                        .any(|function| function.name.name() == ident)
+            })
             .map(|ident| {
                 let ident: syn::Ident = ident.try_into()?;
                 Ok(quote::quote! {
@@ -649,20 +1892,17 @@ impl Module {
         }
 
         // Finalize the prelude with its content
-        let prelude_ident: syn::Ident = {
-            let mut i = 0;
-            loop {
-                let ident = Ident::from_py(&format!(
-                    "call{}",
-                    (i > 0).then(|| i.to_string()).unwrap_or_default()
-                ));
-                if !self.check_ident_exists_immediate(&ident, true) {
-                    break ident;
-                }
-                i += 1;
-            }
-        }
-        .try_into()?;
+        let Some(prelude_ident) = cfg.on_collision.resolve(
+            sink,
+            &self.name.join(&Path::from_py("__all__")),
+            Ident::from_py("call"),
+            Some(Ident::from_py("call_from_all")),
+            |ident| self.check_ident_exists_immediate(ident, true),
+        )?
+        else {
+            return Ok(proc_macro2::TokenStream::new());
+        };
+        let prelude_ident: syn::Ident = prelude_ident.try_into()?;
         Ok(quote::quote! {
             pub mod #prelude_ident {
                 pub use super::{#exports};
@@ -686,6 +1926,28 @@ impl Module {
                 .any(|module| module.check_path_exists_recursive(path, consider_imports))
     }
 
+    /// Same as `check_path_exists_recursive` without imports, but only considers items that are generated
+    /// according to the configuration.
+    fn check_path_generated_recursive(&self, cfg: &Config, path: &Path) -> bool {
+        (cfg.traverse_submodules && self.submodules.iter().any(|module| module.name == *path))
+            || (cfg.generate_classes && self.classes.iter().any(|class| class.name == *path))
+            || (cfg.generate_functions
+                && !cfg.module_handle_injection
+                && self.functions.iter().any(|function| function.name == *path))
+            || (cfg.generate_type_vars
+                && self.type_vars.iter().any(|type_var| type_var.name == *path))
+            || (cfg.generate_properties
+                && self
+                    .properties
+                    .iter()
+                    .any(|property| property.name == *path))
+            || (cfg.traverse_submodules
+                && self
+                    .submodules
+                    .iter()
+                    .any(|module| module.check_path_generated_recursive(cfg, path)))
+    }
+
     fn check_ident_exists_immediate(&self, ident: &Ident, consider_imports: bool) -> bool {
         (consider_imports
             && self
@@ -1,5 +1,9 @@
 use super::{FunctionImplementation, Ident, Path, TraitMethod};
-use crate::{typing::Type, Config, Result};
+use crate::{
+    typing::{well_known::WellKnownType, Type, TypeQualifier},
+    utils::report::ReportSink,
+    Config, Result,
+};
 use pyo3::prelude::*;
 use rustc_hash::FxHashMap as HashMap;
 
@@ -7,9 +11,20 @@ use rustc_hash::FxHashMap as HashMap;
 pub struct Property {
     pub name: Path,
     owner: PropertyOwner,
+    /// Flag that indicates whether the property can be read, which is not the case for write-only class properties
+    /// (i.e. `property` with a setter but without a getter).
+    is_readable: bool,
     is_mutable: bool,
     annotation: Type,
     setter_annotation: Type,
+    /// Flag that indicates whether the annotation was inferred from the runtime value of the property.
+    is_inferred: bool,
+    /// Flag that indicates whether the property is a plain data attribute of a class (e.g. `x = 5` in the class
+    /// body) rather than a descriptor, which can also be read from the class itself.
+    is_class_attribute: bool,
+    /// Qualifier of the annotation of the property (if any), i.e. `ClassVar` properties are also readable from the
+    /// class itself and `Final` properties are immutable.
+    qualifier: Option<TypeQualifier>,
     docstring: Option<String>,
     setter_docstring: Option<String>,
 }
@@ -21,11 +36,15 @@ pub enum PropertyOwner {
 }
 
 impl Property {
+    /// Parse the property from its runtime value. The `class_annotation` of plain class attributes is their
+    /// annotation in the class body (if any), e.g. `x: ClassVar[int] = 5`.
     pub fn parse(
-        _cfg: &Config,
+        cfg: &Config,
+        sink: &ReportSink,
         property: &pyo3::Bound<pyo3::types::PyAny>,
         name: Path,
         owner: PropertyOwner,
+        class_annotation: Option<pyo3::Bound<pyo3::types::PyAny>>,
     ) -> Result<Self> {
         let py = property.py();
 
@@ -36,21 +55,82 @@ impl Property {
         let mut docstring = None;
 
         // Determine the mutability and type of the property
-        let (is_mutable, annotation, setter_annotation);
+        let (mut is_mutable, mut annotation, setter_annotation);
         let mut setter_docstring = None;
+        let mut is_readable = true;
+        let mut is_inferred = false;
+        let mut is_class_attribute = false;
+        let mut qualifier = None;
         match owner {
             PropertyOwner::Module => {
                 is_mutable = true;
-                annotation = Type::try_from(typ)?;
+
+                // The qualifier of the annotation of the variable (if any) determines its mutability, e.g. `Final`
+                let module_annotation = name
+                    .parent()
+                    .and_then(|module_name| py.import_bound(module_name.to_py().as_str()).ok())
+                    .and_then(|module| module.getattr(pyo3::intern!(py, "__annotations__")).ok())
+                    .and_then(|annotations| annotations.get_item(name.name().as_py()).ok())
+                    .map(Type::strip_qualifier)
+                    .transpose()?
+                    .and_then(|(module_annotation, module_qualifier)| {
+                        qualifier = module_qualifier;
+                        module_annotation
+                    });
+
+                // Prefer the annotation over the type of the runtime value (which might differ between platforms)
+                // only if the bindings are kept portable
+                let module_annotation = module_annotation.filter(|_| cfg.portable_property_types);
+                if let Some(module_annotation) = module_annotation {
+                    annotation =
+                        Type::from_py_with_max_depth(module_annotation, cfg.max_type_depth, sink)?;
+                } else {
+                    annotation = Type::try_from(typ)?;
+                    is_inferred = true;
+                }
                 setter_annotation = annotation.clone();
                 docstring.clone_from(&setter_docstring);
             }
             PropertyOwner::Class => {
+                // Plain data attributes do not implement the descriptor protocol (unlike `property`)
+                is_class_attribute = !typ.hasattr(pyo3::intern!(py, "__get__"))?;
+
                 let signature = py
                     .import_bound(pyo3::intern!(py, "inspect"))?
                     .getattr(pyo3::intern!(py, "signature"))?;
 
-                if let Ok(getter) = property.getattr(pyo3::intern!(py, "fget")) {
+                // Besides `property`, objects of other (non-builtin) types that implement the descriptor protocol
+                // are supported, e.g. `functools.cached_property` or attributes of ORMs, which are accessed via the
+                // wrapped function (`func`) or their `__get__`/`__set__` methods
+                let is_custom_descriptor = property.getattr(pyo3::intern!(py, "fget")).is_err()
+                    && typ.hasattr(pyo3::intern!(py, "__get__"))?
+                    && typ
+                        .getattr(pyo3::intern!(py, "__module__"))
+                        .is_ok_and(|module| module.to_string() != "builtins");
+                let getter = if is_custom_descriptor {
+                    property
+                        .getattr(pyo3::intern!(py, "func"))
+                        .ok()
+                        .filter(|func| func.is_callable())
+                        .map_or_else(|| property.getattr(pyo3::intern!(py, "__get__")), Ok)
+                } else {
+                    property.getattr(pyo3::intern!(py, "fget"))
+                };
+                // Write-only properties have no getter (`fget` is `None`), so their type is taken from the setter
+                is_readable = !getter.as_ref().is_ok_and(PyAnyMethods::is_none);
+                let setter = if is_custom_descriptor {
+                    if typ.hasattr(pyo3::intern!(py, "__set__"))? {
+                        property.getattr(pyo3::intern!(py, "__set__"))
+                    } else {
+                        Ok(py.None().into_bound(py))
+                    }
+                } else {
+                    property.getattr(pyo3::intern!(py, "fset"))
+                };
+
+                if !is_readable {
+                    annotation = Type::Unknown;
+                } else if let Ok(getter) = getter {
                     // Extract the annotation from the return of the function (if available)
                     if let Ok(function_signature) = signature.call1((&getter,)) {
                         annotation = {
@@ -61,15 +141,21 @@ impl Property {
                             {
                                 Type::Unknown
                             } else {
-                                return_annotation.try_into()?
+                                Type::from_py_with_max_depth(
+                                    return_annotation,
+                                    cfg.max_type_depth,
+                                    sink,
+                                )?
                             }
                         };
+                    } else if is_custom_descriptor {
+                        annotation = Type::Unknown;
                     } else {
                         annotation = Type::try_from(typ)?;
                     }
 
-                    // Update the docstring if it is empty
-                    if docstring.is_none() {
+                    // Update the docstring if it is empty (the docstring of `__get__` does not describe the property)
+                    if docstring.is_none() && !Self::is_descriptor_method(&getter) {
                         docstring = {
                             let docstring =
                                 getter.getattr(pyo3::intern!(py, "__doc__"))?.to_string();
@@ -84,25 +170,32 @@ impl Property {
                     annotation = Type::try_from(typ)?;
                 }
 
-                match property.getattr(pyo3::intern!(py, "fset")) {
+                match setter {
                     Ok(setter) if !setter.is_none() => {
                         is_mutable = true;
 
                         // Extract the annotation from the parameter of the function (if available)
                         if let Ok(function_signature) = signature.call1((&setter,)) {
-                            setter_annotation = {
-                                let param = function_signature
-                                    .getattr(pyo3::intern!(py, "parameters"))?
-                                    .call_method0(pyo3::intern!(py, "values"))?
-                                    .iter()?
-                                    .nth(1)
-                                    .unwrap()?;
+                            // The value might not be a distinct parameter (e.g. `def __set__(self, *args)`)
+                            let param = function_signature
+                                .getattr(pyo3::intern!(py, "parameters"))?
+                                .call_method0(pyo3::intern!(py, "values"))?
+                                .iter()?
+                                .nth(1)
+                                .transpose()?;
+                            setter_annotation = if let Some(param) = param {
                                 let annotation = param.getattr(pyo3::intern!(py, "annotation"))?;
                                 if annotation.is(&param.getattr(pyo3::intern!(py, "empty"))?) {
                                     Type::Unknown
                                 } else {
-                                    annotation.try_into()?
+                                    Type::from_py_with_max_depth(
+                                        annotation,
+                                        cfg.max_type_depth,
+                                        sink,
+                                    )?
                                 }
+                            } else {
+                                Type::Unknown
                             };
                         } else {
                             setter_annotation = Type::Unknown;
@@ -111,7 +204,10 @@ impl Property {
                         setter_docstring = {
                             let docstring =
                                 setter.getattr(pyo3::intern!(py, "__doc__"))?.to_string();
-                            if docstring.is_empty() || docstring == "None" {
+                            if docstring.is_empty()
+                                || docstring == "None"
+                                || Self::is_descriptor_method(&setter)
+                            {
                                 None
                             } else {
                                 Some(docstring)
@@ -132,23 +228,297 @@ impl Property {
                         setter_docstring = None;
                     }
                 }
+                if !is_readable {
+                    annotation = setter_annotation.clone();
+                }
+
+                // The inner type of qualified class attributes (e.g. `ClassVar[int]`) is preferred over the type
+                // of their runtime value
+                if let Some(class_annotation) = class_annotation.filter(|_| is_class_attribute) {
+                    let (inner_annotation, class_qualifier) =
+                        Type::strip_qualifier(class_annotation)?;
+                    qualifier = class_qualifier;
+                    if let (Some(inner_annotation), Some(_)) = (inner_annotation, qualifier) {
+                        annotation = Type::from_py_with_max_depth(
+                            inner_annotation,
+                            cfg.max_type_depth,
+                            sink,
+                        )?;
+                    }
+                }
             }
         }
 
+        // Final properties cannot be reassigned
+        if qualifier == Some(TypeQualifier::Final) {
+            is_mutable = false;
+        }
+
         Ok(Self {
             name,
             owner,
+            is_readable,
             is_mutable,
             annotation,
             setter_annotation,
+            is_inferred,
+            is_class_attribute,
+            qualifier,
+            docstring,
+            setter_docstring,
+        })
+    }
+
+    /// Check whether the accessor is a method of the descriptor protocol (`__get__`/`__set__`) of a custom descriptor.
+    fn is_descriptor_method(accessor: &pyo3::Bound<pyo3::types::PyAny>) -> bool {
+        accessor
+            .getattr(pyo3::intern!(accessor.py(), "__name__"))
+            .is_ok_and(|name| ["__get__", "__set__"].contains(&name.to_string().as_str()))
+    }
+
+    /// Parse the class property from the `ast.FunctionDef` nodes of its getter and setter (if any) without evaluating them.
+    pub fn from_ast_accessors(
+        cfg: &Config,
+        sink: &ReportSink,
+        getter: &pyo3::Bound<pyo3::types::PyAny>,
+        setter: Option<&pyo3::Bound<pyo3::types::PyAny>>,
+        name: Path,
+        scope: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let py = getter.py();
+
+        // Extract the annotation from the return of the getter and the second parameter of the setter (if available)
+        let annotation = Type::from_ast(
+            &getter.getattr(pyo3::intern!(py, "returns"))?,
+            scope,
+            cfg.max_type_depth,
+            sink,
+        )?;
+        let setter_annotation = if let Some(setter) = setter {
+            let params = super::common::ast::children(
+                &setter.getattr(pyo3::intern!(py, "args"))?,
+                pyo3::intern!(py, "args"),
+            )?;
+            if let Some(param) = params.get(1) {
+                Type::from_ast(
+                    &param.getattr(pyo3::intern!(py, "annotation"))?,
+                    scope,
+                    cfg.max_type_depth,
+                    sink,
+                )?
+            } else {
+                Type::Unknown
+            }
+        } else {
+            Type::Unknown
+        };
+
+        // Share the docstring between the getter and setter if only one of them is documented
+        let mut docstring = super::common::ast::docstring(getter)?;
+        let mut setter_docstring = setter
+            .map(super::common::ast::docstring)
+            .transpose()?
+            .flatten();
+        if setter.is_some() {
+            if docstring.is_none() {
+                docstring.clone_from(&setter_docstring);
+            } else if setter_docstring.is_none() {
+                setter_docstring.clone_from(&docstring);
+            }
+        }
+
+        Ok(Self {
+            name,
+            owner: PropertyOwner::Class,
+            is_readable: true,
+            is_mutable: setter.is_some(),
+            annotation,
+            setter_annotation,
+            is_inferred: false,
+            is_class_attribute: false,
+            qualifier: None,
             docstring,
             setter_docstring,
         })
     }
 
+    /// Parse the instance attribute of a class that is only declared via its `annotation` in the class body
+    /// (e.g. `x: int`), i.e. without a class attribute or descriptor. Such attributes are readable and writable,
+    /// unless they are `Final`.
+    pub fn from_annotation(
+        cfg: &Config,
+        sink: &ReportSink,
+        annotation: pyo3::Bound<pyo3::types::PyAny>,
+        name: Path,
+    ) -> Result<Self> {
+        let (annotation, qualifier) = Type::strip_qualifier(annotation)?;
+        let annotation = annotation
+            .map(|annotation| Type::from_py_with_max_depth(annotation, cfg.max_type_depth, sink))
+            .transpose()?
+            .unwrap_or(Type::Unknown);
+        Ok(Self {
+            name,
+            owner: PropertyOwner::Class,
+            is_readable: true,
+            is_mutable: qualifier != Some(TypeQualifier::Final),
+            setter_annotation: annotation.clone(),
+            annotation,
+            is_inferred: false,
+            is_class_attribute: false,
+            qualifier,
+            docstring: None,
+            setter_docstring: None,
+        })
+    }
+
+    /// Parse the property from the `ast` nodes of a variable assignment without evaluating them. The type of the
+    /// property is taken from its `annotation` node (if any), otherwise it is inferred from its literal `value`.
+    pub fn from_ast_variable(
+        cfg: &Config,
+        sink: &ReportSink,
+        annotation: Option<&pyo3::Bound<pyo3::types::PyAny>>,
+        value: Option<&pyo3::Bound<pyo3::types::PyAny>>,
+        name: Path,
+        owner: PropertyOwner,
+        scope: &HashMap<String, String>,
+    ) -> Result<Self> {
+        // The qualifier of the annotation is stripped, so that the type of a bare `Final` is inferred from the value
+        let (annotation, qualifier) = match annotation {
+            Some(annotation) => {
+                let (inner_annotation, qualifier) = Type::strip_qualifier(
+                    pyo3::types::PyString::new_bound(
+                        annotation.py(),
+                        &super::common::ast::unparse(annotation)?,
+                    )
+                    .into_any(),
+                )?;
+                (inner_annotation.map(|_| annotation), qualifier)
+            }
+            None => (None, None),
+        };
+        let (annotation, is_inferred) = if let Some(annotation) = annotation {
+            (
+                Type::from_ast(annotation, scope, cfg.max_type_depth, sink)?,
+                false,
+            )
+        } else {
+            let annotation = match value.and_then(super::common::ast::literal_eval) {
+                Some(value) => Type::try_from(value.get_type())?,
+                None => Type::Unknown,
+            };
+            (annotation, true)
+        };
+
+        Ok(Self {
+            name,
+            owner,
+            is_readable: true,
+            is_mutable: qualifier != Some(TypeQualifier::Final),
+            setter_annotation: annotation.clone(),
+            annotation,
+            is_inferred,
+            is_class_attribute: false,
+            qualifier,
+            docstring: None,
+            setter_docstring: None,
+        })
+    }
+
+    /// Iterate over the type annotations of the property.
+    pub(crate) fn annotations(&self) -> impl Iterator<Item = &Type> {
+        std::iter::once(&self.annotation)
+    }
+
+    /// Iterate mutably over the type annotations of the property (including the annotation of its setter).
+    pub(crate) fn annotations_mut(&mut self) -> impl Iterator<Item = &mut Type> {
+        [&mut self.annotation, &mut self.setter_annotation].into_iter()
+    }
+
+    /// Determine whether the type of the property is replaced by `PyAny` to keep the bindings portable,
+    /// which applies to module-level properties with a type inferred from their runtime value.
+    fn is_portable(&self, cfg: &Config) -> bool {
+        cfg.portable_property_types && self.owner == PropertyOwner::Module && self.is_inferred
+    }
+
+    /// Get the annotation of the property that is used in the generated bindings.
+    fn effective_annotation(&self, cfg: &Config) -> Type {
+        if self.is_portable(cfg) {
+            Type::PyAny
+        } else {
+            self.annotation.clone()
+        }
+    }
+
+    /// Export the property as a schema object.
+    pub fn export_schema(&self) -> serde_json::Value {
+        let mut schema = self.annotation.to_schema();
+        schema["readonly"] = (!self.is_mutable).into();
+        schema["docstring"] = self.docstring.clone().into();
+        schema
+    }
+
+    /// Render everything about the property that the generated bindings depend on as a stable snapshot.
+    pub(crate) fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name.to_py(),
+            "owner": match self.owner {
+                PropertyOwner::Module => "Module",
+                PropertyOwner::Class => "Class",
+            },
+            "is_readable": self.is_readable,
+            "is_mutable": self.is_mutable,
+            "annotation": self.annotation.to_snapshot(),
+            "setter_annotation": self.setter_annotation.to_snapshot(),
+            "is_inferred": self.is_inferred,
+            "is_class_attribute": self.is_class_attribute,
+            "qualifier": self.qualifier.map(|qualifier| match qualifier {
+                TypeQualifier::ClassVar => "ClassVar",
+                TypeQualifier::Final => "Final",
+            }),
+            "docstring": self.docstring,
+            "setter_docstring": self.setter_docstring,
+        })
+    }
+
+    /// Check whether the name of a generated accessor is already taken by another item or reserved.
+    fn is_name_taken(ident: &Ident, scoped_function_idents: &[&Ident]) -> bool {
+        scoped_function_idents.contains(&ident)
+            || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&ident.as_py())
+    }
+
+    /// Resolve the name of the getter, which is renamed according to `Config::rename_conflicting_getters` if the
+    /// name of the property is already taken. Returns `None` if the getter is skipped.
+    fn resolve_getter_name(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        scoped_function_idents: &[&Ident],
+    ) -> Result<Option<Ident>> {
+        let name = self.name.name();
+        let is_taken = |ident: &Ident| Self::is_name_taken(ident, scoped_function_idents);
+        if syn::Ident::try_from(name).is_err() {
+            // Names that are not valid identifiers are always accessed via the getter
+            return cfg.on_collision.resolve(
+                sink,
+                &self.name,
+                name.with_prefix("get_"),
+                None,
+                is_taken,
+            );
+        }
+        let renamed = if is_taken(name) {
+            Some(cfg.rename_conflicting_getters.rename(&self.name, name)?)
+        } else {
+            None
+        };
+        cfg.on_collision
+            .resolve(sink, &self.name, name.clone(), renamed, is_taken)
+    }
+
     pub fn generate(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         scoped_function_idents: &[&Ident],
         local_types: &HashMap<Path, Path>,
     ) -> Result<FunctionImplementation> {
@@ -157,33 +527,40 @@ impl Property {
                 let mut functions = proc_macro2::TokenStream::new();
 
                 // Getter
-                let impl_fn = self
-                    .generate_getter(cfg, scoped_function_idents, local_types)?
-                    .impl_fn;
-                functions.extend(quote::quote! { pub #impl_fn });
+                functions.extend(
+                    self.generate_getter(cfg, sink, scoped_function_idents, local_types)?
+                        .impl_fn,
+                );
 
                 // Setter (if mutable)
                 if self.is_mutable {
-                    let impl_fn = self
-                        .generate_setter(cfg, scoped_function_idents, local_types)?
-                        .impl_fn;
-                    functions.extend(quote::quote! { pub #impl_fn });
+                    functions.extend(
+                        self.generate_setter(cfg, sink, scoped_function_idents, local_types)?
+                            .impl_fn,
+                    );
                 }
 
+                // Helpers for well-known types of the standard library
+                functions.extend(self.generate_well_known_helpers(cfg)?);
+
                 FunctionImplementation::Function(functions)
             }
             PropertyOwner::Class => {
                 let mut trait_fn = proc_macro2::TokenStream::new();
                 let mut impl_fn = proc_macro2::TokenStream::new();
 
-                // Getter
-                let getter = self.generate_getter(cfg, scoped_function_idents, local_types)?;
-                trait_fn.extend(getter.trait_fn);
-                impl_fn.extend(getter.impl_fn);
+                // Getter (if readable)
+                if self.is_readable {
+                    let getter =
+                        self.generate_getter(cfg, sink, scoped_function_idents, local_types)?;
+                    trait_fn.extend(getter.trait_fn);
+                    impl_fn.extend(getter.impl_fn);
+                }
 
                 // Setter (if mutable)
                 if self.is_mutable {
-                    let setter = self.generate_setter(cfg, scoped_function_idents, local_types)?;
+                    let setter =
+                        self.generate_setter(cfg, sink, scoped_function_idents, local_types)?;
                     trait_fn.extend(setter.trait_fn);
                     impl_fn.extend(setter.impl_fn);
                 }
@@ -196,6 +573,7 @@ impl Property {
     pub fn generate_getter(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         scoped_function_idents: &[&Ident],
         local_types: &HashMap<Path, Path>,
     ) -> Result<TraitMethod> {
@@ -204,45 +582,55 @@ impl Property {
 
         // Documentation
         if cfg.generate_docs {
-            if let Some(mut docstring) = self.docstring.clone() {
-                crate::utils::text::format_docstring(&mut docstring);
+            let mut docstring = self.docstring.clone();
+            if self.is_portable(cfg) {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(&format!(
+                    "Note: The value of this property had the type `{}` while generating the bindings. Its type is not annotated and might differ between platforms, so it is returned as `PyAny`.",
+                    self.annotation.to_py_annotation()
+                ));
+            }
+            if let Some(note) = self
+                .effective_annotation(cfg)
+                .well_known()
+                .and_then(WellKnownType::doc_note)
+            {
+                let docstring = docstring.get_or_insert_with(String::new);
+                if !docstring.is_empty() {
+                    docstring.push_str("\n\n");
+                }
+                docstring.push_str(note);
+            }
+            if let Some(mut docstring) = docstring {
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
                 impl_fn.extend(quote::quote! {
                     #[doc = #docstring]
                 });
             }
         }
 
+        // Inlining
+        if cfg.inline_accessors {
+            impl_fn.extend(quote::quote! {
+                #[inline]
+            });
+        }
+
         // Function
         let function_ident: syn::Ident = {
-            let name = self.name.name();
-            if let Ok(ident) = name.try_into() {
-                if scoped_function_idents.contains(&name)
-                    || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&name.as_py())
-                {
-                    let getter_name = Ident::from_py(&format!("get_{}", name.as_py()));
-                    if scoped_function_idents.contains(&&getter_name)
-                        || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&getter_name.as_py())
-                    {
-                        return Ok(TraitMethod::empty());
-                    } else {
-                        getter_name.try_into()?
-                    }
-                } else {
-                    ident
-                }
-            } else {
-                let getter_name = Ident::from_py(&format!("get_{}", name.as_py()));
-                if scoped_function_idents.contains(&&getter_name)
-                    || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&getter_name.as_py())
-                {
-                    return Ok(TraitMethod::empty());
-                } else {
-                    getter_name.try_into()?
-                }
-            }
+            let Some(resolved_name) =
+                self.resolve_getter_name(cfg, sink, scoped_function_idents)?
+            else {
+                return Ok(TraitMethod::empty());
+            };
+            resolved_name.try_into()?
         };
         let param_name = self.name.name().as_py();
-        let param_type = self.annotation.clone().into_rs_owned(local_types);
+        let annotation = self.effective_annotation(cfg);
+        let param_type = annotation.clone().into_rs_returned(cfg, local_types);
         match &self.owner {
             PropertyOwner::Module => {
                 let import = pyo3::Python::with_gil(|py| {
@@ -251,42 +639,266 @@ impl Property {
                         .unwrap_or_else(|| unreachable!())
                         .import_quote(py)
                 });
-                impl_fn.extend(quote::quote! {
-                    fn #function_ident<'py>(
-                        py: ::pyo3::marker::Python<'py>,
-                    ) -> ::pyo3::PyResult<#param_type> {
-                        ::pyo3::types::PyAnyMethods::extract(
-                            &::pyo3::types::PyAnyMethods::getattr(#import.as_any(), ::pyo3::intern!(py, #param_name))?
-                        )
+                let extract = annotation.extract_returned(
+                    cfg,
+                    quote::quote!(::pyo3::types::PyAnyMethods::getattr(#import.as_any(), ::pyo3::intern!(py, #param_name))?),
+                );
+                let body = cfg.bodies.body(quote::quote! {
+                    {
+                        #extract
                     }
                 });
+                let signature = crate::utils::lint::omit_unused_py_lifetime(
+                    quote::quote! {
+                        pub fn #function_ident<'py>(
+                            py: ::pyo3::marker::Python<'py>,
+                        ) -> ::pyo3::PyResult<#param_type>
+                    },
+                    Some(&body),
+                );
+                let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+                impl_fn.extend(quote::quote! {
+                    #allow
+                    #signature #body
+                });
             }
             PropertyOwner::Class => {
                 let param_name = self.name.name().as_py();
 
-                trait_fn.extend(quote::quote! {
+                let signature = quote::quote! {
                     fn #function_ident<'py>(
                         &'py self,
-                    ) -> ::pyo3::PyResult<#param_type>;
+                    ) -> ::pyo3::PyResult<#param_type>
+                };
+                let trait_signature =
+                    crate::utils::lint::omit_unused_py_lifetime(signature.clone(), None);
+                let allow = crate::utils::lint::allow_fn(cfg, &trait_signature)?;
+                trait_fn.extend(quote::quote! {
+                    #allow
+                    #trait_signature;
                 });
-                impl_fn.extend(quote::quote! {
-                    fn #function_ident<'py>(
-                        &'py self,
-                    ) -> ::pyo3::PyResult<#param_type> {
-                        ::pyo3::types::PyAnyMethods::extract(
-                            &::pyo3::types::PyAnyMethods::getattr(self.as_any(), ::pyo3::intern!(self.py(), #param_name))?
-                        )
+                let extract = annotation.extract_returned(
+                    cfg,
+                    quote::quote!(::pyo3::types::PyAnyMethods::getattr(
+                        self.as_any(),
+                        ::pyo3::intern!(self.py(), #param_name)
+                    )?),
+                );
+                let body = cfg.bodies.body(quote::quote! {
+                    {
+                        #extract
                     }
                 });
+                let signature = crate::utils::lint::omit_unused_py_lifetime(signature, Some(&body));
+                impl_fn.extend(quote::quote! {
+                    #signature #body
+                });
             }
         }
 
         Ok(TraitMethod { trait_fn, impl_fn })
     }
 
+    /// Generate an associated function of the class that reads a plain class attribute (e.g. `x = 5` in the class
+    /// body) or a `ClassVar` from the class itself via `getattr`, so that it is accessible without an instance of the
+    /// class.
+    pub fn generate_class_attribute_getter(
+        &self,
+        cfg: &Config,
+        sink: &ReportSink,
+        scoped_function_idents: &[&Ident],
+        local_types: &HashMap<Path, Path>,
+    ) -> Result<proc_macro2::TokenStream> {
+        let mut impl_fn = proc_macro2::TokenStream::new();
+        if !(self.is_class_attribute || self.qualifier == Some(TypeQualifier::ClassVar))
+            || !self.is_readable
+        {
+            return Ok(impl_fn);
+        }
+
+        // Documentation
+        let attr_name = self.name.name().as_py();
+        if cfg.generate_docs {
+            let docstring = format!(
+                " Get the value of the class attribute `{attr_name}` from the class itself."
+            );
+            impl_fn.extend(quote::quote! {
+                #[doc = #docstring]
+            });
+        }
+
+        // Inlining
+        if cfg.inline_accessors {
+            impl_fn.extend(quote::quote! {
+                #[inline]
+            });
+        }
+
+        // Function
+        let function_ident: syn::Ident = {
+            let Some(resolved_name) =
+                self.resolve_getter_name(cfg, sink, scoped_function_idents)?
+            else {
+                return Ok(proc_macro2::TokenStream::new());
+            };
+            resolved_name.try_into()?
+        };
+        let import = pyo3::Python::with_gil(|py| {
+            self.name
+                .parent()
+                .unwrap_or_else(|| unreachable!())
+                .import_quote(py)
+        });
+        let annotation = self.effective_annotation(cfg);
+        let param_type = annotation.clone().into_rs_returned(cfg, local_types);
+        let extract = annotation.extract_returned(
+            cfg,
+            quote::quote!(::pyo3::types::PyAnyMethods::getattr(#import.as_any(), ::pyo3::intern!(py, #attr_name))?),
+        );
+        let body = cfg.bodies.body(quote::quote! {
+            {
+                #extract
+            }
+        });
+        let signature = crate::utils::lint::omit_unused_py_lifetime(
+            quote::quote! {
+                pub fn #function_ident<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<#param_type>
+            },
+            Some(&body),
+        );
+        let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+        impl_fn.extend(quote::quote! {
+            #allow
+            #signature #body
+        });
+
+        Ok(impl_fn)
+    }
+
+    /// Generate helper functions for module properties of well-known types of the standard library,
+    /// i.e. logging helpers for `logging.Logger` (if enabled) and matching helpers for `re.Pattern`.
+    fn generate_well_known_helpers(&self, cfg: &Config) -> Result<proc_macro2::TokenStream> {
+        let mut output = proc_macro2::TokenStream::new();
+
+        let property_name = self.name.name().as_py();
+        let import = pyo3::Python::with_gil(|py| {
+            self.name
+                .parent()
+                .unwrap_or_else(|| unreachable!())
+                .import_quote(py)
+        });
+        let property = quote::quote! {
+            ::pyo3::types::PyAnyMethods::getattr(#import.as_any(), ::pyo3::intern!(py, #property_name))?
+        };
+        match self.annotation.well_known() {
+            Some(WellKnownType::Logger) if cfg.logging_helpers => {
+                // Note: The helpers of the conventional `logger` property are not prefixed
+                let prefix = if property_name == "logger" {
+                    String::new()
+                } else {
+                    format!("{property_name}_")
+                };
+                for level in ["info", "warning", "error"] {
+                    let Ok(function_ident) =
+                        syn::Ident::try_from(Ident::from_py(&format!("{prefix}log_{level}")))
+                    else {
+                        continue;
+                    };
+                    let docstring = format!(
+                        " Log the message with the `{}` level via the `{property_name}` logger of the module.",
+                        level.to_uppercase()
+                    );
+                    let body = cfg.bodies.body(quote::quote! {
+                        {
+                            ::pyo3::types::PyAnyMethods::call_method1(
+                                &#property,
+                                ::pyo3::intern!(py, #level),
+                                (msg,),
+                            )?;
+                            ::std::result::Result::Ok(())
+                        }
+                    });
+                    let signature = crate::utils::lint::omit_unused_py_lifetime(
+                        quote::quote! {
+                            pub fn #function_ident<'py>(
+                                py: ::pyo3::marker::Python<'py>,
+                                msg: &str,
+                            ) -> ::pyo3::PyResult<()>
+                        },
+                        Some(&body),
+                    );
+                    let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+                    output.extend(quote::quote! {
+                        #[doc = #docstring]
+                        #allow
+                        #signature #body
+                    });
+                }
+            }
+            Some(WellKnownType::Pattern) => {
+                for (method_name, return_type) in [
+                    (
+                        "search",
+                        quote::quote!(
+                            ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>
+                        ),
+                    ),
+                    (
+                        "match",
+                        quote::quote!(
+                            ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>
+                        ),
+                    ),
+                    (
+                        "findall",
+                        quote::quote!(::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>),
+                    ),
+                ] {
+                    let Ok(function_ident) = syn::Ident::try_from(Ident::from_py(&format!(
+                        "{property_name}_{method_name}"
+                    ))) else {
+                        continue;
+                    };
+                    let docstring = format!(
+                        " Call `{method_name}` of the `{property_name}` regular expression pattern of the module with the given string."
+                    );
+                    let body = cfg.bodies.body(quote::quote! {
+                        {
+                            ::pyo3::types::PyAnyMethods::extract(
+                                &::pyo3::types::PyAnyMethods::call_method1(
+                                    &#property,
+                                    ::pyo3::intern!(py, #method_name),
+                                    (string,),
+                                )?
+                            )
+                        }
+                    });
+                    let signature = quote::quote! {
+                        pub fn #function_ident<'py>(
+                            py: ::pyo3::marker::Python<'py>,
+                            string: &str,
+                        ) -> ::pyo3::PyResult<#return_type>
+                    };
+                    let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+                    output.extend(quote::quote! {
+                        #[doc = #docstring]
+                        #allow
+                        #signature #body
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        Ok(output)
+    }
+
     pub fn generate_setter(
         &self,
         cfg: &Config,
+        sink: &ReportSink,
         scoped_function_idents: &[&Ident],
         local_types: &HashMap<Path, Path>,
     ) -> Result<TraitMethod> {
@@ -296,30 +908,46 @@ impl Property {
         // Documentation
         if cfg.generate_docs {
             if let Some(mut docstring) = self.setter_docstring.clone() {
-                crate::utils::text::format_docstring(&mut docstring);
+                crate::utils::text::format_docstring(&mut docstring, cfg.docstring_max_width);
                 impl_fn.extend(quote::quote! {
                     #[doc = #docstring]
                 });
             }
         }
 
+        // Inlining
+        if cfg.inline_accessors {
+            impl_fn.extend(quote::quote! {
+                #[inline]
+            });
+        }
+
         // Function
         let function_ident: syn::Ident = {
-            let setter_name = Ident::from_py(&format!("set_{}", self.name.name().as_py()));
-            if scoped_function_idents.contains(&&setter_name)
-                || crate::config::FORBIDDEN_FUNCTION_NAMES.contains(&setter_name.as_py())
-            {
-                return Ok(TraitMethod::empty());
+            let setter_prefix = if cfg.infallible_setters && self.owner == PropertyOwner::Class {
+                "try_set_"
             } else {
-                setter_name.try_into()?
-            }
+                "set_"
+            };
+            let setter_name = self.name.name().with_prefix(setter_prefix);
+            let Some(resolved_name) =
+                cfg.on_collision
+                    .resolve(sink, &self.name, setter_name, None, |ident| {
+                        Self::is_name_taken(ident, scoped_function_idents)
+                    })?
+            else {
+                return Ok(TraitMethod::empty());
+            };
+            resolved_name.try_into()?
         };
         let param_name = self.name.name().as_py();
-        let param_preprocessing = self.annotation.preprocess_borrowed(
+        let annotation = self.effective_annotation(cfg);
+        let param_preprocessing = annotation.preprocess_borrowed(
             &syn::Ident::new("p_value", proc_macro2::Span::call_site()),
+            cfg,
             local_types,
         );
-        let param_type = self.annotation.clone().into_rs_borrowed(local_types);
+        let param_type = annotation.into_rs_borrowed(cfg, local_types);
         match &self.owner {
             PropertyOwner::Module => {
                 let import = pyo3::Python::with_gil(|py| {
@@ -328,33 +956,52 @@ impl Property {
                         .unwrap_or_else(|| unreachable!())
                         .import_quote(py)
                 });
-                impl_fn.extend(quote::quote! {
-                    fn #function_ident<'py>(
-                        py: ::pyo3::marker::Python<'py>,
-                        p_value: #param_type,
-                    ) -> ::pyo3::PyResult<()> {
+                let body = cfg.bodies.body(quote::quote! {
+                    {
                         #param_preprocessing
                         ::pyo3::types::PyAnyMethods::setattr(#import.as_any(), ::pyo3::intern!(py, #param_name), p_value)
                     }
                 });
+                let signature = crate::utils::lint::omit_unused_py_lifetime(
+                    quote::quote! {
+                        pub fn #function_ident<'py>(
+                            py: ::pyo3::marker::Python<'py>,
+                            p_value: #param_type,
+                        ) -> ::pyo3::PyResult<()>
+                    },
+                    Some(&body),
+                );
+                let allow = crate::utils::lint::allow_fn(cfg, &signature)?;
+                impl_fn.extend(quote::quote! {
+                    #allow
+                    #signature #body
+                });
             }
             PropertyOwner::Class => {
-                trait_fn.extend(quote::quote! {
+                let signature = quote::quote! {
                     fn #function_ident<'py>(
                         &'py self,
                         p_value: #param_type,
-                    ) -> ::pyo3::PyResult<()>;
+                    ) -> ::pyo3::PyResult<()>
+                };
+                let trait_signature =
+                    crate::utils::lint::omit_unused_py_lifetime(signature.clone(), None);
+                let allow = crate::utils::lint::allow_fn(cfg, &trait_signature)?;
+                trait_fn.extend(quote::quote! {
+                    #allow
+                    #trait_signature;
                 });
-                impl_fn.extend(quote::quote! {
-                    fn #function_ident<'py>(
-                        &'py self,
-                        p_value: #param_type,
-                    ) -> ::pyo3::PyResult<()> {
+                let body = cfg.bodies.body(quote::quote! {
+                    {
                         let py = self.py();
                         #param_preprocessing
                         ::pyo3::types::PyAnyMethods::setattr(self.as_any(), ::pyo3::intern!(py, #param_name), p_value)
                     }
                 });
+                let signature = crate::utils::lint::omit_unused_py_lifetime(signature, Some(&body));
+                impl_fn.extend(quote::quote! {
+                    #signature #body
+                });
             }
         }
 
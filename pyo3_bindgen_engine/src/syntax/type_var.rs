@@ -11,9 +11,11 @@ impl TypeVar {
         Self { name }
     }
 
-    pub fn generate(&self, _cfg: &Config) -> Result<proc_macro2::TokenStream> {
+    pub fn generate(&self, cfg: &Config) -> Result<proc_macro2::TokenStream> {
         let typevar_ident: syn::Ident = self.name.name().try_into()?;
+        let allow = crate::utils::lint::allow_type(cfg, &typevar_ident, []);
         Ok(quote::quote! {
+            #allow
             pub type #typevar_ident = ::pyo3::types::PyAny;
         })
     }
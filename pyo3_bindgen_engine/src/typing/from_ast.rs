@@ -0,0 +1,127 @@
+use super::Type;
+use crate::{utils::report::ReportSink, Result};
+use pyo3::prelude::*;
+use rustc_hash::FxHashMap as HashMap;
+
+impl Type {
+    /// Parse the type annotation from a node of Python's `ast` module (or `None` if the annotation is missing).
+    /// Names bound in the `scope` of the module (e.g. classes and imports) are resolved to their full path,
+    /// and string constants are treated as forward references. Parts of the annotation nested deeper than
+    /// `max_depth` are mapped to `Any`.
+    pub fn from_ast(
+        node: &pyo3::Bound<pyo3::types::PyAny>,
+        scope: &HashMap<String, String>,
+        max_depth: usize,
+        sink: &ReportSink,
+    ) -> Result<Self> {
+        let py = node.py();
+        if node.is_none() {
+            return Ok(Self::Unknown);
+        }
+
+        // Parse forward references into an expression (or keep them as a string if they are not valid)
+        let ast = py.import_bound(pyo3::intern!(py, "ast"))?;
+        let mut node = node.clone();
+        if node.is_instance(&ast.getattr(pyo3::intern!(py, "Constant"))?)? {
+            let value = node.getattr(pyo3::intern!(py, "value"))?;
+            if let Ok(forward_ref) = value.downcast::<pyo3::types::PyString>() {
+                let kwargs = pyo3::types::PyDict::new_bound(py);
+                kwargs.set_item(pyo3::intern!(py, "mode"), pyo3::intern!(py, "eval"))?;
+                match ast.call_method(pyo3::intern!(py, "parse"), (forward_ref,), Some(&kwargs)) {
+                    Ok(expression) => node = expression.getattr(pyo3::intern!(py, "body"))?,
+                    Err(_) => {
+                        return Self::from_str_with_max_depth(
+                            forward_ref.to_str()?,
+                            max_depth,
+                            sink,
+                        )
+                    }
+                }
+            }
+        }
+
+        // Resolve the names of a copy of the node, so that the original tree is not modified
+        let node = py
+            .import_bound(pyo3::intern!(py, "copy"))?
+            .call_method1(pyo3::intern!(py, "deepcopy"), (node,))?;
+        for child in ast
+            .call_method1(pyo3::intern!(py, "walk"), (&node,))?
+            .iter()?
+        {
+            let child = child?;
+            if crate::syntax::common::ast::is_node(&child, &["Name"])? {
+                let id = child.getattr(pyo3::intern!(py, "id"))?.to_string();
+                if let Some(full_name) = scope.get(&id) {
+                    child.setattr(pyo3::intern!(py, "id"), full_name)?;
+                }
+            }
+        }
+        Self::from_str_with_max_depth(
+            &crate::syntax::common::ast::unparse(&node)?,
+            max_depth,
+            sink,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_MAX_TYPE_DEPTH;
+
+    #[test]
+    fn test_from_ast() {
+        pyo3::prepare_freethreaded_python();
+        pyo3::Python::with_gil(|py| {
+            // Arrange
+            let ast = py.import_bound("ast").unwrap();
+            let parse_annotation = |source: &str| {
+                ast.call_method1("parse", (source, "<annotation>", "eval"))
+                    .unwrap()
+                    .getattr("body")
+                    .unwrap()
+            };
+            let scope = [("Point".to_owned(), "geometry.Point".to_owned())]
+                .into_iter()
+                .collect();
+            let sink = ReportSink::default();
+
+            // Act
+            let annotation = Type::from_ast(
+                &parse_annotation("Optional[list[int]]"),
+                &scope,
+                DEFAULT_MAX_TYPE_DEPTH,
+                &sink,
+            )
+            .unwrap();
+            let forward_ref = Type::from_ast(
+                &parse_annotation("'dict[str, Point]'"),
+                &scope,
+                DEFAULT_MAX_TYPE_DEPTH,
+                &sink,
+            )
+            .unwrap();
+            let missing = Type::from_ast(
+                &py.None().into_bound(py),
+                &scope,
+                DEFAULT_MAX_TYPE_DEPTH,
+                &sink,
+            )
+            .unwrap();
+
+            // Assert
+            assert_eq!(
+                annotation,
+                Type::Optional(Box::new(Type::PyList(Box::new(Type::PyLong))))
+            );
+            assert_eq!(
+                forward_ref,
+                Type::PyDict {
+                    key_type: Box::new(Type::PyString),
+                    value_type: Box::new(Type::Other("geometry.Point".to_owned())),
+                }
+            );
+            assert_eq!(missing, Type::Unknown);
+        });
+    }
+}
@@ -1,12 +1,98 @@
-use super::Type;
-use crate::{PyBindgenError, Result};
-use itertools::Itertools;
+use super::{well_known::WellKnownType, Type, TypeQualifier};
+use crate::{config::DEFAULT_MAX_TYPE_DEPTH, utils::report::ReportSink, PyBindgenError, Result};
 use pyo3::prelude::*;
 use std::str::FromStr;
 
+/// State of parsing a single type annotation, which guards against unbounded recursion of nested
+/// (e.g. self-referential) annotations.
+#[derive(Debug, Clone)]
+struct Context<'a> {
+    /// Sink of the warnings about parts of the annotation that are mapped to `Any`.
+    sink: &'a ReportSink,
+    /// Nesting depth of the currently parsed part of the annotation.
+    depth: usize,
+    /// Maximum nesting depth, beyond which the remainder of the annotation is mapped to `Any`.
+    max_depth: usize,
+    /// Names of the forward references that are currently being resolved.
+    forward_refs: Vec<String>,
+}
+
+impl<'a> Context<'a> {
+    fn new(max_depth: usize, sink: &'a ReportSink) -> Self {
+        Self {
+            sink,
+            depth: 0,
+            max_depth,
+            forward_refs: Vec::new(),
+        }
+    }
+
+    fn nested(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Check whether the given part of the annotation exceeds the maximum nesting depth (with a warning).
+    fn exceeds_max_depth(&self, value: &dyn std::fmt::Display) -> bool {
+        let exceeds_max_depth = self.depth > self.max_depth;
+        if exceeds_max_depth {
+            self.sink.warn(
+                crate::WarningKind::Other,
+                value,
+                format!(
+                    "Type annotation '{value}' exceeds the maximum nesting depth of {}. It is mapped to 'Any'.",
+                    self.max_depth
+                ),
+            );
+        }
+        exceeds_max_depth
+    }
+
+    /// Enter the resolution of the given forward reference, unless it refers to itself (with a warning).
+    fn enter_forward_ref(&self, name: &str) -> Option<Self> {
+        if self
+            .forward_refs
+            .iter()
+            .any(|forward_ref| forward_ref == name)
+        {
+            self.sink.warn(
+                crate::WarningKind::Other,
+                name,
+                format!("Type annotation '{name}' refers to itself. It is mapped to 'Any'."),
+            );
+            return None;
+        }
+        let mut ctx = self.nested();
+        ctx.forward_refs.push(name.to_owned());
+        Some(ctx)
+    }
+}
+
 impl TryFrom<pyo3::Bound<'_, pyo3::types::PyAny>> for Type {
     type Error = PyBindgenError;
     fn try_from(value: pyo3::Bound<pyo3::types::PyAny>) -> Result<Self> {
+        // Warnings are only printed, as there is no report to which they could be added
+        Self::from_py_with_max_depth(value, DEFAULT_MAX_TYPE_DEPTH, &ReportSink::default())
+    }
+}
+
+impl Type {
+    /// Parse a type annotation, whose parts nested deeper than `max_depth` are mapped to `Any`.
+    pub(crate) fn from_py_with_max_depth(
+        value: pyo3::Bound<pyo3::types::PyAny>,
+        max_depth: usize,
+        sink: &ReportSink,
+    ) -> Result<Self> {
+        Self::from_py_in_context(value, &Context::new(max_depth, sink))
+    }
+
+    fn from_py_in_context(value: pyo3::Bound<pyo3::types::PyAny>, ctx: &Context) -> Result<Self> {
+        if ctx.exceeds_max_depth(&value) {
+            return Ok(Self::PyAny);
+        }
+
         match value {
             // None -> Unknown type
             none if none.is_none() => Ok(Self::Unknown),
@@ -23,19 +109,34 @@ impl TryFrom<pyo3::Bound<'_, pyo3::types::PyAny>> for Type {
                     .to_string()
                     == "typing" =>
             {
-                Self::from_typing(typing)
+                Self::from_typing(typing, ctx)
+            }
+            // `dataclasses.InitVar[T]` is represented by its inner type `T` (it is only passed to the constructor)
+            init_var
+                if init_var.get_type().qualname()? == "InitVar"
+                    && init_var
+                        .get_type()
+                        .getattr(pyo3::intern!(init_var.py(), "__module__"))?
+                        .to_string()
+                        == "dataclasses" =>
+            {
+                Self::from_py_in_context(
+                    init_var.getattr(pyo3::intern!(init_var.py(), "type"))?,
+                    &ctx.nested(),
+                )
             }
             // Handle everything else as string
             _ => {
                 if value.is_instance_of::<pyo3::types::PyString>() {
-                    Self::from_str(
+                    Self::from_str_in_context(
                         value
                             .downcast::<pyo3::types::PyString>()
                             .unwrap()
                             .to_str()?,
+                        ctx,
                     )
                 } else {
-                    Self::from_str(&value.to_string())
+                    Self::from_str_in_context(&value.to_string(), ctx)
                 }
             }
         }
@@ -54,6 +155,10 @@ impl TryFrom<pyo3::Bound<'_, pyo3::types::PyType>> for Type {
             t if t.is_subclass_of::<pyo3::types::PyLong>()? => Self::PyLong,
             t if t.is_subclass_of::<pyo3::types::PyString>()? => Self::PyString,
 
+            // Records (derived from collections)
+            t if is_typed_dict(&t)? => Self::TypedDict(qualified_name(&t)?),
+            t if is_named_tuple(&t)? => Self::NamedTuple(qualified_name(&t)?),
+
             // Collections
             t if t.is_subclass_of::<pyo3::types::PyDict>()? => Self::PyDict {
                 key_type: Box::new(Self::Unknown),
@@ -104,6 +209,10 @@ impl TryFrom<pyo3::Bound<'_, pyo3::types::PyType>> for Type {
             t if t.is_subclass_of::<pyo3::types::PyTraceback>()? => Self::PyTraceback,
             t if t.is_subclass_of::<pyo3::types::PyType>()? => Self::PyType,
 
+            // Enums are named via their module, because their metaclass customizes the representation
+            // (e.g. `<enum 'Color'>`), which lacks the module
+            t if is_enum(&t)? => Self::from_str(&qualified_name(&t)?)?,
+
             // Handle everything else as string
             _ => Self::from_str(&value.to_string())?,
         })
@@ -111,7 +220,7 @@ impl TryFrom<pyo3::Bound<'_, pyo3::types::PyType>> for Type {
 }
 
 impl Type {
-    fn from_typing(value: pyo3::Bound<pyo3::types::PyAny>) -> Result<Self> {
+    fn from_typing(value: pyo3::Bound<pyo3::types::PyAny>, ctx: &Context) -> Result<Self> {
         let py = value.py();
         debug_assert_eq!(
             value
@@ -121,8 +230,43 @@ impl Type {
             "typing"
         );
 
+        // `ForwardRef` is resolved via its evaluated value if available (e.g. after `typing.get_type_hints`),
+        // which refers back to the forward reference itself for self-referential annotations
+        if let Ok(forward_arg) = value.getattr(pyo3::intern!(py, "__forward_arg__")) {
+            let name = forward_arg.to_string();
+            let Some(ctx) = ctx.enter_forward_ref(&name) else {
+                return Ok(Self::PyAny);
+            };
+            let is_evaluated = value
+                .getattr(pyo3::intern!(py, "__forward_evaluated__"))
+                .and_then(|is_evaluated| is_evaluated.is_truthy())
+                .unwrap_or(false);
+            return if is_evaluated {
+                Self::from_py_in_context(
+                    value.getattr(pyo3::intern!(py, "__forward_value__"))?,
+                    &ctx,
+                )
+            } else {
+                Self::from_str_in_context(&name, &ctx)
+            };
+        }
+
+        // `ClassVar[T]` and `Final[T]` are represented by their inner type `T` (see `Type::strip_qualifier`)
+        if let (Some(inner_type), Some(_)) = Self::strip_qualifier(value.clone())? {
+            return Self::from_py_in_context(inner_type, &ctx.nested());
+        }
+
+        // `Annotated[T, ...]` is represented by its base type `T` (its metadata does not affect the type)
+        if value.hasattr(pyo3::intern!(py, "__metadata__"))? {
+            if let Ok(base_type) = value.getattr(pyo3::intern!(py, "__origin__")) {
+                return Self::from_py_in_context(base_type, &ctx.nested());
+            }
+        }
+
         if let Ok(wrapping_type) = value.getattr(pyo3::intern!(py, "__origin__")) {
-            let wrapping_type = Self::try_from(wrapping_type)?;
+            // Note: `ItemsView[K, V]` is represented by a list of `(K, V)` tuples
+            let is_items_view = wrapping_type.to_string() == "<class 'collections.abc.ItemsView'>";
+            let wrapping_type = Self::from_py_in_context(wrapping_type, &ctx.nested())?;
             Ok(
                 if let Ok(inner_types) =
                     value
@@ -133,64 +277,43 @@ impl Type {
                 {
                     let inner_types = inner_types
                         .iter()
-                        .map(Self::try_from)
+                        .map(|inner_type| Self::from_py_in_context(inner_type, &ctx.nested()))
                         .collect::<Result<Vec<_>>>()?;
                     match wrapping_type {
-                        Self::Union(..) => {
-                            if inner_types.len() == 2 && inner_types.contains(&Self::PyNone) {
-                                Self::Optional(Box::new(
-                                    inner_types
-                                        .iter()
-                                        .find(|x| **x != Self::PyNone)
-                                        .unwrap_or_else(|| unreachable!())
-                                        .to_owned(),
-                                ))
-                            } else {
-                                Self::Union(inner_types)
-                            }
-                        }
+                        Self::Union(..) => Self::from_union(inner_types),
                         Self::Optional(..) => {
-                            // debug_assert_eq!(inner_types.len(), 1);
-                            Self::Optional(Box::new(inner_types[0].clone()))
+                            let [inner_type] = expect_inner_types("Optional", inner_types)?;
+                            Self::Optional(Box::new(inner_type))
                         }
                         Self::PyDict { .. } => {
-                            // debug_assert_eq!(inner_types.len(), 2);
+                            let [key_type, value_type] = expect_inner_types("dict", inner_types)?;
                             Self::PyDict {
-                                key_type: Box::new(inner_types[0].clone()),
-                                value_type: Box::new(inner_types[1].clone()),
+                                key_type: Box::new(key_type),
+                                value_type: Box::new(value_type),
                             }
                         }
                         Self::PyFrozenSet(..) => {
-                            // debug_assert_eq!(inner_types.len(), 1);
-                            Self::PyFrozenSet(Box::new(inner_types[0].clone()))
+                            let [inner_type] = expect_inner_types("frozenset", inner_types)?;
+                            Self::PyFrozenSet(Box::new(inner_type))
+                        }
+                        Self::PyList(..) if is_items_view => {
+                            let [key_type, value_type] =
+                                expect_inner_types("ItemsView", inner_types)?;
+                            Self::PyList(Box::new(Self::PyTuple(vec![key_type, value_type])))
                         }
                         Self::PyList(..) => {
-                            // debug_assert_eq!(inner_types.len(), 1);
-                            Self::PyList(Box::new(inner_types[0].clone()))
+                            let [inner_type] = expect_inner_types("list", inner_types)?;
+                            Self::PyList(Box::new(inner_type))
                         }
                         Self::PySet(..) => {
-                            // debug_assert_eq!(inner_types.len(), 1);
-                            Self::PySet(Box::new(inner_types[0].clone()))
+                            let [inner_type] = expect_inner_types("set", inner_types)?;
+                            Self::PySet(Box::new(inner_type))
                         }
                         Self::PyTuple(..) => Self::PyTuple(inner_types),
-                        Self::PyFunction { .. } => {
-                            // debug_assert!(!inner_types.is_empty());
-                            Self::PyFunction {
-                                param_types: match inner_types.len() {
-                                    1 => Vec::default(),
-                                    _ => inner_types[..inner_types.len() - 1].to_owned(),
-                                },
-                                return_annotation: Box::new(
-                                    inner_types
-                                        .last()
-                                        .unwrap_or_else(|| unreachable!())
-                                        .to_owned(),
-                                ),
-                            }
-                        }
+                        Self::PyFunction { .. } => Self::from_callable(inner_types)?,
                         Self::PyType => {
-                            // debug_assert_eq!(inner_types.len(), 1);
-                            inner_types[0].clone()
+                            let [inner_type] = expect_inner_types("type", inner_types)?;
+                            inner_type
                         }
                         _ => {
                             // TODO: Handle other types with inner types if useful (e.g. Generator)
@@ -204,14 +327,68 @@ impl Type {
             )
         } else {
             // Handle everything else as string
-            Type::from_str(&value.to_string())
+            Self::from_str_in_context(&value.to_string(), &ctx.nested())
+        }
+    }
+
+    fn from_union(inner_types: Vec<Self>) -> Self {
+        if inner_types.len() == 2 && inner_types.contains(&Self::PyNone) {
+            if let Some(inner_type) = inner_types.iter().find(|x| **x != Self::PyNone) {
+                return Self::Optional(Box::new(inner_type.to_owned()));
+            }
         }
+        Self::Union(inner_types)
+    }
+
+    fn from_callable(mut inner_types: Vec<Self>) -> Result<Self> {
+        let Some(return_annotation) = inner_types.pop() else {
+            return Err(PyBindgenError::ParseError(
+                "Callable must specify at least its return type".to_owned(),
+            ));
+        };
+        Ok(Self::PyFunction {
+            param_types: inner_types,
+            return_annotation: Box::new(return_annotation),
+        })
     }
 }
 
 impl std::str::FromStr for Type {
     type Err = PyBindgenError;
     fn from_str(value: &str) -> Result<Self> {
+        // Warnings are only printed, as there is no report to which they could be added
+        Self::from_str_with_max_depth(value, DEFAULT_MAX_TYPE_DEPTH, &ReportSink::default())
+    }
+}
+
+impl Type {
+    /// Parse a type annotation given as a string, whose parts nested deeper than `max_depth` are mapped to `Any`.
+    pub(crate) fn from_str_with_max_depth(
+        value: &str,
+        max_depth: usize,
+        sink: &ReportSink,
+    ) -> Result<Self> {
+        Self::from_str_in_context(value, &Context::new(max_depth, sink))
+    }
+
+    fn from_str_in_context(value: &str, ctx: &Context) -> Result<Self> {
+        if ctx.exceeds_max_depth(&value) {
+            return Ok(Self::PyAny);
+        }
+        let nested_ctx = ctx.nested();
+        let from_str = |value: &str| Self::from_str_in_context(value, &nested_ctx);
+        let from_str_sequence = |value: &str| Self::from_str_sequence(value, &nested_ctx);
+        let from_forward_ref = |name: &str| match ctx.enter_forward_ref(name) {
+            Some(ctx) => Self::from_str_in_context(name, &ctx),
+            None => Ok(Self::PyAny),
+        };
+
+        if !has_balanced_brackets(value) {
+            return Err(PyBindgenError::ParseError(format!(
+                "Unbalanced brackets in type annotation '{value}'"
+            )));
+        }
+
         Ok(match value {
             "Any" => Self::PyAny,
 
@@ -224,60 +401,99 @@ impl std::str::FromStr for Type {
             "str" => Self::PyString,
 
             // Enums
-            optional
-                if optional.matches('|').count() == 1 && optional.matches("None").count() == 1 =>
-            {
-                let inner_type = Self::from_str(
-                    optional
-                        .split('|')
-                        .map(str::trim)
-                        .find(|x| *x != "None")
-                        .unwrap_or_else(|| unreachable!()),
+            r#union if split_top_level(r#union, '|').len() > 1 => Self::from_union(
+                split_top_level(r#union, '|')
+                    .into_iter()
+                    .map(from_str)
+                    .collect::<Result<_>>()?,
+            ),
+            optional if optional.starts_with("Optional[") && optional.ends_with(']') => {
+                let [inner_type] = expect_inner_types(
+                    "Optional",
+                    from_str_sequence(
+                        optional
+                            .strip_prefix("Optional[")
+                            .unwrap_or_else(|| unreachable!())
+                            .strip_suffix(']')
+                            .unwrap_or_else(|| unreachable!()),
+                    )?,
                 )?;
                 Self::Optional(Box::new(inner_type))
             }
-            r#union if r#union.contains('|') => {
-                let mut inner_types = r#union
-                    .split('|')
-                    .map(|x| x.trim().to_owned())
-                    .collect_vec();
-                repair_complex_sequence(&mut inner_types, ',');
-                let inner_types = inner_types
-                    .iter()
-                    .map(|x| Self::from_str(x))
-                    .collect::<Result<_>>()?;
-                Self::Union(inner_types)
+            r#union if r#union.starts_with("Union[") && r#union.ends_with(']') => {
+                Self::from_union(from_str_sequence(
+                    r#union
+                        .strip_prefix("Union[")
+                        .unwrap_or_else(|| unreachable!())
+                        .strip_suffix(']')
+                        .unwrap_or_else(|| unreachable!()),
+                )?)
             }
             "Union" => Self::Union(vec![Self::Unknown]),
+            annotated if annotated.starts_with("Annotated[") && annotated.ends_with(']') => {
+                let inner = annotated
+                    .strip_prefix("Annotated[")
+                    .unwrap_or_else(|| unreachable!())
+                    .strip_suffix(']')
+                    .unwrap_or_else(|| unreachable!());
+                match split_top_level(inner, ',').first() {
+                    Some(base_type) if !base_type.is_empty() => from_str(base_type)?,
+                    _ => {
+                        return Err(PyBindgenError::ParseError(format!(
+                            "Type 'Annotated' expects a base type, but none was given in '{value}'"
+                        )))
+                    }
+                }
+            }
+            init_var if strip_generic(init_var, &["dataclasses.InitVar", "InitVar"]).is_some() => {
+                from_str(
+                    strip_generic(init_var, &["dataclasses.InitVar", "InitVar"])
+                        .unwrap_or_else(|| unreachable!()),
+                )?
+            }
+            qualified if strip_generic(qualified, &["ClassVar", "Final"]).is_some() => from_str(
+                strip_generic(qualified, &["ClassVar", "Final"]).unwrap_or_else(|| unreachable!()),
+            )?,
             "" | "None" | "NoneType" => Self::PyNone,
 
             // Collections
             dict if dict.starts_with("dict[") && dict.ends_with(']') => {
-                let mut inner_types = dict
-                    .strip_prefix("dict[")
-                    .unwrap_or_else(|| unreachable!())
-                    .strip_suffix(']')
-                    .unwrap_or_else(|| unreachable!())
-                    .split(',')
-                    .map(|x| x.trim().to_owned())
-                    .collect_vec();
-                repair_complex_sequence(&mut inner_types, ',');
-                // debug_assert_eq!(inner_types.len(), 2);
-                let inner_types = inner_types
-                    .iter()
-                    .map(|x| Self::from_str(x))
-                    .collect::<Result<Vec<_>>>()?;
+                let [key_type, value_type] = expect_inner_types(
+                    "dict",
+                    from_str_sequence(
+                        dict.strip_prefix("dict[")
+                            .unwrap_or_else(|| unreachable!())
+                            .strip_suffix(']')
+                            .unwrap_or_else(|| unreachable!()),
+                    )?,
+                )?;
+                Self::PyDict {
+                    key_type: Box::new(key_type),
+                    value_type: Box::new(value_type),
+                }
+            }
+            "dict" | "Dict" => Self::PyDict {
+                key_type: Box::new(Self::Unknown),
+                value_type: Box::new(Self::Unknown),
+            },
+            mapping if strip_generic(mapping, &DICT_ABCS).is_some() => {
+                let [key_type, value_type] = expect_inner_types(
+                    "Mapping",
+                    from_str_sequence(
+                        strip_generic(mapping, &DICT_ABCS).unwrap_or_else(|| unreachable!()),
+                    )?,
+                )?;
                 Self::PyDict {
-                    key_type: Box::new(inner_types[0].clone()),
-                    value_type: Box::new(inner_types[1].clone()),
+                    key_type: Box::new(key_type),
+                    value_type: Box::new(value_type),
                 }
             }
-            "dict" | "Dict" | "Mapping" => Self::PyDict {
+            mapping if DICT_ABCS.contains(&mapping) => Self::PyDict {
                 key_type: Box::new(Self::Unknown),
                 value_type: Box::new(Self::Unknown),
             },
             frozenset if frozenset.starts_with("frozenset[") && frozenset.ends_with(']') => {
-                let inner_type = Self::from_str(
+                let inner_type = from_str(
                     frozenset
                         .strip_prefix("frozenset[")
                         .unwrap_or_else(|| unreachable!())
@@ -287,7 +503,7 @@ impl std::str::FromStr for Type {
                 Self::PyFrozenSet(Box::new(inner_type))
             }
             list if list.starts_with("list[") && list.ends_with(']') => {
-                let inner_type = Self::from_str(
+                let inner_type = from_str(
                     list.strip_prefix("list[")
                         .unwrap_or_else(|| unreachable!())
                         .strip_suffix(']')
@@ -296,39 +512,27 @@ impl std::str::FromStr for Type {
                 Self::PyList(Box::new(inner_type))
             }
             "list" => Self::PyList(Box::new(Self::Unknown)),
-            sequence if sequence.starts_with("Sequence[") && sequence.ends_with(']') => {
-                let inner_type = Self::from_str(
-                    sequence
-                        .strip_prefix("Sequence[")
-                        .unwrap_or_else(|| unreachable!())
-                        .strip_suffix(']')
-                        .unwrap_or_else(|| unreachable!()),
+            sequence if strip_generic(sequence, &LIST_ABCS).is_some() => {
+                let inner_type = from_str(
+                    strip_generic(sequence, &LIST_ABCS).unwrap_or_else(|| unreachable!()),
                 )?;
                 Self::PyList(Box::new(inner_type))
             }
-            "Sequence" | "Iterable" | "Iterator" => Self::PyList(Box::new(Self::Unknown)),
-            iterable if iterable.starts_with("Iterable[") && iterable.ends_with(']') => {
-                let inner_type = Self::from_str(
-                    iterable
-                        .strip_prefix("Iterable[")
-                        .unwrap_or_else(|| unreachable!())
-                        .strip_suffix(']')
-                        .unwrap_or_else(|| unreachable!()),
+            sequence if LIST_ABCS.contains(&sequence) => Self::PyList(Box::new(Self::Unknown)),
+            items_view if strip_generic(items_view, &["ItemsView"]).is_some() => {
+                let [key_type, value_type] = expect_inner_types(
+                    "ItemsView",
+                    from_str_sequence(
+                        strip_generic(items_view, &["ItemsView"]).unwrap_or_else(|| unreachable!()),
+                    )?,
                 )?;
-                Self::PyList(Box::new(inner_type))
+                Self::PyList(Box::new(Self::PyTuple(vec![key_type, value_type])))
             }
-            iterator if iterator.starts_with("Iterator[") && iterator.ends_with(']') => {
-                let inner_type = Self::from_str(
-                    iterator
-                        .strip_prefix("Iterator[")
-                        .unwrap_or_else(|| unreachable!())
-                        .strip_suffix(']')
-                        .unwrap_or_else(|| unreachable!()),
-                )?;
-                Self::PyList(Box::new(inner_type))
+            "ItemsView" => {
+                Self::PyList(Box::new(Self::PyTuple(vec![Self::Unknown, Self::Unknown])))
             }
             set if set.starts_with("set[") && set.ends_with(']') => {
-                let inner_type = Self::from_str(
+                let inner_type = from_str(
                     set.strip_prefix("set[")
                         .unwrap_or_else(|| unreachable!())
                         .strip_suffix(']')
@@ -336,29 +540,42 @@ impl std::str::FromStr for Type {
                 )?;
                 Self::PySet(Box::new(inner_type))
             }
+            set if strip_generic(set, &SET_ABCS).is_some() => {
+                let inner_type =
+                    from_str(strip_generic(set, &SET_ABCS).unwrap_or_else(|| unreachable!()))?;
+                Self::PySet(Box::new(inner_type))
+            }
+            set if SET_ABCS.contains(&set) => Self::PySet(Box::new(Self::Unknown)),
             tuple if tuple.starts_with("tuple[") && tuple.ends_with(']') => {
-                let mut inner_types = tuple
-                    .strip_prefix("tuple[")
-                    .unwrap_or_else(|| unreachable!())
-                    .strip_suffix(']')
-                    .unwrap_or_else(|| unreachable!())
-                    .split(',')
-                    .map(|x| x.trim().to_owned())
-                    .collect_vec();
-                repair_complex_sequence(&mut inner_types, ',');
-                let inner_types = inner_types
-                    .iter()
-                    .map(|x| Self::from_str(x))
-                    .collect::<Result<_>>()?;
-                Self::PyTuple(inner_types)
+                Self::PyTuple(from_str_sequence(
+                    tuple
+                        .strip_prefix("tuple[")
+                        .unwrap_or_else(|| unreachable!())
+                        .strip_suffix(']')
+                        .unwrap_or_else(|| unreachable!()),
+                )?)
             }
             "tuple" => Self::PyTuple(vec![Self::Unknown]),
 
             // Additional types - std
             "ipaddress.IPv4Address" => Self::IpV4Addr,
             "ipaddress.IPv6Address" => Self::IpV6Addr,
-            "os.PathLike" | "pathlib.Path" => Self::Path,
+            "os.PathLike" | "PathLike" => Self::Path,
+            // Paths of `os.PathLike[bytes]` are raw bytes that are not necessarily valid UTF-8, so they are kept
+            // as bytes (generic `os.PathLike[AnyStr]` is assumed to be a `str` path)
+            pathlike if strip_generic(pathlike, &["os.PathLike", "PathLike"]).is_some() => {
+                match strip_generic(pathlike, &["os.PathLike", "PathLike"])
+                    .unwrap_or_else(|| unreachable!())
+                {
+                    "bytes" => Self::PyBytes,
+                    _ => Self::Path,
+                }
+            }
+            path if WellKnownType::from_type_name(path) == Some(WellKnownType::Path) => Self::Path,
             "slice" => Self::PySlice,
+            "uuid.UUID" => Self::Uuid,
+            "decimal.Decimal" => Self::Decimal,
+            "fractions.Fraction" => Self::Fraction,
 
             // Additional types - num-complex
             "complex" => Self::PyComplex,
@@ -387,31 +604,22 @@ impl std::str::FromStr for Type {
                 return_annotation: Box::new(Self::Unknown),
             },
             callable if callable.starts_with("Callable[") && callable.ends_with(']') => {
-                let mut inner_types = callable
+                let inner = callable
                     .strip_prefix("Callable[")
                     .unwrap_or_else(|| unreachable!())
                     .strip_suffix(']')
-                    .unwrap_or_else(|| unreachable!())
-                    .split(',')
-                    .map(|x| x.trim().to_owned())
-                    .collect_vec();
-                repair_complex_sequence(&mut inner_types, ',');
-                // debug_assert!(!inner_types.is_empty());
-                let inner_types = inner_types
-                    .iter()
-                    .map(|x| Self::from_str(x))
-                    .collect::<Result<Vec<_>>>()?;
-                Self::PyFunction {
-                    param_types: match inner_types.len() {
-                        1 => Vec::default(),
-                        _ => inner_types[..inner_types.len() - 1].to_owned(),
-                    },
-                    return_annotation: Box::new(
-                        inner_types
-                            .last()
-                            .unwrap_or_else(|| unreachable!())
-                            .to_owned(),
-                    ),
+                    .unwrap_or_else(|| unreachable!());
+                match split_top_level(inner, ',').as_slice() {
+                    // The parameters are usually given as a nested list, e.g. `Callable[[int, str], None]`
+                    [params, return_annotation]
+                        if params.starts_with('[') && params.ends_with(']') =>
+                    {
+                        Self::PyFunction {
+                            param_types: from_str_sequence(&params[1..params.len() - 1])?,
+                            return_annotation: Box::new(from_str(return_annotation)?),
+                        }
+                    }
+                    _ => Self::from_callable(from_str_sequence(inner)?)?,
                 }
             }
             "Callable" | "callable" => Self::PyFunction {
@@ -422,7 +630,7 @@ impl std::str::FromStr for Type {
             #[cfg(not(PyPy))]
             "super" => Self::PySuper,
             "traceback" => Self::PyTraceback,
-            typ if typ.starts_with("type[") && typ.ends_with(']') => Self::from_str(
+            typ if typ.starts_with("type[") && typ.ends_with(']') => from_str(
                 typ.strip_prefix("type[")
                     .unwrap_or_else(|| unreachable!())
                     .strip_suffix(']')
@@ -430,7 +638,7 @@ impl std::str::FromStr for Type {
             )?,
 
             // classes
-            class if class.starts_with("<class '") && class.ends_with("'>") => Self::from_str(
+            class if class.starts_with("<class '") && class.ends_with("'>") => from_str(
                 class
                     .strip_prefix("<class '")
                     .unwrap_or_else(|| unreachable!())
@@ -439,7 +647,7 @@ impl std::str::FromStr for Type {
             )?,
 
             // enums
-            enume if enume.starts_with("<enum '") && enume.ends_with("'>") => Self::from_str(
+            enume if enume.starts_with("<enum '") && enume.ends_with("'>") => from_str(
                 enume
                     .strip_prefix("<enum '")
                     .unwrap_or_else(|| unreachable!())
@@ -448,25 +656,45 @@ impl std::str::FromStr for Type {
             )?,
 
             // typing
-            typing if typing.starts_with("typing.") => Self::from_str(
+            typing if typing.starts_with("typing.") => from_str(
                 typing
                     .strip_prefix("typing.")
                     .unwrap_or_else(|| unreachable!()),
             )?,
 
             // collections.abc
-            collections_abc if collections_abc.starts_with("collections.abc.") => Self::from_str(
+            collections_abc if collections_abc.starts_with("collections.abc.") => from_str(
                 collections_abc
                     .strip_prefix("collections.abc.")
                     .unwrap_or_else(|| unreachable!()),
             )?,
             // collections
-            collections if collections.starts_with("collections.") => Self::from_str(
+            collections if collections.starts_with("collections.") => from_str(
                 collections
                     .strip_prefix("collections.")
                     .unwrap_or_else(|| unreachable!()),
             )?,
 
+            // Forward references (e.g. self-referential annotations)
+            forward_ref
+                if forward_ref.starts_with("ForwardRef('") && forward_ref.ends_with("')") =>
+            {
+                from_forward_ref(
+                    forward_ref
+                        .strip_prefix("ForwardRef('")
+                        .unwrap_or_else(|| unreachable!())
+                        .strip_suffix("')")
+                        .unwrap_or_else(|| unreachable!()),
+                )?
+            }
+            quoted
+                if quoted.len() > 2
+                    && ((quoted.starts_with('\'') && quoted.ends_with('\''))
+                        || (quoted.starts_with('"') && quoted.ends_with('"'))) =>
+            {
+                from_forward_ref(&quoted[1..quoted.len() - 1])?
+            }
+
             // Forbidden types
             forbidden if crate::config::FORBIDDEN_TYPE_NAMES.contains(&forbidden) => Self::PyAny,
 
@@ -476,60 +704,698 @@ impl std::str::FromStr for Type {
     }
 }
 
-// TODO: Refactor `repair_complex_sequence()` into something more sensible
-/// Repairs complex wrapped sequences.
-fn repair_complex_sequence(sequence: &mut Vec<String>, separator: char) {
-    // debug_assert!(!sequence.is_empty());
-    // debug_assert!({
-    //     let merged_sequence = sequence.iter().join("");
-    //     merged_sequence.matches('[').count() == merged_sequence.matches(']').count()
-    // });
-
-    let mut traversed_all_elements = false;
-    let mut start_index = 0;
-    'outer: while !traversed_all_elements {
-        traversed_all_elements = true;
-        'inner: for i in start_index..(sequence.len() - 1) {
-            let mut n_scopes = sequence[i].matches('[').count() - sequence[i].matches(']').count();
-            if n_scopes == 0 {
-                continue;
-            }
-            for j in (i + 1)..sequence.len() {
-                n_scopes += sequence[j].matches('[').count();
-                n_scopes -= sequence[j].matches(']').count();
-                if n_scopes == 0 {
-                    let mut new_element = sequence[i].clone();
-                    for relevant_element in sequence.iter().take(j + 1).skip(i + 1) {
-                        new_element = format!("{new_element}{separator}{relevant_element}");
-                    }
-                    sequence[i] = new_element;
-                    sequence.drain((i + 1)..=j);
-                    if j < sequence.len() - 1 {
-                        traversed_all_elements = false;
-                        start_index = i;
-                        break 'inner;
-                    } else {
-                        break 'outer;
-                    }
+impl Type {
+    /// Extract the `repr()` of the metadata of an `Annotated[T, ...]` type annotation (empty for other annotations).
+    pub(crate) fn annotated_metadata(
+        value: &pyo3::Bound<pyo3::types::PyAny>,
+    ) -> Result<Vec<String>> {
+        if let Ok(value) = value.downcast::<pyo3::types::PyString>() {
+            return Ok(Self::annotated_metadata_from_str(value.to_str()?));
+        }
+        match value.getattr(pyo3::intern!(value.py(), "__metadata__")) {
+            Ok(metadata) => metadata
+                .iter()?
+                .map(|item| Ok(item?.repr()?.to_string()))
+                .collect(),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Strip the `ClassVar[T]` or `Final[T]` qualifier of a type annotation (also given as a string), i.e. return
+    /// the inner annotation `T` together with the qualifier. The inner annotation of a bare `ClassVar` or `Final`
+    /// is `None`, in which case the type is inferred from the value. Other annotations are returned unchanged.
+    pub(crate) fn strip_qualifier<'py>(
+        value: pyo3::Bound<'py, pyo3::types::PyAny>,
+    ) -> Result<(
+        Option<pyo3::Bound<'py, pyo3::types::PyAny>>,
+        Option<TypeQualifier>,
+    )> {
+        let py = value.py();
+        let qualifiers = [
+            ("ClassVar", TypeQualifier::ClassVar),
+            ("Final", TypeQualifier::Final),
+        ];
+
+        if let Ok(string) = value.downcast::<pyo3::types::PyString>() {
+            let string = string.to_str()?.trim();
+            let string = string.strip_prefix("typing.").unwrap_or(string);
+            for (name, qualifier) in qualifiers {
+                if string == name {
+                    return Ok((None, Some(qualifier)));
+                }
+                if let Some(inner_type) = strip_generic(string, &[name]) {
+                    return Ok((
+                        Some(pyo3::types::PyString::new_bound(py, inner_type).into_any()),
+                        Some(qualifier),
+                    ));
                 }
             }
+            return Ok((Some(value), None));
+        }
+
+        let typing = py.import_bound(pyo3::intern!(py, "typing"))?;
+        let origin = typing.call_method1(pyo3::intern!(py, "get_origin"), (&value,))?;
+        for (name, qualifier) in qualifiers {
+            let wrapper = typing.getattr(name)?;
+            if value.is(&wrapper) {
+                return Ok((None, Some(qualifier)));
+            }
+            if origin.is(&wrapper) {
+                let inner_type = typing
+                    .call_method1(pyo3::intern!(py, "get_args"), (&value,))?
+                    .get_item(0)?;
+                return Ok((Some(inner_type), Some(qualifier)));
+            }
+        }
+        Ok((Some(value), None))
+    }
+
+    /// Extract the metadata of an `Annotated[T, ...]` type annotation given as a string (empty for other annotations).
+    pub(crate) fn annotated_metadata_from_str(value: &str) -> Vec<String> {
+        value
+            .trim()
+            .strip_prefix("typing.")
+            .unwrap_or(value.trim())
+            .strip_prefix("Annotated[")
+            .and_then(|annotated| annotated.strip_suffix(']'))
+            .map(|inner| {
+                split_top_level(inner, ',')
+                    .into_iter()
+                    .skip(1)
+                    .filter(|x| !x.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parses a comma-separated sequence of types, e.g. the inner types of `tuple[int, str]`.
+    fn from_str_sequence(value: &str, ctx: &Context) -> Result<Vec<Self>> {
+        split_top_level(value, ',')
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .map(|x| Self::from_str_in_context(x, ctx))
+            .collect()
+    }
+}
+
+/// Abstract base classes of `collections.abc` (and their aliases in `typing`) that are represented by `list`.
+/// Note that `Collection`, `Container` and `Reversible` are approximated as `list`, even though they do not
+/// guarantee indexing (e.g. `set` is also a `Collection`).
+/// Check whether the class is an enum, i.e. a subclass of `enum.Enum`.
+pub(crate) fn is_enum(class: &pyo3::Bound<pyo3::types::PyType>) -> Result<bool> {
+    let py = class.py();
+    Ok(class.is_subclass(
+        &py.import_bound(pyo3::intern!(py, "enum"))?
+            .getattr(pyo3::intern!(py, "Enum"))?,
+    )?)
+}
+
+/// Check whether the class is a `typing.TypedDict`, i.e. a subclass of `dict` with `__required_keys__`.
+pub(crate) fn is_typed_dict(class: &pyo3::Bound<pyo3::types::PyType>) -> Result<bool> {
+    Ok(class.is_subclass_of::<pyo3::types::PyDict>()?
+        && class.hasattr(pyo3::intern!(class.py(), "__required_keys__"))?)
+}
+
+/// Check whether the class is a named tuple, i.e. a subclass of `tuple` with `_fields`.
+pub(crate) fn is_named_tuple(class: &pyo3::Bound<pyo3::types::PyType>) -> Result<bool> {
+    Ok(class.is_subclass_of::<pyo3::types::PyTuple>()?
+        && class.hasattr(pyo3::intern!(class.py(), "_fields"))?)
+}
+
+/// Get the name of the class that is qualified by its module.
+fn qualified_name(class: &pyo3::Bound<pyo3::types::PyType>) -> Result<String> {
+    Ok(format!(
+        "{}.{}",
+        class.getattr(pyo3::intern!(class.py(), "__module__"))?,
+        class.qualname()?
+    ))
+}
+
+const LIST_ABCS: [&str; 9] = [
+    "Collection",
+    "Container",
+    "Iterable",
+    "Iterator",
+    "KeysView",
+    "MutableSequence",
+    "Reversible",
+    "Sequence",
+    "ValuesView",
+];
+/// Abstract base classes of `collections.abc` (and their aliases in `typing`) that are represented by `dict`.
+const DICT_ABCS: [&str; 2] = ["Mapping", "MutableMapping"];
+/// Abstract base classes of `collections.abc` (and their aliases in `typing`) that are represented by `set`.
+const SET_ABCS: [&str; 3] = ["AbstractSet", "MutableSet", "Set"];
+
+/// Returns the inner part of a generic type whose name is one of the given `names`, e.g. `int` for `Sequence[int]`.
+fn strip_generic<'a>(value: &'a str, names: &[&str]) -> Option<&'a str> {
+    names.iter().find_map(|name| {
+        value
+            .strip_prefix(name)
+            .and_then(|value| value.strip_prefix('['))
+            .and_then(|value| value.strip_suffix(']'))
+    })
+}
+
+/// Ensures that the given type has exactly `N` inner types.
+fn expect_inner_types<const N: usize>(
+    type_name: &str,
+    inner_types: Vec<Type>,
+) -> Result<[Type; N]> {
+    let n_inner_types = inner_types.len();
+    inner_types.try_into().map_err(|_| {
+        PyBindgenError::ParseError(format!(
+            "Type '{type_name}' expects {N} inner type(s), but {n_inner_types} were given"
+        ))
+    })
+}
+
+/// Splits the given type string at each occurrence of `separator` that is not
+/// nested within brackets, parentheses or quotes. The returned segments are trimmed.
+pub(crate) fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth = depth.saturating_sub(1),
+                c if c == separator && depth == 0 => {
+                    segments.push(value[start..i].trim());
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    segments.push(value[start..].trim());
+    segments
+}
+
+/// Checks that all brackets outside of quotes are properly paired.
+fn has_balanced_brackets(value: &str) -> bool {
+    let mut depth = 0_usize;
+    let mut quote = None;
+    for c in value.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '[' => depth += 1,
+                ']' => {
+                    let Some(new_depth) = depth.checked_sub(1) else {
+                        return false;
+                    };
+                    depth = new_depth;
+                }
+                _ => {}
+            },
         }
     }
+    depth == 0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_split_top_level_nested_dict() {
+        // Arrange
+        let input = "str, tuple[int, dict[str, int]]";
+
+        // Act
+        let segments = split_top_level(input, ',');
+
+        // Assert
+        assert_eq!(segments, ["str", "tuple[int, dict[str, int]]"]);
+    }
+
+    #[test]
+    fn test_split_top_level_callable() {
+        // Arrange
+        let input = "[int, str], None";
+
+        // Act
+        let segments = split_top_level(input, ',');
+
+        // Assert
+        assert_eq!(segments, ["[int, str]", "None"]);
+    }
+
+    #[test]
+    fn test_split_top_level_union() {
+        // Arrange
+        let input = "int | list[int | None]";
+
+        // Act
+        let segments = split_top_level(input, '|');
+
+        // Assert
+        assert_eq!(segments, ["int", "list[int | None]"]);
+    }
+
+    #[test]
+    fn test_split_top_level_quoted() {
+        // Arrange
+        let input = "Literal['[', ','], int";
+
+        // Act
+        let segments = split_top_level(input, ',');
+
+        // Assert
+        assert_eq!(segments, ["Literal['[', ',']", "int"]);
+    }
+
+    #[test]
+    fn test_from_str_nested_dict() {
+        // Act
+        let typ = Type::from_str("dict[str, tuple[int, dict[str, int]]]").unwrap();
+
+        // Assert
+        assert_eq!(
+            typ,
+            Type::PyDict {
+                key_type: Box::new(Type::PyString),
+                value_type: Box::new(Type::PyTuple(vec![
+                    Type::PyLong,
+                    Type::PyDict {
+                        key_type: Box::new(Type::PyString),
+                        value_type: Box::new(Type::PyLong),
+                    },
+                ])),
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_str_collections_abc() {
+        // Arrange
+        let list = |inner_type| Type::PyList(Box::new(inner_type));
+        let set = |inner_type| Type::PySet(Box::new(inner_type));
+        let dict = |key_type, value_type| Type::PyDict {
+            key_type: Box::new(key_type),
+            value_type: Box::new(value_type),
+        };
+        let cases = [
+            ("MutableSequence[int]", list(Type::PyLong)),
+            ("MutableSequence", list(Type::Unknown)),
+            ("Collection[int]", list(Type::PyLong)),
+            ("Container", list(Type::Unknown)),
+            ("Reversible[str]", list(Type::PyString)),
+            ("KeysView[str]", list(Type::PyString)),
+            ("ValuesView[float]", list(Type::PyFloat)),
+            (
+                "collections.abc.Mapping[str, float]",
+                dict(Type::PyString, Type::PyFloat),
+            ),
+            ("MutableMapping", dict(Type::Unknown, Type::Unknown)),
+            ("AbstractSet[int]", set(Type::PyLong)),
+            ("MutableSet", set(Type::Unknown)),
+            ("Set[str]", set(Type::PyString)),
+        ];
+
+        for (annotation, expected) in cases {
+            // Act
+            let typ = Type::from_str(annotation).unwrap();
+
+            // Assert
+            assert_eq!(typ, expected, "{annotation}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_items_view() {
+        // Act
+        let typ = Type::from_str("ItemsView[str, int]").unwrap();
+
+        // Assert
+        assert_eq!(
+            typ,
+            Type::PyList(Box::new(Type::PyTuple(vec![Type::PyString, Type::PyLong])))
+        );
+    }
+
+    #[test]
+    fn test_from_str_callable() {
+        // Act
+        let typ = Type::from_str("Callable[[int, str], None]").unwrap();
+
+        // Assert
+        assert_eq!(
+            typ,
+            Type::PyFunction {
+                param_types: vec![Type::PyLong, Type::PyString],
+                return_annotation: Box::new(Type::PyNone),
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_str_union() {
+        // Act
+        let typ = Type::from_str("int | list[int | None]").unwrap();
+
+        // Assert
+        assert_eq!(
+            typ,
+            Type::Union(vec![
+                Type::PyLong,
+                Type::PyList(Box::new(Type::Optional(Box::new(Type::PyLong)))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_from_str_annotated() {
+        // Arrange
+        let input = "Annotated[list[int], Interval(ge=0, le=10), 'units: seconds']";
+
+        // Act
+        let typ = Type::from_str(input).unwrap();
+        let metadata = Type::annotated_metadata_from_str(input);
+
+        // Assert
+        assert_eq!(typ, Type::PyList(Box::new(Type::PyLong)));
+        assert_eq!(
+            metadata,
+            [
+                "Interval(ge=0, le=10)".to_owned(),
+                "'units: seconds'".to_owned()
+            ]
+        );
+    }
 
     #[test]
-    fn test_repair_complex_sequence() {
+    fn test_from_str_malformed() {
+        for input in [
+            "dict[str]",
+            "dict[str, int",
+            "list[int]]",
+            "Optional[]",
+            "Callable[]",
+            "Annotated[]",
+        ] {
+            assert!(Type::from_str(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_pathlike() {
+        for (annotation, expected) in [
+            ("os.PathLike", Type::Path),
+            ("os.PathLike[str]", Type::Path),
+            ("PathLike[str]", Type::Path),
+            ("os.PathLike[AnyStr]", Type::Path),
+            ("os.PathLike[bytes]", Type::PyBytes),
+            ("PathLike[bytes]", Type::PyBytes),
+        ] {
+            // Act
+            let typ = Type::from_str(annotation).unwrap();
+
+            // Assert
+            assert_eq!(typ, expected, "{annotation}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_init_var() {
+        for (annotation, expected) in [
+            ("dataclasses.InitVar[int]", Type::PyLong),
+            ("InitVar[str]", Type::PyString),
+            ("InitVar[list[int]]", Type::PyList(Box::new(Type::PyLong))),
+        ] {
+            // Act
+            let typ = Type::from_str(annotation).unwrap();
+
+            // Assert
+            assert_eq!(typ, expected, "{annotation}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_qualifiers() {
+        for (annotation, expected) in [
+            ("typing.ClassVar[int]", Type::PyLong),
+            (
+                "ClassVar[Optional[int]]",
+                Type::Optional(Box::new(Type::PyLong)),
+            ),
+            ("Final[str]", Type::PyString),
+        ] {
+            // Act
+            let typ = Type::from_str(annotation).unwrap();
+
+            // Assert
+            assert_eq!(typ, expected, "{annotation}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_forward_ref() {
+        // Arrange
+        let input = "Optional[ForwardRef('Node')]";
+
+        // Act
+        let typ = Type::from_str(input).unwrap();
+
+        // Assert
+        assert_eq!(
+            typ,
+            Type::Optional(Box::new(Type::Other("Node".to_owned())))
+        );
+    }
+
+    #[test]
+    fn test_from_str_exceeding_max_depth() {
+        // Arrange
+        let n = 10 * DEFAULT_MAX_TYPE_DEPTH;
+        let input = format!("{}int{}", "list[".repeat(n), "]".repeat(n));
+
+        // Act
+        let mut typ = Type::from_str(&input).unwrap();
+
+        // Assert
+        let mut depth = 0;
+        while let Type::PyList(inner_type) = typ {
+            typ = *inner_type;
+            depth += 1;
+        }
+        assert_eq!(depth, DEFAULT_MAX_TYPE_DEPTH + 1);
+        assert_eq!(typ, Type::PyAny);
+    }
+
+    #[test]
+    fn test_from_str_exceeding_configured_max_depth() {
+        // Arrange
+        let input = format!("{}int{}", "list[".repeat(100), "]".repeat(100));
+
+        // Act
+        let mut typ = Type::from_str_with_max_depth(&input, 8, &ReportSink::default()).unwrap();
+
+        // Assert
+        let mut depth = 0;
+        while let Type::PyList(inner_type) = typ {
+            typ = *inner_type;
+            depth += 1;
+        }
+        assert_eq!(depth, 9);
+        assert_eq!(typ, Type::PyAny);
+    }
+
+    #[test]
+    fn test_from_str_json_alias() {
+        // Arrange
+        let input = "Union[str, int, None, list['JSON'], dict[str, \"JSON\"], ForwardRef('JSON')]";
+
+        // Act
+        let typ = Type::from_str(input).unwrap();
+
+        // Assert
+        let json = Type::Other("JSON".to_owned());
+        assert_eq!(
+            typ,
+            Type::Union(vec![
+                Type::PyString,
+                Type::PyLong,
+                Type::PyNone,
+                Type::PyList(Box::new(json.clone())),
+                Type::PyDict {
+                    key_type: Box::new(Type::PyString),
+                    value_type: Box::new(json.clone()),
+                },
+                json,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_from_str_self_referential_forward_ref() {
         // Arrange
-        let mut sequence = vec!["dict[str".to_string(), "Any]".to_string()];
+        let sink = ReportSink::default();
+        let ctx = Context::new(DEFAULT_MAX_TYPE_DEPTH, &sink)
+            .enter_forward_ref("JSON")
+            .unwrap();
 
         // Act
-        repair_complex_sequence(&mut sequence, ',');
+        let typ = Type::from_str_in_context("list['JSON']", &ctx).unwrap();
 
         // Assert
-        assert_eq!(sequence, vec!["dict[str,Any]".to_string()]);
+        assert_eq!(typ, Type::PyList(Box::new(Type::PyAny)));
+    }
+
+    /// Strategy that generates well-formed type strings alongside their expected parsed type.
+    fn arb_type() -> impl Strategy<Value = (String, Type)> {
+        let leaf = prop_oneof![
+            Just(("int".to_owned(), Type::PyLong)),
+            Just(("float".to_owned(), Type::PyFloat)),
+            Just(("str".to_owned(), Type::PyString)),
+            Just(("bool".to_owned(), Type::PyBool)),
+            Just(("bytes".to_owned(), Type::PyBytes)),
+            Just(("Any".to_owned(), Type::PyAny)),
+            "My[A-Z][a-z]{0,8}".prop_map(|name| (name.clone(), Type::Other(name))),
+        ];
+        leaf.prop_recursive(4, 32, 3, |inner| {
+            let non_union = inner
+                .clone()
+                .prop_filter("union operands must not be unions", |(_, typ)| {
+                    !matches!(typ, Type::Optional(..) | Type::Union(..))
+                });
+            prop_oneof![
+                inner
+                    .clone()
+                    .prop_map(|(s, t)| (format!("list[{s}]"), Type::PyList(Box::new(t)))),
+                inner
+                    .clone()
+                    .prop_map(|(s, t)| (format!("set[{s}]"), Type::PySet(Box::new(t)))),
+                (inner.clone(), inner.clone()).prop_map(|((ks, kt), (vs, vt))| (
+                    format!("dict[{ks}, {vs}]"),
+                    Type::PyDict {
+                        key_type: Box::new(kt),
+                        value_type: Box::new(vt),
+                    }
+                )),
+                prop::collection::vec(inner.clone(), 1..4).prop_map(|items| {
+                    let (strs, types): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+                    (format!("tuple[{}]", strs.join(", ")), Type::PyTuple(types))
+                }),
+                (prop::collection::vec(inner.clone(), 0..3), inner.clone()).prop_map(
+                    |(params, (rs, rt))| {
+                        let (strs, types): (Vec<_>, Vec<_>) = params.into_iter().unzip();
+                        (
+                            format!("Callable[[{}], {rs}]", strs.join(", ")),
+                            Type::PyFunction {
+                                param_types: types,
+                                return_annotation: Box::new(rt),
+                            },
+                        )
+                    }
+                ),
+                non_union
+                    .clone()
+                    .prop_map(|(s, t)| (format!("{s} | None"), Type::Optional(Box::new(t)))),
+                non_union
+                    .clone()
+                    .prop_map(|(s, t)| (format!("Optional[{s}]"), Type::Optional(Box::new(t)))),
+                (non_union.clone(), non_union).prop_map(|((ls, lt), (rs, rt))| (
+                    format!("{ls} | {rs}"),
+                    Type::Union(vec![lt, rt])
+                )),
+            ]
+        })
+    }
+
+    #[test]
+    fn test_from_py_class_names() {
+        pyo3::prepare_freethreaded_python();
+        pyo3::Python::with_gil(|py| {
+            // Arrange
+            let module = pyo3::types::PyModule::from_code_bound(
+                py,
+                r#"
+import enum
+import typing
+
+class Color(enum.Enum):
+    RED = 1
+
+class Movie(typing.TypedDict):
+    title: str
+
+class Point(typing.NamedTuple):
+    x: int
+
+class Meta(type):
+    def __repr__(cls):
+        return "<meta>"
+
+class Custom(metaclass=Meta):
+    pass
+"#,
+                "mod_class_names.py",
+                "mod_class_names",
+            )
+            .unwrap();
+            let parse = |name: &str| {
+                Type::try_from(
+                    module
+                        .getattr(name)
+                        .unwrap()
+                        .downcast_into::<pyo3::types::PyType>()
+                        .unwrap(),
+                )
+                .unwrap()
+            };
+
+            // Act & Assert
+            assert_eq!(
+                parse("Color"),
+                Type::Other("mod_class_names.Color".to_owned())
+            );
+            assert_eq!(
+                parse("Movie"),
+                Type::TypedDict("mod_class_names.Movie".to_owned())
+            );
+            assert_eq!(
+                parse("Point"),
+                Type::NamedTuple("mod_class_names.Point".to_owned())
+            );
+            assert_eq!(parse("Custom"), Type::Other("<meta>".to_owned()));
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_from_str_roundtrip((input, expected) in arb_type()) {
+            prop_assert_eq!(Type::from_str(&input).unwrap(), expected);
+        }
+
+        #[test]
+        fn proptest_from_str_mutated_never_panics(
+            (input, _) in arb_type(),
+            index in any::<prop::sample::Index>(),
+            insertion in prop::sample::select(vec!["", "[", "]", ",", "|", " ", "'"]),
+        ) {
+            let mut input = input;
+            let position = index.index(input.len() + 1);
+            input.insert_str(position, insertion);
+            if !insertion.is_empty() && position < input.len() - insertion.len() {
+                input.remove(position + insertion.len());
+            }
+            let _ = Type::from_str(&input);
+        }
+
+        #[test]
+        fn proptest_from_str_arbitrary_never_panics(input in "[a-zA-Z.\\[\\]|,' ]{0,48}") {
+            let _ = Type::from_str(&input);
+        }
     }
 }
@@ -1,24 +1,213 @@
-use super::Type;
-use crate::syntax::Path;
+use super::{well_known::WellKnownType, Type};
+use crate::{
+    config::{FloatType, IntType, StringReturnType},
+    syntax::Path,
+    Config,
+};
 use itertools::Itertools;
 use quote::quote;
 use rustc_hash::FxHashMap as HashMap;
 use std::rc::Rc;
 
 impl Type {
-    pub fn into_rs_owned(self, local_types: &HashMap<Path, Path>) -> proc_macro2::TokenStream {
-        let owned = self.into_rs(local_types).owned;
+    pub fn into_rs_owned(
+        self,
+        cfg: &Config,
+        local_types: &HashMap<Path, Path>,
+    ) -> proc_macro2::TokenStream {
+        let owned = self.into_rs(cfg, local_types).owned;
         Rc::into_inner(owned).unwrap_or_else(|| unreachable!())
     }
 
-    pub fn into_rs_borrowed(self, local_types: &HashMap<Path, Path>) -> proc_macro2::TokenStream {
-        let borrowed = self.into_rs(local_types).borrowed;
+    /// Get the Rust type of a returned value, which respects `Config::string_return_type` for (optional) strings.
+    pub fn into_rs_returned(
+        self,
+        cfg: &Config,
+        local_types: &HashMap<Path, Path>,
+    ) -> proc_macro2::TokenStream {
+        let string_type = match cfg.string_return_type {
+            StringReturnType::String => return self.into_rs_owned(cfg, local_types),
+            StringReturnType::BoxStr => quote!(::std::boxed::Box<str>),
+            StringReturnType::CowStr => quote!(::std::borrow::Cow<'static, str>),
+        };
+        match self {
+            Self::PyString => string_type,
+            Self::Optional(inner_type) if *inner_type == Self::PyString => {
+                quote!(::std::option::Option<#string_type>)
+            }
+            _ => self.into_rs_owned(cfg, local_types),
+        }
+    }
+
+    /// Extract the returned `value` into the type given by `into_rs_returned`. Strings are extracted as `String`
+    /// and converted afterwards, because `pyo3` does not extract `Box<str>` and `Cow<'static, str>` directly.
+    pub fn extract_returned(
+        &self,
+        cfg: &Config,
+        value: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            Self::PyString if cfg.string_return_type != StringReturnType::String => quote! {
+                ::pyo3::types::PyAnyMethods::extract::<::std::string::String>(&#value)
+                    .map(::std::convert::Into::into)
+            },
+            Self::Optional(inner_type)
+                if **inner_type == Self::PyString
+                    && cfg.string_return_type != StringReturnType::String =>
+            {
+                quote! {
+                    ::pyo3::types::PyAnyMethods::extract::<::std::option::Option<::std::string::String>>(&#value)
+                        .map(|value| value.map(::std::convert::Into::into))
+                }
+            }
+            Self::Uuid
+            | Self::Decimal
+            | Self::Fraction
+            | Self::EnumMember(..)
+            | Self::NamedTuple(..)
+            | Self::TypedDict(..) => {
+                let extract = self.extract_converted();
+                quote! {
+                    {
+                        let value = #value;
+                        #extract
+                    }
+                }
+            }
+            Self::Optional(inner_type) if inner_type.is_converted() => {
+                let extract = inner_type.extract_converted();
+                quote! {
+                    {
+                        let value = #value;
+                        if ::pyo3::types::PyAnyMethods::is_none(&value) {
+                            ::std::result::Result::Ok(::std::option::Option::None)
+                        } else {
+                            ::std::result::Result::map(#extract, ::std::option::Option::Some)
+                        }
+                    }
+                }
+            }
+            _ => quote! {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &#value
+                )
+            },
+        }
+    }
+
+    pub fn into_rs_borrowed(
+        self,
+        cfg: &Config,
+        local_types: &HashMap<Path, Path>,
+    ) -> proc_macro2::TokenStream {
+        let borrowed = self.into_rs(cfg, local_types).borrowed;
         Rc::into_inner(borrowed).unwrap_or_else(|| unreachable!())
     }
 
+    /// Check whether the type is converted via generated code instead of `FromPyObject` and `IntoPy`,
+    /// i.e. `uuid.UUID`, `decimal.Decimal`, `fractions.Fraction` and the classes that are converted by value
+    /// (or their optional variants).
+    pub(crate) fn is_converted(&self) -> bool {
+        match self {
+            Self::Uuid
+            | Self::Decimal
+            | Self::Fraction
+            | Self::EnumMember(..)
+            | Self::NamedTuple(..)
+            | Self::TypedDict(..) => true,
+            Self::Optional(inner_type) => inner_type.is_converted(),
+            _ => false,
+        }
+    }
+
+    /// Get the Rust type of a type that is nested in a collection. Converted types are kept as Python objects,
+    /// because their conversion cannot be applied to the individual items of the collection.
+    fn into_rs_nested(self, cfg: &Config, local_types: &HashMap<Path, Path>) -> OutputType {
+        if self.is_converted() {
+            Self::PyAny.into_rs(cfg, local_types)
+        } else {
+            self.into_rs(cfg, local_types)
+        }
+    }
+
+    /// Construct the Python object of a converted type from the Rust value of `ident`.
+    fn construct_converted(&self, ident: &syn::Ident) -> proc_macro2::TokenStream {
+        if let Self::EnumMember(..) | Self::NamedTuple(..) | Self::TypedDict(..) = self {
+            return quote!(#ident.into_bound(py)?);
+        }
+        let (module_name, class_name, args) = match self {
+            #[cfg(feature = "uuid")]
+            Self::Uuid => ("uuid", "UUID", quote!((#ident.to_string(),))),
+            #[cfg(not(feature = "uuid"))]
+            Self::Uuid => ("uuid", "UUID", quote!((#ident,))),
+            #[cfg(feature = "rust-decimal")]
+            Self::Decimal => ("decimal", "Decimal", quote!((#ident.to_string(),))),
+            #[cfg(not(feature = "rust-decimal"))]
+            Self::Decimal => ("decimal", "Decimal", quote!((#ident,))),
+            Self::Fraction => ("fractions", "Fraction", quote!((#ident.0, #ident.1))),
+            _ => unreachable!(),
+        };
+        quote! {
+            ::pyo3::types::PyAnyMethods::call1(
+                &::pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(::pyo3::intern!(py, #module_name))?.as_any(),
+                    ::pyo3::intern!(py, #class_name),
+                )?,
+                #args,
+            )?
+        }
+    }
+
+    /// Extract the Rust value of a converted type from the Python object bound to `value`.
+    fn extract_converted(&self) -> proc_macro2::TokenStream {
+        match self {
+            #[cfg(feature = "uuid")]
+            Self::Uuid => quote! {
+                ::uuid::Uuid::parse_str(&::pyo3::types::PyStringMethods::to_cow(
+                    &::pyo3::types::PyAnyMethods::str(&value)?,
+                )?)
+                .map_err(|err| ::pyo3::exceptions::PyValueError::new_err(err.to_string()))
+            },
+            // The fixed-point notation avoids the scientific notation of `str()`, which `rust_decimal` cannot parse
+            #[cfg(feature = "rust-decimal")]
+            Self::Decimal => quote! {
+                <::rust_decimal::Decimal as ::std::str::FromStr>::from_str(&::pyo3::types::PyStringMethods::to_cow(
+                    ::pyo3::types::PyAnyMethods::downcast::<::pyo3::types::PyString>(
+                        &::pyo3::types::PyAnyMethods::call_method1(&value, "__format__", ("f",))?,
+                    )?,
+                )?)
+                .map_err(|err| ::pyo3::exceptions::PyValueError::new_err(err.to_string()))
+            },
+            #[cfg(not(feature = "uuid"))]
+            Self::Uuid => quote! {
+                ::pyo3::types::PyAnyMethods::extract::<::std::string::String>(
+                    ::pyo3::types::PyAnyMethods::str(&value)?.as_any(),
+                )
+            },
+            #[cfg(not(feature = "rust-decimal"))]
+            Self::Decimal => quote! {
+                ::pyo3::types::PyAnyMethods::extract::<::std::string::String>(
+                    ::pyo3::types::PyAnyMethods::str(&value)?.as_any(),
+                )
+            },
+            // Note: Extraction of the numerator and denominator is overflow-checked by PyO3
+            Self::Fraction => quote! {
+                ::std::result::Result::Ok((
+                    ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::getattr(&value, "numerator")?)?,
+                    ::pyo3::types::PyAnyMethods::extract(&::pyo3::types::PyAnyMethods::getattr(&value, "denominator")?)?,
+                ))
+            },
+            Self::EnumMember(..) | Self::NamedTuple(..) | Self::TypedDict(..) => quote! {
+                ::pyo3::types::PyAnyMethods::extract(&value)
+            },
+            _ => unreachable!(),
+        }
+    }
+
     pub fn preprocess_borrowed(
         &self,
         ident: &syn::Ident,
+        cfg: &Config,
         local_types: &HashMap<Path, Path>,
     ) -> proc_macro2::TokenStream {
         match self {
@@ -28,7 +217,7 @@ impl Type {
             } if !key_type.is_hashable()
                 || value_type
                     .clone()
-                    .into_rs(local_types)
+                    .into_rs_nested(cfg, local_types)
                     .owned
                     .to_string()
                     .contains("PyAny") =>
@@ -37,6 +226,22 @@ impl Type {
                     let #ident = ::pyo3::types::IntoPyDict::into_py_dict_bound(#ident, py);
                 }
             }
+            Self::PyLong | Self::PyFloat if cfg.flexible_primitives => {
+                let owned_type = self.clone().into_rs(cfg, local_types).owned;
+                quote! {
+                    let #ident = ::std::convert::Into::<#owned_type>::into(#ident);
+                }
+            }
+            Self::PyString if cfg.flexible_primitives => {
+                quote! {
+                    let #ident = ::std::convert::AsRef::<str>::as_ref(&#ident);
+                }
+            }
+            Self::Path if cfg.flexible_primitives => {
+                quote! {
+                    let #ident = ::std::convert::AsRef::<::std::path::Path>::as_ref(&#ident);
+                }
+            }
             Self::PyTuple(inner_types) if inner_types.len() < 2 => {
                 quote! {
                     let #ident = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyTuple>>::into_py(#ident, py);
@@ -70,6 +275,37 @@ impl Type {
                     let #ident = #ident.bind(py);
                 }
             }
+            Self::Other(type_name)
+                if Self::try_map_external_type(type_name).is_none()
+                    && local_types.contains_key(&Path::from_py(
+                        type_name.split_once('[').map_or(type_name, |s| s.0),
+                    )) =>
+            {
+                let owned_type = self.clone().into_rs(cfg, local_types).owned;
+                quote! {
+                    let #ident = ::std::borrow::Borrow::<#owned_type>::borrow(&#ident);
+                }
+            }
+            Self::Uuid
+            | Self::Decimal
+            | Self::Fraction
+            | Self::EnumMember(..)
+            | Self::NamedTuple(..)
+            | Self::TypedDict(..) => {
+                let construct = self.construct_converted(ident);
+                quote! {
+                    let #ident = #construct;
+                }
+            }
+            Self::Optional(inner_type) if inner_type.is_converted() => {
+                let construct = inner_type.construct_converted(ident);
+                quote! {
+                    let #ident = match #ident {
+                        ::std::option::Option::Some(#ident) => ::std::option::Option::Some(#construct),
+                        ::std::option::Option::None => ::std::option::Option::None,
+                    };
+                }
+            }
             Self::Optional(inner_type) => match inner_type.as_ref() {
                 Self::PyDict {
                     key_type,
@@ -77,7 +313,7 @@ impl Type {
                 } if !key_type.is_hashable()
                     || value_type
                         .clone()
-                        .into_rs(local_types)
+                        .into_rs_nested(cfg, local_types)
                         .owned
                         .to_string()
                         .contains("PyAny") =>
@@ -96,7 +332,7 @@ impl Type {
         }
     }
 
-    fn into_rs(self, local_types: &HashMap<Path, Path>) -> OutputType {
+    fn into_rs(self, cfg: &Config, local_types: &HashMap<Path, Path>) -> OutputType {
         match self {
             Self::PyAny | Self::Unknown => OutputType::new(
                 quote!(::pyo3::Bound<'py, ::pyo3::types::PyAny>),
@@ -107,13 +343,62 @@ impl Type {
             // Primitives
             Self::PyBool => OutputType::new_identical(quote!(bool)),
             Self::PyByteArray | Self::PyBytes => OutputType::new(quote!(Vec<u8>), quote!(&[u8])),
-            Self::PyFloat => OutputType::new_identical(quote!(f64)),
-            Self::PyLong => OutputType::new_identical(quote!(i64)),
-            Self::PyString => OutputType::new(quote!(::std::string::String), quote!(&str)),
+            Self::PyFloat => {
+                let float_type = match cfg.float_type {
+                    FloatType::F32 => quote!(f32),
+                    FloatType::F64 => quote!(f64),
+                };
+                if cfg.flexible_primitives {
+                    OutputType::new(
+                        float_type.clone(),
+                        quote!(impl ::std::convert::Into<#float_type>),
+                    )
+                } else {
+                    OutputType::new_identical(float_type)
+                }
+            }
+            Self::PyLong => {
+                let int_type = match cfg.int_type {
+                    IntType::I64 => quote!(i64),
+                    IntType::I128 => quote!(i128),
+                    #[cfg(feature = "num-bigint")]
+                    IntType::BigInt => {
+                        return if cfg.flexible_primitives {
+                            OutputType::new(
+                                quote!(::num_bigint::BigInt),
+                                quote!(impl ::std::convert::Into<::num_bigint::BigInt>),
+                            )
+                        } else {
+                            OutputType::new(
+                                quote!(::num_bigint::BigInt),
+                                quote!(&::num_bigint::BigInt),
+                            )
+                        };
+                    }
+                };
+                if cfg.flexible_primitives {
+                    OutputType::new(
+                        int_type.clone(),
+                        quote!(impl ::std::convert::Into<#int_type>),
+                    )
+                } else {
+                    OutputType::new_identical(int_type)
+                }
+            }
+            Self::PyString => {
+                if cfg.flexible_primitives {
+                    OutputType::new(
+                        quote!(::std::string::String),
+                        quote!(impl ::std::convert::AsRef<str>),
+                    )
+                } else {
+                    OutputType::new(quote!(::std::string::String), quote!(&str))
+                }
+            }
 
             // Enums
             Self::Optional(inner_type) => {
-                let inner_type = inner_type.into_rs(local_types).owned;
+                let inner_type = inner_type.into_rs(cfg, local_types).owned;
                 OutputType::new_identical(quote!(::std::option::Option<#inner_type>))
             }
             Self::Union(_inner_types) => {
@@ -136,9 +421,9 @@ impl Type {
                 key_type,
                 value_type,
             } => {
-                let value_type = value_type.into_rs(local_types).owned;
+                let value_type = value_type.into_rs_nested(cfg, local_types).owned;
                 if key_type.is_hashable() && !value_type.to_string().contains("PyAny") {
-                    let key_type = key_type.into_rs(local_types).owned;
+                    let key_type = key_type.into_rs_nested(cfg, local_types).owned;
                     OutputType::new(
                         quote!(::std::collections::HashMap<#key_type, #value_type>),
                         quote!(&::std::collections::HashMap<#key_type, #value_type>),
@@ -152,7 +437,7 @@ impl Type {
             }
             Self::PyFrozenSet(inner_type) => {
                 if inner_type.is_hashable() {
-                    let inner_type = inner_type.into_rs(local_types).owned;
+                    let inner_type = inner_type.into_rs_nested(cfg, local_types).owned;
                     OutputType::new(
                         quote!(::std::collections::HashSet<#inner_type>),
                         quote!(&::std::collections::HashSet<#inner_type>),
@@ -165,12 +450,12 @@ impl Type {
                 }
             }
             Self::PyList(inner_type) => {
-                let inner_type = inner_type.into_rs(local_types).owned;
+                let inner_type = inner_type.into_rs_nested(cfg, local_types).owned;
                 OutputType::new(quote!(Vec<#inner_type>), quote!(&[#inner_type]))
             }
             Self::PySet(inner_type) => {
                 if inner_type.is_hashable() {
-                    let inner_type = inner_type.into_rs(local_types).owned;
+                    let inner_type = inner_type.into_rs_nested(cfg, local_types).owned;
                     OutputType::new(
                         quote!(::std::collections::HashSet<#inner_type>),
                         quote!(&::std::collections::HashSet<#inner_type>),
@@ -191,11 +476,11 @@ impl Type {
                 } else if inner_types.len() == 2
                     && *inner_types.last().unwrap_or_else(|| unreachable!()) == Self::PyEllipsis
                 {
-                    Self::PyList(Box::new(inner_types[0].clone())).into_rs(local_types)
+                    Self::PyList(Box::new(inner_types[0].clone())).into_rs(cfg, local_types)
                 } else {
                     let inner_types = inner_types
                         .into_iter()
-                        .map(|inner_type| inner_type.into_rs(local_types).owned)
+                        .map(|inner_type| inner_type.into_rs_nested(cfg, local_types).owned)
                         .collect_vec();
                     OutputType::new_identical(quote!((#(#inner_types),*)))
                 }
@@ -204,12 +489,38 @@ impl Type {
             // Additional types - std
             Self::IpV4Addr => OutputType::new_identical(quote!(::std::net::IpV4Addr)),
             Self::IpV6Addr => OutputType::new_identical(quote!(::std::net::IpV6Addr)),
-            Self::Path => OutputType::new(quote!(::std::path::PathBuf), quote!(&::std::path::Path)),
+            Self::Path => {
+                if cfg.flexible_primitives {
+                    OutputType::new(
+                        quote!(::std::path::PathBuf),
+                        quote!(impl ::std::convert::AsRef<::std::path::Path>),
+                    )
+                } else {
+                    OutputType::new(quote!(::std::path::PathBuf), quote!(&::std::path::Path))
+                }
+            }
             // TODO: Map `PySlice` to `std::ops::Range` if possible
             Self::PySlice => OutputType::new(
                 quote!(::pyo3::Bound<'py, ::pyo3::types::PySlice>),
                 quote!(&::pyo3::Bound<'py, ::pyo3::types::PySlice>),
             ),
+            #[cfg(feature = "uuid")]
+            Self::Uuid => OutputType::new_identical(quote!(::uuid::Uuid)),
+            #[cfg(not(feature = "uuid"))]
+            Self::Uuid => OutputType::new(quote!(::std::string::String), quote!(&str)),
+            #[cfg(feature = "rust-decimal")]
+            Self::Decimal => OutputType::new_identical(quote!(::rust_decimal::Decimal)),
+            #[cfg(not(feature = "rust-decimal"))]
+            Self::Decimal => OutputType::new(quote!(::std::string::String), quote!(&str)),
+            Self::Fraction => OutputType::new_identical(quote!((i64, i64))),
+
+            // Classes that are converted by value via generated code
+            Self::EnumMember(type_name) => {
+                Self::map_by_value_type(&type_name, "Member", local_types)
+            }
+            Self::NamedTuple(type_name) | Self::TypedDict(type_name) => {
+                Self::map_by_value_type(&type_name, "Record", local_types)
+            }
 
             // Additional types - num-complex
             // TODO: Support conversion of `PyComplex` to `num_complex::Complex` if enabled via `num-complex` feature
@@ -304,6 +615,49 @@ impl Type {
         }
     }
 
+    /// Collect the names of all (nested) types that cannot be mapped to a Rust type,
+    /// i.e. types that are neither external types nor any of the `known_types`.
+    pub(crate) fn unmapped_types(&self, known_types: &[Path]) -> Vec<&str> {
+        match self {
+            Self::Other(type_name) => {
+                let type_name_without_delimiters =
+                    type_name.split_once('[').map(|s| s.0).unwrap_or(type_name);
+                if Self::try_map_external_type(type_name).is_none()
+                    && !known_types.contains(&Path::from_py(type_name_without_delimiters))
+                {
+                    vec![type_name]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::Optional(inner_type)
+            | Self::PyFrozenSet(inner_type)
+            | Self::PyList(inner_type)
+            | Self::PySet(inner_type) => inner_type.unmapped_types(known_types),
+            Self::Union(inner_types) | Self::PyTuple(inner_types) => inner_types
+                .iter()
+                .flat_map(|inner_type| inner_type.unmapped_types(known_types))
+                .collect(),
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => key_type
+                .unmapped_types(known_types)
+                .into_iter()
+                .chain(value_type.unmapped_types(known_types))
+                .collect(),
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => param_types
+                .iter()
+                .flat_map(|param_type| param_type.unmapped_types(known_types))
+                .chain(return_annotation.unmapped_types(known_types))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     fn map_type(self, local_types: &HashMap<Path, Path>) -> OutputType {
         // Get the inner name of the type
         let Self::Other(type_name) = self else {
@@ -322,7 +676,7 @@ impl Type {
             let relative_path: syn::Path = relative_path.try_into().unwrap();
             return OutputType::new(
                 quote!(::pyo3::Bound<'py, #relative_path>),
-                quote!(&::pyo3::Bound<'py, #relative_path>),
+                quote!(impl ::std::borrow::Borrow<::pyo3::Bound<'py, #relative_path>>),
             );
         }
 
@@ -333,6 +687,23 @@ impl Type {
         )
     }
 
+    /// Map a class that is converted by value to the generated Rust type with the `suffix`, e.g. `ColorMember`
+    /// for the enum `Color`, which is defined next to the struct of the class.
+    fn map_by_value_type(
+        type_name: &str,
+        suffix: &str,
+        local_types: &HashMap<Path, Path>,
+    ) -> OutputType {
+        let Some(relative_path) = local_types.get(&Path::from_py(type_name)) else {
+            unreachable!("Only generated classes are converted by value")
+        };
+        let mut relative_path: syn::Path = relative_path.try_into().unwrap();
+        if let Some(segment) = relative_path.segments.last_mut() {
+            segment.ident = quote::format_ident!("{}{suffix}", segment.ident);
+        }
+        OutputType::new_identical(quote!(#relative_path))
+    }
+
     fn try_map_external_type(type_name: &str) -> Option<OutputType> {
         // TODO: Handle types from other packages with Rust bindings here
         match type_name {
@@ -363,9 +734,35 @@ impl Type {
                     ),
                 ))
             }
-            _ => None,
+            // Well-known types of the standard library that are kept as Python objects
+            // Note: Their Rust surface is provided via generated helpers and documentation
+            well_known if WellKnownType::from_type_name(well_known).is_some() => {
+                Some(OutputType::new(
+                    quote!(::pyo3::Bound<'py, ::pyo3::types::PyAny>),
+                    quote!(&::pyo3::Bound<'py, ::pyo3::types::PyAny>),
+                ))
+            }
+            // Fixed-width integers of `numpy` and `ctypes`
+            // Note: Extraction of these is overflow-checked by PyO3 (`OverflowError` instead of wrapping)
+            fixed_width_int => Self::try_map_fixed_width_int(fixed_width_int),
         }
     }
+
+    fn try_map_fixed_width_int(type_name: &str) -> Option<OutputType> {
+        let (module_name, int_name) = type_name.rsplit_once('.')?;
+        let int_type = match (module_name, int_name) {
+            ("numpy" | "np", "int8") | ("ctypes", "c_int8" | "c_byte") => quote!(i8),
+            ("numpy" | "np", "int16") | ("ctypes", "c_int16" | "c_short") => quote!(i16),
+            ("numpy" | "np", "int32") | ("ctypes", "c_int32") => quote!(i32),
+            ("numpy" | "np", "int64") | ("ctypes", "c_int64") => quote!(i64),
+            ("numpy" | "np", "uint8") | ("ctypes", "c_uint8" | "c_ubyte") => quote!(u8),
+            ("numpy" | "np", "uint16") | ("ctypes", "c_uint16" | "c_ushort") => quote!(u16),
+            ("numpy" | "np", "uint32") | ("ctypes", "c_uint32") => quote!(u32),
+            ("numpy" | "np", "uint64") | ("ctypes", "c_uint64") => quote!(u64),
+            _ => return None,
+        };
+        Some(OutputType::new_identical(int_type))
+    }
 }
 
 #[derive(Debug, Clone)]
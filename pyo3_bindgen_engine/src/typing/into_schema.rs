@@ -0,0 +1,283 @@
+use super::Type;
+use itertools::Itertools;
+
+impl Type {
+    /// Render the type as a normalized tag that is independent of the generated Rust code,
+    /// e.g. `integer`, `string`, `array<number>`, `object` or `ref:module.Class`.
+    pub fn to_schema_tag(&self) -> String {
+        match self {
+            Self::PyAny | Self::Unknown => "any".to_owned(),
+            Self::Other(type_name) => format!(
+                "ref:{}",
+                type_name
+                    .split_once('[')
+                    .map_or(type_name.as_str(), |s| s.0)
+            ),
+            Self::EnumMember(type_name)
+            | Self::NamedTuple(type_name)
+            | Self::TypedDict(type_name) => {
+                format!("ref:{type_name}")
+            }
+
+            // Primitives
+            Self::PyBool => "boolean".to_owned(),
+            Self::PyByteArray | Self::PyBytes => "bytes".to_owned(),
+            Self::PyFloat => "number".to_owned(),
+            Self::PyLong => "integer".to_owned(),
+            Self::PyString => "string".to_owned(),
+
+            // Enums
+            Self::Optional(inner_type) => format!("nullable<{}>", inner_type.to_schema_tag()),
+            Self::Union(inner_types) => format!(
+                "union<{}>",
+                inner_types.iter().map(Self::to_schema_tag).join(",")
+            ),
+            Self::PyNone => "null".to_owned(),
+
+            // Collections
+            Self::PyDict { .. } => "object".to_owned(),
+            Self::PyFrozenSet(inner_type) | Self::PyList(inner_type) | Self::PySet(inner_type) => {
+                format!("array<{}>", inner_type.to_schema_tag())
+            }
+            // Note: Unparameterized tuples are represented by a single `Unknown` type and variable-length tuples
+            // (`tuple[T, ...]`) by their element type followed by `PyEllipsis`
+            Self::PyTuple(inner_types) => match inner_types.as_slice() {
+                [] | [Self::Unknown] => "array<any>".to_owned(),
+                [inner_type, Self::PyEllipsis] => format!("array<{}>", inner_type.to_schema_tag()),
+                _ => format!(
+                    "tuple<{}>",
+                    inner_types.iter().map(Self::to_schema_tag).join(",")
+                ),
+            },
+
+            // Additional types - std
+            Self::IpV4Addr | Self::IpV6Addr | Self::Path | Self::Uuid | Self::Decimal => {
+                "string".to_owned()
+            }
+            Self::Fraction => "tuple<integer,integer>".to_owned(),
+
+            // Additional types - datetime
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyDate | Self::PyDateTime | Self::PyTime => "string".to_owned(),
+
+            // Python-specific types
+            Self::PyCFunction | Self::PyFunction { .. } => "callable".to_owned(),
+
+            // Other types without a meaningful normalized representation
+            _ => "any".to_owned(),
+        }
+    }
+
+    /// Render the type as a Python type annotation, e.g. `Optional[int]`.
+    pub fn to_py_annotation(&self) -> String {
+        match self {
+            Self::PyAny | Self::Unknown => "Any".to_owned(),
+            Self::Other(type_name)
+            | Self::EnumMember(type_name)
+            | Self::NamedTuple(type_name)
+            | Self::TypedDict(type_name) => type_name.clone(),
+
+            // Primitives
+            Self::PyBool => "bool".to_owned(),
+            Self::PyByteArray => "bytearray".to_owned(),
+            Self::PyBytes => "bytes".to_owned(),
+            Self::PyFloat => "float".to_owned(),
+            Self::PyLong => "int".to_owned(),
+            Self::PyString => "str".to_owned(),
+
+            // Enums
+            Self::Optional(inner_type) => format!("Optional[{}]", inner_type.to_py_annotation()),
+            Self::Union(inner_types) => format!(
+                "Union[{}]",
+                inner_types.iter().map(Self::to_py_annotation).join(", ")
+            ),
+            Self::PyNone => "None".to_owned(),
+
+            // Collections
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => format!(
+                "Dict[{}, {}]",
+                key_type.to_py_annotation(),
+                value_type.to_py_annotation()
+            ),
+            Self::PyFrozenSet(inner_type) => {
+                format!("FrozenSet[{}]", inner_type.to_py_annotation())
+            }
+            Self::PyList(inner_type) => format!("List[{}]", inner_type.to_py_annotation()),
+            Self::PySet(inner_type) => format!("Set[{}]", inner_type.to_py_annotation()),
+            Self::PyTuple(inner_types) => match inner_types.as_slice() {
+                [] | [Self::Unknown] => "Tuple".to_owned(),
+                [inner_type, Self::PyEllipsis] => {
+                    format!("Tuple[{}, ...]", inner_type.to_py_annotation())
+                }
+                _ => format!(
+                    "Tuple[{}]",
+                    inner_types.iter().map(Self::to_py_annotation).join(", ")
+                ),
+            },
+
+            // Additional types - std
+            Self::IpV4Addr => "ipaddress.IPv4Address".to_owned(),
+            Self::IpV6Addr => "ipaddress.IPv6Address".to_owned(),
+            Self::Path => "os.PathLike".to_owned(),
+            Self::PySlice => "slice".to_owned(),
+            Self::Uuid => "uuid.UUID".to_owned(),
+            Self::Decimal => "decimal.Decimal".to_owned(),
+            Self::Fraction => "fractions.Fraction".to_owned(),
+
+            // Additional types - num-complex
+            Self::PyComplex => "complex".to_owned(),
+
+            // Additional types - datetime
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyDate => "datetime.date".to_owned(),
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyDateTime => "datetime.datetime".to_owned(),
+            Self::PyDelta => "datetime.timedelta".to_owned(),
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyTime => "datetime.time".to_owned(),
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyTzInfo => "datetime.tzinfo".to_owned(),
+
+            // Python-specific types
+            Self::PyCapsule => "types.CapsuleType".to_owned(),
+            Self::PyCFunction => "types.BuiltinFunctionType".to_owned(),
+            #[cfg(not(Py_LIMITED_API))]
+            Self::PyCode => "types.CodeType".to_owned(),
+            Self::PyEllipsis => "ellipsis".to_owned(),
+            #[cfg(all(not(Py_LIMITED_API), not(PyPy)))]
+            Self::PyFrame => "types.FrameType".to_owned(),
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => format!(
+                "Callable[[{}], {}]",
+                param_types.iter().map(Self::to_py_annotation).join(", "),
+                return_annotation.to_py_annotation()
+            ),
+            Self::PyModule => "types.ModuleType".to_owned(),
+            #[cfg(not(PyPy))]
+            Self::PySuper => "super".to_owned(),
+            Self::PyTraceback => "types.TracebackType".to_owned(),
+            Self::PyType => "type".to_owned(),
+        }
+    }
+
+    /// Render the type as a schema object with its Python annotation and normalized tag.
+    /// The top-level `Optional` is represented by the `nullable` flag.
+    pub fn to_schema(&self) -> serde_json::Value {
+        let (inner_type, nullable) = match self {
+            Self::Optional(inner_type) => (inner_type.as_ref(), true),
+            _ => (self, false),
+        };
+        serde_json::json!({
+            "annotation": self.to_py_annotation(),
+            "type": inner_type.to_schema_tag(),
+            "nullable": nullable,
+        })
+    }
+
+    /// Render the type as a lossless snapshot, which unlike the Python annotation distinguishes all variants
+    /// that result in different bindings (e.g. `Any` and unknown types).
+    pub(crate) fn to_snapshot(&self) -> serde_json::Value {
+        let snapshots = |types: &[Self]| types.iter().map(Self::to_snapshot).collect::<Vec<_>>();
+        match self {
+            Self::PyAny => "PyAny".into(),
+            Self::Unknown => "Unknown".into(),
+            Self::Other(type_name) => serde_json::json!({ "Other": type_name }),
+            Self::EnumMember(type_name) => serde_json::json!({ "EnumMember": type_name }),
+            Self::NamedTuple(type_name) => serde_json::json!({ "NamedTuple": type_name }),
+            Self::TypedDict(type_name) => serde_json::json!({ "TypedDict": type_name }),
+            Self::Optional(inner_type) => {
+                serde_json::json!({ "Optional": inner_type.to_snapshot() })
+            }
+            Self::Union(inner_types) => serde_json::json!({ "Union": snapshots(inner_types) }),
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => {
+                serde_json::json!({ "PyDict": [key_type.to_snapshot(), value_type.to_snapshot()] })
+            }
+            Self::PyFrozenSet(inner_type) => {
+                serde_json::json!({ "PyFrozenSet": inner_type.to_snapshot() })
+            }
+            Self::PyList(inner_type) => serde_json::json!({ "PyList": inner_type.to_snapshot() }),
+            Self::PySet(inner_type) => serde_json::json!({ "PySet": inner_type.to_snapshot() }),
+            Self::PyTuple(inner_types) => serde_json::json!({ "PyTuple": snapshots(inner_types) }),
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => serde_json::json!({
+                "PyFunction": [snapshots(param_types), return_annotation.to_snapshot()],
+            }),
+            // The annotation of all other types is unique
+            _ => self.to_py_annotation().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_schema_tag() {
+        // Arrange
+        let typ = Type::PyList(Box::new(Type::Optional(Box::new(Type::Other(
+            "other.Class".to_owned(),
+        )))));
+
+        // Act
+        let tag = typ.to_schema_tag();
+        let annotation = typ.to_py_annotation();
+
+        // Assert
+        assert_eq!(tag, "array<nullable<ref:other.Class>>");
+        assert_eq!(annotation, "List[Optional[other.Class]]");
+    }
+
+    #[test]
+    fn test_to_schema_tag_tuples() {
+        // Arrange
+        let cases = [
+            ("tuple[int]", "tuple<integer>", "Tuple[int]"),
+            ("tuple[int, ...]", "array<integer>", "Tuple[int, ...]"),
+            (
+                "tuple[int, str]",
+                "tuple<integer,string>",
+                "Tuple[int, str]",
+            ),
+            ("tuple", "array<any>", "Tuple"),
+        ];
+
+        for (input, expected_tag, expected_annotation) in cases {
+            // Act
+            let typ = <Type as std::str::FromStr>::from_str(input).unwrap();
+
+            // Assert
+            assert_eq!(typ.to_schema_tag(), expected_tag, "{input}");
+            assert_eq!(typ.to_py_annotation(), expected_annotation, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_to_schema_nullable() {
+        // Arrange
+        let typ = Type::Optional(Box::new(Type::PyLong));
+
+        // Act
+        let schema = typ.to_schema();
+
+        // Assert
+        assert_eq!(
+            schema,
+            serde_json::json!({
+                "annotation": "Optional[int]",
+                "type": "integer",
+                "nullable": true,
+            })
+        );
+    }
+}
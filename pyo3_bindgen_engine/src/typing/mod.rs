@@ -1,5 +1,12 @@
+pub(crate) mod from_ast;
 pub(crate) mod from_py;
 pub(crate) mod into_rs;
+pub(crate) mod into_schema;
+pub(crate) mod well_known;
+
+use crate::{syntax::Path, utils::report::ReportSink};
+use itertools::Itertools;
+use rustc_hash::FxHashMap as HashMap;
 
 /// Enum that maps Python types to Rust types.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -36,6 +43,14 @@ pub enum Type {
     IpV6Addr,
     Path,
     PySlice,
+    Uuid,
+    Decimal,
+    Fraction,
+
+    // Classes that are converted by value via generated code (see `Config::emit_into_py_object`)
+    EnumMember(String),
+    NamedTuple(String),
+    TypedDict(String),
 
     // Additional types - num-complex
     PyComplex,
@@ -71,6 +86,15 @@ pub enum Type {
     PyType,
 }
 
+/// Qualifier of a type annotation of a class or module attribute, which wraps the actual type of the attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TypeQualifier {
+    /// `ClassVar[T]`, i.e. the attribute lives on the class rather than on its instances.
+    ClassVar,
+    /// `Final[T]` (or bare `Final`), i.e. the attribute cannot be reassigned.
+    Final,
+}
+
 impl Type {
     fn is_hashable(&self) -> bool {
         matches!(
@@ -87,4 +111,231 @@ impl Type {
                 | Self::PyString
         )
     }
+
+    /// Resolve the names of (nested) types that are neither external types nor any of the `known_types` to the known
+    /// type with the same name (last segment of the path), e.g. classes that are only imported under
+    /// `if TYPE_CHECKING:`. Names that match multiple known types are ambiguous and they are mapped to `Any`.
+    /// Only unqualified names and names within the generated modules are resolved, so that types of other
+    /// packages (e.g. `logging.Logger`) are never bound to a generated type that merely shares their name.
+    pub(crate) fn resolve_by_name(&mut self, known_types: &[Path], sink: &ReportSink) {
+        match self {
+            Self::Other(type_name) => {
+                if Self::Other(type_name.clone())
+                    .unmapped_types(known_types)
+                    .is_empty()
+                {
+                    return;
+                }
+                let (type_name_without_delimiters, delimited) = type_name
+                    .split_once('[')
+                    .map_or((type_name.as_str(), None), |(name, rest)| {
+                        (name, Some(rest))
+                    });
+                let path = Path::from_py(type_name_without_delimiters);
+                let is_resolvable = path.len() == 1
+                    || known_types
+                        .iter()
+                        .any(|known_type| known_type.root() == path.root());
+                if !is_resolvable {
+                    return;
+                }
+                let candidates = known_types
+                    .iter()
+                    .filter(|known_type| known_type.name() == path.name())
+                    .collect_vec();
+                match candidates.as_slice() {
+                    [] => {}
+                    [known_type] => {
+                        *type_name = match delimited {
+                            Some(rest) => format!("{}[{rest}", known_type.to_py()),
+                            None => known_type.to_py(),
+                        };
+                    }
+                    _ => {
+                        sink.warn(
+                            crate::WarningKind::UnmappedType,
+                            &type_name,
+                            format!(
+                                "Type '{type_name}' is ambiguous, as it matches multiple types ({}). It is mapped to 'Any'.",
+                                candidates.iter().map(|known_type| known_type.to_py()).join(", ")
+                            ),
+                        );
+                        *self = Self::PyAny;
+                    }
+                }
+            }
+            Self::Optional(inner_type)
+            | Self::PyFrozenSet(inner_type)
+            | Self::PyList(inner_type)
+            | Self::PySet(inner_type) => inner_type.resolve_by_name(known_types, sink),
+            Self::Union(inner_types) | Self::PyTuple(inner_types) => inner_types
+                .iter_mut()
+                .for_each(|inner_type| inner_type.resolve_by_name(known_types, sink)),
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => {
+                key_type.resolve_by_name(known_types, sink);
+                value_type.resolve_by_name(known_types, sink);
+            }
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => {
+                param_types
+                    .iter_mut()
+                    .for_each(|param_type| param_type.resolve_by_name(known_types, sink));
+                return_annotation.resolve_by_name(known_types, sink);
+            }
+            _ => {}
+        }
+    }
+
+    /// Anchor the names of (nested) types that originate from an unresolvable module (e.g. `__main__`) to the module
+    /// that defines them. Types that remain unresolvable are mapped to `Any`.
+    pub(crate) fn anchor_unresolvable_origins(
+        &mut self,
+        anchored_types: &HashMap<String, String>,
+        sink: &ReportSink,
+    ) {
+        match self {
+            Self::Other(type_name) => {
+                let type_name_without_delimiters =
+                    type_name.split_once('[').map(|s| s.0).unwrap_or(type_name);
+                if let Some(anchored_type) = anchored_types.get(type_name_without_delimiters) {
+                    *type_name = anchored_type.clone();
+                } else if Path::from_py(type_name_without_delimiters).is_unresolvable_origin() {
+                    sink.warn(
+                        crate::WarningKind::UnmappedType,
+                        &type_name,
+                        format!("Type '{type_name}' originates from a module that cannot be resolved. It is mapped to 'Any'."),
+                    );
+                    *self = Self::PyAny;
+                }
+            }
+            Self::Optional(inner_type)
+            | Self::PyFrozenSet(inner_type)
+            | Self::PyList(inner_type)
+            | Self::PySet(inner_type) => {
+                inner_type.anchor_unresolvable_origins(anchored_types, sink)
+            }
+            Self::Union(inner_types) | Self::PyTuple(inner_types) => {
+                inner_types.iter_mut().for_each(|inner_type| {
+                    inner_type.anchor_unresolvable_origins(anchored_types, sink)
+                })
+            }
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => {
+                key_type.anchor_unresolvable_origins(anchored_types, sink);
+                value_type.anchor_unresolvable_origins(anchored_types, sink);
+            }
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => {
+                param_types.iter_mut().for_each(|param_type| {
+                    param_type.anchor_unresolvable_origins(anchored_types, sink)
+                });
+                return_annotation.anchor_unresolvable_origins(anchored_types, sink);
+            }
+            _ => {}
+        }
+    }
+
+    /// Pass the members of enums and the records that are among the `by_value_types` by value if the type is
+    /// annotated directly or as optional (see `Config::emit_into_py_object`). All other records fall back to
+    /// their Python representation, i.e. `dict` for `TypedDict` and `tuple` for named tuples.
+    pub(crate) fn resolve_by_value_types(&mut self, by_value_types: &HashMap<Path, Type>) {
+        let top_level_type = match self {
+            Self::Optional(inner_type) => inner_type.as_mut(),
+            top_level_type => top_level_type,
+        };
+        let by_value_type = match top_level_type {
+            Self::Other(type_name) | Self::NamedTuple(type_name) | Self::TypedDict(type_name) => {
+                by_value_types.get(&Path::from_py(type_name)).cloned()
+            }
+            _ => None,
+        };
+        match by_value_type {
+            Some(by_value_type) => *top_level_type = by_value_type,
+            None => top_level_type.fall_back_records(),
+        }
+    }
+
+    /// Replace all (nested) records with their Python representation.
+    fn fall_back_records(&mut self) {
+        match self {
+            Self::NamedTuple(..) => *self = Self::PyTuple(vec![Self::Unknown]),
+            Self::TypedDict(..) => {
+                *self = Self::PyDict {
+                    key_type: Box::new(Self::Unknown),
+                    value_type: Box::new(Self::Unknown),
+                }
+            }
+            Self::Optional(inner_type)
+            | Self::PyFrozenSet(inner_type)
+            | Self::PyList(inner_type)
+            | Self::PySet(inner_type) => inner_type.fall_back_records(),
+            Self::Union(inner_types) | Self::PyTuple(inner_types) => {
+                inner_types.iter_mut().for_each(Self::fall_back_records)
+            }
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => {
+                key_type.fall_back_records();
+                value_type.fall_back_records();
+            }
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => {
+                param_types.iter_mut().for_each(Self::fall_back_records);
+                return_annotation.fall_back_records();
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolve the aliases of imported modules in the names of (nested) types, e.g. `np.ndarray` is resolved
+    /// to `numpy.ndarray` if the module contains `import numpy as np`.
+    pub(crate) fn resolve_module_aliases(&mut self, module_aliases: &HashMap<String, String>) {
+        match self {
+            Self::Other(type_name) => {
+                if let Some(origin) = type_name
+                    .split_once('.')
+                    .and_then(|(alias, _)| module_aliases.get(alias))
+                {
+                    let (_, name) = type_name.split_once('.').unwrap_or_else(|| unreachable!());
+                    *type_name = format!("{origin}.{name}");
+                }
+            }
+            Self::Optional(inner_type)
+            | Self::PyFrozenSet(inner_type)
+            | Self::PyList(inner_type)
+            | Self::PySet(inner_type) => inner_type.resolve_module_aliases(module_aliases),
+            Self::Union(inner_types) | Self::PyTuple(inner_types) => inner_types
+                .iter_mut()
+                .for_each(|inner_type| inner_type.resolve_module_aliases(module_aliases)),
+            Self::PyDict {
+                key_type,
+                value_type,
+            } => {
+                key_type.resolve_module_aliases(module_aliases);
+                value_type.resolve_module_aliases(module_aliases);
+            }
+            Self::PyFunction {
+                param_types,
+                return_annotation,
+            } => {
+                param_types
+                    .iter_mut()
+                    .for_each(|param_type| param_type.resolve_module_aliases(module_aliases));
+                return_annotation.resolve_module_aliases(module_aliases);
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,94 @@
+//! Well-known types of the Python standard library that are mapped to more specific Rust surfaces.
+
+use super::Type;
+
+/// Well-known type of the Python standard library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WellKnownType {
+    /// `logging.Logger`, for which logging helpers can be generated (see `Config::logging_helpers`).
+    Logger,
+    /// `re.Pattern`, for which `search`, `match` and `findall` helpers with string arguments are generated.
+    Pattern,
+    /// `pathlib.Path` and its flavours, which are mapped to `std::path::PathBuf`.
+    Path,
+    /// `decimal.Decimal`, which is mapped to `rust_decimal::Decimal` (with the `rust-decimal` feature) or to its exact
+    /// string representation.
+    Decimal,
+}
+
+/// Full Python names of the well-known types.
+const WELL_KNOWN_TYPES: &[(&str, WellKnownType)] = &[
+    ("logging.Logger", WellKnownType::Logger),
+    ("logging.RootLogger", WellKnownType::Logger),
+    ("re.Pattern", WellKnownType::Pattern),
+    ("pathlib.Path", WellKnownType::Path),
+    ("pathlib.PosixPath", WellKnownType::Path),
+    ("pathlib.WindowsPath", WellKnownType::Path),
+    ("pathlib.PurePath", WellKnownType::Path),
+    ("pathlib.PurePosixPath", WellKnownType::Path),
+    ("pathlib.PureWindowsPath", WellKnownType::Path),
+    ("decimal.Decimal", WellKnownType::Decimal),
+];
+
+impl WellKnownType {
+    /// Look up the well-known type by its full Python name, e.g. `logging.Logger`.
+    pub(crate) fn from_type_name(type_name: &str) -> Option<Self> {
+        WELL_KNOWN_TYPES
+            .iter()
+            .find(|(name, _)| *name == type_name)
+            .map(|(_, typ)| *typ)
+    }
+
+    /// Note that is added to the documentation of items that return the type (if any).
+    pub(crate) fn doc_note(self) -> Option<&'static str> {
+        match self {
+            #[cfg(not(feature = "rust-decimal"))]
+            Self::Decimal => Some("Note: The `decimal.Decimal` value is returned as its exact string representation in order to preserve its precision."),
+            #[cfg(feature = "rust-decimal")]
+            Self::Decimal => None,
+            Self::Logger | Self::Pattern | Self::Path => None,
+        }
+    }
+}
+
+impl Type {
+    /// Get the well-known type of the standard library that this type refers to (if any).
+    pub(crate) fn well_known(&self) -> Option<WellKnownType> {
+        match self {
+            Self::Other(type_name) => WellKnownType::from_type_name(type_name),
+            Self::Decimal => Some(WellKnownType::Decimal),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_well_known_types() {
+        // Arrange
+        let type_names = [
+            "<class 'logging.Logger'>",
+            "<class 're.Pattern'>",
+            "<class 'pathlib.PosixPath'>",
+            "<class 'decimal.Decimal'>",
+            "<class 'logging.Handler'>",
+        ];
+
+        // Act
+        let types = type_names
+            .map(|type_name| Type::from_str(type_name).unwrap())
+            .map(|typ| (typ.well_known(), typ));
+
+        // Assert
+        assert_eq!(types[0].0, Some(WellKnownType::Logger));
+        assert_eq!(types[1].0, Some(WellKnownType::Pattern));
+        assert_eq!(types[2].1, Type::Path);
+        assert_eq!(types[3].0, Some(WellKnownType::Decimal));
+        assert_eq!(types[3].1, Type::Decimal);
+        assert_eq!(types[4].0, None);
+    }
+}
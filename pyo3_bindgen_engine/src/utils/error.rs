@@ -1,5 +1,8 @@
 /// Error type for `pyo3_bindgen` operations.
+///
+/// New variants might be added in any release, so matching on this error must include a wildcard arm.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum PyBindgenError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
@@ -13,8 +16,33 @@ pub enum PyBindgenError {
     ParseError(String),
     #[error("Failed to generate Rust code: {0}")]
     CodegenError(String),
+    #[doc(hidden)]
     #[error(transparent)]
     Infallible(#[from] std::convert::Infallible),
+    /// The Python module could not be imported by the interpreter.
+    #[error("Failed to import Python module '{module}': {source}")]
+    ModuleImportFailed {
+        module: String,
+        #[source]
+        source: pyo3::PyErr,
+    },
+    /// An attribute of a Python module or class could not be parsed.
+    #[error("Failed to parse Python attribute '{path}': {source}")]
+    AttributeParseFailed {
+        path: String,
+        #[source]
+        source: Box<PyBindgenError>,
+    },
+    /// The generated Rust code is not syntactically valid.
+    #[error("Failed to generate syntactically valid Rust code: {span_context}")]
+    CodegenSyntaxError { span_context: String },
+    /// The installed version of a Python package does not satisfy the required version.
+    #[error("Installed version '{found}' of Python package '{package}' does not satisfy the requirement '{required}'")]
+    VersionMismatch {
+        package: String,
+        required: String,
+        found: String,
+    },
 }
 
 impl From<pyo3::PyDowncastError<'_>> for PyBindgenError {
@@ -22,3 +50,144 @@ impl From<pyo3::PyDowncastError<'_>> for PyBindgenError {
         pyo3::PyErr::from(value).into()
     }
 }
+
+impl PyBindgenError {
+    /// Attach the path of the attribute that failed to parse to the error.
+    /// Errors that already carry the path of a (more specific) attribute are returned unchanged.
+    pub(crate) fn in_attribute(self, path: &crate::syntax::Path) -> Self {
+        match self {
+            Self::AttributeParseFailed { .. } => self,
+            source => Self::AttributeParseFailed {
+                path: path.to_py(),
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// Create an error for generated Rust `code` that failed to parse, which includes the byte offset of the
+    /// error and a snippet of the surrounding code.
+    pub(crate) fn codegen_syntax_error(code: &str, err: &syn::Error) -> Self {
+        /// Number of bytes of the surrounding code that are included on each side of the error.
+        const SNIPPET_RADIUS: usize = 40;
+
+        let start = err.span().start();
+        let offset = code
+            .split_inclusive('\n')
+            .take(start.line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>()
+            + code
+                .lines()
+                .nth(start.line.saturating_sub(1))
+                .unwrap_or_default()
+                .chars()
+                .take(start.column)
+                .map(char::len_utf8)
+                .sum::<usize>();
+        let floor_char_boundary = |mut index: usize| {
+            index = index.min(code.len());
+            while !code.is_char_boundary(index) {
+                index -= 1;
+            }
+            index
+        };
+        let snippet = &code[floor_char_boundary(offset.saturating_sub(SNIPPET_RADIUS))
+            ..floor_char_boundary(offset + SNIPPET_RADIUS)];
+        Self::CodegenSyntaxError {
+            span_context: format!("{err} at byte {offset} near `{snippet}`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_import_failed() {
+        // Arrange
+        pyo3::prepare_freethreaded_python();
+
+        // Act
+        let err = crate::Codegen::default()
+            .module_name("pyo3_bindgen_nonexistent_module")
+            .unwrap_err();
+
+        // Assert
+        assert!(
+            matches!(&err, PyBindgenError::ModuleImportFailed { module, .. } if module == "pyo3_bindgen_nonexistent_module")
+        );
+        assert!(err
+            .to_string()
+            .starts_with("Failed to import Python module 'pyo3_bindgen_nonexistent_module': "));
+    }
+
+    #[test]
+    fn test_attribute_parse_failed() {
+        // Arrange
+        let path = crate::syntax::Path::from_py("mod.MyClass.my_method");
+
+        // Act
+        let err = PyBindgenError::ParseError("Unbalanced brackets".to_owned())
+            .in_attribute(&path)
+            .in_attribute(&crate::syntax::Path::from_py("mod.MyClass"));
+
+        // Assert
+        assert!(matches!(
+            &err,
+            PyBindgenError::AttributeParseFailed { path, source }
+                if path == "mod.MyClass.my_method" && matches!(**source, PyBindgenError::ParseError(..))
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse Python attribute 'mod.MyClass.my_method': Failed to parse Python code: Unbalanced brackets"
+        );
+    }
+
+    #[test]
+    fn test_codegen_syntax_error() {
+        // Act
+        let err = PyBindgenError::CodegenSyntaxError {
+            span_context: "pub mod 3d".to_owned(),
+        };
+
+        // Assert
+        assert!(
+            matches!(&err, PyBindgenError::CodegenSyntaxError { span_context } if span_context == "pub mod 3d")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Failed to generate syntactically valid Rust code: pub mod 3d"
+        );
+    }
+
+    #[test]
+    fn test_codegen_syntax_error_context() {
+        // Arrange
+        let code = "pub mod valid {}\npub mod 3d {}\n";
+        let Err(err) = syn::parse_file(code) else {
+            unreachable!()
+        };
+
+        // Act
+        let err = PyBindgenError::codegen_syntax_error(code, &err);
+
+        // Assert
+        assert_eq!(
+            err.to_string(),
+            "Failed to generate syntactically valid Rust code: expected identifier at byte 25 near `pub mod valid {}\npub mod 3d {}\n`"
+        );
+    }
+
+    #[test]
+    fn test_display_preserved() {
+        assert_eq!(
+            PyBindgenError::CodegenError("reason".to_owned()).to_string(),
+            "Failed to generate Rust code: reason"
+        );
+        assert_eq!(
+            PyBindgenError::ParseError("reason".to_owned()).to_string(),
+            "Failed to parse Python code: reason"
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! State of incremental code generation that is persisted between runs.
+
+use crate::{syntax::Module, Config};
+use itertools::Itertools;
+use pyo3::prelude::*;
+use rustc_hash::FxHasher;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::Hasher,
+};
+
+/// Name of the state file that is stored next to the generated bindings.
+pub(crate) const STATE_FILE_NAME: &str = ".pyo3_bindgen_state";
+
+/// Header of the state file (invalidated with each version of the engine).
+const STATE_FILE_HEADER: &str = concat!("pyo3_bindgen_engine ", env!("CARGO_PKG_VERSION"));
+
+/// Fingerprints of the inputs that were used to generate the bindings of each top-level module.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct IncrementalState {
+    pub config_hash: u64,
+    pub modules: BTreeMap<String, ModuleState>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ModuleState {
+    /// Fingerprint of the source files of the module, which is `None` for modules without source files
+    /// (e.g. built-in modules) or with inputs that are not imported (e.g. modules added from their AST).
+    pub source_hash: Option<u64>,
+    /// Fingerprint of the parsed content of the module (incl. embedded source code).
+    pub content_hash: u64,
+    /// Names of other top-level modules that the module references via imports or type annotations.
+    pub dependencies: Vec<String>,
+    /// Names (last segments) of the types that the module defines.
+    pub defined_types: BTreeSet<String>,
+    /// Names (last segments) of the types that the module references in type annotations.
+    pub referenced_types: BTreeSet<String>,
+}
+
+impl IncrementalState {
+    /// Load the state from a file. Returns `None` if the file is missing or corrupt.
+    pub fn load(path: &std::path::Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let state = Self::parse(&content);
+        if state.is_none() {
+            eprintln!(
+                "WARN: State file '{}' is corrupt. All modules are regenerated.",
+                path.display()
+            );
+        }
+        state
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let mut content = format!("{STATE_FILE_HEADER}\nconfig {:016x}\n", self.config_hash);
+        for (name, module) in &self.modules {
+            content.push_str(&format!(
+                "module {name} {} {:016x} {} {} {}\n",
+                module.source_hash.map_or_else(
+                    || "-".to_owned(),
+                    |source_hash| format!("{source_hash:016x}")
+                ),
+                module.content_hash,
+                module.dependencies.join(","),
+                module.defined_types.iter().join(","),
+                module.referenced_types.iter().join(","),
+            ));
+        }
+        write_file(path, content)
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        if lines.next()? != STATE_FILE_HEADER {
+            return None;
+        }
+        let config_hash = u64::from_str_radix(lines.next()?.strip_prefix("config ")?, 16).ok()?;
+        let modules = lines
+            .map(|line| {
+                let mut fields = line.strip_prefix("module ")?.split(' ');
+                let name = fields.next()?.to_owned();
+                let source_hash = match fields.next()? {
+                    "-" => None,
+                    source_hash => Some(u64::from_str_radix(source_hash, 16).ok()?),
+                };
+                let content_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+                let mut next_list = || {
+                    fields.next().map(|list| {
+                        list.split(',')
+                            .filter(|name| !name.is_empty())
+                            .map(str::to_owned)
+                    })
+                };
+                let dependencies = next_list()?.collect();
+                let defined_types = next_list()?.collect();
+                let referenced_types = next_list()?.collect();
+                if fields.next().is_some() {
+                    return None;
+                }
+                Some((
+                    name,
+                    ModuleState {
+                        source_hash,
+                        content_hash,
+                        dependencies,
+                        defined_types,
+                        referenced_types,
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            config_hash,
+            modules,
+        })
+    }
+
+    /// Determine the names of all modules that the module with the given name transitively depends on.
+    pub fn dependency_closure(&self, name: &str) -> BTreeSet<String> {
+        let mut closure = BTreeSet::new();
+        let mut queue = vec![name];
+        while let Some(name) = queue.pop() {
+            for dependency in self
+                .modules
+                .get(name)
+                .map(|module| module.dependencies.as_slice())
+                .unwrap_or_default()
+            {
+                if dependency != name && closure.insert(dependency.clone()) {
+                    queue.push(dependency);
+                }
+            }
+        }
+        closure
+    }
+}
+
+/// Write the content to a file via a temporary file in the same directory, so that an interrupted or failed
+/// write never leaves a truncated file behind. The temporary file is removed if the write fails.
+pub(crate) fn write_file(path: &std::path::Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut temp_file_name = path.file_name().unwrap_or_default().to_owned();
+    temp_file_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_file_name);
+    std::fs::write(&temp_path, content)
+        .and_then(|()| std::fs::rename(&temp_path, path))
+        .map_err(|err| {
+            let _ = std::fs::remove_file(&temp_path);
+            err
+        })
+}
+
+/// Compute the fingerprint of the configuration.
+pub(crate) fn hash_config(cfg: &Config) -> u64 {
+    let mut hasher = FxHasher::default();
+    std::hash::Hash::hash(cfg, &mut hasher);
+    hasher.finish()
+}
+
+/// Compute the fingerprint of the source files of a module that was added to the engine (all files within
+/// the directories of a package), which is available without parsing the module. The embedded source code
+/// is used instead for modules that were added from their source code. Returns `None` if the module has no
+/// source files (e.g. built-in modules).
+pub(crate) fn hash_module_source(
+    module: &pyo3::Bound<pyo3::types::PyModule>,
+    embedded_source_code: Option<&str>,
+) -> Option<u64> {
+    fn hash_path_recursive(hasher: &mut FxHasher, path: &std::path::Path) -> std::io::Result<()> {
+        hasher.write(path.as_os_str().as_encoded_bytes());
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            entries.sort_unstable();
+            entries
+                .iter()
+                .filter(|entry| entry.file_name().is_some_and(|name| name != "__pycache__"))
+                .try_for_each(|entry| hash_path_recursive(hasher, entry))
+        } else {
+            hasher.write(&std::fs::read(path)?);
+            Ok(())
+        }
+    }
+
+    let py = module.py();
+    let mut hasher = FxHasher::default();
+    hasher.write(module.name().ok()?.to_string().as_bytes());
+    // The parsed content also depends on the version of the interpreter (e.g. of the `typing` module)
+    hasher.write(
+        py.import_bound(pyo3::intern!(py, "sys"))
+            .and_then(|sys| sys.getattr(pyo3::intern!(py, "version")))
+            .ok()?
+            .to_string()
+            .as_bytes(),
+    );
+    if let Some(source_code) = embedded_source_code {
+        hasher.write(source_code.as_bytes());
+        return Some(hasher.finish());
+    }
+    let paths = if let Ok(package_paths) = module.getattr(pyo3::intern!(py, "__path__")) {
+        package_paths
+            .iter()
+            .and_then(|paths| paths.map(|path| path?.extract()).collect())
+            .ok()?
+    } else {
+        vec![module
+            .getattr(pyo3::intern!(py, "__file__"))
+            .and_then(|path| path.extract::<std::path::PathBuf>())
+            .ok()?]
+    };
+    if paths.is_empty() {
+        return None;
+    }
+    paths
+        .iter()
+        .try_for_each(|path: &std::path::PathBuf| hash_path_recursive(&mut hasher, path))
+        .ok()?;
+    Some(hasher.finish())
+}
+
+/// Combine the fingerprints of multiple inputs (irrespective of their order). Returns `None` if any of
+/// the fingerprints is missing.
+pub(crate) fn combine_hashes(hashes: impl IntoIterator<Item = Option<u64>>) -> Option<u64> {
+    let mut hashes = hashes.into_iter().collect::<Option<Vec<_>>>()?;
+    hashes.sort_unstable();
+    let mut hasher = FxHasher::default();
+    hashes.iter().for_each(|hash| hasher.write_u64(*hash));
+    Some(hasher.finish())
+}
+
+/// Compute the fingerprint of the parsed content of the module and its submodules from the serialized
+/// `Module::snapshot`, which covers everything that the generated bindings depend on (incl. default values that
+/// are not part of `Hash`), whereas changes that do not affect the bindings (e.g. function bodies) are not reflected.
+pub(crate) fn hash_module_content(module: &Module) -> u64 {
+    let mut hasher = FxHasher::default();
+    hasher.write(module.snapshot().to_string().as_bytes());
+    hasher.finish()
+}
+
+/// Determine the names of all types that the module and its submodules reference in type annotations.
+fn annotated_type_names(module: &Module) -> Vec<String> {
+    let annotations = module
+        .functions
+        .iter()
+        .flat_map(|function| function.annotations())
+        .chain(
+            module
+                .properties
+                .iter()
+                .flat_map(|property| property.annotations()),
+        )
+        .chain(module.classes.iter().flat_map(|class| {
+            class
+                .methods()
+                .iter()
+                .flat_map(|method| method.annotations())
+                .chain(
+                    class
+                        .properties()
+                        .iter()
+                        .flat_map(|property| property.annotations()),
+                )
+        }));
+    annotations
+        .flat_map(|annotation| annotation.unmapped_types(&[]))
+        .map(|type_name| {
+            type_name
+                .split_once('[')
+                .map_or(type_name, |s| s.0)
+                .to_owned()
+        })
+        .chain(module.submodules.iter().flat_map(annotated_type_names))
+        .collect()
+}
+
+/// Determine the names of all other `top_level_modules` that the module references via imports or type annotations.
+pub(crate) fn module_dependencies(module: &Module, top_level_modules: &[String]) -> Vec<String> {
+    fn import_roots_recursive(module: &Module, roots: &mut Vec<String>) {
+        roots.extend(
+            module
+                .imports
+                .iter()
+                .filter_map(|import| import.origin.root())
+                .map(|root| root.to_py()),
+        );
+        module
+            .submodules
+            .iter()
+            .for_each(|submodule| import_roots_recursive(submodule, roots));
+    }
+
+    let mut roots = annotated_type_names(module)
+        .into_iter()
+        .filter_map(|type_name| {
+            crate::syntax::Path::from_py(&type_name)
+                .root()
+                .map(|root| root.to_py())
+        })
+        .collect();
+    import_roots_recursive(module, &mut roots);
+    let own_root = module.name.root().map(|root| root.to_py());
+    let mut dependencies = top_level_modules
+        .iter()
+        .filter(|name| Some(*name) != own_root.as_ref() && roots.contains(name))
+        .cloned()
+        .collect::<Vec<_>>();
+    dependencies.sort_unstable();
+    dependencies.dedup();
+    dependencies
+}
+
+/// Determine the names (last segments) of all types that the module references in type annotations, which might
+/// be resolved to the types of other modules only by their name (see `Config::fuzzy_type_resolution`).
+pub(crate) fn referenced_type_names(module: &Module) -> BTreeSet<String> {
+    annotated_type_names(module)
+        .into_iter()
+        .map(|type_name| {
+            crate::syntax::Path::from_py(&type_name)
+                .name()
+                .as_py()
+                .to_owned()
+        })
+        .filter(|name| is_listable(name))
+        .collect()
+}
+
+/// Determine the names (last segments) of all types that are defined in the module and its submodules.
+pub(crate) fn defined_type_names(module: &Module) -> BTreeSet<String> {
+    module
+        .classes
+        .iter()
+        .map(|class| &class.name)
+        .chain(module.type_vars.iter().map(|type_var| &type_var.name))
+        .map(|name| name.name().as_py().to_owned())
+        .filter(|name| is_listable(name))
+        .chain(module.submodules.iter().flat_map(defined_type_names))
+        .collect()
+}
+
+/// Names are stored in comma-separated lists of the state file, so they must not contain any delimiters.
+fn is_listable(name: &str) -> bool {
+    !name.is_empty() && !name.contains([' ', ',', '\n'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_roundtrip() {
+        // Arrange
+        let state = IncrementalState {
+            config_hash: 42,
+            modules: [
+                (
+                    "a".to_owned(),
+                    ModuleState {
+                        source_hash: Some(2),
+                        content_hash: 1,
+                        dependencies: vec!["b".to_owned()],
+                        defined_types: ["A".to_owned(), "T".to_owned()].into(),
+                        referenced_types: ["B".to_owned()].into(),
+                    },
+                ),
+                ("b".to_owned(), ModuleState::default()),
+            ]
+            .into(),
+        };
+        let path = std::env::temp_dir().join(format!(
+            "pyo3_bindgen_test_state_roundtrip_{}",
+            std::process::id()
+        ));
+
+        // Act
+        state.save(&path).unwrap();
+        let loaded_state = IncrementalState::load(&path);
+
+        // Assert
+        assert_eq!(loaded_state, Some(state));
+    }
+
+    #[test]
+    fn test_state_corrupt() {
+        for content in [
+            "",
+            "pyo3_bindgen_engine 0.0.0\nconfig 0\n",
+            &format!("{STATE_FILE_HEADER}\nconfig xyz\n"),
+            &format!("{STATE_FILE_HEADER}\nconfig 0\nmodule a\n"),
+            &format!("{STATE_FILE_HEADER}\nconfig 0\nmodule a 0 0 \n"),
+            &format!("{STATE_FILE_HEADER}\nconfig 0\nmodule a x 0   \n"),
+            &format!("{STATE_FILE_HEADER}\nconfig 0\nmodule a - 0    \n"),
+        ] {
+            assert_eq!(IncrementalState::parse(content), None, "{content}");
+        }
+    }
+
+    #[test]
+    fn test_dependency_closure() {
+        // Arrange
+        let module = |dependencies: &[&str]| ModuleState {
+            dependencies: dependencies.iter().map(|&name| name.to_owned()).collect(),
+            ..Default::default()
+        };
+        let state = IncrementalState {
+            config_hash: 0,
+            modules: [
+                ("a".to_owned(), module(&["b"])),
+                ("b".to_owned(), module(&["c"])),
+                ("c".to_owned(), module(&["a"])),
+                ("d".to_owned(), module(&[])),
+            ]
+            .into(),
+        };
+
+        // Act
+        let closure = state.dependency_closure("a");
+
+        // Assert
+        assert_eq!(
+            closure.into_iter().collect::<Vec<_>>(),
+            ["a", "b", "c"].map(str::to_owned)
+        );
+        assert!(state.dependency_closure("d").is_empty());
+        assert!(state.dependency_closure("missing").is_empty());
+    }
+
+    #[test]
+    fn test_write_file_cleanup() {
+        // Arrange
+        let dir = std::env::temp_dir().join(format!(
+            "pyo3_bindgen_test_write_file_cleanup_{}",
+            std::process::id()
+        ));
+        let path = dir.join("target.rs");
+        std::fs::create_dir_all(&path).unwrap();
+
+        // Act
+        let result = write_file(&path, "content");
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name())
+                .collect::<Vec<_>>(),
+            ["target.rs"]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
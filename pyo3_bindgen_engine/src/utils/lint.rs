@@ -0,0 +1,243 @@
+use crate::{BodyMode, Config, LintAllowMode, PyBindgenError, Result};
+use syn::ext::IdentExt;
+
+/// Allow the lints that the top-level module of the bindings triggers as a whole, i.e. `dead_code` because the
+/// bindings are not necessarily used by the crate that includes them and `unused_variables` for the parameters
+/// that stub bodies do not use.
+pub(crate) fn allow_top_level(cfg: &Config) -> proc_macro2::TokenStream {
+    let mut lints = vec![quote::quote!(dead_code)];
+    if cfg.bodies == BodyMode::Stub {
+        lints.push(quote::quote!(unused_variables));
+    }
+    allow(cfg, &lints)
+}
+
+/// Allow the lints that a module triggers, i.e. `non_snake_case` for its name and `clippy::module_inception` if
+/// it contains a submodule of the same name.
+pub(crate) fn allow_mod<'a>(
+    cfg: &Config,
+    ident: &syn::Ident,
+    submodule_idents: impl IntoIterator<Item = &'a syn::Ident>,
+) -> proc_macro2::TokenStream {
+    let mut lints = Vec::new();
+    if !is_snake_case(ident) {
+        lints.push(quote::quote!(non_snake_case));
+    }
+    if submodule_idents
+        .into_iter()
+        .any(|submodule_ident| submodule_ident.unraw() == ident.unraw())
+    {
+        lints.push(quote::quote!(clippy::module_inception));
+    }
+    allow(cfg, &lints)
+}
+
+/// Allow the lints that a struct triggers, i.e. `non_camel_case_types` for its name and `non_snake_case` for the
+/// names of its fields.
+pub(crate) fn allow_struct<'a>(
+    cfg: &Config,
+    ident: &syn::Ident,
+    field_idents: impl IntoIterator<Item = &'a syn::Ident>,
+) -> proc_macro2::TokenStream {
+    let mut lints = Vec::new();
+    if !is_camel_case(ident) {
+        lints.push(quote::quote!(non_camel_case_types));
+    }
+    if field_idents
+        .into_iter()
+        .any(|field_ident| !is_snake_case(field_ident))
+    {
+        lints.push(quote::quote!(non_snake_case));
+    }
+    allow(cfg, &lints)
+}
+
+/// Allow the lints that an enum, trait or type alias triggers, i.e. `non_camel_case_types` for its name or the
+/// names of its variants.
+pub(crate) fn allow_type<'a>(
+    cfg: &Config,
+    ident: &syn::Ident,
+    variant_idents: impl IntoIterator<Item = &'a syn::Ident>,
+) -> proc_macro2::TokenStream {
+    if is_camel_case(ident) && variant_idents.into_iter().all(is_camel_case) {
+        return proc_macro2::TokenStream::new();
+    }
+    allow(cfg, &[quote::quote!(non_camel_case_types)])
+}
+
+/// Allow the lints that a constant triggers, i.e. `non_upper_case_globals` for its name.
+pub(crate) fn allow_const(cfg: &Config, ident: &syn::Ident) -> proc_macro2::TokenStream {
+    if is_upper_case(ident) {
+        return proc_macro2::TokenStream::new();
+    }
+    allow(cfg, &[quote::quote!(non_upper_case_globals)])
+}
+
+/// Allow the lints that a function with the given signature (e.g. `pub fn name<'py>(py: Python<'py>) -> ...`)
+/// triggers, i.e. `non_snake_case` for the names of the function and its parameters, `clippy::wrong_self_convention`
+/// for methods that mirror the conventions of Python and `clippy::too_many_arguments`.
+pub(crate) fn allow_fn(
+    cfg: &Config,
+    signature: &proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream> {
+    if cfg.lint_allows != LintAllowMode::Targeted {
+        return Ok(proc_macro2::TokenStream::new());
+    }
+    let parser = |input: syn::parse::ParseStream| {
+        input.parse::<syn::Visibility>()?;
+        input.parse::<syn::Signature>()
+    };
+    let sig = syn::parse::Parser::parse2(parser, signature.clone()).map_err(|err| {
+        PyBindgenError::CodegenSyntaxError {
+            span_context: format!(
+                "{signature} (cannot determine the lints of the function: {err})"
+            ),
+        }
+    })?;
+    Ok(allow(cfg, &signature_lints(&sig)))
+}
+
+/// Clippy lints the number of arguments above this threshold (`too-many-arguments-threshold`).
+const MAX_ARGUMENTS: usize = 7;
+
+fn signature_lints(sig: &syn::Signature) -> Vec<proc_macro2::TokenStream> {
+    let mut lints = Vec::new();
+
+    // Python names of the function and its parameters
+    let is_param_snake_case = sig.inputs.iter().all(|input| match input {
+        syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+            syn::Pat::Ident(pat_ident) => is_snake_case(&pat_ident.ident),
+            _ => true,
+        },
+        syn::FnArg::Receiver(_) => true,
+    });
+    if !is_snake_case(&sig.ident) || !is_param_snake_case {
+        lints.push(quote::quote!(non_snake_case));
+    }
+
+    // Methods mirror the conventions of Python instead of Rust
+    let fn_name = sig.ident.unraw().to_string();
+    if sig.receiver().is_some() && (fn_name.starts_with("into_") || fn_name.starts_with("from_")) {
+        lints.push(quote::quote!(clippy::wrong_self_convention));
+    }
+
+    // Python functions can have an arbitrary number of parameters
+    if sig.inputs.len() > MAX_ARGUMENTS {
+        lints.push(quote::quote!(clippy::too_many_arguments));
+    }
+
+    lints
+}
+
+/// Attribute that allows the `lints` on a generated item with `LintAllowMode::Targeted` (empty otherwise).
+fn allow(cfg: &Config, lints: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    if cfg.lint_allows != LintAllowMode::Targeted || lints.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+    quote::quote!(#[allow(#(#lints),*)])
+}
+
+/// Determine whether the given tokens mention the lifetime with the given name, e.g. `py` for `'py`.
+pub(crate) fn mentions_lifetime(
+    tokens: proc_macro2::TokenStream,
+    ident: &proc_macro2::Ident,
+) -> bool {
+    let mut is_lifetime = false;
+    tokens.into_iter().any(|token| {
+        let mentions = match &token {
+            proc_macro2::TokenTree::Ident(token_ident) => is_lifetime && token_ident == ident,
+            proc_macro2::TokenTree::Group(group) => mentions_lifetime(group.stream(), ident),
+            _ => false,
+        };
+        is_lifetime =
+            matches!(&token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'');
+        mentions
+    })
+}
+
+/// Omit the `'py` lifetime of the given function signature (e.g. `pub fn name<'py>(py: Python<'py>) -> ...` or
+/// `fn name<'py>(&'py self) -> ...`) if neither the other parameters, the return type nor the `body` mention it.
+/// The `py` parameter is then taken as `Python<'_>` and the receiver as `&self`, which avoids the
+/// `needless_lifetimes` lint. The signature is returned unchanged if it cannot be parsed.
+pub(crate) fn omit_unused_py_lifetime(
+    signature: proc_macro2::TokenStream,
+    body: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        Ok((
+            input.parse::<syn::Visibility>()?,
+            input.parse::<syn::Signature>()?,
+        ))
+    };
+    let Ok((vis, mut sig)) = syn::parse::Parser::parse2(parser, signature.clone()) else {
+        return signature;
+    };
+    let py_lifetime = proc_macro2::Ident::new("py", proc_macro2::Span::call_site());
+    let is_py_param = |input: &syn::FnArg| matches!(input, syn::FnArg::Typed(pat_type) if matches!(pat_type.pat.as_ref(), syn::Pat::Ident(pat_ident) if pat_ident.ident == "py"));
+    let is_declared = sig
+        .generics
+        .lifetimes()
+        .any(|param| param.lifetime.ident == py_lifetime);
+    let is_used = sig
+        .inputs
+        .iter()
+        .filter(|input| matches!(input, syn::FnArg::Typed(_)) && !is_py_param(input))
+        .map(quote::ToTokens::to_token_stream)
+        .chain(std::iter::once(quote::ToTokens::to_token_stream(
+            &sig.output,
+        )))
+        .chain(body.cloned())
+        .any(|tokens| mentions_lifetime(tokens, &py_lifetime));
+    if !is_declared || is_used {
+        return signature;
+    }
+
+    sig.generics.params = std::mem::take(&mut sig.generics.params)
+        .into_iter()
+        .filter(|param| !matches!(param, syn::GenericParam::Lifetime(param) if param.lifetime.ident == py_lifetime))
+        .collect();
+    if sig.generics.params.is_empty() {
+        sig.generics.lt_token = None;
+        sig.generics.gt_token = None;
+    }
+    for input in &mut sig.inputs {
+        let is_py_param = is_py_param(input);
+        match input {
+            syn::FnArg::Receiver(receiver) => {
+                if let Some((_, lifetime)) = &mut receiver.reference {
+                    *lifetime = None;
+                }
+            }
+            syn::FnArg::Typed(pat_type) if is_py_param => {
+                *pat_type.ty = syn::parse_quote!(::pyo3::marker::Python<'_>);
+            }
+            syn::FnArg::Typed(_) => {}
+        }
+    }
+    quote::quote! { #vis #sig }
+}
+
+/// Mirrors the `non_snake_case` lint of `rustc`.
+fn is_snake_case(ident: &proc_macro2::Ident) -> bool {
+    let name = ident.unraw().to_string();
+    let name = name.trim_matches('_');
+    !name.contains("__") && !name.chars().any(char::is_uppercase)
+}
+
+/// Mirrors the `non_camel_case_types` lint of `rustc`.
+fn is_camel_case(ident: &proc_macro2::Ident) -> bool {
+    let name = ident.unraw().to_string();
+    let name = name.trim_matches('_');
+    let has_case = |c: char| c.is_lowercase() || c.is_uppercase();
+    !name.chars().next().is_some_and(char::is_lowercase)
+        && !name.contains("__")
+        && !name
+            .chars()
+            .zip(name.chars().skip(1))
+            .any(|(fst, snd)| (has_case(fst) && snd == '_') || (fst == '_' && has_case(snd)))
+}
+
+/// Mirrors the `non_upper_case_globals` lint of `rustc`.
+fn is_upper_case(ident: &proc_macro2::Ident) -> bool {
+    !ident.unraw().to_string().chars().any(char::is_lowercase)
+}
@@ -1,6 +1,10 @@
 //! Various utilities.
 
 pub mod error;
+pub(crate) mod incremental;
 pub(crate) mod io;
+pub(crate) mod lint;
+pub mod report;
 pub mod result;
 pub(crate) mod text;
+pub(crate) mod version;
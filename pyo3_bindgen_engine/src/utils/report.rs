@@ -0,0 +1,400 @@
+//! Report about the completeness of the generated bindings.
+
+use crate::syntax::{Module, Path};
+use std::cell::RefCell;
+
+/// Report about the generated bindings that can be used to assess their completeness.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// Number of modules (including submodules).
+    pub n_modules: usize,
+    /// Number of classes.
+    pub n_classes: usize,
+    /// Number of module-level functions.
+    pub n_functions: usize,
+    /// Number of class methods (including constructors).
+    pub n_methods: usize,
+    /// Number of module-level and class properties.
+    pub n_properties: usize,
+    /// Items for which bindings were not generated.
+    pub skipped: Vec<SkippedItem>,
+    /// Number of type annotations of all functions, methods and properties.
+    pub n_annotations: usize,
+    /// Number of type annotations that contain at least one type that cannot be mapped to a Rust type.
+    pub n_unmapped_annotations: usize,
+    /// Sorted list of unique types that cannot be mapped to a Rust type (these fall back to `PyAny`).
+    pub unmapped_types: Vec<String>,
+    /// Items that were renamed due to name collisions.
+    pub collisions: CollisionReport,
+    /// Versions of Python packages that were verified via `Codegen::require_version()`.
+    pub verified_versions: Vec<VerifiedVersion>,
+    /// Size and complexity metrics of the generated bindings.
+    pub metrics: GenerationMetrics,
+    /// Non-fatal issues encountered while parsing the modules and generating the bindings.
+    pub warnings: Vec<Warning>,
+}
+
+/// Size and complexity metrics of the generated bindings, which show the modules and items that contribute the most
+/// to the size of the output (e.g. to decide which options to disable or which names to blocklist).
+///
+/// Sizes are estimated as the number of tokens, where each identifier, punctuation, literal and delimited group
+/// counts as one token.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationMetrics {
+    /// Metrics of each module (including submodules) sorted by their path.
+    pub modules: Vec<ModuleMetrics>,
+    /// Metrics of each generated class, function and method sorted by their path.
+    pub items: Vec<ItemMetrics>,
+}
+
+/// Size metrics of a single generated module.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ModuleMetrics {
+    /// Full path of the module.
+    pub path: String,
+    /// Number of generated classes.
+    pub n_classes: usize,
+    /// Number of generated module-level functions.
+    pub n_functions: usize,
+    /// Number of generated class methods (including constructors).
+    pub n_methods: usize,
+    /// Number of generated module-level and class properties.
+    pub n_properties: usize,
+    /// Number of tokens of the module, excluding the content of its submodules.
+    pub n_tokens: usize,
+}
+
+/// Size metrics of a single generated class, function or method.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemMetrics {
+    /// Full path of the item.
+    pub path: String,
+    /// Kind of the item.
+    pub kind: ItemKind,
+    /// Number of tokens of the item (for classes including all methods and properties).
+    pub n_tokens: usize,
+}
+
+/// Kind of a generated item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    /// Class, including its method trait.
+    Class,
+    /// Module-level function.
+    Function,
+    /// Class method (including constructors).
+    Method,
+}
+
+impl std::fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Class => "class",
+            Self::Function => "function",
+            Self::Method => "method",
+        })
+    }
+}
+
+impl GenerationMetrics {
+    /// Total number of tokens of all modules.
+    #[must_use]
+    pub fn n_tokens(&self) -> usize {
+        self.modules.iter().map(|module| module.n_tokens).sum()
+    }
+
+    /// Total number of generated classes.
+    #[must_use]
+    pub fn n_classes(&self) -> usize {
+        self.modules.iter().map(|module| module.n_classes).sum()
+    }
+
+    /// Total number of generated module-level functions.
+    #[must_use]
+    pub fn n_functions(&self) -> usize {
+        self.modules.iter().map(|module| module.n_functions).sum()
+    }
+
+    /// Total number of generated class methods.
+    #[must_use]
+    pub fn n_methods(&self) -> usize {
+        self.modules.iter().map(|module| module.n_methods).sum()
+    }
+
+    /// Total number of generated properties.
+    #[must_use]
+    pub fn n_properties(&self) -> usize {
+        self.modules.iter().map(|module| module.n_properties).sum()
+    }
+
+    /// The `n` largest classes and functions (including methods) by their number of tokens in descending order.
+    #[must_use]
+    pub fn largest_items(&self, n: usize) -> Vec<&ItemMetrics> {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by(|a, b| {
+            b.n_tokens
+                .cmp(&a.n_tokens)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        items.truncate(n);
+        items
+    }
+}
+
+/// Report about the items that were renamed due to name collisions (see `CollisionPolicy`). After regenerating
+/// the bindings, it can be used to find breaking changes of the generated API.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollisionReport {
+    /// Renamed items sorted by their path.
+    pub renamed: Vec<RenamedItem>,
+}
+
+/// Item that was renamed due to a name collision.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RenamedItem {
+    /// Full path of the item.
+    pub path: String,
+    /// Name that the item would have without the collision.
+    pub intended_name: String,
+    /// Name of the item in the generated bindings.
+    pub final_name: String,
+}
+
+/// Version of a Python package that satisfies the requirement of the generated bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VerifiedVersion {
+    /// Name of the Python package.
+    pub package: String,
+    /// Version specifier that the package must satisfy.
+    pub required: String,
+    /// Installed version of the package.
+    pub found: String,
+}
+
+/// Item for which bindings were not generated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SkippedItem {
+    /// Full path of the item.
+    pub path: String,
+    /// Reason why the item was skipped.
+    pub reason: String,
+}
+
+/// Non-fatal issue encountered while parsing the modules or generating the bindings, which is also printed to
+/// stderr. Build scripts can inspect the collected warnings to decide whether to fail or report them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Warning {
+    /// Kind of the warning.
+    pub kind: WarningKind,
+    /// Full path of the affected item (or the affected type for unmapped types).
+    pub path: String,
+    /// Human-readable description of the warning.
+    pub message: String,
+}
+
+/// Kind of a [`Warning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// Item for which bindings were not generated (see [`SkippedItem`]).
+    SkippedItem,
+    /// Item whose name is an invalid Rust ident, so it was sanitized or skipped (see `NameSanitization`).
+    UnnameableSymbol,
+    /// Type that cannot be mapped to a Rust type, so it falls back to `PyAny`.
+    UnmappedType,
+    /// Any other issue, e.g. a type annotation that exceeds the maximum nesting depth.
+    Other,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl GenerationReport {
+    /// Fraction of type annotations that contain at least one unmapped type (`0.0` if there are no annotations).
+    #[must_use]
+    pub fn unmapped_ratio(&self) -> f64 {
+        if self.n_annotations == 0 {
+            0.0
+        } else {
+            self.n_unmapped_annotations as f64 / self.n_annotations as f64
+        }
+    }
+
+    pub(crate) fn new(modules: &[Module], all_types: &[Path], sink: ReportSink) -> Self {
+        let mut renamed = sink.renamed_items.into_inner();
+        renamed.sort_unstable();
+        renamed.dedup();
+        let mut metrics = sink.metrics.into_inner();
+        metrics.modules.sort_by(|a, b| a.path.cmp(&b.path));
+        metrics.items.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut report = Self {
+            skipped: sink.skipped_items.into_inner(),
+            collisions: CollisionReport { renamed },
+            metrics,
+            warnings: sink.warnings.into_inner(),
+            ..Self::default()
+        };
+        modules
+            .iter()
+            .for_each(|module| report.add_module(module, all_types));
+        report.unmapped_types.sort_unstable();
+        report.unmapped_types.dedup();
+        report
+            .warnings
+            .extend(report.unmapped_types.iter().map(|unmapped_type| Warning {
+                kind: WarningKind::UnmappedType,
+                path: unmapped_type.clone(),
+                message: format!(
+                "Type '{unmapped_type}' cannot be mapped to a Rust type. It is mapped to 'PyAny'."
+            ),
+            }));
+        report
+    }
+
+    fn add_module(&mut self, module: &Module, all_types: &[Path]) {
+        self.n_modules += 1;
+        self.n_classes += module.classes.len();
+        self.n_functions += module.functions.len();
+        self.n_properties += module.properties.len();
+        module.classes.iter().for_each(|class| {
+            self.n_methods += class.methods().len();
+            self.n_properties += class.properties().len();
+        });
+
+        // Annotations
+        module
+            .functions
+            .iter()
+            .flat_map(|function| function.annotations())
+            .chain(
+                module
+                    .properties
+                    .iter()
+                    .flat_map(|property| property.annotations()),
+            )
+            .chain(module.classes.iter().flat_map(|class| {
+                class
+                    .methods()
+                    .iter()
+                    .flat_map(|method| method.annotations())
+                    .chain(
+                        class
+                            .properties()
+                            .iter()
+                            .flat_map(|property| property.annotations()),
+                    )
+            }))
+            .for_each(|annotation| {
+                self.n_annotations += 1;
+                let unmapped_types = annotation.unmapped_types(all_types);
+                if !unmapped_types.is_empty() {
+                    self.n_unmapped_annotations += 1;
+                    self.unmapped_types
+                        .extend(unmapped_types.into_iter().map(str::to_owned));
+                }
+            });
+
+        module
+            .submodules
+            .iter()
+            .for_each(|submodule| self.add_module(submodule, all_types));
+    }
+}
+
+/// Sink that collects the skipped items, renamed items, warnings and metrics while parsing the modules and
+/// generating their bindings, which are summarized in the [`GenerationReport`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReportSink {
+    skipped_items: RefCell<Vec<SkippedItem>>,
+    warnings: RefCell<Vec<Warning>>,
+    renamed_items: RefCell<Vec<RenamedItem>>,
+    metrics: RefCell<GenerationMetrics>,
+}
+
+impl ReportSink {
+    /// Print a warning to stderr and record an item for which bindings will not be generated.
+    pub(crate) fn skip(&self, path: impl std::fmt::Display, reason: impl Into<String>) {
+        let item = SkippedItem {
+            path: path.to_string(),
+            reason: reason.into(),
+        };
+        eprintln!(
+            "WARN: {}: '{}'. Bindings will not be generated.",
+            item.reason, item.path
+        );
+        self.warnings.borrow_mut().push(Warning {
+            kind: WarningKind::SkippedItem,
+            path: item.path.clone(),
+            message: item.reason.clone(),
+        });
+        self.skipped_items.borrow_mut().push(item);
+    }
+
+    /// Print a warning to stderr and record it.
+    pub(crate) fn warn(
+        &self,
+        kind: WarningKind,
+        path: impl std::fmt::Display,
+        message: impl Into<String>,
+    ) {
+        let message = message.into();
+        eprintln!("WARN: {message}");
+        self.warnings.borrow_mut().push(Warning {
+            kind,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    /// Get the warnings recorded so far.
+    pub(crate) fn warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow().clone()
+    }
+
+    /// Record an item that will be renamed due to a name collision.
+    pub(crate) fn rename(
+        &self,
+        path: impl std::fmt::Display,
+        intended_name: impl std::fmt::Display,
+        final_name: impl std::fmt::Display,
+    ) {
+        self.renamed_items.borrow_mut().push(RenamedItem {
+            path: path.to_string(),
+            intended_name: intended_name.to_string(),
+            final_name: final_name.to_string(),
+        });
+    }
+
+    /// Record the metrics of a generated module.
+    pub(crate) fn record_module(&self, metrics: ModuleMetrics) {
+        self.metrics.borrow_mut().modules.push(metrics);
+    }
+
+    /// Record the size of a generated item.
+    pub(crate) fn record_item(
+        &self,
+        path: impl std::fmt::Display,
+        kind: ItemKind,
+        tokens: &proc_macro2::TokenStream,
+    ) {
+        self.metrics.borrow_mut().items.push(ItemMetrics {
+            path: path.to_string(),
+            kind,
+            n_tokens: count_tokens(tokens),
+        });
+    }
+}
+
+/// Count the tokens of the given stream, where each delimited group counts as one token alongside its content.
+pub(crate) fn count_tokens(tokens: &proc_macro2::TokenStream) -> usize {
+    tokens
+        .clone()
+        .into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Group(group) => 1 + count_tokens(&group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
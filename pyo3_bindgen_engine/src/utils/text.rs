@@ -1,5 +1,5 @@
-/// Sanitize and format the given docstring.
-pub fn format_docstring(docstring: &mut String) {
+/// Sanitize and format the given docstring, optionally wrapping lines that exceed `max_width` characters.
+pub fn format_docstring(docstring: &mut String, max_width: Option<usize>) {
     // Remove leading and trailing whitespace for each line
     *docstring = docstring
         .lines()
@@ -16,6 +16,11 @@ pub fn format_docstring(docstring: &mut String) {
     // Remove duplicate whitespace characters (except line breaks)
     conditioned_dedup(docstring, |c| c.is_whitespace() && c != '\n');
 
+    // Wrap long lines (before fences of code blocks are affected by the deduplication of backticks)
+    if let Some(max_width) = max_width {
+        *docstring = wrap_lines(docstring, max_width);
+    }
+
     // Remove duplicate backticks to avoid potential doctests
     conditioned_dedup(docstring, |c| c == '`');
 
@@ -38,15 +43,173 @@ pub fn format_docstring(docstring: &mut String) {
     docstring.insert(0, ' ');
 }
 
+/// Wrap lines that exceed `max_width` characters at whitespace. Lines are never joined, so that the structure of the
+/// docstring (e.g. lists and sections) is preserved, and continuation lines of list items are indented accordingly.
+/// Lines of code blocks (fenced, doctest and reStructuredText literal blocks) are kept as-is.
+fn wrap_lines(docstring: &str, max_width: usize) -> String {
+    let mut output = Vec::new();
+    let mut is_in_fenced_block = false;
+    // Literal blocks are introduced by `::` and are separated from the surrounding text by empty lines
+    let mut literal_block = LiteralBlock::None;
+    for line in docstring.lines() {
+        let is_fence = line.starts_with("```") || line.starts_with("~~~");
+        if is_fence {
+            is_in_fenced_block = !is_in_fenced_block;
+        }
+        literal_block = match (literal_block, line.is_empty()) {
+            (LiteralBlock::Pending, true) => LiteralBlock::Active,
+            (LiteralBlock::Active, true) => LiteralBlock::None,
+            (LiteralBlock::Pending | LiteralBlock::None, false) if line.ends_with("::") => {
+                LiteralBlock::Pending
+            }
+            (LiteralBlock::Pending, false) => LiteralBlock::None,
+            (literal_block, _) => literal_block,
+        };
+        let is_code = is_fence
+            || is_in_fenced_block
+            || literal_block == LiteralBlock::Active
+            || line.starts_with(">>>")
+            || line.starts_with("...");
+        if is_code || line.chars().count() <= max_width {
+            output.push(line.to_owned());
+        } else {
+            output.extend(wrap_line(line, max_width));
+        }
+    }
+    output.join("\n")
+}
+
+/// State of a reStructuredText literal block while iterating over the lines of a docstring.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LiteralBlock {
+    None,
+    Pending,
+    Active,
+}
+
+/// Wrap a single line greedily at whitespace. Words that exceed `max_width` on their own are not split.
+fn wrap_line(line: &str, max_width: usize) -> Vec<String> {
+    // Indent the continuation lines of list items (e.g. `- item` or `1. item`) to the start of their text
+    let indent = line
+        .split_once(' ')
+        .filter(|(marker, _)| {
+            ["-", "*", "+"].contains(marker)
+                || marker.strip_suffix(['.', ')']).is_some_and(|number| {
+                    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+                })
+        })
+        .map_or(0, |(marker, _)| marker.len() + 1);
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        if !current.trim_start().is_empty()
+            && current.chars().count() + 1 + word.chars().count() > max_width
+        {
+            lines.push(std::mem::take(&mut current));
+            current = " ".repeat(indent);
+        } else if !current.trim_start().is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines.push(current);
+    lines
+}
+
 /// Remove duplicate characters from the input string that satisfy the given predicate.
 fn conditioned_dedup(input: &mut String, mut predicate: impl FnMut(char) -> bool) {
     let mut previous = None;
     input.retain(|c| {
         if predicate(c) {
-            Some(c) != std::mem::replace(&mut previous, Some(c))
+            Some(c) != previous.replace(c)
         } else {
             previous = None;
             true
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONG_PARAGRAPH: &str = "Compute the weighted average of the given values, where each weight must be non-negative and the weights must not all be zero.";
+
+    #[test]
+    fn test_format_docstring_unlimited_width() {
+        // Arrange
+        let mut docstring = LONG_PARAGRAPH.to_owned();
+
+        // Act
+        format_docstring(&mut docstring, None);
+
+        // Assert
+        assert_eq!(docstring, format!(" {LONG_PARAGRAPH}"));
+    }
+
+    #[test]
+    fn test_format_docstring_wrap_paragraph() {
+        // Arrange
+        let mut docstring = format!("{LONG_PARAGRAPH}\n\nReturns the average.");
+
+        // Act
+        format_docstring(&mut docstring, Some(80));
+
+        // Assert
+        assert_eq!(
+            docstring,
+            [
+                " Compute the weighted average of the given values, where each weight must be",
+                "non-negative and the weights must not all be zero.",
+                "",
+                "Returns the average.",
+                "",
+            ]
+            .join("\n")
+        );
+        assert!(docstring.lines().all(|line| line.chars().count() <= 80));
+    }
+
+    #[test]
+    fn test_format_docstring_wrap_preserves_code_and_lists() {
+        // Arrange
+        let code_line =
+            "result = weighted_average(values=[1.0, 2.0, 3.0], weights=[0.5, 0.25, 0.25])";
+        let mut docstring = [
+            "Example::",
+            "",
+            code_line,
+            "",
+            &format!(">>> {code_line}"),
+            "```python",
+            code_line,
+            "```",
+            "- The first item of the list is long enough to be wrapped onto a second line.",
+        ]
+        .join("\n");
+
+        // Act
+        format_docstring(&mut docstring, Some(40));
+
+        // Assert
+        assert_eq!(
+            docstring,
+            [
+                " Example::",
+                "",
+                code_line,
+                "",
+                &format!(">>> {code_line}"),
+                "`python",
+                code_line,
+                "`",
+                "- The first item of the list is long",
+                "  enough to be wrapped onto a second",
+                "  line.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
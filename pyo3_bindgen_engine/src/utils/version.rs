@@ -0,0 +1,135 @@
+//! Verification of the versions of installed Python packages.
+
+use crate::{PyBindgenError, Result};
+use pyo3::prelude::*;
+
+/// Verify that the installed version of a Python package satisfies the given version specifier
+/// (e.g. `==1.2.3` or `>=1.2,<2`) and return the installed version.
+///
+/// The specifier is evaluated via `packaging.specifiers.SpecifierSet` if the `packaging` Python package is
+/// available. Otherwise, only exact versions (`1.2.3` or `==1.2.3`) are supported.
+pub(crate) fn verify_version(py: Python, package: &str, spec: &str) -> Result<String> {
+    let version_mismatch = |found: &str| PyBindgenError::VersionMismatch {
+        package: package.to_owned(),
+        required: spec.to_owned(),
+        found: found.to_owned(),
+    };
+
+    // Determine the installed version of the package
+    let metadata = py.import_bound(pyo3::intern!(py, "importlib.metadata"))?;
+    let found: String = match metadata.call_method1(pyo3::intern!(py, "version"), (package,)) {
+        Ok(found) => found.extract()?,
+        Err(err)
+            if err.is_instance_bound(
+                py,
+                &metadata.getattr(pyo3::intern!(py, "PackageNotFoundError"))?,
+            ) =>
+        {
+            return Err(version_mismatch("not installed"));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Compare the installed version with the specifier
+    let is_satisfied = if let Ok(specifiers) =
+        py.import_bound(pyo3::intern!(py, "packaging.specifiers"))
+    {
+        specifiers
+            .call_method1(pyo3::intern!(py, "SpecifierSet"), (spec,))?
+            .call_method1(pyo3::intern!(py, "contains"), (&found, true))?
+            .extract()?
+    } else {
+        let exact_version = spec.trim().strip_prefix("==").unwrap_or(spec).trim();
+        if exact_version
+            .chars()
+            .any(|c| matches!(c, '<' | '>' | '=' | '!' | '~' | ',' | '*'))
+        {
+            return Err(PyBindgenError::ParseError(format!(
+                "Version specifier '{spec}' of '{package}' requires the `packaging` Python package (only exact versions are supported without it)"
+            )));
+        }
+        found == exact_version
+    };
+    if is_satisfied {
+        Ok(found)
+    } else {
+        Err(version_mismatch(&found))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Install a fake distribution with the given version into a temporary directory on `sys.path`.
+    fn install_fake_distribution(py: Python, package: &str, version: &str) {
+        let site_dir = std::env::temp_dir().join(format!("pyo3_bindgen_{package}_{version}"));
+        let dist_info = site_dir.join(format!("{package}-{version}.dist-info"));
+        std::fs::create_dir_all(&dist_info).unwrap();
+        std::fs::write(
+            dist_info.join("METADATA"),
+            format!("Metadata-Version: 2.1\nName: {package}\nVersion: {version}\n"),
+        )
+        .unwrap();
+        py.import_bound("sys")
+            .unwrap()
+            .getattr("path")
+            .unwrap()
+            .call_method1("insert", (0, site_dir))
+            .unwrap();
+        py.import_bound("importlib")
+            .unwrap()
+            .call_method0("invalidate_caches")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_verify_version_match() {
+        // Arrange
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            install_fake_distribution(py, "pyo3_bindgen_fake_match", "1.2.3");
+
+            // Act
+            let found = verify_version(py, "pyo3_bindgen_fake_match", "==1.2.3");
+
+            // Assert
+            assert_eq!(found.unwrap(), "1.2.3");
+        });
+    }
+
+    #[test]
+    fn test_verify_version_mismatch() {
+        // Arrange
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            install_fake_distribution(py, "pyo3_bindgen_fake_mismatch", "1.2.3");
+
+            // Act
+            let err = verify_version(py, "pyo3_bindgen_fake_mismatch", "==2.0.0").unwrap_err();
+
+            // Assert
+            assert!(matches!(
+                &err,
+                PyBindgenError::VersionMismatch { package, required, found }
+                    if package == "pyo3_bindgen_fake_mismatch" && required == "==2.0.0" && found == "1.2.3"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_verify_version_not_installed() {
+        // Arrange
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            // Act
+            let err = verify_version(py, "pyo3_bindgen_nonexistent_package", "==1.0").unwrap_err();
+
+            // Assert
+            assert!(matches!(
+                &err,
+                PyBindgenError::VersionMismatch { found, .. } if found == "not installed"
+            ));
+        });
+    }
+}
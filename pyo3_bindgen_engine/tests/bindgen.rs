@@ -1,7 +1,14 @@
 macro_rules! test_bindgen {
+    (@cfg) => {
+        pyo3_bindgen_engine::Config::default()
+    };
+    (@cfg $cfg:expr) => {
+        $cfg
+    };
     {
         $(#[$meta:meta])*
         $test_name:ident                       $(,)?
+        $(cfg: $cfg:expr                       ,)?
         $(py)?$(python)?$(:)? $code_py:literal $(,)?
         $(rs)?$(rust)?$(:)?   $code_rs:literal $(,)?
     } => {
@@ -12,27 +19,74 @@ macro_rules! test_bindgen {
             const CODE_PY: &str = indoc::indoc! { $code_py };
             const CODE_RS: &str = indoc::indoc! { $code_rs };
 
-            // Act
-            let bindings = pyo3_bindgen_engine::Codegen::default()
-                .module_from_str(CODE_PY, concat!("mod_", stringify!($test_name)))
-                .unwrap()
-                .generate()
-                .unwrap();
+            // Act & Assert
+            assert_bindgen(
+                test_bindgen!(@cfg $($cfg)?),
+                CODE_PY,
+                concat!("mod_", stringify!($test_name)),
+                CODE_RS,
+            );
+        }
+    };
+    {
+        $(#[$meta:meta])*
+        $test_name:ident                       $(,)?
+        $(cfg: $cfg:expr                       ,)?
+        $(py)?$(python)?$(:)? $code_py:literal $(,)?
+        $(rs)?$(rust)?$(:)?   { $($code_rs:tt)* } $(,)?
+        run: |$py:ident| $run:block            $(,)?
+    } => {
+        // The expected bindings are also compiled, so that their behaviour can be verified at runtime (items at the
+        // root of the bindings are referred to via `super`, so they cannot be scoped within the test function)
+        $($code_rs)*
 
-            // Assert
-            fn format_code(input: &str) -> String {
-                prettyplease::unparse(&syn::parse_str(input).unwrap())
-            }
-            let generated_code = format_code(&bindings.to_string());
-            let target_code = format_code(CODE_RS);
-            assert_eq!(
-                generated_code, target_code,
-                "\nGenerated:\n\n{generated_code}"
+        #[test]
+        $(#[$meta])*
+        fn $test_name() {
+            // Arrange
+            const CODE_PY: &str = indoc::indoc! { $code_py };
+            const CODE_RS: &str = stringify! { $($code_rs)* };
+
+            // Act & Assert
+            assert_bindgen(
+                test_bindgen!(@cfg $($cfg)?),
+                CODE_PY,
+                concat!("mod_", stringify!($test_name)),
+                CODE_RS,
             );
+
+            // Verify the behaviour of the expected bindings at runtime
+            pyo3::prepare_freethreaded_python();
+            pyo3::Python::with_gil(|$py| $run);
         }
     };
 }
 
+fn assert_bindgen(
+    cfg: pyo3_bindgen_engine::Config,
+    code_py: &str,
+    module_name: &str,
+    code_rs: &str,
+) {
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(cfg)
+        .module_from_str(code_py, module_name)
+        .unwrap()
+        .generate()
+        .unwrap();
+
+    // Assert
+    fn format_code(input: &str) -> String {
+        prettyplease::unparse(&syn::parse_str(input).unwrap())
+    }
+    let generated_code = format_code(&bindings.to_string());
+    let target_code = format_code(code_rs);
+    assert_eq!(
+        generated_code, target_code,
+        "\nGenerated:\n\n{generated_code}"
+    );
+}
+
 test_bindgen! {
     bindgen_property
 
@@ -53,15 +107,23 @@ test_bindgen! {
     pub mod mod_bindgen_property {
         /// Embed the Python source code of the module into the Python interpreter
         /// in order to enable the use of the generated Rust bindings.
-        pub fn pyo3_embed_python_source_code<'py>(
-            py: ::pyo3::marker::Python<'py>,
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
         ) -> ::pyo3::PyResult<()> {
             const SOURCE_CODE: &str = "my_property: float = 0.42\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_property")? {
+                return Ok(());
+            }
             pyo3::types::PyAnyMethods::set_item(
-                &pyo3::types::PyAnyMethods::getattr(
-                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
-                    pyo3::intern!(py, "modules"),
-                )?,
+                &modules,
                 "mod_bindgen_property",
                 pyo3::types::PyModule::from_code_bound(
                     py,
@@ -71,7 +133,7 @@ test_bindgen! {
                 )?,
             )
         }
-        pub fn my_property<'py>(py: ::pyo3::marker::Python<'py>) -> ::pyo3::PyResult<f64> {
+        pub fn my_property(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<f64> {
             ::pyo3::types::PyAnyMethods::extract(
                 &::pyo3::types::PyAnyMethods::getattr(
                     py.import_bound(::pyo3::intern!(py, "mod_bindgen_property"))?.as_any(),
@@ -79,8 +141,8 @@ test_bindgen! {
                 )?,
             )
         }
-        pub fn set_my_property<'py>(
-            py: ::pyo3::marker::Python<'py>,
+        pub fn set_my_property(
+            py: ::pyo3::marker::Python<'_>,
             p_value: f64,
         ) -> ::pyo3::PyResult<()> {
             ::pyo3::types::PyAnyMethods::setattr(
@@ -115,15 +177,23 @@ test_bindgen! {
     pub mod mod_bindgen_function {
         /// Embed the Python source code of the module into the Python interpreter
         /// in order to enable the use of the generated Rust bindings.
-        pub fn pyo3_embed_python_source_code<'py>(
-            py: ::pyo3::marker::Python<'py>,
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
         ) -> ::pyo3::PyResult<()> {
             const SOURCE_CODE: &str = "def my_function(my_arg1: str) -> int:\n    \"\"\"My docstring for `my_function`\"\"\"\n    ...\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_function")? {
+                return Ok(());
+            }
             pyo3::types::PyAnyMethods::set_item(
-                &pyo3::types::PyAnyMethods::getattr(
-                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
-                    pyo3::intern!(py, "modules"),
-                )?,
+                &modules,
                 "mod_bindgen_function",
                 pyo3::types::PyModule::from_code_bound(
                     py,
@@ -134,8 +204,8 @@ test_bindgen! {
             )
         }
         /// My docstring for `my_function`
-        pub fn my_function<'py>(
-            py: ::pyo3::marker::Python<'py>,
+        pub fn my_function(
+            py: ::pyo3::marker::Python<'_>,
             p_my_arg1: &str,
         ) -> ::pyo3::PyResult<i64> {
             ::pyo3::types::PyAnyMethods::extract(
@@ -153,6 +223,112 @@ test_bindgen! {
     "#
 }
 
+// Leaf modules are generated in full unless `Config::compact_leaf_modules` is set, i.e. including their prelude,
+// imports and module documentation
+test_bindgen! {
+    bindgen_leaf_module
+
+    py: r#"
+    """My docstring for the module"""
+    import os
+
+    __all__ = ["my_first_function", "my_second_function"]
+
+    def my_first_function() -> int:
+        """My docstring for `my_first_function`"""
+        return 1
+
+    def my_second_function() -> str:
+        return os.sep
+
+    def my_third_function() -> None:
+        ...
+    "#
+
+    rs: r#"
+    #[allow(
+        clippy::all,
+        clippy::nursery,
+        clippy::pedantic,
+        non_camel_case_types,
+        non_snake_case,
+        non_upper_case_globals,
+        unused
+    )]
+    /// My docstring for the module
+    pub mod mod_bindgen_leaf_module {
+        /// Embed the Python source code of the module into the Python interpreter
+        /// in order to enable the use of the generated Rust bindings.
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
+        ) -> ::pyo3::PyResult<()> {
+            const SOURCE_CODE: &str = "\"\"\"My docstring for the module\"\"\"\nimport os\n\n__all__ = [\"my_first_function\", \"my_second_function\"]\n\ndef my_first_function() -> int:\n    \"\"\"My docstring for `my_first_function`\"\"\"\n    return 1\n\ndef my_second_function() -> str:\n    return os.sep\n\ndef my_third_function() -> None:\n    ...\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force
+                && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_leaf_module")?
+            {
+                return Ok(());
+            }
+            pyo3::types::PyAnyMethods::set_item(
+                &modules,
+                "mod_bindgen_leaf_module",
+                pyo3::types::PyModule::from_code_bound(
+                    py,
+                    SOURCE_CODE,
+                    "mod_bindgen_leaf_module/__init__.py",
+                    "mod_bindgen_leaf_module",
+                )?,
+            )
+        }
+        pub mod call {
+            pub use super::{my_first_function, my_second_function};
+        }
+        /// My docstring for `my_first_function`
+        pub fn my_first_function(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+            ::pyo3::types::PyAnyMethods::extract(
+                &::pyo3::types::PyAnyMethods::call_method0(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_leaf_module"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "my_first_function"),
+                )?,
+            )
+        }
+        pub fn my_second_function(
+            py: ::pyo3::marker::Python<'_>,
+        ) -> ::pyo3::PyResult<::std::string::String> {
+            ::pyo3::types::PyAnyMethods::extract(
+                &::pyo3::types::PyAnyMethods::call_method0(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_leaf_module"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "my_second_function"),
+                )?,
+            )
+        }
+        pub fn my_third_function<'py>(
+            py: ::pyo3::marker::Python<'py>,
+        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+            ::pyo3::types::PyAnyMethods::extract(
+                &::pyo3::types::PyAnyMethods::call_method0(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_leaf_module"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "my_third_function"),
+                )?,
+            )
+        }
+    }
+    "#
+}
+
 test_bindgen! {
     bindgen_class
 
@@ -193,15 +369,24 @@ test_bindgen! {
     pub mod mod_bindgen_class {
         /// Embed the Python source code of the module into the Python interpreter
         /// in order to enable the use of the generated Rust bindings.
-        pub fn pyo3_embed_python_source_code<'py>(
-            py: ::pyo3::marker::Python<'py>,
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
         ) -> ::pyo3::PyResult<()> {
             const SOURCE_CODE: &str = "from typing import Dict, Optional\nclass MyClass:\n    \"\"\"My docstring for `MyClass`\"\"\"\n    def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):\n        \"\"\"My docstring for __init__\"\"\"\n        ...\n    def my_method(self, my_arg1: Dict[str, int], **kwargs):\n        \"\"\"My docstring for `my_method`\"\"\"\n        ...\n    @property\n    def my_property(self) -> int:\n        ...\n    @my_property.setter\n    def my_property(self, value: int):\n        ...\n\ndef my_function_with_class_param(my_arg1: MyClass):\n    ...\n\ndef my_function_with_class_return() -> MyClass:\n    ...\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_class")?
+            {
+                return Ok(());
+            }
             pyo3::types::PyAnyMethods::set_item(
-                &pyo3::types::PyAnyMethods::getattr(
-                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
-                    pyo3::intern!(py, "modules"),
-                )?,
+                &modules,
                 "mod_bindgen_class",
                 pyo3::types::PyModule::from_code_bound(
                     py,
@@ -220,7 +405,6 @@ test_bindgen! {
             ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
             ::std::option::Option::Some("mod_bindgen_class.MyClass")
         );
-        #[automatically_derived]
         impl MyClass {
             /// My docstring for __init__
             pub fn new<'py>(
@@ -229,7 +413,7 @@ test_bindgen! {
                 p_my_arg2: ::std::option::Option<i64>,
             ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
                 ::pyo3::types::PyAnyMethods::extract(
-                    &::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::call(
                         ::pyo3::types::PyAnyMethods::getattr(
                                 py
                                     .import_bound(::pyo3::intern!(py, "mod_bindgen_class"))?
@@ -239,10 +423,26 @@ test_bindgen! {
                             .as_any(),
                         ::pyo3::types::PyTuple::new_bound(
                             py,
-                            [
-                                ::pyo3::ToPyObject::to_object(&p_my_arg1, py),
-                                ::pyo3::ToPyObject::to_object(&p_my_arg2, py),
-                            ],
+                            [::pyo3::ToPyObject::to_object(&p_my_arg1, py)],
+                        ),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_my_arg2,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "my_arg2"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
                         ),
                     )?,
                 )
@@ -252,15 +452,21 @@ test_bindgen! {
         /// method call syntax these methods are separated into a trait, because stable
         /// Rust does not yet support `arbitrary_self_types`.
         #[doc(alias = "MyClass")]
-        #[automatically_derived]
         pub trait MyClassMethods {
             fn my_method<'py>(
                 &'py self,
                 p_my_arg1: &::std::collections::HashMap<::std::string::String, i64>,
                 p_kwargs: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyDict>>,
             ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
-            fn my_property<'py>(&'py self) -> ::pyo3::PyResult<i64>;
-            fn set_my_property<'py>(&'py self, p_value: i64) -> ::pyo3::PyResult<()>;
+            fn my_property(&self) -> ::pyo3::PyResult<i64>;
+            fn set_my_property(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+            fn to_dict<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+            fn update_from_dict<'py>(
+                &'py self,
+                p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+            ) -> ::pyo3::PyResult<()>;
         }
         #[automatically_derived]
         impl MyClassMethods for ::pyo3::Bound<'_, MyClass> {
@@ -288,7 +494,7 @@ test_bindgen! {
                     )?,
                 )
             }
-            fn my_property<'py>(&'py self) -> ::pyo3::PyResult<i64> {
+            fn my_property(&self) -> ::pyo3::PyResult<i64> {
                 ::pyo3::types::PyAnyMethods::extract(
                     &::pyo3::types::PyAnyMethods::getattr(
                         self.as_any(),
@@ -296,7 +502,7 @@ test_bindgen! {
                     )?,
                 )
             }
-            fn set_my_property<'py>(&'py self, p_value: i64) -> ::pyo3::PyResult<()> {
+            fn set_my_property(&self, p_value: i64) -> ::pyo3::PyResult<()> {
                 let py = self.py();
                 ::pyo3::types::PyAnyMethods::setattr(
                     self.as_any(),
@@ -304,11 +510,56 @@ test_bindgen! {
                     p_value,
                 )
             }
+            /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+            fn to_dict<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                let py = self.py();
+                let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                        builtins.as_any(),
+                        ::pyo3::intern!(py, "vars"),
+                        (self.as_any(),),
+                    )
+                    .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                        builtins.as_any(),
+                        ::pyo3::intern!(py, "dict"),
+                        (self.as_any(),),
+                    ))
+                    .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "__dict__"),
+                    ))?;
+                ::std::result::Result::Ok(
+                    ::pyo3::types::PyAnyMethods::downcast_into::<
+                        ::pyo3::types::PyDict,
+                    >(dict)?,
+                )
+            }
+            /// Set the attributes of the object to the values of the given dict via `setattr()`.
+            fn update_from_dict<'py>(
+                &'py self,
+                p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+            ) -> ::pyo3::PyResult<()> {
+                for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyString,
+                        >(name)?,
+                        value,
+                    )?;
+                }
+                ::std::result::Result::Ok(())
+            }
         }
         pub fn my_function_with_class_param<'py>(
             py: ::pyo3::marker::Python<'py>,
-            p_my_arg1: &::pyo3::Bound<'py, MyClass>,
+            p_my_arg1: impl ::std::borrow::Borrow<::pyo3::Bound<'py, MyClass>>,
         ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+            let p_my_arg1 = ::std::borrow::Borrow::<
+                ::pyo3::Bound<'py, MyClass>,
+            >::borrow(&p_my_arg1);
             ::pyo3::types::PyAnyMethods::extract(
                 &::pyo3::types::PyAnyMethods::call_method1(
                     py.import_bound(::pyo3::intern!(py, "mod_bindgen_class"))?.as_any(),
@@ -333,3 +584,15961 @@ test_bindgen! {
     }
     "#
 }
+
+test_bindgen! {
+    bindgen_class_targeted_lint_allows
+    cfg: pyo3_bindgen_engine::Config::builder().lint_allows(pyo3_bindgen_engine::LintAllowMode::Targeted).build(),
+
+    py: r#"
+    from typing import Dict, Optional
+    class MyClass:
+        """My docstring for `MyClass`"""
+        def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):
+            """My docstring for __init__"""
+            self._value = my_arg2 or 0
+        def my_method(self, my_arg1: Dict[str, int], **kwargs):
+            """My docstring for `my_method`"""
+            ...
+        @property
+        def my_property(self) -> int:
+            return self._value
+        @my_property.setter
+        def my_property(self, value: int):
+            self._value = value
+
+    def my_function_with_class_param(my_arg1: MyClass):
+        ...
+
+    def my_function_with_class_return() -> MyClass:
+        return MyClass("my_arg1")
+    "#
+
+    rs: {
+        #[allow(dead_code)]
+        pub mod mod_bindgen_class_targeted_lint_allows {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from typing import Dict, Optional\nclass MyClass:\n    \"\"\"My docstring for `MyClass`\"\"\"\n    def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):\n        \"\"\"My docstring for __init__\"\"\"\n        self._value = my_arg2 or 0\n    def my_method(self, my_arg1: Dict[str, int], **kwargs):\n        \"\"\"My docstring for `my_method`\"\"\"\n        ...\n    @property\n    def my_property(self) -> int:\n        return self._value\n    @my_property.setter\n    def my_property(self, value: int):\n        self._value = value\n\ndef my_function_with_class_param(my_arg1: MyClass):\n    ...\n\ndef my_function_with_class_return() -> MyClass:\n    return MyClass(\"my_arg1\")\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_class_targeted_lint_allows",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_targeted_lint_allows",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_targeted_lint_allows/__init__.py",
+                        "mod_bindgen_class_targeted_lint_allows",
+                    )?,
+                )
+            }
+            /// My docstring for `MyClass`
+            #[repr(transparent)]
+            pub struct MyClass(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(MyClass);
+            ::pyo3::pyobject_native_type_info!(
+                MyClass,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_targeted_lint_allows.MyClass")
+            );
+            impl MyClass {
+                /// My docstring for __init__
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_my_arg1: &str,
+                    p_my_arg2: ::std::option::Option<i64>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(
+                                                py, "mod_bindgen_class_targeted_lint_allows"
+                                            ),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "MyClass"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_my_arg1, py)],
+                            ),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_my_arg2,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "my_arg2"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "MyClass")]
+            pub trait MyClassMethods {
+                fn my_method<'py>(
+                    &'py self,
+                    p_my_arg1: &::std::collections::HashMap<::std::string::String, i64>,
+                    p_kwargs: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyDict>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn my_property(&self) -> ::pyo3::PyResult<i64>;
+                fn set_my_property(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl MyClassMethods for ::pyo3::Bound<'_, MyClass> {
+                /// My docstring for `my_method`
+                fn my_method<'py>(
+                    &'py self,
+                    p_my_arg1: &::std::collections::HashMap<::std::string::String, i64>,
+                    p_kwargs: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyDict>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    let py = self.py();
+                    let p_kwargs = if let Some(p_kwargs) = p_kwargs {
+                        ::pyo3::types::IntoPyDict::into_py_dict_bound(p_kwargs, py)
+                    } else {
+                        ::pyo3::types::PyDict::new_bound(py)
+                    };
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "my_method"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_my_arg1, py)],
+                            ),
+                            Some(&p_kwargs),
+                        )?,
+                    )
+                }
+                fn my_property(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "my_property"),
+                        )?,
+                    )
+                }
+                fn set_my_property(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "my_property"),
+                        p_value,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn my_function_with_class_param<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_my_arg1: impl ::std::borrow::Borrow<::pyo3::Bound<'py, MyClass>>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                let p_my_arg1 = ::std::borrow::Borrow::<
+                    ::pyo3::Bound<'py, MyClass>,
+                >::borrow(&p_my_arg1);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_class_targeted_lint_allows"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "my_function_with_class_param"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_my_arg1, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn my_function_with_class_return<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, MyClass>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_class_targeted_lint_allows"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "my_function_with_class_return"),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_class_targeted_lint_allows::{
+            my_function_with_class_param, my_function_with_class_return, MyClass, MyClassMethods,
+        };
+        mod_bindgen_class_targeted_lint_allows::pyo3_embed_python_source_code(py, false).unwrap();
+        let my_class = MyClass::new(py, "my_arg1", Some(1)).unwrap();
+        my_class.my_method(&::std::collections::HashMap::new(), None).unwrap();
+        my_class.set_my_property(2).unwrap();
+        assert_eq!(my_class.my_property().unwrap(), 2);
+        my_function_with_class_param(py, &my_class).unwrap();
+        assert_eq!(my_function_with_class_return(py).unwrap().my_property().unwrap(), 0);
+        my_class.update_from_dict(&my_class.to_dict().unwrap()).unwrap();
+    }
+}
+
+// The bindings are compiled under `deny(warnings)`, so that every lint that they trigger must be allowed by the
+// targeted `#[allow(...)]` attributes (also for the items that are not used by the test)
+mod targeted_lint_allows {
+    #![deny(warnings)]
+    use super::assert_bindgen;
+
+    test_bindgen! {
+        bindgen_targeted_lint_allows_deny_warnings
+
+        cfg: pyo3_bindgen_engine::Config::builder()
+            .lint_allows(pyo3_bindgen_engine::LintAllowMode::Targeted)
+            .default_helpers(true)
+            .emit_builder_for_constructors(true)
+            .emit_into_py_object(true)
+            .emit_send_wrappers(true)
+            .emit_shared_methods(true)
+            .build(),
+
+        py: r#"
+        import enum
+        from typing import TypeVar
+
+        T = TypeVar("T")
+
+        class colorKind(enum.Enum):
+            red = 1
+            darkBlue = 2
+
+        class myCounter:
+            """Counter with a name that is not in camel case"""
+            def __init__(self, startValue: int = 0, stepSize: int = 1):
+                self.currentValue = startValue
+                self.stepSize = stepSize
+            def getValue(self) -> int:
+                return self.currentValue
+            def into_list(self) -> list:
+                return [self.currentValue]
+            @property
+            def totalValue(self) -> int:
+                return self.currentValue
+
+        def sumAll(a: int, b: int, c: int, d: int, e: int, f: int, g: int, h: int) -> int:
+            return a + b + c + d + e + f + g + h
+
+        def scaleValue(counterObj: myCounter, scaleFactor: float = 2.0) -> float:
+            return counterObj.currentValue * scaleFactor
+
+        def isDark(colorValue: colorKind) -> bool:
+            return colorValue == colorKind.darkBlue
+
+        maxValue: int = 100
+        "#
+
+        rs: {
+            #[allow(dead_code)]
+            pub mod mod_bindgen_targeted_lint_allows_deny_warnings {
+                /// Embed the Python source code of the module into the Python interpreter
+                /// in order to enable the use of the generated Rust bindings.
+                ///
+                /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+                /// the host application of an extension module) is kept, unless `force` is set.
+                pub fn pyo3_embed_python_source_code(
+                    py: ::pyo3::marker::Python<'_>,
+                    force: bool,
+                ) -> ::pyo3::PyResult<()> {
+                    const SOURCE_CODE: &str = "import enum\nfrom typing import TypeVar\n\nT = TypeVar(\"T\")\n\nclass colorKind(enum.Enum):\n    red = 1\n    darkBlue = 2\n\nclass myCounter:\n    \"\"\"Counter with a name that is not in camel case\"\"\"\n    def __init__(self, startValue: int = 0, stepSize: int = 1):\n        self.currentValue = startValue\n        self.stepSize = stepSize\n    def getValue(self) -> int:\n        return self.currentValue\n    def into_list(self) -> list:\n        return [self.currentValue]\n    @property\n    def totalValue(self) -> int:\n        return self.currentValue\n\ndef sumAll(a: int, b: int, c: int, d: int, e: int, f: int, g: int, h: int) -> int:\n    return a + b + c + d + e + f + g + h\n\ndef scaleValue(counterObj: myCounter, scaleFactor: float = 2.0) -> float:\n    return counterObj.currentValue * scaleFactor\n\ndef isDark(colorValue: colorKind) -> bool:\n    return colorValue == colorKind.darkBlue\n\nmaxValue: int = 100\n";
+                    let modules = pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                        pyo3::intern!(py, "modules"),
+                    )?;
+                    if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_targeted_lint_allows_deny_warnings")? {
+                        return Ok(());
+                    }
+                    pyo3::types::PyAnyMethods::set_item(
+                        &modules,
+                        "mod_bindgen_targeted_lint_allows_deny_warnings",
+                        pyo3::types::PyModule::from_code_bound(
+                            py,
+                            SOURCE_CODE,
+                            "mod_bindgen_targeted_lint_allows_deny_warnings/__init__.py",
+                            "mod_bindgen_targeted_lint_allows_deny_warnings",
+                        )?,
+                    )
+                }
+                pub type T = ::pyo3::types::PyAny;
+                #[repr(transparent)]
+                #[allow(non_camel_case_types)]
+                pub struct colorKind(::pyo3::PyAny);
+                ::pyo3::pyobject_native_type_named!(colorKind);
+                ::pyo3::pyobject_native_type_info!(
+                    colorKind,
+                    ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                    ::std::option::Option::Some("mod_bindgen_targeted_lint_allows_deny_warnings.colorKind")
+                );
+                impl colorKind {
+                    /// Get the value of the class attribute `darkBlue` from the class itself.
+                    #[allow(non_snake_case)]
+                    pub fn darkBlue(
+                        py: ::pyo3::marker::Python<'_>,
+                    ) -> ::pyo3::PyResult<colorKindMember> {
+                        {
+                            let value = ::pyo3::types::PyAnyMethods::getattr(
+                                ::pyo3::types::PyAnyMethods::getattr(
+                                        py
+                                            .import_bound(
+                                                ::pyo3::intern!(
+                                                    py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                                ),
+                                            )?
+                                            .as_any(),
+                                        ::pyo3::intern!(py, "colorKind"),
+                                    )?
+                                    .as_any(),
+                                ::pyo3::intern!(py, "darkBlue"),
+                            )?;
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                        }
+                    }
+                    /// Get the value of the class attribute `red` from the class itself.
+                    pub fn red(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<colorKindMember> {
+                        {
+                            let value = ::pyo3::types::PyAnyMethods::getattr(
+                                ::pyo3::types::PyAnyMethods::getattr(
+                                        py
+                                            .import_bound(
+                                                ::pyo3::intern!(
+                                                    py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                                ),
+                                            )?
+                                            .as_any(),
+                                        ::pyo3::intern!(py, "colorKind"),
+                                    )?
+                                    .as_any(),
+                                ::pyo3::intern!(py, "red"),
+                            )?;
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                        }
+                    }
+                    /// Unbind the object from the lifetime of the GIL, so that it can be stored and sent across threads.
+                    pub fn into_py_shared(self_: ::pyo3::Bound<'_, Self>) -> ::pyo3::Py<Self> {
+                        ::pyo3::Bound::unbind(self_)
+                    }
+                }
+                /// `Send` handle to an object of `colorKind`, which can be moved across threads. Its methods acquire the GIL internally.
+                #[derive(Debug)]
+                #[repr(transparent)]
+                #[allow(non_camel_case_types)]
+                pub struct colorKindHandle(pub ::pyo3::Py<colorKind>);
+                impl colorKindHandle {
+                    /// Create a handle to the given object.
+                    pub fn new(object: &::pyo3::Bound<'_, colorKind>) -> Self {
+                        Self(::pyo3::Bound::clone(object).unbind())
+                    }
+                    /// Borrow the object for the lifetime of the GIL.
+                    pub fn bind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> &::pyo3::Bound<'py, colorKind> {
+                        self.0.bind(py)
+                    }
+                    /// Call `colorKindMethods::darkBlue` while holding the GIL.
+                    #[allow(non_snake_case)]
+                    pub fn darkBlue(&self) -> ::pyo3::PyResult<colorKindMember> {
+                        ::pyo3::Python::with_gil(|py| {
+                            colorKindMethods::darkBlue(self.0.bind(py))
+                        })
+                    }
+                    /// Call `colorKindMethods::red` while holding the GIL.
+                    pub fn red(&self) -> ::pyo3::PyResult<colorKindMember> {
+                        ::pyo3::Python::with_gil(|py| { colorKindMethods::red(self.0.bind(py)) })
+                    }
+                    /// Call `colorKindMethods::to_dict` while holding the GIL.
+                    pub fn to_dict(&self) -> ::pyo3::PyResult<::pyo3::Py<::pyo3::types::PyDict>> {
+                        ::pyo3::Python::with_gil(|py| {
+                            colorKindMethods::to_dict(self.0.bind(py)).map(::pyo3::Bound::unbind)
+                        })
+                    }
+                }
+                #[automatically_derived]
+                impl ::std::convert::From<::pyo3::Bound<'_, colorKind>> for colorKindHandle {
+                    fn from(object: ::pyo3::Bound<'_, colorKind>) -> Self {
+                        Self(object.unbind())
+                    }
+                }
+                /// Methods of `colorKind` for the `Py<T>` smart pointer, which can be stored and sent across threads. Each method binds the object to the GIL token `py`.
+                #[allow(non_camel_case_types)]
+                pub trait colorKindSharedMethods {
+                    fn rebind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::Bound<'py, colorKind>;
+                    #[allow(non_snake_case)]
+                    fn darkBlue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<colorKindMember>;
+                    fn red<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<colorKindMember>;
+                    fn to_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()>;
+                }
+                #[automatically_derived]
+                impl colorKindSharedMethods for ::pyo3::Py<colorKind> {
+                    /// Bind the object to the lifetime of the GIL token `py`.
+                    fn rebind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::Bound<'py, colorKind> {
+                        ::pyo3::Bound::clone(self.bind(py))
+                    }
+                    /// Call `colorKindMethods::darkBlue` on the object bound to the GIL token `py`.
+                    fn darkBlue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<colorKindMember> {
+                        colorKindMethods::darkBlue(self.bind(py))
+                    }
+                    /// Call `colorKindMethods::red` on the object bound to the GIL token `py`.
+                    fn red<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<colorKindMember> {
+                        colorKindMethods::red(self.bind(py))
+                    }
+                    /// Call `colorKindMethods::to_dict` on the object bound to the GIL token `py`.
+                    fn to_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                        colorKindMethods::to_dict(self.bind(py))
+                    }
+                    /// Call `colorKindMethods::update_from_dict` on the object bound to the GIL token `py`.
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()> {
+                        colorKindMethods::update_from_dict(self.bind(py), p_dict)
+                    }
+                }
+                /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+                /// method call syntax these methods are separated into a trait, because stable
+                /// Rust does not yet support `arbitrary_self_types`.
+                #[doc(alias = "colorKind")]
+                #[allow(non_camel_case_types)]
+                pub trait colorKindMethods {
+                    #[allow(non_snake_case)]
+                    fn darkBlue(&self) -> ::pyo3::PyResult<colorKindMember>;
+                    fn red(&self) -> ::pyo3::PyResult<colorKindMember>;
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()>;
+                }
+                #[automatically_derived]
+                impl colorKindMethods for ::pyo3::Bound<'_, colorKind> {
+                    fn darkBlue(&self) -> ::pyo3::PyResult<colorKindMember> {
+                        {
+                            let value = ::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "darkBlue"),
+                            )?;
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                        }
+                    }
+                    fn red(&self) -> ::pyo3::PyResult<colorKindMember> {
+                        {
+                            let value = ::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "red"),
+                            )?;
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                        }
+                    }
+                    /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                        let py = self.py();
+                        let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                        let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "vars"),
+                                (self.as_any(),),
+                            )
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "dict"),
+                                (self.as_any(),),
+                            ))
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "__dict__"),
+                            ))?;
+                        ::std::result::Result::Ok(
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyDict,
+                            >(dict)?,
+                        )
+                    }
+                    /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()> {
+                        for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                        ::std::result::Result::Ok(())
+                    }
+                }
+                /// Members of the Python enum [`colorKind`], which can be passed to and returned from Python by value.
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                #[allow(non_camel_case_types)]
+                pub enum colorKindMember {
+                    red,
+                    darkBlue,
+                }
+                impl colorKindMember {
+                    /// Get the corresponding member of the Python enum.
+                    pub fn into_bound<'py>(
+                        self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, colorKind>> {
+                        let name = match self {
+                            Self::red => "red",
+                            Self::darkBlue => "darkBlue",
+                        };
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                ::pyo3::types::PyAnyMethods::getattr(
+                                        py
+                                            .import_bound(
+                                                ::pyo3::intern!(
+                                                    py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                                ),
+                                            )?
+                                            .as_any(),
+                                        ::pyo3::intern!(py, "colorKind"),
+                                    )?
+                                    .as_any(),
+                                name,
+                            )?,
+                        )
+                    }
+                }
+                impl<'py> ::pyo3::FromPyObject<'py> for colorKindMember {
+                    fn extract_bound(
+                        object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                    ) -> ::pyo3::PyResult<Self> {
+                        let py = object.py();
+                        let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                object,
+                                ::pyo3::intern!(py, "name"),
+                            )?,
+                        )?;
+                        let member = match name.as_str() {
+                            "red" => Self::red,
+                            "darkBlue" => Self::darkBlue,
+                            _ => {
+                                return ::std::result::Result::Err(
+                                    ::pyo3::exceptions::PyValueError::new_err(
+                                        ::std::format!(
+                                            "`{name}` is not a member of `mod_bindgen_targeted_lint_allows_deny_warnings.colorKind`"
+                                        ),
+                                    ),
+                                );
+                            }
+                        };
+                        if !::pyo3::types::PyAnyMethods::is(
+                            member.into_bound(py)?.as_any(),
+                            object,
+                        ) {
+                            return ::std::result::Result::Err(
+                                ::pyo3::exceptions::PyValueError::new_err(
+                                    "the object is not a member of `mod_bindgen_targeted_lint_allows_deny_warnings.colorKind`",
+                                ),
+                            );
+                        }
+                        ::std::result::Result::Ok(member)
+                    }
+                }
+                /// Counter with a name that is not in camel case
+                #[repr(transparent)]
+                #[allow(non_camel_case_types)]
+                pub struct myCounter(::pyo3::PyAny);
+                ::pyo3::pyobject_native_type_named!(myCounter);
+                ::pyo3::pyobject_native_type_info!(
+                    myCounter,
+                    ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                    ::std::option::Option::Some("mod_bindgen_targeted_lint_allows_deny_warnings.myCounter")
+                );
+                impl myCounter {
+                    #[allow(non_snake_case)]
+                    pub fn new<'py>(
+                        py: ::pyo3::marker::Python<'py>,
+                        p_startValue: i64,
+                        p_stepSize: i64,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::call(
+                                ::pyo3::types::PyAnyMethods::getattr(
+                                        py
+                                            .import_bound(
+                                                ::pyo3::intern!(
+                                                    py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                                ),
+                                            )?
+                                            .as_any(),
+                                        ::pyo3::intern!(py, "myCounter"),
+                                    )?
+                                    .as_any(),
+                                (),
+                                Some(
+                                    &{
+                                        let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                        {
+                                            let __internal_value = ::pyo3::ToPyObject::to_object(
+                                                &p_startValue,
+                                                py,
+                                            );
+                                            if !__internal_value.is_none(py) {
+                                                ::pyo3::types::PyDictMethods::set_item(
+                                                    &__internal_kwargs,
+                                                    ::pyo3::intern!(py, "startValue"),
+                                                    __internal_value,
+                                                )?;
+                                            }
+                                        }
+                                        {
+                                            let __internal_value = ::pyo3::ToPyObject::to_object(
+                                                &p_stepSize,
+                                                py,
+                                            );
+                                            if !__internal_value.is_none(py) {
+                                                ::pyo3::types::PyDictMethods::set_item(
+                                                    &__internal_kwargs,
+                                                    ::pyo3::intern!(py, "stepSize"),
+                                                    __internal_value,
+                                                )?;
+                                            }
+                                        }
+                                        __internal_kwargs
+                                    },
+                                ),
+                            )?,
+                        )
+                    }
+                    /// Unbind the object from the lifetime of the GIL, so that it can be stored and sent across threads.
+                    pub fn into_py_shared(self_: ::pyo3::Bound<'_, Self>) -> ::pyo3::Py<Self> {
+                        ::pyo3::Bound::unbind(self_)
+                    }
+                }
+                impl myCounter {
+                    pub fn builder<'py>(py: ::pyo3::marker::Python<'py>) -> myCounterBuilder<'py> {
+                        myCounterBuilder {
+                            py,
+                            p_startValue: ::std::option::Option::None,
+                            p_stepSize: ::std::option::Option::None,
+                        }
+                    }
+                }
+                /// Builder for [`myCounter`] that calls its constructor (`__init__`) once all desired parameters are set.
+                #[allow(non_camel_case_types, non_snake_case)]
+                pub struct myCounterBuilder<'py> {
+                    py: ::pyo3::marker::Python<'py>,
+                    p_startValue: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                    p_stepSize: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                }
+                impl<'py> myCounterBuilder<'py> {
+                    #[allow(non_snake_case)]
+                    pub fn startValue(mut self, p_startValue: i64) -> Self {
+                        let py = self.py;
+                        self.p_startValue = ::std::option::Option::Some(
+                            ::pyo3::ToPyObject::to_object(&p_startValue, py).into_bound(py),
+                        );
+                        self
+                    }
+                    #[allow(non_snake_case)]
+                    pub fn stepSize(mut self, p_stepSize: i64) -> Self {
+                        let py = self.py;
+                        self.p_stepSize = ::std::option::Option::Some(
+                            ::pyo3::ToPyObject::to_object(&p_stepSize, py).into_bound(py),
+                        );
+                        self
+                    }
+                    pub fn build(self) -> ::pyo3::PyResult<::pyo3::Bound<'py, myCounter>> {
+                        let py = self.py;
+                        let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                        if let ::std::option::Option::Some(__internal_value) = self.p_startValue {
+                            ::pyo3::types::PyDictMethods::set_item(
+                                &__internal_kwargs,
+                                ::pyo3::intern!(py, "startValue"),
+                                __internal_value,
+                            )?;
+                        }
+                        if let ::std::option::Option::Some(__internal_value) = self.p_stepSize {
+                            ::pyo3::types::PyDictMethods::set_item(
+                                &__internal_kwargs,
+                                ::pyo3::intern!(py, "stepSize"),
+                                __internal_value,
+                            )?;
+                        }
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::call(
+                                ::pyo3::types::PyAnyMethods::getattr(
+                                        py
+                                            .import_bound(
+                                                ::pyo3::intern!(
+                                                    py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                                ),
+                                            )?
+                                            .as_any(),
+                                        ::pyo3::intern!(py, "myCounter"),
+                                    )?
+                                    .as_any(),
+                                (),
+                                ::std::option::Option::Some(&__internal_kwargs),
+                            )?,
+                        )
+                    }
+                }
+                /// `Send` handle to an object of `myCounter`, which can be moved across threads. Its methods acquire the GIL internally.
+                #[derive(Debug)]
+                #[repr(transparent)]
+                #[allow(non_camel_case_types)]
+                pub struct myCounterHandle(pub ::pyo3::Py<myCounter>);
+                impl myCounterHandle {
+                    /// Create a handle to the given object.
+                    pub fn new(object: &::pyo3::Bound<'_, myCounter>) -> Self {
+                        Self(::pyo3::Bound::clone(object).unbind())
+                    }
+                    /// Borrow the object for the lifetime of the GIL.
+                    pub fn bind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> &::pyo3::Bound<'py, myCounter> {
+                        self.0.bind(py)
+                    }
+                    /// Call `myCounterMethods::getValue` while holding the GIL.
+                    #[allow(non_snake_case)]
+                    pub fn getValue(&self) -> ::pyo3::PyResult<i64> {
+                        ::pyo3::Python::with_gil(|py| {
+                            myCounterMethods::getValue(self.0.bind(py))
+                        })
+                    }
+                    /// Call `myCounterMethods::totalValue` while holding the GIL.
+                    #[allow(non_snake_case)]
+                    pub fn totalValue(&self) -> ::pyo3::PyResult<i64> {
+                        ::pyo3::Python::with_gil(|py| {
+                            myCounterMethods::totalValue(self.0.bind(py))
+                        })
+                    }
+                    /// Call `myCounterMethods::to_dict` while holding the GIL.
+                    pub fn to_dict(&self) -> ::pyo3::PyResult<::pyo3::Py<::pyo3::types::PyDict>> {
+                        ::pyo3::Python::with_gil(|py| {
+                            myCounterMethods::to_dict(self.0.bind(py)).map(::pyo3::Bound::unbind)
+                        })
+                    }
+                }
+                #[automatically_derived]
+                impl ::std::convert::From<::pyo3::Bound<'_, myCounter>> for myCounterHandle {
+                    fn from(object: ::pyo3::Bound<'_, myCounter>) -> Self {
+                        Self(object.unbind())
+                    }
+                }
+                /// Methods of `myCounter` for the `Py<T>` smart pointer, which can be stored and sent across threads. Each method binds the object to the GIL token `py`.
+                #[allow(non_camel_case_types)]
+                pub trait myCounterSharedMethods {
+                    fn rebind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::Bound<'py, myCounter>;
+                    #[allow(non_snake_case)]
+                    fn getValue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<i64>;
+                    #[allow(clippy::wrong_self_convention)]
+                    fn into_list<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                    #[allow(non_snake_case)]
+                    fn totalValue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<i64>;
+                    fn to_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()>;
+                }
+                #[automatically_derived]
+                impl myCounterSharedMethods for ::pyo3::Py<myCounter> {
+                    /// Bind the object to the lifetime of the GIL token `py`.
+                    fn rebind<'py>(
+                        &self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::Bound<'py, myCounter> {
+                        ::pyo3::Bound::clone(self.bind(py))
+                    }
+                    /// Call `myCounterMethods::getValue` on the object bound to the GIL token `py`.
+                    fn getValue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<i64> {
+                        myCounterMethods::getValue(self.bind(py))
+                    }
+                    /// Call `myCounterMethods::into_list` on the object bound to the GIL token `py`.
+                    fn into_list<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>> {
+                        myCounterMethods::into_list(self.bind(py))
+                    }
+                    /// Call `myCounterMethods::totalValue` on the object bound to the GIL token `py`.
+                    fn totalValue<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<i64> {
+                        myCounterMethods::totalValue(self.bind(py))
+                    }
+                    /// Call `myCounterMethods::to_dict` on the object bound to the GIL token `py`.
+                    fn to_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                        myCounterMethods::to_dict(self.bind(py))
+                    }
+                    /// Call `myCounterMethods::update_from_dict` on the object bound to the GIL token `py`.
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        py: ::pyo3::marker::Python<'py>,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()> {
+                        myCounterMethods::update_from_dict(self.bind(py), p_dict)
+                    }
+                }
+                /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+                /// method call syntax these methods are separated into a trait, because stable
+                /// Rust does not yet support `arbitrary_self_types`.
+                #[doc(alias = "myCounter")]
+                #[allow(non_camel_case_types)]
+                pub trait myCounterMethods {
+                    #[allow(non_snake_case)]
+                    fn getValue(&self) -> ::pyo3::PyResult<i64>;
+                    #[allow(clippy::wrong_self_convention)]
+                    fn into_list<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                    #[allow(non_snake_case)]
+                    fn totalValue(&self) -> ::pyo3::PyResult<i64>;
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()>;
+                }
+                #[automatically_derived]
+                impl myCounterMethods for ::pyo3::Bound<'_, myCounter> {
+                    fn getValue(&self) -> ::pyo3::PyResult<i64> {
+                        let py = self.py();
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "getValue"),
+                            )?,
+                        )
+                    }
+                    fn into_list<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>> {
+                        let py = self.py();
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "into_list"),
+                            )?,
+                        )
+                    }
+                    fn totalValue(&self) -> ::pyo3::PyResult<i64> {
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "totalValue"),
+                            )?,
+                        )
+                    }
+                    /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                        let py = self.py();
+                        let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                        let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "vars"),
+                                (self.as_any(),),
+                            )
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "dict"),
+                                (self.as_any(),),
+                            ))
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "__dict__"),
+                            ))?;
+                        ::std::result::Result::Ok(
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyDict,
+                            >(dict)?,
+                        )
+                    }
+                    /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()> {
+                        for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                        ::std::result::Result::Ok(())
+                    }
+                }
+                #[allow(non_snake_case)]
+                pub fn isDark(
+                    py: ::pyo3::marker::Python<'_>,
+                    p_colorValue: colorKindMember,
+                ) -> ::pyo3::PyResult<bool> {
+                    let p_colorValue = p_colorValue.into_bound(py)?;
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(
+                                        py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                    ),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "isDark"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_colorValue, py)],
+                            ),
+                        )?,
+                    )
+                }
+                #[allow(non_snake_case)]
+                pub fn scaleValue<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_counterObj: impl ::std::borrow::Borrow<::pyo3::Bound<'py, myCounter>>,
+                    p_scaleFactor: f64,
+                ) -> ::pyo3::PyResult<f64> {
+                    let p_counterObj = ::std::borrow::Borrow::<
+                        ::pyo3::Bound<'py, myCounter>,
+                    >::borrow(&p_counterObj);
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(
+                                        py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                    ),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "scaleValue"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_counterObj, py)],
+                            ),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_scaleFactor,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "scaleFactor"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+                /// Default values of the parameters of [`scaleValue`].
+                #[allow(non_snake_case)]
+                pub mod scaleValue_defaults {
+                    pub const SCALEFACTOR: f64 = 2.0;
+                }
+                #[allow(non_snake_case, clippy::too_many_arguments)]
+                pub fn sumAll(
+                    py: ::pyo3::marker::Python<'_>,
+                    p_a: i64,
+                    p_b: i64,
+                    p_c: i64,
+                    p_d: i64,
+                    p_e: i64,
+                    p_f: i64,
+                    p_g: i64,
+                    p_h: i64,
+                ) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(
+                                        py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                    ),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "sumAll"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_a, py),
+                                    ::pyo3::ToPyObject::to_object(&p_b, py),
+                                    ::pyo3::ToPyObject::to_object(&p_c, py),
+                                    ::pyo3::ToPyObject::to_object(&p_d, py),
+                                    ::pyo3::ToPyObject::to_object(&p_e, py),
+                                    ::pyo3::ToPyObject::to_object(&p_f, py),
+                                    ::pyo3::ToPyObject::to_object(&p_g, py),
+                                    ::pyo3::ToPyObject::to_object(&p_h, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+                #[allow(non_snake_case)]
+                pub fn maxValue(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(
+                                        py, "mod_bindgen_targeted_lint_allows_deny_warnings"
+                                    ),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "maxValue"),
+                        )?,
+                    )
+                }
+                #[allow(non_snake_case)]
+                pub fn set_maxValue(
+                    py: ::pyo3::marker::Python<'_>,
+                    p_value: i64,
+                ) -> ::pyo3::PyResult<()> {
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_targeted_lint_allows_deny_warnings"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "maxValue"),
+                        p_value,
+                    )
+                }
+            }
+        }
+        run: |py| {
+            use mod_bindgen_targeted_lint_allows_deny_warnings::{
+                colorKind, colorKindMember, isDark, myCounter, myCounterMethods, scaleValue,
+                scaleValue_defaults, sumAll,
+            };
+            mod_bindgen_targeted_lint_allows_deny_warnings::pyo3_embed_python_source_code(py, false)
+                .unwrap();
+            let counter = myCounter::builder(py).startValue(3).build().unwrap();
+            assert_eq!(counter.getValue().unwrap(), 3);
+            assert_eq!(counter.totalValue().unwrap(), 3);
+            assert_eq!(
+                scaleValue(py, &counter, scaleValue_defaults::SCALEFACTOR).unwrap(),
+                6.0
+            );
+            assert_eq!(sumAll(py, 1, 2, 3, 4, 5, 6, 7, 8).unwrap(), 36);
+            assert_eq!(colorKind::darkBlue(py).unwrap(), colorKindMember::darkBlue);
+            assert!(isDark(py, colorKindMember::darkBlue).unwrap());
+        }
+    }
+}
+
+test_bindgen! {
+    bindgen_property_inline_accessors
+
+    cfg: pyo3_bindgen_engine::Config::builder().inline_accessors(true).build(),
+
+    py: r#"
+    my_property: float = 0.42
+    "#
+
+    rs: r#"
+    #[allow(
+        clippy::all,
+        clippy::nursery,
+        clippy::pedantic,
+        non_camel_case_types,
+        non_snake_case,
+        non_upper_case_globals,
+        unused
+    )]
+    pub mod mod_bindgen_property_inline_accessors {
+        /// Embed the Python source code of the module into the Python interpreter
+        /// in order to enable the use of the generated Rust bindings.
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
+        ) -> ::pyo3::PyResult<()> {
+            const SOURCE_CODE: &str = "my_property: float = 0.42\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_property_inline_accessors")? {
+                return Ok(());
+            }
+            pyo3::types::PyAnyMethods::set_item(
+                &modules,
+                "mod_bindgen_property_inline_accessors",
+                pyo3::types::PyModule::from_code_bound(
+                    py,
+                    SOURCE_CODE,
+                    "mod_bindgen_property_inline_accessors/__init__.py",
+                    "mod_bindgen_property_inline_accessors",
+                )?,
+            )
+        }
+        #[inline]
+        pub fn my_property(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<f64> {
+            ::pyo3::types::PyAnyMethods::extract(
+                &::pyo3::types::PyAnyMethods::getattr(
+                    py
+                        .import_bound(
+                            ::pyo3::intern!(py, "mod_bindgen_property_inline_accessors"),
+                        )?
+                        .as_any(),
+                    ::pyo3::intern!(py, "my_property"),
+                )?,
+            )
+        }
+        #[inline]
+        pub fn set_my_property(
+            py: ::pyo3::marker::Python<'_>,
+            p_value: f64,
+        ) -> ::pyo3::PyResult<()> {
+            ::pyo3::types::PyAnyMethods::setattr(
+                py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_property_inline_accessors"),
+                    )?
+                    .as_any(),
+                ::pyo3::intern!(py, "my_property"),
+                p_value,
+            )
+        }
+    }
+    "#
+}
+
+test_bindgen! {
+    bindgen_property_write_only
+
+    py: r#"
+    class Sink:
+        def __init__(self):
+            self.values = []
+
+        def _set_value(self, value: int):
+            self.values.append(value)
+
+        value = property(fset=_set_value)
+
+        def total(self) -> int:
+            return sum(self.values)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_property_write_only {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Sink:\n    def __init__(self):\n        self.values = []\n\n    def _set_value(self, value: int):\n        self.values.append(value)\n\n    value = property(fset=_set_value)\n\n    def total(self) -> int:\n        return sum(self.values)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_property_write_only")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_property_write_only",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_property_write_only/__init__.py",
+                        "mod_bindgen_property_write_only",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Sink(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Sink);
+            ::pyo3::pyobject_native_type_info!(
+                Sink,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_property_write_only.Sink")
+            );
+            impl Sink {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_property_write_only"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Sink"),
+                                )?
+                                .as_any(),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Sink")]
+            pub trait SinkMethods {
+                fn total(&self) -> ::pyo3::PyResult<i64>;
+                fn set_value(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl SinkMethods for ::pyo3::Bound<'_, Sink> {
+                fn total(&self) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "total"),
+                        )?,
+                    )
+                }
+                fn set_value(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "value"),
+                        p_value,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_property_write_only::pyo3_embed_python_source_code(py, false).unwrap();
+        use mod_bindgen_property_write_only::SinkMethods;
+
+        let sink = mod_bindgen_property_write_only::Sink::new(py).unwrap();
+        sink.set_value(1).unwrap();
+        sink.set_value(2).unwrap();
+        assert_eq!(sink.total().unwrap(), 3);
+    }
+}
+
+test_bindgen! {
+    bindgen_annotated_instance_attributes
+
+    py: r#"
+    from typing import ClassVar
+
+    class Counter:
+        count: int
+        limit: ClassVar[int]
+
+        def __init__(self, count: int):
+            self.count = count
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_annotated_instance_attributes {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from typing import ClassVar\n\nclass Counter:\n    count: int\n    limit: ClassVar[int]\n\n    def __init__(self, count: int):\n        self.count = count\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_annotated_instance_attributes")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_annotated_instance_attributes",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_annotated_instance_attributes/__init__.py",
+                        "mod_bindgen_annotated_instance_attributes",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_annotated_instance_attributes.Counter")
+            );
+            impl Counter {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_count: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(
+                                                py, "mod_bindgen_annotated_instance_attributes"
+                                            ),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Counter"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_count, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn count(&self) -> ::pyo3::PyResult<i64>;
+                fn set_count(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                fn count(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "count"),
+                        )?,
+                    )
+                }
+                fn set_count(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "count"),
+                        p_value,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_annotated_instance_attributes::pyo3_embed_python_source_code(py, false).unwrap();
+        use mod_bindgen_annotated_instance_attributes::CounterMethods;
+
+        let counter = mod_bindgen_annotated_instance_attributes::Counter::new(py, 1).unwrap();
+        assert_eq!(counter.count().unwrap(), 1);
+        counter.set_count(2).unwrap();
+        assert_eq!(counter.count().unwrap(), 2);
+    }
+}
+
+#[test]
+fn bindgen_infallible_setters() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Counter:
+        count: int
+
+    total: int = 0
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .infallible_setters(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_infallible_setters")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("fn try_set_count("));
+    assert!(!bindings.contains("fn set_count("));
+    assert!(bindings.contains("pub fn set_total("));
+}
+
+test_bindgen! {
+    bindgen_class_comparisons
+
+    cfg: pyo3_bindgen_engine::Config::builder().impl_comparisons(true).build(),
+
+    py: r#"
+    class Point:
+        def __init__(self, x: int, y: int):
+            self.x = x
+            self.y = y
+        def __eq__(self, other):
+            return (self.x, self.y) == (other.x, other.y)
+        def __hash__(self):
+            return hash((self.x, self.y))
+        def __lt__(self, other):
+            return (self.x, self.y) < (other.x, other.y)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_comparisons {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Point:\n    def __init__(self, x: int, y: int):\n        self.x = x\n        self.y = y\n    def __eq__(self, other):\n        return (self.x, self.y) == (other.x, other.y)\n    def __hash__(self):\n        return hash((self.x, self.y))\n    def __lt__(self, other):\n        return (self.x, self.y) < (other.x, other.y)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_class_comparisons",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_comparisons",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_comparisons/__init__.py",
+                        "mod_bindgen_class_comparisons",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Point(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Point);
+            ::pyo3::pyobject_native_type_info!(
+                Point,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_comparisons.Point")
+            );
+            impl Point {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_x: i64,
+                    p_y: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_comparisons"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Point"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_x, py),
+                                    ::pyo3::ToPyObject::to_object(&p_y, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Point")]
+            pub trait PointMethods {
+                fn py_eq<'py>(
+                    &'py self,
+                    other: &::pyo3::Bound<'py, Point>,
+                ) -> ::pyo3::PyResult<bool>;
+                fn py_hash(&self) -> ::pyo3::PyResult<isize>;
+                fn py_cmp<'py>(
+                    &'py self,
+                    other: &::pyo3::Bound<'py, Point>,
+                ) -> ::pyo3::PyResult<::std::cmp::Ordering>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl PointMethods for ::pyo3::Bound<'_, Point> {
+                /// Compare with `other` for equality via the Python `==` operator (`__eq__`).
+                fn py_eq<'py>(
+                    &'py self,
+                    other: &::pyo3::Bound<'py, Point>,
+                ) -> ::pyo3::PyResult<bool> {
+                    ::pyo3::types::PyAnyMethods::eq(self.as_any(), other)
+                }
+                /// Compute the hash via the Python `hash()` built-in function (`__hash__`).
+                fn py_hash(&self) -> ::pyo3::PyResult<isize> {
+                    ::pyo3::types::PyAnyMethods::hash(self.as_any())
+                }
+                /// Compare with `other` via the Python `==`, `<` and `>` operators.
+                fn py_cmp<'py>(
+                    &'py self,
+                    other: &::pyo3::Bound<'py, Point>,
+                ) -> ::pyo3::PyResult<::std::cmp::Ordering> {
+                    ::pyo3::types::PyAnyMethods::compare(self.as_any(), other)
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_comparisons::{Point, PointMethods};
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_class_comparisons::pyo3_embed_python_source_code(py, false).unwrap();
+        let a = Point::new(py, 1, 2).unwrap();
+        let b = Point::new(py, 1, 2).unwrap();
+        let c = Point::new(py, 3, 4).unwrap();
+        assert!(!a.is(&b));
+        assert!(a.py_eq(&b).unwrap());
+        assert!(!a.py_eq(&c).unwrap());
+        assert_eq!(a.py_hash().unwrap(), b.py_hash().unwrap());
+        assert_eq!(a.py_cmp(&b).unwrap(), std::cmp::Ordering::Equal);
+        assert_eq!(a.py_cmp(&c).unwrap(), std::cmp::Ordering::Less);
+    }
+}
+
+test_bindgen! {
+    bindgen_class_constructor_builder
+
+    cfg: pyo3_bindgen_engine::Config::builder().emit_builder_for_constructors(true).build(),
+
+    py: r#"
+    class Rect:
+        def __init__(self, width: int, height: int = 1, *, label: str = "rect"):
+            self.width = width
+            self.height = height
+            self.label = label
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_constructor_builder {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Rect:\n    def __init__(self, width: int, height: int = 1, *, label: str = \"rect\"):\n        self.width = width\n        self.height = height\n        self.label = label\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_class_constructor_builder",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_constructor_builder",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_constructor_builder/__init__.py",
+                        "mod_bindgen_class_constructor_builder",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Rect(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Rect);
+            ::pyo3::pyobject_native_type_info!(
+                Rect,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_constructor_builder.Rect")
+            );
+            impl Rect {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_width: i64,
+                    p_height: i64,
+                    p_label: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_constructor_builder"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Rect"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_width, py)],
+                            ),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_height,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "height"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_label,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "label"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+            }
+            impl Rect {
+                pub fn builder<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_width: i64,
+                ) -> RectBuilder<'py> {
+                    RectBuilder {
+                        py,
+                        p_width: ::pyo3::ToPyObject::to_object(&p_width, py).into_bound(py),
+                        p_height: ::std::option::Option::None,
+                        p_label: ::std::option::Option::None,
+                    }
+                }
+            }
+            /// Builder for [`Rect`] that calls its constructor (`__init__`) once all desired parameters are set.
+            pub struct RectBuilder<'py> {
+                py: ::pyo3::marker::Python<'py>,
+                p_width: ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                p_height: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                p_label: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            }
+            impl<'py> RectBuilder<'py> {
+                pub fn height(mut self, p_height: i64) -> Self {
+                    let py = self.py;
+                    self.p_height = ::std::option::Option::Some(
+                        ::pyo3::ToPyObject::to_object(&p_height, py).into_bound(py),
+                    );
+                    self
+                }
+                pub fn label(mut self, p_label: &str) -> Self {
+                    let py = self.py;
+                    self.p_label = ::std::option::Option::Some(
+                        ::pyo3::ToPyObject::to_object(&p_label, py).into_bound(py),
+                    );
+                    self
+                }
+                pub fn build(self) -> ::pyo3::PyResult<::pyo3::Bound<'py, Rect>> {
+                    let py = self.py;
+                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &__internal_kwargs,
+                        ::pyo3::intern!(py, "width"),
+                        self.p_width,
+                    )?;
+                    if let ::std::option::Option::Some(__internal_value) = self.p_height {
+                        ::pyo3::types::PyDictMethods::set_item(
+                            &__internal_kwargs,
+                            ::pyo3::intern!(py, "height"),
+                            __internal_value,
+                        )?;
+                    }
+                    if let ::std::option::Option::Some(__internal_value) = self.p_label {
+                        ::pyo3::types::PyDictMethods::set_item(
+                            &__internal_kwargs,
+                            ::pyo3::intern!(py, "label"),
+                            __internal_value,
+                        )?;
+                    }
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_constructor_builder"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Rect"),
+                                )?
+                                .as_any(),
+                            (),
+                            ::std::option::Option::Some(&__internal_kwargs),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Rect")]
+            pub trait RectMethods {
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl RectMethods for ::pyo3::Bound<'_, Rect> {
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_constructor_builder::Rect;
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_class_constructor_builder::pyo3_embed_python_source_code(py, false).unwrap();
+        let rect = Rect::builder(py, 3).label("square").build().unwrap();
+        assert_eq!(rect.getattr("width").unwrap().extract::<i64>().unwrap(), 3);
+        assert_eq!(rect.getattr("height").unwrap().extract::<i64>().unwrap(), 1);
+        assert_eq!(rect.getattr("label").unwrap().extract::<String>().unwrap(), "square");
+        let rect = Rect::builder(py, 3).height(2).build().unwrap();
+        assert_eq!(rect.getattr("height").unwrap().extract::<i64>().unwrap(), 2);
+        assert_eq!(rect.getattr("label").unwrap().extract::<String>().unwrap(), "rect");
+    }
+}
+
+test_bindgen! {
+    bindgen_callable_class_params_accept_closures
+
+    cfg: pyo3_bindgen_engine::Config::builder().callable_class_params_accept_closures(true).build(),
+
+    py: r#"
+    class Doubler:
+        def __call__(self, x: int) -> int:
+            return 2 * x
+
+    def apply(callback: Doubler, x: int) -> int:
+        return callback(x)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_callable_class_params_accept_closures {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Doubler:\n    def __call__(self, x: int) -> int:\n        return 2 * x\n\ndef apply(callback: Doubler, x: int) -> int:\n    return callback(x)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_callable_class_params_accept_closures",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_callable_class_params_accept_closures",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_callable_class_params_accept_closures/__init__.py",
+                        "mod_bindgen_callable_class_params_accept_closures",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Doubler(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Doubler);
+            ::pyo3::pyobject_native_type_info!(
+                Doubler,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_callable_class_params_accept_closures.Doubler")
+            );
+            impl Doubler {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Doubler")]
+            pub trait DoublerMethods {
+                fn call(&self, p_x: i64) -> ::pyo3::PyResult<i64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl DoublerMethods for ::pyo3::Bound<'_, Doubler> {
+                fn call(&self, p_x: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            self.as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_x, py)],
+                            ),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// Note: Parameter `callback` accepts any Python callable, e.g. a Rust closure wrapped via `PyCFunction::new_closure_bound`.
+            pub fn apply(
+                py: ::pyo3::marker::Python<'_>,
+                p_callback: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_x: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                let p_callback = ::pyo3::IntoPy::<
+                    ::pyo3::Py<::pyo3::types::PyAny>,
+                >::into_py(p_callback, py);
+                let p_callback = p_callback.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_callable_class_params_accept_closures"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "apply"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_callback, py),
+                                ::pyo3::ToPyObject::to_object(&p_x, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_callable_class_params_accept_closures::{apply, Doubler};
+        use pyo3::types::{PyAnyMethods, PyCFunction, PyTuple};
+        mod_bindgen_callable_class_params_accept_closures::pyo3_embed_python_source_code(py, false).unwrap();
+
+        // Instance of the callable class
+        let doubler = py
+            .import_bound("mod_bindgen_callable_class_params_accept_closures")
+            .unwrap()
+            .getattr("Doubler")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .downcast_into::<Doubler>()
+            .unwrap();
+        assert_eq!(apply(py, &doubler, 21).unwrap(), 42);
+
+        // Rust closure
+        let tripler = PyCFunction::new_closure_bound(py, None, None, |args: &pyo3::Bound<'_, PyTuple>, _kwargs| {
+            args.get_item(0)?.extract::<i64>().map(|x| 3 * x)
+        })
+        .unwrap();
+        assert_eq!(apply(py, tripler, 14).unwrap(), 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_module_callable_instance
+
+    py: r#"
+    class Greeter:
+        def __init__(self, greeting: str):
+            self.greeting = greeting
+
+        def __call__(self, name: str, punctuation: str = "!") -> str:
+            """Greet someone"""
+            return f"{self.greeting}, {name}{punctuation}"
+
+    greet = Greeter("Hello")
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_module_callable_instance {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Greeter:\n    def __init__(self, greeting: str):\n        self.greeting = greeting\n\n    def __call__(self, name: str, punctuation: str = \"!\") -> str:\n        \"\"\"Greet someone\"\"\"\n        return f\"{self.greeting}, {name}{punctuation}\"\n\ngreet = Greeter(\"Hello\")\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_module_callable_instance",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_module_callable_instance",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_module_callable_instance/__init__.py",
+                        "mod_bindgen_module_callable_instance",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Greeter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Greeter);
+            ::pyo3::pyobject_native_type_info!(
+                Greeter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_module_callable_instance.Greeter")
+            );
+            impl Greeter {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_greeting: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_module_callable_instance"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Greeter"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_greeting, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Greeter")]
+            pub trait GreeterMethods {
+                fn call(
+                    &self,
+                    p_name: &str,
+                    p_punctuation: &str,
+                ) -> ::pyo3::PyResult<::std::string::String>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl GreeterMethods for ::pyo3::Bound<'_, Greeter> {
+                /// Greet someone
+                fn call(
+                    &self,
+                    p_name: &str,
+                    p_punctuation: &str,
+                ) -> ::pyo3::PyResult<::std::string::String> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            self.as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_name, py)],
+                            ),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_punctuation,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "punctuation"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// Greet someone
+            pub fn greet_call(
+                py: ::pyo3::marker::Python<'_>,
+                p_name: &str,
+                p_punctuation: &str,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call(
+                        ::pyo3::types::PyAnyMethods::getattr(
+                                py
+                                    .import_bound(
+                                        ::pyo3::intern!(py, "mod_bindgen_module_callable_instance"),
+                                    )?
+                                    .as_any(),
+                                ::pyo3::intern!(py, "greet"),
+                            )?
+                            .as_any(),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_name, py)],
+                        ),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_punctuation,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "punctuation"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn greet<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Greeter>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_module_callable_instance"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "greet"),
+                    )?,
+                )
+            }
+            pub fn set_greet<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: impl ::std::borrow::Borrow<::pyo3::Bound<'py, Greeter>>,
+            ) -> ::pyo3::PyResult<()> {
+                let p_value = ::std::borrow::Borrow::<
+                    ::pyo3::Bound<'py, Greeter>,
+                >::borrow(&p_value);
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(
+                            ::pyo3::intern!(py, "mod_bindgen_module_callable_instance"),
+                        )?
+                        .as_any(),
+                    ::pyo3::intern!(py, "greet"),
+                    p_value,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_module_callable_instance::greet_call;
+        mod_bindgen_module_callable_instance::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(greet_call(py, "World", "!").unwrap(), "Hello, World!");
+        assert_eq!(greet_call(py, "Rust", "?").unwrap(), "Hello, Rust?");
+    }
+}
+
+/// Write a Python package with the given files (relative to the package directory) and make it importable.
+///
+/// An empty `__init__.py` is written unless it is among the files.
+fn setup_package(package_name: &str, files: &[(impl AsRef<std::path::Path>, impl AsRef<str>)]) {
+    let package_dir = std::env::temp_dir()
+        .join(format!("pyo3_bindgen_{}", std::process::id()))
+        .join(package_name);
+    std::fs::create_dir_all(&package_dir).unwrap();
+    std::fs::write(package_dir.join("__init__.py"), "").unwrap();
+    for (relative_path, source) in files {
+        let path = package_dir.join(relative_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source.as_ref()).unwrap();
+    }
+
+    pyo3::prepare_freethreaded_python();
+    pyo3::Python::with_gil(|py| {
+        use pyo3::types::{PyAnyMethods, PyListMethods};
+        py.import_bound("sys")
+            .unwrap()
+            .getattr("path")
+            .unwrap()
+            .downcast_into::<pyo3::types::PyList>()
+            .unwrap()
+            .insert(0, package_dir.parent().unwrap())
+            .unwrap();
+    });
+}
+
+/// Files of a Python package with a submodule that raises `ImportError`.
+const PACKAGE_WITH_UNIMPORTABLE_SUBMODULE: [(&str, &str); 2] = [
+    ("portable.py", "def answer() -> int:\n    return 42\n"),
+    (
+        "win32.py",
+        "raise ImportError('win32 is not available on this platform')\n",
+    ),
+];
+
+/// Write a Python package into a zip archive and make it importable via `zipimport`.
+///
+/// Returns the path to the archive, which is also the entry inserted into `sys.path`.
+fn setup_zipped_package(package_name: &str) -> std::path::PathBuf {
+    let archive = std::env::temp_dir()
+        .join(format!("pyo3_bindgen_{}", std::process::id()))
+        .join(format!("{package_name}.zip"));
+    std::fs::create_dir_all(archive.parent().unwrap()).unwrap();
+
+    pyo3::prepare_freethreaded_python();
+    pyo3::Python::with_gil(|py| {
+        let locals = pyo3::types::PyDict::new_bound(py);
+        pyo3::types::PyDictMethods::set_item(&locals, "archive", &archive).unwrap();
+        pyo3::types::PyDictMethods::set_item(&locals, "package_name", package_name).unwrap();
+        py.run_bound(
+            indoc::indoc! { r##"
+            import sys
+            import zipfile
+
+            with zipfile.ZipFile(archive, "w") as zip_file:
+                zip_file.writestr(f"{package_name}/__init__.py", "")
+                zip_file.writestr(f"{package_name}/alpha.py", "def alpha() -> int:\n    return 1\n")
+                zip_file.writestr(f"{package_name}/sub/__init__.py", "")
+                zip_file.writestr(f"{package_name}/sub/beta.py", "def beta() -> int:\n    return 2\n")
+                zip_file.writestr(
+                    f"{package_name}/embedded/__init__.py",
+                    "from . import gamma\n\n# Mimic a loader that does not expose any search path\n__path__ = []\n",
+                )
+                zip_file.writestr(f"{package_name}/embedded/gamma.py", "def gamma() -> int:\n    return 3\n")
+            sys.path.insert(0, str(archive))
+            "## },
+            None,
+            Some(&locals),
+        )
+        .unwrap();
+    });
+    archive
+}
+
+#[test]
+fn bindgen_zipped_package() {
+    // Arrange
+    let archive = setup_zipped_package("pkg_zipped");
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_name("pkg_zipped")
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+    pyo3::Python::with_gil(|py| {
+        use pyo3::types::PyAnyMethods;
+        let sys = py.import_bound("sys").unwrap();
+        sys.getattr("path")
+            .unwrap()
+            .call_method1("remove", (archive.to_str().unwrap(),))
+            .unwrap();
+        let _ = sys
+            .getattr("path_importer_cache")
+            .unwrap()
+            .del_item(archive.to_str().unwrap());
+    });
+    std::fs::remove_file(&archive).unwrap();
+
+    // Assert
+    assert!(bindings.contains("pub mod alpha"));
+    assert!(bindings.contains("pub fn alpha"));
+    assert!(bindings.contains("pub mod sub"));
+    assert!(bindings.contains("pub fn beta"));
+    assert!(bindings.contains("pub mod embedded"));
+    assert!(bindings.contains("pub fn gamma"));
+}
+
+#[test]
+fn bindgen_unlistable_package() {
+    // Arrange
+    setup_package(
+        "pkg_unlistable",
+        &[(
+            "__init__.py",
+            "# Mimic a loader with a search path that `pkgutil` cannot iterate\n__path__ = 42\n",
+        )],
+    );
+
+    // Act
+    let (_bindings, report) = pyo3_bindgen_engine::Codegen::default()
+        .module_name("pkg_unlistable")
+        .unwrap()
+        .generate_with_report()
+        .unwrap();
+
+    // Assert
+    assert!(report.warnings.iter().any(|warning| {
+        warning.kind == pyo3_bindgen_engine::WarningKind::Other
+            && warning.path == "pkg_unlistable"
+            && warning
+                .message
+                .starts_with("Submodules of 'pkg_unlistable' cannot be listed via `pkgutil` (")
+    }));
+}
+
+#[test]
+fn bindgen_generate_ast() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class MyClass:
+        ...
+
+    def my_function() -> int:
+        ...
+    "# };
+    let codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_generate_ast")
+        .unwrap();
+
+    // Act
+    let file = codegen.clone().generate_ast().unwrap();
+    let bindings = codegen
+        .generate_with(|file| {
+            let syn::Item::Mod(module) = &mut file.items[0] else {
+                unreachable!()
+            };
+            let (_, items) = module.content.as_mut().unwrap();
+            let my_class = items
+                .iter_mut()
+                .find_map(|item| match item {
+                    syn::Item::Struct(item_struct) => Some(item_struct),
+                    _ => None,
+                })
+                .unwrap();
+            my_class
+                .attrs
+                .push(syn::parse_quote!(#[derive(my_crate::MyDerive)]));
+        })
+        .unwrap()
+        .to_string();
+
+    // Assert
+    assert!(matches!(
+        &file.items[..],
+        [syn::Item::Mod(module)] if module.ident == "mod_bindgen_generate_ast"
+    ));
+    assert!(bindings.contains("# [derive (my_crate :: MyDerive)] pub struct MyClass"));
+}
+
+#[test]
+fn bindgen_unimportable_submodule_skipped() {
+    // Arrange
+    setup_package("pkg_platform_skipped", &PACKAGE_WITH_UNIMPORTABLE_SUBMODULE);
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_name("pkg_platform_skipped")
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert
+    assert!(bindings.contains("pub mod portable"));
+    assert!(!bindings.contains("pub mod win32"));
+}
+
+#[test]
+fn bindgen_collect_all_types_from_stubs() {
+    // Arrange
+    setup_package(
+        "pkg_referenced_types",
+        &[
+            (
+                "internal.py",
+                "class Widget:\n    def __init__(self, name: str):\n        self._name = name\n\n    def name(self) -> str:\n        return self._name\n",
+            ),
+            // The `api` module references a class of the `internal` module only in annotations
+            (
+                "api.py",
+                "from pkg_referenced_types import internal as _internal\n\ndef make_widget(name: str) -> _internal.Widget:\n    return _internal.Widget(name)\n",
+            ),
+        ],
+    );
+    let generate = |cfg| {
+        pyo3_bindgen_engine::Codegen::new(cfg)
+            .module_name("pkg_referenced_types.api")
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string()
+    };
+
+    // Act
+    let default_bindings = generate(pyo3_bindgen_engine::Config::default());
+    let collected_bindings = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .collect_all_types_from_stubs(true)
+            .build(),
+    );
+
+    // Assert
+    assert!(!default_bindings.contains("pub mod internal"));
+    assert!(default_bindings.contains("pub fn make_widget"));
+    assert!(collected_bindings.contains("pub mod internal"));
+    assert!(collected_bindings.contains("pub struct Widget"));
+    assert!(collected_bindings.contains("pub trait WidgetMethods { fn name"));
+    assert!(collected_bindings.contains(
+        "-> :: pyo3 :: PyResult < :: pyo3 :: Bound < 'py , super :: internal :: Widget > >"
+    ));
+    syn::parse_str::<syn::File>(&collected_bindings).unwrap();
+}
+
+#[test]
+fn bindgen_star_reexports() {
+    // Arrange
+    setup_package(
+        "pkg_star_reexports",
+        &[
+            // The package re-exports the items of its submodules via star-imports
+            ("__init__.py", "from .core import *\nfrom .shapes import *\n"),
+            (
+                "core.py",
+                "import functools\n\n__all__ = [\"add\", \"cached\"]\n\ndef add(a: int, b: int) -> int:\n    return a + b\n\n@functools.lru_cache\ndef cached(n: int) -> int:\n    return n\n",
+            ),
+            (
+                "shapes.py",
+                "class Circle:\n    def __init__(self, r: float):\n        self.r = r\n",
+            ),
+        ],
+    );
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_name("pkg_star_reexports")
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert
+    for (definition, reexport) in [
+        ("pub fn add (", "pub use self :: core :: add ;"),
+        ("pub fn cached_call <", "pub use self :: core :: cached ;"),
+        ("pub struct Circle (", "pub use self :: shapes :: Circle ;"),
+    ] {
+        assert_eq!(bindings.matches(definition).count(), 1, "{definition}");
+        assert_eq!(bindings.matches(reexport).count(), 1, "{reexport}");
+    }
+}
+
+#[test]
+fn bindgen_compact_leaf_modules() {
+    // Arrange
+    let files = (0..16).flat_map(|i| {
+        [
+            // The `__all__` of the leaf module differs from its public attributes, so a prelude would be generated
+            (
+                format!("leaf_{i}.py"),
+                format!(
+                    "import os\n\n__all__ = [\"first_{i}\", \"second_{i}\"]\n\ndef first_{i}() -> int:\n    return {i}\n\ndef second_{i}() -> str:\n    return os.sep\n"
+                ),
+            ),
+            (
+                format!("single_{i}.py"),
+                format!("def only_{i}(x: int) -> int:\n    return x + {i}\n"),
+            ),
+        ]
+    });
+    setup_package("pkg_compact_leaf_modules", &files.collect::<Vec<_>>());
+    let generate = |cfg| {
+        pyo3_bindgen_engine::Codegen::new(cfg)
+            .module_name("pkg_compact_leaf_modules")
+            .unwrap()
+            .generate()
+            .unwrap()
+            .to_string()
+    };
+
+    // Act
+    let default_bindings = generate(pyo3_bindgen_engine::Config::default());
+    let compact_bindings = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .compact_leaf_modules(4)
+            .build(),
+    );
+    let flattened_bindings = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .flatten_single_item_modules(true)
+            .build(),
+    );
+
+    // Assert
+    assert!(default_bindings.contains("pub use super :: { first_0 , second_0 , }"));
+    assert!(!compact_bindings.contains("pub use super :: { first_0 , second_0 , }"));
+    assert!(compact_bindings.contains("pub fn first_0"));
+    assert!(compact_bindings.len() < default_bindings.len());
+    assert!(default_bindings.contains("pub mod single_0 { pub fn only_0"));
+    assert!(flattened_bindings.contains("pub mod single_0 { pub use super :: { only_0 } ; }"));
+    assert_eq!(flattened_bindings.matches("pub fn only_0").count(), 1);
+    assert!(flattened_bindings.contains("pub mod leaf_0 {"));
+    syn::parse_str::<syn::File>(&flattened_bindings).unwrap();
+}
+
+#[test]
+fn bindgen_flatten_single_item_modules() {
+    // Arrange
+    setup_package(
+        "pkg_flatten_single_item_modules",
+        &[
+            ("__init__.py", "def PointMethods() -> int:\n    return 0\n"),
+            (
+                "point.py",
+                "class Point:\n    def norm(self) -> float:\n        return 0.0\n",
+            ),
+            (
+                "shape.py",
+                "\"\"\"Shape module\"\"\"\n\ndef area() -> float:\n    \"\"\"Area docstring\"\"\"\n    return 0.0\n",
+            ),
+        ],
+    );
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .flatten_single_item_modules(true)
+            .build(),
+    )
+    .module_name("pkg_flatten_single_item_modules")
+    .unwrap()
+    .generate()
+    .unwrap()
+    .to_string();
+
+    // Assert (the trait with the methods of `Point` would clash with the function of the parent)
+    assert!(bindings.contains("pub mod point {"));
+    assert_eq!(bindings.matches("pub trait PointMethods").count(), 1);
+    assert!(bindings.contains(
+        "# [doc = \" Area docstring\"] # [doc = \"\"] # [doc = \" Shape module\"] pub fn area"
+    ));
+    assert!(bindings.contains("pub mod shape { pub use super :: { area } ; }"));
+}
+
+#[test]
+fn bindgen_unimportable_submodule_stubbed() {
+    // Arrange
+    setup_package("pkg_platform_stubbed", &PACKAGE_WITH_UNIMPORTABLE_SUBMODULE);
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .platform_stubs(true)
+            .build(),
+    )
+    .module_name("pkg_platform_stubbed")
+    .unwrap()
+    .generate()
+    .unwrap()
+    .to_string();
+
+    // Assert
+    assert!(bindings.contains("pub mod portable"));
+    assert!(bindings.contains("pub mod win32 { }"));
+    assert!(bindings.contains("ImportError: win32 is not available on this platform"));
+}
+
+#[test]
+fn bindgen_skip_modules() {
+    // Arrange
+    setup_package("pkg_skip_modules", &PACKAGE_WITH_UNIMPORTABLE_SUBMODULE);
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .platform_stubs(true)
+            .skip_modules(vec!["pkg_skip_modules.win32".to_owned()])
+            .build(),
+    )
+    .module_name("pkg_skip_modules")
+    .unwrap()
+    .generate()
+    .unwrap()
+    .to_string();
+
+    // Assert (a stub would be generated if the skipped submodule was imported)
+    assert!(bindings.contains("pub mod portable"));
+    assert!(!bindings.contains("pub mod win32"));
+}
+
+test_bindgen! {
+    bindgen_class_self_referential
+
+    py: r#"
+    from typing import Optional
+
+    class Node:
+        def __init__(self, value: int, next: Optional["Node"] = None):
+            self.value = value
+            self.next = next
+        def get_next(self) -> Optional["Node"]:
+            return self.next
+    "#
+
+    rs: r#"
+    #[allow(
+        clippy::all,
+        clippy::nursery,
+        clippy::pedantic,
+        non_camel_case_types,
+        non_snake_case,
+        non_upper_case_globals,
+        unused
+    )]
+    pub mod mod_bindgen_class_self_referential {
+        /// Embed the Python source code of the module into the Python interpreter
+        /// in order to enable the use of the generated Rust bindings.
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
+        ) -> ::pyo3::PyResult<()> {
+            const SOURCE_CODE: &str = "from typing import Optional\n\nclass Node:\n    def __init__(self, value: int, next: Optional[\"Node\"] = None):\n        self.value = value\n        self.next = next\n    def get_next(self) -> Optional[\"Node\"]:\n        return self.next\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force
+                && pyo3::types::PyAnyMethods::contains(
+                    &modules,
+                    "mod_bindgen_class_self_referential",
+                )?
+            {
+                return Ok(());
+            }
+            pyo3::types::PyAnyMethods::set_item(
+                &modules,
+                "mod_bindgen_class_self_referential",
+                pyo3::types::PyModule::from_code_bound(
+                    py,
+                    SOURCE_CODE,
+                    "mod_bindgen_class_self_referential/__init__.py",
+                    "mod_bindgen_class_self_referential",
+                )?,
+            )
+        }
+        #[repr(transparent)]
+        pub struct Node(::pyo3::PyAny);
+        ::pyo3::pyobject_native_type_named!(Node);
+        ::pyo3::pyobject_native_type_info!(
+            Node,
+            ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+            ::std::option::Option::Some("mod_bindgen_class_self_referential.Node")
+        );
+        impl Node {
+            pub fn new<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: i64,
+                p_next: ::std::option::Option<::pyo3::Bound<'py, Node>>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call(
+                        ::pyo3::types::PyAnyMethods::getattr(
+                                py
+                                    .import_bound(
+                                        ::pyo3::intern!(py, "mod_bindgen_class_self_referential"),
+                                    )?
+                                    .as_any(),
+                                ::pyo3::intern!(py, "Node"),
+                            )?
+                            .as_any(),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_next,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "next"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+        }
+        /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+        /// method call syntax these methods are separated into a trait, because stable
+        /// Rust does not yet support `arbitrary_self_types`.
+        #[doc(alias = "Node")]
+        pub trait NodeMethods {
+            fn get_next<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::std::option::Option<::pyo3::Bound<'py, Node>>>;
+            fn to_dict<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+            fn update_from_dict<'py>(
+                &'py self,
+                p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+            ) -> ::pyo3::PyResult<()>;
+        }
+        #[automatically_derived]
+        impl NodeMethods for ::pyo3::Bound<'_, Node> {
+            fn get_next<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::std::option::Option<::pyo3::Bound<'py, Node>>> {
+                let py = self.py();
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "get_next"),
+                    )?,
+                )
+            }
+            /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+            fn to_dict<'py>(
+                &'py self,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                let py = self.py();
+                let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                        builtins.as_any(),
+                        ::pyo3::intern!(py, "vars"),
+                        (self.as_any(),),
+                    )
+                    .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                        builtins.as_any(),
+                        ::pyo3::intern!(py, "dict"),
+                        (self.as_any(),),
+                    ))
+                    .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "__dict__"),
+                    ))?;
+                ::std::result::Result::Ok(
+                    ::pyo3::types::PyAnyMethods::downcast_into::<
+                        ::pyo3::types::PyDict,
+                    >(dict)?,
+                )
+            }
+            /// Set the attributes of the object to the values of the given dict via `setattr()`.
+            fn update_from_dict<'py>(
+                &'py self,
+                p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+            ) -> ::pyo3::PyResult<()> {
+                for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyString,
+                        >(name)?,
+                        value,
+                    )?;
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+    "#
+}
+
+#[test]
+fn bindgen_generation_report() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Counter:
+        def __init__(self):
+            pass
+        def increment(self, step: int) -> "Mystery":
+            ...
+        @property
+        def value(self) -> int:
+            return 0
+
+    def make_counter(start: int) -> Counter:
+        return Counter()
+
+    increment = Counter().increment
+    limit: int = 10
+    "# };
+
+    // Act
+    let (_bindings, report) = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_generation_report")
+        .unwrap()
+        .generate_with_report()
+        .unwrap();
+
+    // Assert
+    assert_eq!(report.n_modules, 1);
+    assert_eq!(report.n_classes, 1);
+    assert_eq!(report.n_functions, 1);
+    assert_eq!(report.n_methods, 2);
+    assert_eq!(report.n_properties, 2);
+    assert_eq!(
+        report.skipped,
+        [pyo3_bindgen_engine::SkippedItem {
+            path: "mod_bindgen_generation_report.increment".to_owned(),
+            reason: "Methods in modules are not supported".to_owned(),
+        }]
+    );
+    assert_eq!(report.unmapped_types, ["Mystery"]);
+    assert_eq!(report.n_annotations, 7);
+    assert_eq!(report.n_unmapped_annotations, 1);
+    assert!((report.unmapped_ratio() - 1.0 / 7.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn bindgen_builder_skipped_report() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Variadic:
+        def __init__(self, *values: int):
+            pass
+
+    class Clashing:
+        def __init__(self, build: int = 0):
+            pass
+    "# };
+
+    // Act
+    let (_bindings, report) = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .emit_builder_for_constructors(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_builder_skipped_report")
+    .unwrap()
+    .generate_with_report()
+    .unwrap();
+
+    // Assert
+    assert_eq!(
+        report.skipped,
+        [
+            pyo3_bindgen_engine::SkippedItem {
+                path: "mod_bindgen_builder_skipped_report.Clashing.builder".to_owned(),
+                reason: "Builder whose name or parameters would clash with the generated items"
+                    .to_owned(),
+            },
+            pyo3_bindgen_engine::SkippedItem {
+                path: "mod_bindgen_builder_skipped_report.Variadic.builder".to_owned(),
+                reason: "Builder of a constructor with positional-only or variadic parameters"
+                    .to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn bindgen_warnings() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Counter:
+        def increment(self, step: int) -> "Mystery":
+            ...
+
+    def orphan(self, step: int) -> int:
+        return step
+
+    increment = Counter().increment
+    "# };
+
+    // Act
+    let mut codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_warnings")
+        .unwrap();
+    let warnings = codegen.warnings().unwrap();
+    let (_bindings, report) = codegen.generate_with_report().unwrap();
+
+    // Assert
+    let expected = [
+        pyo3_bindgen_engine::Warning {
+            kind: pyo3_bindgen_engine::WarningKind::SkippedItem,
+            path: "mod_bindgen_warnings.increment".to_owned(),
+            message: "Methods in modules are not supported".to_owned(),
+        },
+        pyo3_bindgen_engine::Warning {
+            kind: pyo3_bindgen_engine::WarningKind::Other,
+            path: "mod_bindgen_warnings.orphan".to_owned(),
+            message: "Function 'mod_bindgen_warnings.orphan' has the first parameter named 'self', but is not marked as a method. The parameter is renamed to '__unknown_self__'.".to_owned(),
+        },
+        pyo3_bindgen_engine::Warning {
+            kind: pyo3_bindgen_engine::WarningKind::UnmappedType,
+            path: "Mystery".to_owned(),
+            message: "Type 'Mystery' cannot be mapped to a Rust type. It is mapped to 'PyAny'.".to_owned(),
+        },
+    ];
+    // Types are mapped only while generating the bindings, while the modules are not parsed again
+    assert_eq!(warnings, expected[..2]);
+    for warning in &expected {
+        assert_eq!(
+            report.warnings.iter().filter(|w| *w == warning).count(),
+            1,
+            "{:#?}",
+            report.warnings
+        );
+    }
+}
+
+#[test]
+fn bindgen_report_after_export_schema() {
+    // Arrange
+    const CODE_PY_ENGINE: &str = indoc::indoc! { r#"
+    class Widget:
+        pass
+    "# };
+    const CODE_PY_GADGETS: &str = indoc::indoc! { r#"
+    class Widget:
+        pass
+    "# };
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def place(widget: "Widget") -> None:
+        pass
+    "# };
+    let codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(
+            CODE_PY_ENGINE,
+            "mod_bindgen_report_after_export_schema_engine",
+        )
+        .unwrap()
+        .module_from_str(
+            CODE_PY_GADGETS,
+            "mod_bindgen_report_after_export_schema_gadgets",
+        )
+        .unwrap()
+        .module_from_str(CODE_PY, "mod_bindgen_report_after_export_schema")
+        .unwrap();
+
+    // Act
+    codegen.export_schema().unwrap();
+    let (_bindings, report) = codegen.generate_with_report().unwrap();
+
+    // Assert
+    let n_ambiguous = report
+        .warnings
+        .iter()
+        .filter(|warning| warning.message.starts_with("Type 'Widget' is ambiguous"))
+        .count();
+    assert_eq!(n_ambiguous, 1, "{:#?}", report.warnings);
+}
+
+#[test]
+fn bindgen_metaclass_members() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class InjectingMeta(type):
+        def __new__(mcs, name, bases, namespace):
+            def injected(self, value: int) -> int:
+                return value * 2
+
+            namespace["injected"] = injected
+            return super().__new__(mcs, name, bases, namespace)
+
+        def __dir__(cls):
+            return sorted(set(super().__dir__()) | {"dynamic", "broken"})
+
+        def __getattr__(cls, name):
+            if name == "dynamic":
+                return cls.injected
+            if name == "broken":
+                raise RuntimeError(name)
+            raise AttributeError(name)
+
+
+    class Widget(metaclass=InjectingMeta):
+        def own(self) -> int:
+            return 1
+
+
+    class OpaqueMeta(type):
+        def __dir__(cls):
+            raise RuntimeError("no introspection")
+
+
+    class Opaque(metaclass=OpaqueMeta):
+        def own(self) -> int:
+            return 1
+
+
+    class Base:
+        def __init_subclass__(cls, **kwargs):
+            super().__init_subclass__(**kwargs)
+
+            def describe(self) -> str:
+                return type(self).__name__
+
+            cls.describe = describe
+
+
+    class Child(Base):
+        pass
+    "# };
+
+    // Act
+    let mut codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_metaclass_members")
+        .unwrap();
+    let warnings = codegen.warnings().unwrap();
+    let bindings = codegen.generate().unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("fn injected(&self, p_value: i64) -> ::pyo3::PyResult<i64>;"));
+    assert!(bindings.contains("fn describe(&self) -> ::pyo3::PyResult<::std::string::String>;"));
+    assert!(bindings.contains("pub trait OpaqueMethods {\n        fn own(&self)"));
+    assert!(!bindings.contains("fn dynamic("));
+    assert!(!bindings.contains("fn broken("));
+    assert!(warnings.contains(&pyo3_bindgen_engine::Warning {
+        kind: pyo3_bindgen_engine::WarningKind::SkippedItem,
+        path: "mod_bindgen_metaclass_members.Widget.dynamic".to_owned(),
+        message: "Attributes provided dynamically by the metaclass are not supported".to_owned(),
+    }));
+    assert!(warnings.iter().any(|warning| {
+        warning.path == "mod_bindgen_metaclass_members.Opaque"
+            && warning.message.starts_with(
+                "Cannot list the attributes of 'mod_bindgen_metaclass_members.Opaque' via `dir()`",
+            )
+    }));
+}
+
+#[test]
+fn bindgen_omit_unused_py_lifetime() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def pi() -> float:
+        return 3.14
+
+    def scale(value: int, factor: int) -> int:
+        return value * factor
+
+    def identity(obj: object) -> object:
+        return obj
+
+    class Calculator:
+        @staticmethod
+        def double(value: int) -> int:
+            return 2 * value
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_omit_unused_py_lifetime")
+        .unwrap()
+        .generate()
+        .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("pub fn pi(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<f64>"));
+    assert!(bindings.contains(
+        "pub fn scale(\n        py: ::pyo3::marker::Python<'_>,\n        p_value: i64,\n        p_factor: i64,\n    ) -> ::pyo3::PyResult<i64>"
+    ));
+    assert!(bindings.contains(
+        "pub fn double(\n            py: ::pyo3::marker::Python<'_>,\n            p_value: i64,\n        ) -> ::pyo3::PyResult<i64>"
+    ));
+    assert!(bindings.contains("pub fn identity<'py>("));
+}
+
+#[test]
+fn bindgen_fuzzy_type_resolution() {
+    // Arrange
+    const CODE_PY_ENGINE: &str = indoc::indoc! { r#"
+    class Engine:
+        pass
+
+    class Widget:
+        pass
+
+    class Logger:
+        pass
+
+    class Config:
+        pass
+    "# };
+    const CODE_PY_GADGETS: &str = indoc::indoc! { r#"
+    class Widget:
+        pass
+    "# };
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import TYPE_CHECKING
+
+    if TYPE_CHECKING:
+        from engine import Engine, Widget
+
+    def start(engine: "Engine") -> "Engine":
+        return engine
+
+    def place(widget: "Widget") -> None:
+        pass
+
+    def log(logger: "logging.Logger") -> None:
+        pass
+
+    def configure(config: "otherlib.Config") -> None:
+        pass
+    "# };
+    let codegen = |cfg| {
+        pyo3_bindgen_engine::Codegen::new(cfg)
+            .module_from_str(CODE_PY_ENGINE, "mod_bindgen_fuzzy_type_resolution_engine")
+            .unwrap()
+            .module_from_str(CODE_PY_GADGETS, "mod_bindgen_fuzzy_type_resolution_gadgets")
+            .unwrap()
+            .module_from_str(CODE_PY, "mod_bindgen_fuzzy_type_resolution")
+            .unwrap()
+    };
+
+    // Act
+    let (bindings, report) = codegen(pyo3_bindgen_engine::Config::default())
+        .generate_with_report()
+        .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    let bindings_exact = codegen(
+        pyo3_bindgen_engine::Config::builder()
+            .fuzzy_type_resolution(false)
+            .build(),
+    )
+    .generate()
+    .unwrap();
+    let bindings_exact = prettyplease::unparse(&syn::parse2(bindings_exact).unwrap());
+
+    // Assert
+    assert!(bindings.contains(
+        "p_engine: impl ::std::borrow::Borrow<\n            ::pyo3::Bound<'py, super::mod_bindgen_fuzzy_type_resolution_engine::Engine>,\n        >,"
+    ));
+    assert!(bindings.contains(
+        "-> ::pyo3::PyResult<\n        ::pyo3::Bound<'py, super::mod_bindgen_fuzzy_type_resolution_engine::Engine>,\n    >"
+    ));
+    assert!(bindings.contains("p_widget: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>"));
+    assert!(report.warnings.iter().any(|warning| {
+        warning.kind == pyo3_bindgen_engine::WarningKind::UnmappedType
+            && warning.message.starts_with("Type 'Widget' is ambiguous")
+    }));
+    assert!(!bindings_exact.contains("super::mod_bindgen_fuzzy_type_resolution_engine::Engine>"));
+    // Qualified names of types from other packages are never resolved to generated types of the same name
+    assert!(!bindings.contains("mod_bindgen_fuzzy_type_resolution_engine::Logger>"));
+    assert!(!bindings.contains("mod_bindgen_fuzzy_type_resolution_engine::Config>"));
+    assert!(bindings.contains("p_logger: &::pyo3::Bound<'py, ::pyo3::types::PyAny>"));
+    assert!(bindings.contains("p_config: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>"));
+}
+
+#[test]
+fn bindgen_generation_metrics() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import Dict, Optional
+    class MyClass:
+        """My docstring for `MyClass`"""
+        def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):
+            """My docstring for __init__"""
+            ...
+        def my_method(self, my_arg1: Dict[str, int], **kwargs):
+            """My docstring for `my_method`"""
+            ...
+        def my_other_method(self) -> int:
+            ...
+        @property
+        def my_property(self) -> int:
+            ...
+
+    def my_function_with_class_return() -> MyClass:
+        ...
+    "# };
+    const CODE_PY_OTHER: &str = indoc::indoc! { r#"
+    def my_function(my_arg1: int) -> int:
+        ...
+    "# };
+
+    // Act
+    let (bindings, report) = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_generation_metrics")
+        .unwrap()
+        .module_from_str(CODE_PY_OTHER, "mod_bindgen_generation_metrics_other")
+        .unwrap()
+        .generate_with_report()
+        .unwrap();
+    let metrics = report.metrics;
+
+    // Assert
+    assert_eq!(metrics.modules.len(), 2);
+    let module = &metrics.modules[0];
+    assert_eq!(module.path, "mod_bindgen_generation_metrics");
+    assert_eq!(module.n_classes, 1);
+    assert!(module.n_methods >= 3);
+    assert_eq!(module.n_functions, 1);
+    assert_eq!(module.n_properties, 1);
+    assert!(metrics.modules.iter().all(|module| module.n_tokens > 0));
+    assert!(metrics.items.iter().all(|item| item.n_tokens > 0));
+
+    // Totals equal the sum of their parts
+    fn count_tokens(tokens: proc_macro2::TokenStream) -> usize {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                proc_macro2::TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+                _ => 1,
+            })
+            .sum()
+    }
+    assert_eq!(
+        metrics.n_tokens(),
+        metrics
+            .modules
+            .iter()
+            .map(|module| module.n_tokens)
+            .sum::<usize>()
+    );
+    assert_eq!(metrics.n_tokens(), count_tokens(bindings));
+    assert_eq!(metrics.n_classes(), 1);
+    assert_eq!(metrics.n_functions(), 2);
+    assert_eq!(metrics.n_methods(), module.n_methods);
+    let class = metrics
+        .items
+        .iter()
+        .find(|item| item.kind == pyo3_bindgen_engine::ItemKind::Class)
+        .unwrap();
+    let n_method_tokens = metrics
+        .items
+        .iter()
+        .filter(|item| item.kind == pyo3_bindgen_engine::ItemKind::Method)
+        .map(|item| item.n_tokens)
+        .sum::<usize>();
+    assert!(class.n_tokens > n_method_tokens);
+    assert!(module.n_tokens > class.n_tokens);
+
+    // The class is the largest item
+    let largest_items = metrics.largest_items(2);
+    assert_eq!(largest_items.len(), 2);
+    assert_eq!(
+        largest_items[0].path,
+        "mod_bindgen_generation_metrics.MyClass"
+    );
+    assert!(largest_items[0].n_tokens >= largest_items[1].n_tokens);
+}
+
+#[test]
+fn bindgen_export_schema() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import Dict, Optional
+    class MyClass:
+        """My docstring for `MyClass`"""
+        def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):
+            """My docstring for __init__"""
+            ...
+        def my_method(self, my_arg1: Dict[str, int], **kwargs):
+            """My docstring for `my_method`"""
+            ...
+        @property
+        def my_property(self) -> int:
+            ...
+        @my_property.setter
+        def my_property(self, value: int):
+            ...
+
+    def my_function_with_class_param(my_arg1: MyClass):
+        ...
+    "# };
+
+    // Act
+    let schema = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_export_schema")
+        .unwrap()
+        .export_schema()
+        .unwrap();
+
+    // Assert
+    let class = &schema["modules"][0]["classes"][0];
+    assert_eq!(class["name"], "mod_bindgen_export_schema.MyClass");
+    assert_eq!(
+        class["methods"]["__init__"]["parameters"],
+        serde_json::json!([
+            {
+                "name": "my_arg1",
+                "annotation": "str",
+                "type": "string",
+                "nullable": false,
+                "required": true,
+            },
+            {
+                "name": "my_arg2",
+                "annotation": "Optional[int]",
+                "type": "integer",
+                "nullable": true,
+                "required": false,
+            },
+        ])
+    );
+    assert_eq!(
+        class["methods"]["my_method"]["parameters"][0]["type"],
+        "object"
+    );
+    assert_eq!(
+        class["properties"]["my_property"],
+        serde_json::json!({
+            "annotation": "int",
+            "type": "integer",
+            "nullable": false,
+            "readonly": false,
+            "docstring": null,
+        })
+    );
+}
+
+#[test]
+fn bindgen_portable_property_types() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    separator = "/"
+    version: str = "1.0"
+    ratio: float = 1
+    "# };
+    let generate = |portable_property_types: bool| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .portable_property_types(portable_property_types)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_portable_property_types")
+        .unwrap()
+        .generate()
+        .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+
+    // Act
+    let concrete_bindings = generate(false);
+    let portable_bindings = generate(true);
+
+    // Assert
+    let separator_concrete = "pub fn separator(\n        py: ::pyo3::marker::Python<'_>,\n    ) -> ::pyo3::PyResult<::std::string::String>";
+    let separator_portable = "pub fn separator<'py>(\n        py: ::pyo3::marker::Python<'py>,\n    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>";
+    let version_concrete = "pub fn version(\n        py: ::pyo3::marker::Python<'_>,\n    ) -> ::pyo3::PyResult<::std::string::String>";
+    assert!(concrete_bindings.contains(separator_concrete));
+    assert!(concrete_bindings.contains(version_concrete));
+    assert!(portable_bindings.contains(separator_portable));
+    assert!(portable_bindings.contains(version_concrete));
+    // The annotation is preferred over the type of the runtime value only if the bindings are kept portable
+    assert!(concrete_bindings
+        .contains("pub fn ratio(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64>"));
+    assert!(portable_bindings
+        .contains("pub fn ratio(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<f64>"));
+    assert!(portable_bindings.contains("had the type `str` while generating the bindings"));
+    assert!(!concrete_bindings.contains("while generating the bindings"));
+}
+
+/// Create the directories for the source files and the incrementally generated bindings of a test.
+fn setup_incremental_dirs(test_name: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+    let dir = std::env::temp_dir()
+        .join(format!("pyo3_bindgen_{}", std::process::id()))
+        .join(test_name);
+    let source_dir = dir.join("src");
+    let output_dir = dir.join("out");
+    std::fs::create_dir_all(&source_dir).unwrap();
+    (source_dir, output_dir)
+}
+
+/// Generate the bindings of the Python modules within the source directory incrementally, after making sure
+/// that the modules are imported again from their (potentially modified) source.
+fn build_incremental(
+    source_dir: &std::path::Path,
+    output_dir: &std::path::Path,
+    module_names: &[&str],
+    force: bool,
+) -> Result<(), pyo3_bindgen_engine::PyBindgenError> {
+    pyo3::prepare_freethreaded_python();
+    pyo3::Python::with_gil(|py| {
+        use pyo3::types::{PyAnyMethods, PyDictMethods, PyListMethods};
+        let sys = py.import_bound("sys").unwrap();
+        sys.setattr("dont_write_bytecode", true).unwrap();
+        let path = sys
+            .getattr("path")
+            .unwrap()
+            .downcast_into::<pyo3::types::PyList>()
+            .unwrap();
+        if !path.contains(source_dir).unwrap() {
+            path.insert(0, source_dir).unwrap();
+        }
+        let modules = sys
+            .getattr("modules")
+            .unwrap()
+            .downcast_into::<pyo3::types::PyDict>()
+            .unwrap();
+        for module_name in module_names {
+            if modules.contains(module_name).unwrap() {
+                modules.del_item(module_name).unwrap();
+            }
+        }
+    });
+    pyo3_bindgen_engine::Codegen::default()
+        .module_names(module_names)?
+        .build_incremental(output_dir, force)
+}
+
+#[test]
+fn bindgen_build_incremental() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental");
+    std::fs::write(
+        source_dir.join("mod_incremental_a.py"),
+        "def a() -> int:\n    return 1\n",
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_b.py"),
+        "def b() -> int:\n    return 2\n",
+    )
+    .unwrap();
+    let build = |force: bool| {
+        build_incremental(
+            &source_dir,
+            &output_dir,
+            &["mod_incremental_a", "mod_incremental_b"],
+            force,
+        )
+        .unwrap();
+    };
+    let output_a = output_dir.join("mod_incremental_a.rs");
+    let output_b = output_dir.join("mod_incremental_b.rs");
+    let read = |path: &std::path::Path| {
+        (
+            std::fs::read_to_string(path).unwrap(),
+            std::fs::metadata(path).unwrap().modified().unwrap(),
+        )
+    };
+
+    // Act
+    build(false);
+    let (content_a, _) = read(&output_a);
+    let (content_b, mtime_b) = read(&output_b);
+    std::fs::write(
+        source_dir.join("mod_incremental_a.py"),
+        "def a() -> str:\n    return 'modified'\n",
+    )
+    .unwrap();
+    build(false);
+
+    // Assert
+    let (modified_content_a, _) = read(&output_a);
+    assert_ne!(content_a, modified_content_a);
+    assert!(modified_content_a.contains("String"));
+    assert_eq!(read(&output_b), (content_b.clone(), mtime_b));
+
+    // Act (changes of the source that do not affect the parsed content)
+    std::fs::write(
+        source_dir.join("mod_incremental_b.py"),
+        "def b() -> int:\n    # Modified body\n    return 3\n",
+    )
+    .unwrap();
+    build(false);
+
+    // Assert
+    assert_eq!(read(&output_b), (content_b.clone(), mtime_b));
+
+    // Act (missing/corrupt state file results in a full rebuild)
+    std::fs::write(&output_b, "").unwrap();
+    std::fs::write(output_dir.join(".pyo3_bindgen_state"), "corrupt").unwrap();
+    build(false);
+
+    // Assert
+    assert_eq!(read(&output_b).0, content_b);
+
+    // Act (forced rebuild)
+    std::fs::write(&output_b, "").unwrap();
+    build(true);
+
+    // Assert
+    assert_eq!(read(&output_b).0, content_b);
+}
+
+#[test]
+fn bindgen_build_incremental_skip_unchanged() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental_skip_unchanged");
+    // The module counts how often its attributes are listed, which happens only when it is parsed
+    std::fs::write(
+        source_dir.join("mod_incremental_skip_counted.py"),
+        "dir_count = 0\n\ndef __dir__():\n    global dir_count\n    dir_count += 1\n    return list(globals())\n\ndef counted() -> int:\n    return 1\n",
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_skip_other.py"),
+        "def other() -> int:\n    return 1\n",
+    )
+    .unwrap();
+    let module_names = ["mod_incremental_skip_counted", "mod_incremental_skip_other"];
+    let dir_count = || {
+        pyo3::Python::with_gil(|py| {
+            use pyo3::types::PyAnyMethods;
+            py.import_bound("mod_incremental_skip_counted")
+                .unwrap()
+                .getattr("dir_count")
+                .unwrap()
+                .extract::<usize>()
+                .unwrap()
+        })
+    };
+
+    // Act
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+    let initial_dir_count = dir_count();
+    std::fs::write(
+        source_dir.join("mod_incremental_skip_other.py"),
+        "def other() -> str:\n    return 'modified'\n",
+    )
+    .unwrap();
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+
+    // Assert
+    assert!(initial_dir_count > 0);
+    assert_eq!(dir_count(), 0);
+    assert!(
+        std::fs::read_to_string(output_dir.join("mod_incremental_skip_other.rs"))
+            .unwrap()
+            .contains("String")
+    );
+}
+
+#[test]
+fn bindgen_build_incremental_transitive_dependency() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental_transitive_dependency");
+    std::fs::write(
+        source_dir.join("mod_incremental_transitive_a.py"),
+        "from mod_incremental_transitive_b import B\n\ndef make() -> B:\n    return B()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_transitive_b.py"),
+        "from mod_incremental_transitive_c import C\n\nclass B:\n    def c(self) -> C:\n        return C()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_transitive_c.py"),
+        "class C:\n    pass\n",
+    )
+    .unwrap();
+    let module_names = [
+        "mod_incremental_transitive_a",
+        "mod_incremental_transitive_b",
+        "mod_incremental_transitive_c",
+    ];
+    let output_a = output_dir.join("mod_incremental_transitive_a.rs");
+
+    // Act
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+    let content_a = std::fs::read_to_string(&output_a).unwrap();
+    std::fs::write(&output_a, "").unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_transitive_c.py"),
+        "class C:\n    def value(self) -> int:\n        return 1\n",
+    )
+    .unwrap();
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+
+    // Assert
+    assert_eq!(std::fs::read_to_string(&output_a).unwrap(), content_a);
+}
+
+#[test]
+fn bindgen_build_incremental_fuzzy_type_resolution() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental_fuzzy_type_resolution");
+    std::fs::write(
+        source_dir.join("mod_incremental_fuzzy_widgets.py"),
+        "class Widget:\n    pass\n",
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_fuzzy_users.py"),
+        "def place(widget: 'Widget') -> int:\n    return 1\n",
+    )
+    .unwrap();
+    let module_names = [
+        "mod_incremental_fuzzy_widgets",
+        "mod_incremental_fuzzy_users",
+    ];
+    let output_users = output_dir.join("mod_incremental_fuzzy_users.rs");
+
+    // Act
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+    let content_users = std::fs::read_to_string(&output_users).unwrap();
+    std::fs::write(
+        source_dir.join("mod_incremental_fuzzy_users.py"),
+        "def place(widget: 'Widget') -> int:\n    return 1\n\ndef count() -> int:\n    return 2\n",
+    )
+    .unwrap();
+    build_incremental(&source_dir, &output_dir, &module_names, false).unwrap();
+
+    // Assert
+    let modified_content_users = std::fs::read_to_string(&output_users).unwrap();
+    assert!(content_users.contains("mod_incremental_fuzzy_widgets :: Widget"));
+    assert!(modified_content_users.contains("mod_incremental_fuzzy_widgets :: Widget"));
+    assert!(modified_content_users.contains("fn count"));
+}
+
+#[test]
+fn bindgen_build_incremental_removed_module() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental_removed_module");
+    for module_name in ["mod_incremental_kept", "mod_incremental_removed"] {
+        std::fs::write(
+            source_dir.join(format!("{module_name}.py")),
+            "def f() -> int:\n    return 1\n",
+        )
+        .unwrap();
+    }
+
+    // Act
+    build_incremental(
+        &source_dir,
+        &output_dir,
+        &["mod_incremental_kept", "mod_incremental_removed"],
+        false,
+    )
+    .unwrap();
+    let was_generated = output_dir.join("mod_incremental_removed.rs").exists();
+    build_incremental(&source_dir, &output_dir, &["mod_incremental_kept"], false).unwrap();
+
+    // Assert
+    assert!(was_generated);
+    assert!(output_dir.join("mod_incremental_kept.rs").exists());
+    assert!(!output_dir.join("mod_incremental_removed.rs").exists());
+    assert!(
+        !std::fs::read_to_string(output_dir.join(".pyo3_bindgen_state"))
+            .unwrap()
+            .contains("mod_incremental_removed")
+    );
+}
+
+#[test]
+fn bindgen_build_incremental_failed_write() {
+    // Arrange
+    let (source_dir, output_dir) = setup_incremental_dirs("incremental_failed_write");
+    std::fs::write(
+        source_dir.join("mod_incremental_failed_write.py"),
+        "def f() -> int:\n    return 1\n",
+    )
+    .unwrap();
+    // The output file cannot be replaced by a directory of the same name
+    std::fs::create_dir_all(output_dir.join("mod_incremental_failed_write.rs")).unwrap();
+
+    // Act
+    let result = build_incremental(
+        &source_dir,
+        &output_dir,
+        &["mod_incremental_failed_write"],
+        false,
+    );
+
+    // Assert
+    assert!(result.is_err());
+    let leftover_files = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|file_name| file_name.ends_with(".tmp"))
+        .collect::<Vec<_>>();
+    assert!(leftover_files.is_empty(), "{leftover_files:?}");
+}
+
+test_bindgen! {
+    bindgen_class_method_from_factory
+
+    py: r#"
+    def _make_helper():
+        def helper(obj, x: int) -> int:
+            return obj.base + x
+        return helper
+
+    class C:
+        base = 40
+        method = _make_helper()
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_method_from_factory {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def _make_helper():\n    def helper(obj, x: int) -> int:\n        return obj.base + x\n    return helper\n\nclass C:\n    base = 40\n    method = _make_helper()\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_class_method_from_factory",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_method_from_factory",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_method_from_factory/__init__.py",
+                        "mod_bindgen_class_method_from_factory",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct C(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(C);
+            ::pyo3::pyobject_native_type_info!(
+                C, ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_method_from_factory.C")
+            );
+            impl C {
+                /// Get the value of the class attribute `base` from the class itself.
+                pub fn base(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_method_from_factory"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "C"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "base"),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "C")]
+            pub trait CMethods {
+                fn method(&self, p_x: i64) -> ::pyo3::PyResult<i64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn base(&self) -> ::pyo3::PyResult<i64>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CMethods for ::pyo3::Bound<'_, C> {
+                fn method(&self, p_x: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "method"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_x, py)],
+                            ),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn base(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "base"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_method_from_factory::{C, CMethods};
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_class_method_from_factory::pyo3_embed_python_source_code(py, false).unwrap();
+        let c = py
+            .import_bound("mod_bindgen_class_method_from_factory")
+            .unwrap()
+            .getattr("C")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .downcast_into::<C>()
+            .unwrap();
+        assert_eq!(c.method(2).unwrap(), 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_class_attributes
+
+    cfg: pyo3_bindgen_engine::Config::builder().dict_helpers(false).build(),
+
+    py: r#"
+    class Limits:
+        MAX_SIZE = 10
+        NAME = "limits"
+
+        @property
+        def size(self) -> int:
+            return 3
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_attributes {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Limits:\n    MAX_SIZE = 10\n    NAME = \"limits\"\n\n    @property\n    def size(self) -> int:\n        return 3\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_class_attributes")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_attributes",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_attributes/__init__.py",
+                        "mod_bindgen_class_attributes",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Limits(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Limits);
+            ::pyo3::pyobject_native_type_info!(
+                Limits,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_attributes.Limits")
+            );
+            impl Limits {
+                /// Get the value of the class attribute `MAX_SIZE` from the class itself.
+                pub fn MAX_SIZE(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_attributes"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Limits"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "MAX_SIZE"),
+                        )?,
+                    )
+                }
+                /// Get the value of the class attribute `NAME` from the class itself.
+                pub fn NAME(
+                    py: ::pyo3::marker::Python<'_>,
+                ) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_attributes"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Limits"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "NAME"),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Limits")]
+            pub trait LimitsMethods {
+                fn MAX_SIZE(&self) -> ::pyo3::PyResult<i64>;
+                fn NAME(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn size(&self) -> ::pyo3::PyResult<i64>;
+            }
+            #[automatically_derived]
+            impl LimitsMethods for ::pyo3::Bound<'_, Limits> {
+                fn MAX_SIZE(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "MAX_SIZE"),
+                        )?,
+                    )
+                }
+                fn NAME(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "NAME"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn size(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "size"),
+                        )?,
+                    )
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_attributes::{Limits, LimitsMethods};
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_class_attributes::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(Limits::MAX_SIZE(py).unwrap(), 10);
+        assert_eq!(Limits::NAME(py).unwrap(), "limits");
+
+        // Class attributes are still readable from instances
+        let limits = py
+            .import_bound("mod_bindgen_class_attributes")
+            .unwrap()
+            .getattr("Limits")
+            .unwrap()
+            .call0()
+            .unwrap();
+        let limits = limits.downcast::<Limits>().unwrap();
+        assert_eq!(limits.MAX_SIZE().unwrap(), 10);
+        assert_eq!(limits.size().unwrap(), 3);
+    }
+}
+
+test_bindgen! {
+    bindgen_class_param_from_bound_or_py
+
+    py: r#"
+    class Item:
+        def __init__(self, value: int):
+            self.value = value
+
+    def get_value(item: Item) -> int:
+        return item.value
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_param_from_bound_or_py {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Item:\n    def __init__(self, value: int):\n        self.value = value\n\ndef get_value(item: Item) -> int:\n    return item.value\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_class_param_from_bound_or_py",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_param_from_bound_or_py",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_param_from_bound_or_py/__init__.py",
+                        "mod_bindgen_class_param_from_bound_or_py",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Item(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Item);
+            ::pyo3::pyobject_native_type_info!(
+                Item,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_param_from_bound_or_py.Item")
+            );
+            impl Item {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_value: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(
+                                                py, "mod_bindgen_class_param_from_bound_or_py"
+                                            ),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Item"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_value, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Item")]
+            pub trait ItemMethods {
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl ItemMethods for ::pyo3::Bound<'_, Item> {
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn get_value<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_item: impl ::std::borrow::Borrow<::pyo3::Bound<'py, Item>>,
+            ) -> ::pyo3::PyResult<i64> {
+                let p_item = ::std::borrow::Borrow::<::pyo3::Bound<'py, Item>>::borrow(&p_item);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_class_param_from_bound_or_py"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "get_value"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_item, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_param_from_bound_or_py::{get_value, Item};
+        mod_bindgen_class_param_from_bound_or_py::pyo3_embed_python_source_code(py, false).unwrap();
+        let item = Item::new(py, 42).unwrap();
+        let item_py: pyo3::Py<Item> = item.clone().unbind();
+        assert_eq!(get_value(py, &item).unwrap(), 42);
+        assert_eq!(get_value(py, item_py.bind(py)).unwrap(), 42);
+        assert_eq!(get_value(py, item_py.into_bound(py)).unwrap(), 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_dynamic_module_attributes
+
+    cfg: pyo3_bindgen_engine::Config::builder().dynamic_module_attributes(true).build(),
+
+    py: r#"
+    __all__ = ["answer", "version"]
+
+    def _answer() -> int:
+        return 42
+
+    def __getattr__(name):
+        if name == "answer":
+            return _answer
+        if name == "version":
+            return "1.0"
+        raise AttributeError(name)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_dynamic_module_attributes {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "__all__ = [\"answer\", \"version\"]\n\ndef _answer() -> int:\n    return 42\n\ndef __getattr__(name):\n    if name == \"answer\":\n        return _answer\n    if name == \"version\":\n        return \"1.0\"\n    raise AttributeError(name)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_dynamic_module_attributes")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_dynamic_module_attributes",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_dynamic_module_attributes/__init__.py",
+                        "mod_bindgen_dynamic_module_attributes",
+                    )?,
+                )
+            }
+            /// Get an attribute of the module by its name, including attributes that are resolved
+            /// dynamically via the `__getattr__` of the module.
+            pub fn pyo3_getattr<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                name: &str,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                ::pyo3::types::PyAnyMethods::getattr(
+                    py
+                        .import_bound(
+                            ::pyo3::intern!(py, "mod_bindgen_dynamic_module_attributes"),
+                        )?
+                        .as_any(),
+                    name,
+                )
+            }
+            pub mod call {
+                pub use super::answer;
+            }
+            pub fn answer(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_dynamic_module_attributes"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "answer"),
+                    )?,
+                )
+            }
+            pub fn version(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_dynamic_module_attributes"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "version"),
+                    )?,
+                )
+            }
+            pub fn set_version(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: &str,
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(
+                            ::pyo3::intern!(py, "mod_bindgen_dynamic_module_attributes"),
+                        )?
+                        .as_any(),
+                    ::pyo3::intern!(py, "version"),
+                    p_value,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_dynamic_module_attributes::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_dynamic_module_attributes::answer(py).unwrap(), 42);
+        assert_eq!(mod_bindgen_dynamic_module_attributes::version(py).unwrap(), "1.0");
+        let version = mod_bindgen_dynamic_module_attributes::pyo3_getattr(py, "version").unwrap();
+        assert_eq!(version.extract::<String>().unwrap(), "1.0");
+    }
+}
+
+test_bindgen! {
+    bindgen_float_type_f32
+
+    cfg: pyo3_bindgen_engine::Config::builder().float_type(pyo3_bindgen_engine::FloatType::F32).build(),
+
+    py: r#"
+    def scale(x: float, factor: float) -> float:
+        return x * factor
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_float_type_f32 {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def scale(x: float, factor: float) -> float:\n    return x * factor\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_float_type_f32")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_float_type_f32",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_float_type_f32/__init__.py",
+                        "mod_bindgen_float_type_f32",
+                    )?,
+                )
+            }
+            pub fn scale(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: f32,
+                p_factor: f32,
+            ) -> ::pyo3::PyResult<f32> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_float_type_f32"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scale"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_x, py),
+                                ::pyo3::ToPyObject::to_object(&p_factor, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_float_type_f32::pyo3_embed_python_source_code(py, false).unwrap();
+        let scaled: f32 = mod_bindgen_float_type_f32::scale(py, 1.5, 2.0).unwrap();
+        assert_eq!(scaled, 3.0);
+    }
+}
+
+test_bindgen! {
+    bindgen_flexible_primitives
+
+    cfg: pyo3_bindgen_engine::Config::builder().flexible_primitives(true).build(),
+
+    py: r#"
+    def describe(name: str, count: int, scale: float, label: str) -> str:
+        return f"{label}: {name} x{count * scale}"
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_flexible_primitives {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def describe(name: str, count: int, scale: float, label: str) -> str:\n    return f\"{label}: {name} x{count * scale}\"\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_flexible_primitives")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_flexible_primitives",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_flexible_primitives/__init__.py",
+                        "mod_bindgen_flexible_primitives",
+                    )?,
+                )
+            }
+            pub fn describe(
+                py: ::pyo3::marker::Python<'_>,
+                p_name: impl ::std::convert::AsRef<str>,
+                p_count: impl ::std::convert::Into<i64>,
+                p_scale: impl ::std::convert::Into<f64>,
+                p_label: impl ::std::convert::AsRef<str>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_name = ::std::convert::AsRef::<str>::as_ref(&p_name);
+                let p_count = ::std::convert::Into::<i64>::into(p_count);
+                let p_scale = ::std::convert::Into::<f64>::into(p_scale);
+                let p_label = ::std::convert::AsRef::<str>::as_ref(&p_label);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_flexible_primitives"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "describe"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_name, py),
+                                ::pyo3::ToPyObject::to_object(&p_count, py),
+                                ::pyo3::ToPyObject::to_object(&p_scale, py),
+                                ::pyo3::ToPyObject::to_object(&p_label, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_flexible_primitives::pyo3_embed_python_source_code(py, false).unwrap();
+        let name = String::from("apple");
+        let count: u32 = 3;
+        let scale: f32 = 2.0;
+        let label: &&str = &"fruit";
+        let description =
+            mod_bindgen_flexible_primitives::describe(py, name, count, scale, label).unwrap();
+        assert_eq!(description, "fruit: apple x6.0");
+    }
+}
+
+test_bindgen! {
+    bindgen_int_type_i128
+
+    cfg: pyo3_bindgen_engine::Config::builder().int_type(pyo3_bindgen_engine::IntType::I128).build(),
+
+    py: r#"
+    def square(x: int) -> int:
+        return x * x
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_int_type_i128 {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def square(x: int) -> int:\n    return x * x\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_int_type_i128")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_int_type_i128",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_int_type_i128/__init__.py",
+                        "mod_bindgen_int_type_i128",
+                    )?,
+                )
+            }
+            pub fn square(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: i128,
+            ) -> ::pyo3::PyResult<i128> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_int_type_i128"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "square"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_x, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_int_type_i128::pyo3_embed_python_source_code(py, false).unwrap();
+        let squared = mod_bindgen_int_type_i128::square(py, 1_000_000_000_000_000).unwrap();
+        assert_eq!(squared, 1_000_000_000_000_000_000_000_000_000_000);
+        assert!(mod_bindgen_int_type_i128::square(py, 1 << 100).is_err());
+    }
+}
+
+test_bindgen! {
+    #[cfg(feature = "num-bigint")]
+    bindgen_int_type_bigint
+
+    cfg: pyo3_bindgen_engine::Config::builder().int_type(pyo3_bindgen_engine::IntType::BigInt).build(),
+
+    py: r#"
+    def square(x: int) -> int:
+        return x * x
+    "#
+
+    rs: r#"
+    #[allow(
+        clippy::all,
+        clippy::nursery,
+        clippy::pedantic,
+        non_camel_case_types,
+        non_snake_case,
+        non_upper_case_globals,
+        unused
+    )]
+    pub mod mod_bindgen_int_type_bigint {
+        /// Embed the Python source code of the module into the Python interpreter
+        /// in order to enable the use of the generated Rust bindings.
+        ///
+        /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+        /// the host application of an extension module) is kept, unless `force` is set.
+        pub fn pyo3_embed_python_source_code(
+            py: ::pyo3::marker::Python<'_>,
+            force: bool,
+        ) -> ::pyo3::PyResult<()> {
+            const SOURCE_CODE: &str = "def square(x: int) -> int:\n    return x * x\n";
+            let modules = pyo3::types::PyAnyMethods::getattr(
+                py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                pyo3::intern!(py, "modules"),
+            )?;
+            if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_int_type_bigint")? {
+                return Ok(());
+            }
+            pyo3::types::PyAnyMethods::set_item(
+                &modules,
+                "mod_bindgen_int_type_bigint",
+                pyo3::types::PyModule::from_code_bound(
+                    py,
+                    SOURCE_CODE,
+                    "mod_bindgen_int_type_bigint/__init__.py",
+                    "mod_bindgen_int_type_bigint",
+                )?,
+            )
+        }
+        pub fn square(
+            py: ::pyo3::marker::Python<'_>,
+            p_x: &::num_bigint::BigInt,
+        ) -> ::pyo3::PyResult<::num_bigint::BigInt> {
+            ::pyo3::types::PyAnyMethods::extract(
+                &::pyo3::types::PyAnyMethods::call_method1(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_int_type_bigint"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "square"),
+                    ::pyo3::types::PyTuple::new_bound(
+                        py,
+                        [::pyo3::ToPyObject::to_object(&p_x, py)],
+                    ),
+                )?,
+            )
+        }
+    }
+    "#
+}
+
+test_bindgen! {
+    bindgen_fixed_width_int_overflow
+
+    py: r#"
+    import ctypes
+
+    def to_byte(x: int) -> ctypes.c_uint8:
+        return x
+
+    def to_short(x: "numpy.int16") -> "numpy.int16":
+        return x * 2
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_fixed_width_int_overflow {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import ctypes\n\ndef to_byte(x: int) -> ctypes.c_uint8:\n    return x\n\ndef to_short(x: \"numpy.int16\") -> \"numpy.int16\":\n    return x * 2\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_fixed_width_int_overflow")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_fixed_width_int_overflow",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_fixed_width_int_overflow/__init__.py",
+                        "mod_bindgen_fixed_width_int_overflow",
+                    )?,
+                )
+            }
+            pub fn to_byte(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: i64,
+            ) -> ::pyo3::PyResult<u8> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_fixed_width_int_overflow"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "to_byte"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_x, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn to_short(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: i16,
+            ) -> ::pyo3::PyResult<i16> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_fixed_width_int_overflow"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "to_short"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_x, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_fixed_width_int_overflow::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_fixed_width_int_overflow::to_byte(py, 255).unwrap(), 255);
+        let err = mod_bindgen_fixed_width_int_overflow::to_byte(py, 256).unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyOverflowError>(py));
+        assert!(mod_bindgen_fixed_width_int_overflow::to_byte(py, -1).is_err());
+        assert_eq!(mod_bindgen_fixed_width_int_overflow::to_short(py, 1000).unwrap(), 2000);
+        assert!(mod_bindgen_fixed_width_int_overflow::to_short(py, i16::MAX).is_err());
+    }
+}
+
+#[test]
+fn bindgen_module_from_ast() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    """Module docstring"""
+    import os.path as osp
+
+    raise RuntimeError("The module must not be imported")
+
+    threshold: float = 0.5
+
+    class Point:
+        """Point docstring"""
+
+        def __init__(self, x: int, y: int = 0):
+            self.x = x
+            self.y = y
+
+        @property
+        def norm(self) -> float:
+            return (self.x**2 + self.y**2) ** 0.5
+
+        @classmethod
+        def origin(cls) -> "Point":
+            return cls(0)
+
+        def shift(self, dx: int, /, *, dy: int = 0) -> "Point":
+            return Point(self.x + dx, self.y + dy)
+
+    def distance(a: Point, b: Point) -> float:
+        """Distance docstring"""
+        return ((a.x - b.x) ** 2 + (a.y - b.y) ** 2) ** 0.5
+    "# };
+    pyo3::prepare_freethreaded_python();
+
+    // Act
+    let bindings = pyo3::Python::with_gil(|py| {
+        use pyo3::prelude::*;
+        let ast = py
+            .import_bound("ast")
+            .unwrap()
+            .call_method1("parse", (CODE_PY,))
+            .unwrap();
+        pyo3_bindgen_engine::Codegen::default()
+            .module_from_ast(&ast, "mod_bindgen_module_from_ast")
+            .unwrap()
+            .generate()
+            .unwrap()
+    });
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("/// Module docstring\npub mod mod_bindgen_module_from_ast {"));
+    assert!(
+        bindings.contains("/// Point docstring\n    #[repr(transparent)]\n    pub struct Point")
+    );
+    assert!(bindings.contains("pub fn new<'py>(\n            py: ::pyo3::marker::Python<'py>,\n            p_x: i64,\n            p_y: i64,\n        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>>"));
+    assert!(bindings.contains("pub fn origin<'py>(\n            py: ::pyo3::marker::Python<'py>,\n        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Point>>"));
+    assert!(bindings.contains("fn shift<'py>(\n            &'py self,\n            p_dx: i64,\n            p_dy: i64,\n        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Point>>;"));
+    assert!(bindings.contains("fn norm(&self) -> ::pyo3::PyResult<f64>;"));
+    assert!(bindings.contains("p_a: impl ::std::borrow::Borrow<::pyo3::Bound<'py, Point>>,"));
+    assert!(bindings.contains("pub fn set_threshold("));
+}
+
+#[test]
+fn bindgen_body_mode_stub() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import Optional
+
+    version: str = "1.0"
+
+    class Counter:
+        """Counter docstring"""
+
+        def __init__(self, start: int = 0, *, step: int = 1):
+            self.count = start
+            self.step = step
+
+        def __eq__(self, other) -> bool:
+            return self.count == other.count
+
+        @property
+        def value(self) -> int:
+            return self.count
+
+        @value.setter
+        def value(self, value: int):
+            self.count = value
+
+        def increment(self, times: Optional[int] = None) -> int:
+            self.count += self.step * (times or 1)
+            return self.count
+
+    def make_counter(start: int, *args, **kwargs) -> Counter:
+        """Make counter docstring"""
+        return Counter(start)
+    "# };
+    let generate = |bodies: pyo3_bindgen_engine::BodyMode| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .bodies(bodies)
+                .impl_comparisons(true)
+                .emit_builder_for_constructors(true)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_body_mode_stub")
+        .unwrap()
+        .generate()
+        .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+    let signatures = |bindings: &str| {
+        bindings
+            .match_indices("fn ")
+            .map(|(i, _)| {
+                let signature = &bindings[i..];
+                signature[..signature.find(['{', ';']).unwrap()].to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Act
+    let full_bindings = generate(pyo3_bindgen_engine::BodyMode::Full);
+    let stub_bindings = generate(pyo3_bindgen_engine::BodyMode::Stub);
+
+    // Assert
+    assert!(full_bindings.contains("call_method"));
+    assert!(!stub_bindings.contains("call_method"));
+    assert!(stub_bindings.contains("unimplemented!(\"regenerate with BodyMode::Full\")"));
+    assert!(stub_bindings.len() * 2 < full_bindings.len());
+    assert!(stub_bindings.contains("/// Counter docstring"));
+    assert!(stub_bindings.contains("/// Make counter docstring"));
+    assert!(!signatures(&full_bindings).is_empty());
+    assert_eq!(signatures(&stub_bindings), signatures(&full_bindings));
+}
+
+#[test]
+fn bindgen_function_bare_star() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def f(a, *, b):
+        return (a, b)
+
+    def g(a, /, b, *, c):
+        return (a, b, c)
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_function_bare_star")
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert (the `*` and `/` separators do not produce any parameters)
+    assert!(bindings.contains("p_a"));
+    assert!(bindings.contains("p_b"));
+    assert!(bindings.contains("p_c"));
+    assert!(!bindings.contains("p_args"));
+    assert!(!bindings.contains("p_ :"));
+    assert_eq!(bindings.matches("p_a : impl").count(), 2);
+    assert_eq!(bindings.matches("p_c : impl").count(), 1);
+}
+
+test_bindgen! {
+    bindgen_function_default_args
+
+    py: r#"
+    def f(a, b=5, *, c=7):
+        return (a, b, c)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_function_default_args {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def f(a, b=5, *, c=7):\n    return (a, b, c)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_function_default_args")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_function_default_args",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_function_default_args/__init__.py",
+                        "mod_bindgen_function_default_args",
+                    )?,
+                )
+            }
+            pub fn f<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_a: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_b: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_c: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                let p_a = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_a, py);
+                let p_a = p_a.bind(py);
+                let p_b = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_b, py);
+                let p_b = p_b.bind(py);
+                let p_c = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_c, py);
+                let p_c = p_c.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_function_default_args"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "f"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_a, py)],
+                        ),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_b,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "b"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_c,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "c"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::PyAnyMethods;
+
+        // Python defaults are preserved for optional arguments that are passed as `None`
+        let result = mod_bindgen_function_default_args::f(py, 1, py.None(), py.None()).unwrap();
+        assert_eq!(result.extract::<(i64, i64, i64)>().unwrap(), (1, 5, 7));
+
+        // Provided optional arguments override the Python defaults
+        let result = mod_bindgen_function_default_args::f(py, 1, 2, 3).unwrap();
+        assert_eq!(result.extract::<(i64, i64, i64)>().unwrap(), (1, 2, 3));
+    }
+}
+
+test_bindgen! {
+    bindgen_function_wrapped
+
+    py: r#"
+    import functools
+    import inspect
+
+    def logged(function):
+        @functools.wraps(function)
+        def wrapper(*args, **kwargs):
+            return function(*args, **kwargs)
+        return wrapper
+
+    def opaque(function):
+        @functools.wraps(function)
+        def wrapper(*args, **kwargs):
+            return function(*args, **kwargs)
+        wrapper.__signature__ = inspect.signature(wrapper, follow_wrapped=False)
+        return wrapper
+
+    @logged
+    def scale(value: float, factor: float) -> float:
+        """Scale the value by the factor"""
+        return value * factor
+
+    @opaque
+    def shift(value: int, offset: int) -> int:
+        return value + offset
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_function_wrapped {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import functools\nimport inspect\n\ndef logged(function):\n    @functools.wraps(function)\n    def wrapper(*args, **kwargs):\n        return function(*args, **kwargs)\n    return wrapper\n\ndef opaque(function):\n    @functools.wraps(function)\n    def wrapper(*args, **kwargs):\n        return function(*args, **kwargs)\n    wrapper.__signature__ = inspect.signature(wrapper, follow_wrapped=False)\n    return wrapper\n\n@logged\ndef scale(value: float, factor: float) -> float:\n    \"\"\"Scale the value by the factor\"\"\"\n    return value * factor\n\n@opaque\ndef shift(value: int, offset: int) -> int:\n    return value + offset\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_function_wrapped")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_function_wrapped",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_function_wrapped/__init__.py",
+                        "mod_bindgen_function_wrapped",
+                    )?,
+                )
+            }
+            pub fn logged<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_function: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                let p_function = ::pyo3::IntoPy::<
+                    ::pyo3::Py<::pyo3::types::PyAny>,
+                >::into_py(p_function, py);
+                let p_function = p_function.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_function_wrapped"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "logged"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_function, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn opaque<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_function: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                let p_function = ::pyo3::IntoPy::<
+                    ::pyo3::Py<::pyo3::types::PyAny>,
+                >::into_py(p_function, py);
+                let p_function = p_function.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_function_wrapped"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "opaque"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_function, py)],
+                        ),
+                    )?,
+                )
+            }
+            /// Scale the value by the factor
+            pub fn scale(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: f64,
+                p_factor: f64,
+            ) -> ::pyo3::PyResult<f64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_function_wrapped"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scale"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ::pyo3::ToPyObject::to_object(&p_factor, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+            pub fn shift(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: i64,
+                p_offset: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_function_wrapped"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "shift"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ::pyo3::ToPyObject::to_object(&p_offset, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        // The real signatures of the decorated functions are recovered from `__wrapped__`
+        assert_eq!(mod_bindgen_function_wrapped::scale(py, 1.5, 2.0).unwrap(), 3.0);
+        assert_eq!(mod_bindgen_function_wrapped::shift(py, 1, 2).unwrap(), 3);
+    }
+}
+
+#[test]
+fn bindgen_preserve_default_repr_in_docs() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def scale(value: float, factor: float = 2.5, *, label: str = "x", limit: int = 10):
+        """Scale docstring"""
+        return value * factor
+
+    def shift(value: int, offset: int = -1):
+        return value + offset
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .preserve_default_repr_in_docs(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_preserve_default_repr_in_docs")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("Scale docstring"));
+    assert!(bindings.contains("Parameter `factor` default: `2.5`"));
+    assert!(bindings.contains("Parameter `label` default: `'x'`"));
+    assert!(bindings.contains("Parameter `limit` default: `10`"));
+    assert!(bindings.contains("Parameter `offset` default: `-1`"));
+    assert!(!bindings.contains("Parameter `value` default"));
+}
+
+#[test]
+fn bindgen_default_helpers() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    import pathlib
+
+    def decode(
+        data: bytes,
+        encoding: str = "utf-8",
+        retries: int = -3,
+        fallback: str | None = None,
+        ignored: list = [],
+        root: pathlib.PurePosixPath = pathlib.PurePosixPath("/"),
+    ) -> str:
+        return data.decode(encoding)
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .preserve_default_repr_in_docs(true)
+            .default_helpers(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_default_helpers")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("Parameter `encoding` default: `'utf-8'`"));
+    assert!(bindings.contains("Parameter `retries` default: `-3`"));
+    assert!(bindings.contains("Parameter `fallback` default: `None`"));
+    assert!(bindings.contains("Parameter `ignored` default: `[]`"));
+    assert!(bindings.contains(
+        "Parameter `root` default: instance of `PurePosixPath` (not a literal, so its value is not shown)"
+    ));
+    assert!(bindings.contains("pub mod decode_defaults {"));
+    assert!(bindings.contains("pub const ENCODING: &str = \"utf-8\";"));
+    assert!(bindings.contains("pub const RETRIES: i64 = -3;"));
+    assert!(!bindings.contains("pub const FALLBACK"));
+    assert!(!bindings.contains("pub const IGNORED"));
+    assert!(!bindings.contains("pub const ROOT"));
+}
+
+#[test]
+fn bindgen_append_code() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Point:
+        def __init__(self, x: int, y: int):
+            self.x = x
+            self.y = y
+
+    def origin() -> Point:
+        return Point(0, 0)
+    "# };
+    let codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_append_code")
+        .unwrap();
+
+    // Act
+    let bindings = codegen
+        .clone()
+        .append_to_class_impl(
+            "mod_bindgen_append_code.Point",
+            "pub fn is_point() -> bool { true }",
+        )
+        .append_to_module(
+            "mod_bindgen_append_code",
+            "pub fn distance(a: &::pyo3::Bound<'_, Point>) -> f64 { let _ = a; 0.0 }",
+        )
+        .generate()
+        .unwrap();
+    let invalid_err = codegen
+        .clone()
+        .append_to_module("mod_bindgen_append_code", "pub fn broken(")
+        .generate()
+        .unwrap_err();
+    let missing_err = codegen
+        .append_to_class_impl("mod_bindgen_append_code.Missing", "")
+        .generate()
+        .unwrap_err();
+
+    // Assert
+    let bindings: syn::File = syn::parse2(bindings).unwrap();
+    let syn::Item::Mod(module) = &bindings.items[0] else {
+        panic!("Expected a module");
+    };
+    let items = &module.content.as_ref().unwrap().1;
+    assert!(items.iter().any(|item| matches!(
+        item,
+        syn::Item::Fn(function) if function.sig.ident == "distance"
+    )));
+    assert!(items.iter().any(|item| matches!(
+        item,
+        syn::Item::Impl(impl_block) if impl_block.trait_.is_none()
+            && quote::quote!(#impl_block).to_string().contains("fn is_point")
+    )));
+    assert!(invalid_err.to_string().contains("pub fn broken("));
+    assert!(missing_err
+        .to_string()
+        .contains("mod_bindgen_append_code.Missing"));
+}
+
+#[test]
+fn bindgen_override_signature() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def compress(data, *args, **kwargs):
+        return data
+
+    class Codec:
+        def encode(self, value):
+            return value
+    "# };
+    let codegen = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_override_signature")
+        .unwrap();
+
+    // Act
+    let bindings = codegen
+        .clone()
+        .override_signature(
+            "mod_bindgen_override_signature.compress",
+            "(data: bytes, /, *, level: int = 6) -> bytes",
+        )
+        .override_signature(
+            "mod_bindgen_override_signature.Codec.encode",
+            "(self, value: str) -> list[int]",
+        )
+        .generate()
+        .unwrap()
+        .to_string();
+    let missing_err = codegen
+        .override_signature("mod_bindgen_override_signature.compress", "(data: bytes)")
+        .override_signature("mod_bindgen_override_signature.decompress", "(data: bytes)")
+        .override_signature("mod_bindgen_override_signature.Codec.decode", "()")
+        .generate()
+        .unwrap_err();
+
+    // Assert
+    assert!(bindings.contains(
+        "pub fn compress (py : :: pyo3 :: marker :: Python < '_ > , p_data : & [u8] , p_level : i64) -> :: pyo3 :: PyResult < Vec < u8 > >"
+    ));
+    assert!(bindings
+        .contains("fn encode (& self , p_value : & str) -> :: pyo3 :: PyResult < Vec < i64 > >"));
+    assert!(missing_err.to_string().contains(
+        "'mod_bindgen_override_signature.decompress', 'mod_bindgen_override_signature.Codec.decode'"
+    ));
+}
+
+#[test]
+fn bindgen_override_signature_callable_class_param() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Doubler:
+        def __call__(self, x: int) -> int:
+            return 2 * x
+
+    def apply(callback, x):
+        return callback(x)
+    "# };
+    let codegen = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .fuzzy_type_resolution(true)
+            .callable_class_params_accept_closures(true)
+            .build(),
+    )
+    .module_from_str(
+        CODE_PY,
+        "mod_bindgen_override_signature_callable_class_param",
+    )
+    .unwrap();
+
+    // Act
+    let bindings = codegen
+        .override_signature(
+            "mod_bindgen_override_signature_callable_class_param.apply",
+            "(callback: Doubler, x: int) -> int",
+        )
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert (the overridden annotations are resolved by name and relaxed like the original ones)
+    assert!(bindings.contains(
+        "p_callback : impl :: pyo3 :: IntoPy < :: pyo3 :: Py < :: pyo3 :: types :: PyAny > > , p_x : i64"
+    ));
+    assert!(bindings.contains("Note: Parameter `callback` accepts any Python callable"));
+}
+
+#[test]
+fn bindgen_pathlike_types() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    import os
+
+    def open_str(path: os.PathLike[str]) -> None:
+        pass
+
+    def open_bytes(path: os.PathLike[bytes]) -> None:
+        pass
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_pathlike_types")
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert
+    assert!(bindings.contains("pub fn open_str < 'py > (py : :: pyo3 :: marker :: Python < 'py > , p_path : & :: std :: path :: Path)"));
+    assert!(bindings.contains(
+        "pub fn open_bytes < 'py > (py : :: pyo3 :: marker :: Python < 'py > , p_path : & [u8])"
+    ));
+}
+
+#[test]
+fn bindgen_abstract_functions() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Shape:
+        def area(self) -> float:
+            """Area docstring"""
+            raise NotImplementedError("Subclasses must implement `area`")
+
+        def name(self) -> str:
+            return "shape"
+
+    def draw(shape: Shape):
+        raise NotImplementedError
+
+    def describe(shape: Shape) -> str:
+        if shape is None:
+            raise NotImplementedError
+        return shape.name()
+    "# };
+    // Note: The source code is written to a file so that it is available via `inspect.getsource`
+    let module_dir = std::env::temp_dir().join(format!("pyo3_bindgen_{}", std::process::id()));
+    std::fs::create_dir_all(&module_dir).unwrap();
+    std::fs::write(
+        module_dir.join("mod_bindgen_abstract_functions.py"),
+        CODE_PY,
+    )
+    .unwrap();
+    pyo3::prepare_freethreaded_python();
+    pyo3::Python::with_gil(|py| {
+        use pyo3::types::{PyAnyMethods, PyListMethods};
+        py.import_bound("sys")
+            .unwrap()
+            .getattr("path")
+            .unwrap()
+            .downcast_into::<pyo3::types::PyList>()
+            .unwrap()
+            .insert(0, &module_dir)
+            .unwrap();
+    });
+    let generate = |policy: pyo3_bindgen_engine::AbstractPolicy| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .abstract_functions(policy)
+                .build(),
+        )
+        .module_name("mod_bindgen_abstract_functions")
+        .unwrap()
+        .generate()
+        .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+    let generate_from_ast = |policy: pyo3_bindgen_engine::AbstractPolicy| {
+        let bindings = pyo3::Python::with_gil(|py| {
+            use pyo3::types::PyAnyMethods;
+            let ast = py
+                .import_bound("ast")
+                .unwrap()
+                .call_method1("parse", (CODE_PY,))
+                .unwrap();
+            pyo3_bindgen_engine::Codegen::new(
+                pyo3_bindgen_engine::Config::builder()
+                    .abstract_functions(policy)
+                    .build(),
+            )
+            .module_from_ast(&ast, "mod_bindgen_abstract_functions")
+            .unwrap()
+            .generate()
+            .unwrap()
+        });
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+
+    // Act
+    let generated = generate(pyo3_bindgen_engine::AbstractPolicy::Generate);
+    let warned = generate(pyo3_bindgen_engine::AbstractPolicy::GenerateWithWarning);
+    let skipped = generate(pyo3_bindgen_engine::AbstractPolicy::Skip);
+    let skipped_from_ast = generate_from_ast(pyo3_bindgen_engine::AbstractPolicy::Skip);
+
+    // Assert
+    for bindings in [&generated, &warned] {
+        assert!(bindings.contains("fn area(&self) -> ::pyo3::PyResult<f64>"));
+        assert!(bindings.contains("pub fn draw<'py>("));
+    }
+    assert!(!generated.contains("#[deprecated"));
+    assert!(warned.contains(
+        "#[deprecated(note = \"abstract in Python\")]\n        fn area(&self) -> ::pyo3::PyResult<f64>;"
+    ));
+    assert!(warned.contains("#[deprecated(note = \"abstract in Python\")]\n    pub fn draw<'py>("));
+    assert!(warned.contains("Area docstring\n\nWarning: This function is abstract in Python"));
+    assert_eq!(warned.matches("#[deprecated").count(), 2);
+    for bindings in [&skipped, &skipped_from_ast] {
+        assert!(!bindings.contains("fn area(&self)"));
+        assert!(!bindings.contains("pub fn draw<'py>("));
+    }
+    for bindings in [&generated, &warned, &skipped, &skipped_from_ast] {
+        assert!(bindings.contains("fn name(&self)"));
+        assert!(bindings.contains("pub fn describe<'py>("));
+    }
+}
+
+#[test]
+fn bindgen_abstract_method_handling() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    import abc
+
+    class Shape(abc.ABC):
+        @abc.abstractmethod
+        def area(self) -> float:
+            """Area docstring"""
+
+        def name(self) -> str:
+            return type(self).__name__
+    "# };
+    let generate = |handling: pyo3_bindgen_engine::AbstractMethodHandling| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .abstract_method_handling(handling)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_abstract_method_handling")
+        .unwrap()
+        .generate()
+        .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+    let generate_from_ast = |handling: pyo3_bindgen_engine::AbstractMethodHandling| {
+        let bindings = pyo3::Python::with_gil(|py| {
+            use pyo3::types::PyAnyMethods;
+            let ast = py
+                .import_bound("ast")
+                .unwrap()
+                .call_method1("parse", (CODE_PY,))
+                .unwrap();
+            pyo3_bindgen_engine::Codegen::new(
+                pyo3_bindgen_engine::Config::builder()
+                    .abstract_method_handling(handling)
+                    .build(),
+            )
+            .module_from_ast(&ast, "mod_bindgen_abstract_method_handling")
+            .unwrap()
+            .generate()
+            .unwrap()
+        });
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+
+    // Act
+    let emitted = generate(pyo3_bindgen_engine::AbstractMethodHandling::Emit);
+    let marked = generate(pyo3_bindgen_engine::AbstractMethodHandling::Mark);
+    let skipped = generate(pyo3_bindgen_engine::AbstractMethodHandling::Skip);
+    let skipped_from_ast = generate_from_ast(pyo3_bindgen_engine::AbstractMethodHandling::Skip);
+
+    // Assert
+    for bindings in [&emitted, &marked] {
+        assert!(bindings.contains("fn area(&self) -> ::pyo3::PyResult<f64>"));
+    }
+    assert!(!emitted.contains("#[deprecated"));
+    assert!(marked.contains(
+        "#[deprecated(note = \"abstract in Python\")]\n        fn area(&self) -> ::pyo3::PyResult<f64>;"
+    ));
+    assert!(marked
+        .contains("Area docstring\n\nWarning: This method is declared with `@abstractmethod`"));
+    for bindings in [&skipped, &skipped_from_ast] {
+        assert!(!bindings.contains("fn area(&self)"));
+    }
+    for bindings in [&emitted, &marked, &skipped, &skipped_from_ast] {
+        assert!(bindings.contains("fn name(&self)"));
+    }
+}
+
+test_bindgen! {
+    bindgen_init_strategy_prepare
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .init_strategy(pyo3_bindgen_engine::InitStrategy::Prepare)
+        .build(),
+
+    py: r#"
+    calls = []
+
+    def record(value: int) -> int:
+        calls.append(value)
+        return len(calls)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_init_strategy_prepare {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "calls = []\n\ndef record(value: int) -> int:\n    calls.append(value)\n    return len(calls)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_init_strategy_prepare")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_init_strategy_prepare",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_init_strategy_prepare/__init__.py",
+                        "mod_bindgen_init_strategy_prepare",
+                    )?,
+                )
+            }
+            /// Initialize the Python interpreter (unless it is already initialized) and call the given closure
+            /// while holding the GIL, so that the generated bindings can be used.
+            pub fn pyo3_with_gil<F, R>(f: F) -> ::pyo3::PyResult<R>
+            where
+                F: for<'py> FnOnce(::pyo3::marker::Python<'py>) -> ::pyo3::PyResult<R>,
+            {
+                ::pyo3::prepare_freethreaded_python();
+                ::pyo3::marker::Python::with_gil(|py| {
+                    pyo3_embed_python_source_code(py, false)?;
+                    f(py)
+                })
+            }
+            pub fn record(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_init_strategy_prepare"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "record"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn calls<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_init_strategy_prepare"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "calls"),
+                    )?,
+                )
+            }
+            pub fn set_calls<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: &[::pyo3::Bound<'py, ::pyo3::types::PyAny>],
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_init_strategy_prepare"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "calls"),
+                    p_value,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        // The interpreter is already initialized manually, so it must not be initialized again and
+        // the source code of the module must be embedded only once
+        let _ = py;
+        assert_eq!(mod_bindgen_init_strategy_prepare::pyo3_with_gil(|py| mod_bindgen_init_strategy_prepare::record(py, 1)).unwrap(), 1);
+        assert_eq!(mod_bindgen_init_strategy_prepare::pyo3_with_gil(|py| mod_bindgen_init_strategy_prepare::record(py, 2)).unwrap(), 2);
+    }
+}
+
+test_bindgen! {
+    bindgen_well_known_types
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .logging_helpers(true)
+        .build(),
+
+    py: r#"
+    import logging
+    import re
+
+    logger = logging.getLogger("mod_bindgen_well_known_types")
+    records = []
+
+    class _RecordingHandler(logging.Handler):
+        def emit(self, record):
+            records.append(f"{record.levelname}: {record.getMessage()}")
+
+    logger.handlers.clear()
+    logger.addHandler(_RecordingHandler())
+    logger.setLevel(logging.INFO)
+
+    WORD = re.compile(r"[a-z]+")
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_well_known_types {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import logging\nimport re\n\nlogger = logging.getLogger(\"mod_bindgen_well_known_types\")\nrecords = []\n\nclass _RecordingHandler(logging.Handler):\n    def emit(self, record):\n        records.append(f\"{record.levelname}: {record.getMessage()}\")\n\nlogger.handlers.clear()\nlogger.addHandler(_RecordingHandler())\nlogger.setLevel(logging.INFO)\n\nWORD = re.compile(r\"[a-z]+\")\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_well_known_types")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_well_known_types",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_well_known_types/__init__.py",
+                        "mod_bindgen_well_known_types",
+                    )?,
+                )
+            }
+            pub fn WORD<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "WORD"),
+                    )?,
+                )
+            }
+            pub fn set_WORD<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "WORD"),
+                    p_value,
+                )
+            }
+            /// Call `search` of the `WORD` regular expression pattern of the module with the given string.
+            pub fn WORD_search<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                string: &str,
+            ) -> ::pyo3::PyResult<
+                ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            > {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_well_known_types"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "WORD"),
+                        )?,
+                        ::pyo3::intern!(py, "search"),
+                        (string,),
+                    )?,
+                )
+            }
+            /// Call `match` of the `WORD` regular expression pattern of the module with the given string.
+            pub fn WORD_match<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                string: &str,
+            ) -> ::pyo3::PyResult<
+                ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            > {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_well_known_types"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "WORD"),
+                        )?,
+                        ::pyo3::intern!(py, "match"),
+                        (string,),
+                    )?,
+                )
+            }
+            /// Call `findall` of the `WORD` regular expression pattern of the module with the given string.
+            pub fn WORD_findall<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                string: &str,
+            ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_well_known_types"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "WORD"),
+                        )?,
+                        ::pyo3::intern!(py, "findall"),
+                        (string,),
+                    )?,
+                )
+            }
+            pub fn logger<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "logger"),
+                    )?,
+                )
+            }
+            pub fn set_logger<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "logger"),
+                    p_value,
+                )
+            }
+            /// Log the message with the `INFO` level via the `logger` logger of the module.
+            pub fn log_info(py: ::pyo3::marker::Python<'_>, msg: &str) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::call_method1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "logger"),
+                    )?,
+                    ::pyo3::intern!(py, "info"),
+                    (msg,),
+                )?;
+                ::std::result::Result::Ok(())
+            }
+            /// Log the message with the `WARNING` level via the `logger` logger of the module.
+            pub fn log_warning(
+                py: ::pyo3::marker::Python<'_>,
+                msg: &str,
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::call_method1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "logger"),
+                    )?,
+                    ::pyo3::intern!(py, "warning"),
+                    (msg,),
+                )?;
+                ::std::result::Result::Ok(())
+            }
+            /// Log the message with the `ERROR` level via the `logger` logger of the module.
+            pub fn log_error(py: ::pyo3::marker::Python<'_>, msg: &str) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::call_method1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "logger"),
+                    )?,
+                    ::pyo3::intern!(py, "error"),
+                    (msg,),
+                )?;
+                ::std::result::Result::Ok(())
+            }
+            pub fn records<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "records"),
+                    )?,
+                )
+            }
+            pub fn set_records<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: &[::pyo3::Bound<'py, ::pyo3::types::PyAny>],
+            ) -> ::pyo3::PyResult<()> {
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(::pyo3::intern!(py, "mod_bindgen_well_known_types"))?
+                        .as_any(),
+                    ::pyo3::intern!(py, "records"),
+                    p_value,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_well_known_types::pyo3_embed_python_source_code(py, false).unwrap();
+
+        mod_bindgen_well_known_types::log_info(py, "started").unwrap();
+        mod_bindgen_well_known_types::log_warning(py, "running").unwrap();
+        mod_bindgen_well_known_types::log_error(py, "failed").unwrap();
+        let records: Vec<String> = mod_bindgen_well_known_types::records(py)
+            .unwrap()
+            .iter()
+            .map(|record| pyo3::types::PyAnyMethods::extract(record).unwrap())
+            .collect();
+        assert_eq!(records, ["INFO: started", "WARNING: running", "ERROR: failed"]);
+
+        assert_eq!(mod_bindgen_well_known_types::WORD_findall(py, "ab 12 cd").unwrap().len(), 2);
+        assert!(mod_bindgen_well_known_types::WORD_match(py, "abc").unwrap().is_some());
+        assert!(mod_bindgen_well_known_types::WORD_search(py, "123").unwrap().is_none());
+    }
+}
+
+test_bindgen! {
+    #[cfg(not(any(feature = "uuid", feature = "rust-decimal")))]
+    bindgen_uuid_decimal_types_as_strings
+
+    py: r#"
+    import uuid
+    from decimal import Decimal
+
+    PRICE = Decimal("1.10")
+
+    def next_uuid(value: uuid.UUID) -> uuid.UUID:
+        return uuid.UUID(int=value.int + 1)
+
+    def scale(value: Decimal, factor: int) -> Decimal:
+        return value * factor
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_uuid_decimal_types_as_strings {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import uuid\nfrom decimal import Decimal\n\nPRICE = Decimal(\"1.10\")\n\ndef next_uuid(value: uuid.UUID) -> uuid.UUID:\n    return uuid.UUID(int=value.int + 1)\n\ndef scale(value: Decimal, factor: int) -> Decimal:\n    return value * factor\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_uuid_decimal_types_as_strings")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_uuid_decimal_types_as_strings",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_uuid_decimal_types_as_strings/__init__.py",
+                        "mod_bindgen_uuid_decimal_types_as_strings",
+                    )?,
+                )
+            }
+            pub fn next_uuid(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: &str,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "uuid"))?.as_any(),
+                        ::pyo3::intern!(py, "UUID"),
+                    )?,
+                    (p_value,),
+                )?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_uuid_decimal_types_as_strings"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "next_uuid"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::string::String,
+                    >(::pyo3::types::PyAnyMethods::str(&value)?.as_any())
+                }
+            }
+            /// Note: The `decimal.Decimal` value is returned as its exact string representation in order to preserve its precision.
+            pub fn scale(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: &str,
+                p_factor: i64,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "decimal"))?.as_any(),
+                        ::pyo3::intern!(py, "Decimal"),
+                    )?,
+                    (p_value,),
+                )?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_uuid_decimal_types_as_strings"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scale"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ::pyo3::ToPyObject::to_object(&p_factor, py),
+                            ],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::string::String,
+                    >(::pyo3::types::PyAnyMethods::str(&value)?.as_any())
+                }
+            }
+            /// Note: The `decimal.Decimal` value is returned as its exact string representation in order to preserve its precision.
+            pub fn PRICE(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                {
+                    let value = ::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_uuid_decimal_types_as_strings"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "PRICE"),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::string::String,
+                    >(::pyo3::types::PyAnyMethods::str(&value)?.as_any())
+                }
+            }
+            pub fn set_PRICE(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: &str,
+            ) -> ::pyo3::PyResult<()> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "decimal"))?.as_any(),
+                        ::pyo3::intern!(py, "Decimal"),
+                    )?,
+                    (p_value,),
+                )?;
+                ::pyo3::types::PyAnyMethods::setattr(
+                    py
+                        .import_bound(
+                            ::pyo3::intern!(py, "mod_bindgen_uuid_decimal_types_as_strings"),
+                        )?
+                        .as_any(),
+                    ::pyo3::intern!(py, "PRICE"),
+                    p_value,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_uuid_decimal_types_as_strings::pyo3_embed_python_source_code(py, false).unwrap();
+
+        assert_eq!(
+            mod_bindgen_uuid_decimal_types_as_strings::next_uuid(py, "12345678-1234-5678-1234-567812345678").unwrap(),
+            "12345678-1234-5678-1234-567812345679"
+        );
+        assert!(mod_bindgen_uuid_decimal_types_as_strings::next_uuid(py, "invalid").is_err());
+        assert_eq!(mod_bindgen_uuid_decimal_types_as_strings::scale(py, "1.10", 3).unwrap(), "3.30");
+        assert_eq!(mod_bindgen_uuid_decimal_types_as_strings::PRICE(py).unwrap(), "1.10");
+        mod_bindgen_uuid_decimal_types_as_strings::set_PRICE(py, "2.50").unwrap();
+        assert_eq!(mod_bindgen_uuid_decimal_types_as_strings::PRICE(py).unwrap(), "2.50");
+    }
+}
+
+test_bindgen! {
+    #[cfg(feature = "uuid")]
+    bindgen_uuid_type
+
+    py: r#"
+    import uuid
+
+    def next_uuid(value: uuid.UUID) -> uuid.UUID:
+        return uuid.UUID(int=value.int + 1)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_uuid_type {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import uuid\n\ndef next_uuid(value: uuid.UUID) -> uuid.UUID:\n    return uuid.UUID(int=value.int + 1)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_uuid_type")?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_uuid_type",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_uuid_type/__init__.py",
+                        "mod_bindgen_uuid_type",
+                    )?,
+                )
+            }
+            pub fn next_uuid(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: ::uuid::Uuid,
+            ) -> ::pyo3::PyResult<::uuid::Uuid> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "uuid"))?.as_any(),
+                        ::pyo3::intern!(py, "UUID"),
+                    )?,
+                    (p_value.to_string(),),
+                )?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "mod_bindgen_uuid_type"))?.as_any(),
+                        ::pyo3::intern!(py, "next_uuid"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?;
+                    ::uuid::Uuid::parse_str(
+                            &::pyo3::types::PyStringMethods::to_cow(
+                                &::pyo3::types::PyAnyMethods::str(&value)?,
+                            )?,
+                        )
+                        .map_err(|err| ::pyo3::exceptions::PyValueError::new_err(
+                            err.to_string(),
+                        ))
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_uuid_type::pyo3_embed_python_source_code(py, false).unwrap();
+
+        let value = uuid::Uuid::parse_str("12345678-1234-5678-1234-567812345678").unwrap();
+        assert_eq!(
+            mod_bindgen_uuid_type::next_uuid(py, value).unwrap(),
+            uuid::Uuid::from_u128(value.as_u128() + 1)
+        );
+    }
+}
+
+test_bindgen! {
+    #[cfg(feature = "rust-decimal")]
+    bindgen_rust_decimal_type
+
+    py: r#"
+    from decimal import Decimal
+
+    def scale(value: Decimal, factor: int) -> Decimal:
+        return value * factor
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_rust_decimal_type {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from decimal import Decimal\n\ndef scale(value: Decimal, factor: int) -> Decimal:\n    return value * factor\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_rust_decimal_type")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_rust_decimal_type",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_rust_decimal_type/__init__.py",
+                        "mod_bindgen_rust_decimal_type",
+                    )?,
+                )
+            }
+            pub fn scale(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: ::rust_decimal::Decimal,
+                p_factor: i64,
+            ) -> ::pyo3::PyResult<::rust_decimal::Decimal> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "decimal"))?.as_any(),
+                        ::pyo3::intern!(py, "Decimal"),
+                    )?,
+                    (p_value.to_string(),),
+                )?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_rust_decimal_type"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scale"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ::pyo3::ToPyObject::to_object(&p_factor, py),
+                            ],
+                        ),
+                    )?;
+                    <::rust_decimal::Decimal as ::std::str::FromStr>::from_str(
+                            &::pyo3::types::PyStringMethods::to_cow(
+                                ::pyo3::types::PyAnyMethods::downcast::<
+                                    ::pyo3::types::PyString,
+                                >(
+                                    &::pyo3::types::PyAnyMethods::call_method1(
+                                        &value,
+                                        "__format__",
+                                        ("f",),
+                                    )?,
+                                )?,
+                            )?,
+                        )
+                        .map_err(|err| ::pyo3::exceptions::PyValueError::new_err(
+                            err.to_string(),
+                        ))
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_rust_decimal_type::pyo3_embed_python_source_code(py, false).unwrap();
+
+        let value = std::str::FromStr::from_str("1.10").unwrap();
+        assert_eq!(
+            mod_bindgen_rust_decimal_type::scale(py, value, 3).unwrap().to_string(),
+            "3.30"
+        );
+        assert_eq!(
+            mod_bindgen_rust_decimal_type::scale(py, value, 1000).unwrap().to_string(),
+            "1100.00"
+        );
+    }
+}
+
+test_bindgen! {
+    bindgen_fraction_type
+
+    py: r#"
+    from fractions import Fraction
+    from typing import Optional
+
+    def invert(value: Fraction) -> Fraction:
+        return 1 / value
+
+    def maybe_invert(value: Optional[Fraction]) -> Optional[Fraction]:
+        return None if value is None else 1 / value
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_fraction_type {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from fractions import Fraction\nfrom typing import Optional\n\ndef invert(value: Fraction) -> Fraction:\n    return 1 / value\n\ndef maybe_invert(value: Optional[Fraction]) -> Optional[Fraction]:\n    return None if value is None else 1 / value\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_fraction_type")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_fraction_type",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_fraction_type/__init__.py",
+                        "mod_bindgen_fraction_type",
+                    )?,
+                )
+            }
+            pub fn invert(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: (i64, i64),
+            ) -> ::pyo3::PyResult<(i64, i64)> {
+                let p_value = ::pyo3::types::PyAnyMethods::call1(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py.import_bound(::pyo3::intern!(py, "fractions"))?.as_any(),
+                        ::pyo3::intern!(py, "Fraction"),
+                    )?,
+                    (p_value.0, p_value.1),
+                )?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_fraction_type"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "invert"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?;
+                    ::std::result::Result::Ok((
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(&value, "numerator")?,
+                        )?,
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(&value, "denominator")?,
+                        )?,
+                    ))
+                }
+            }
+            pub fn maybe_invert(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: ::std::option::Option<(i64, i64)>,
+            ) -> ::pyo3::PyResult<::std::option::Option<(i64, i64)>> {
+                let p_value = match p_value {
+                    ::std::option::Option::Some(p_value) => {
+                        ::std::option::Option::Some(
+                            ::pyo3::types::PyAnyMethods::call1(
+                                &::pyo3::types::PyAnyMethods::getattr(
+                                    py.import_bound(::pyo3::intern!(py, "fractions"))?.as_any(),
+                                    ::pyo3::intern!(py, "Fraction"),
+                                )?,
+                                (p_value.0, p_value.1),
+                            )?,
+                        )
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                };
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_fraction_type"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "maybe_invert"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?;
+                    if ::pyo3::types::PyAnyMethods::is_none(&value) {
+                        ::std::result::Result::Ok(::std::option::Option::None)
+                    } else {
+                        ::std::result::Result::map(
+                            ::std::result::Result::Ok((
+                                ::pyo3::types::PyAnyMethods::extract(
+                                    &::pyo3::types::PyAnyMethods::getattr(&value, "numerator")?,
+                                )?,
+                                ::pyo3::types::PyAnyMethods::extract(
+                                    &::pyo3::types::PyAnyMethods::getattr(&value, "denominator")?,
+                                )?,
+                            )),
+                            ::std::option::Option::Some,
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_fraction_type::pyo3_embed_python_source_code(py, false).unwrap();
+
+        assert_eq!(mod_bindgen_fraction_type::invert(py, (2, 4)).unwrap(), (2, 1));
+        assert_eq!(mod_bindgen_fraction_type::invert(py, (-3, 7)).unwrap(), (-7, 3));
+        assert!(mod_bindgen_fraction_type::invert(py, (0, 1)).is_err());
+        assert_eq!(mod_bindgen_fraction_type::maybe_invert(py, Some((1, 3))).unwrap(), Some((3, 1)));
+        assert_eq!(mod_bindgen_fraction_type::maybe_invert(py, None).unwrap(), None);
+    }
+}
+
+test_bindgen! {
+    bindgen_module_alias_import
+
+    py: r#"
+    from __future__ import annotations
+
+    import ctypes as ct
+
+    def checksum(data: ct.c_uint8, seed: ct.c_int32) -> ct.c_uint64:
+        return data + seed
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_module_alias_import {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from __future__ import annotations\n\nimport ctypes as ct\n\ndef checksum(data: ct.c_uint8, seed: ct.c_int32) -> ct.c_uint64:\n    return data + seed\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_module_alias_import")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_module_alias_import",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_module_alias_import/__init__.py",
+                        "mod_bindgen_module_alias_import",
+                    )?,
+                )
+            }
+            pub fn checksum(
+                py: ::pyo3::marker::Python<'_>,
+                p_data: u8,
+                p_seed: i32,
+            ) -> ::pyo3::PyResult<u64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_module_alias_import"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "checksum"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_data, py),
+                                ::pyo3::ToPyObject::to_object(&p_seed, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_module_alias_import::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_module_alias_import::checksum(py, 40, 2).unwrap(), 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_pyo3_version_check
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .emit_pyo3_version_check(true)
+        .build(),
+
+    py: r#"
+    def answer() -> int:
+        return 42
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_pyo3_version_check {
+            /// Compile-time guard that fails if the used version of `pyo3` does not provide the `Bound` API
+            /// targeted by the generated bindings.
+            const _: () = {
+                #[allow(non_upper_case_globals)]
+                const pyo3_bindgen_requires_pyo3_v0_21: for<'py> fn(
+                    ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::Bound<'py, ::pyo3::types::PyDict> = ::pyo3::types::PyDict::new_bound;
+            };
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def answer() -> int:\n    return 42\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_pyo3_version_check")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_pyo3_version_check",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_pyo3_version_check/__init__.py",
+                        "mod_bindgen_pyo3_version_check",
+                    )?,
+                )
+            }
+            pub fn answer(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_pyo3_version_check"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "answer"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_pyo3_version_check::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_pyo3_version_check::answer(py).unwrap(), 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_experimental_warnings
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .emit_experimental_warnings(true)
+        .build(),
+
+    py: r#"
+    def answer() -> int:
+        return 42
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        #[deprecated(
+            note = "These bindings are generated by the experimental `import_python!` macro. Consider generating them in a build script via `pyo3_bindgen::Codegen` instead."
+        )]
+        pub mod mod_bindgen_experimental_warnings {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def answer() -> int:\n    return 42\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_experimental_warnings",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_experimental_warnings",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_experimental_warnings/__init__.py",
+                        "mod_bindgen_experimental_warnings",
+                    )?,
+                )
+            }
+            pub fn answer(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_experimental_warnings"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "answer"),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        #[allow(deprecated)]
+        {
+            mod_bindgen_experimental_warnings::pyo3_embed_python_source_code(py, false).unwrap();
+            assert_eq!(mod_bindgen_experimental_warnings::answer(py).unwrap(), 42);
+        }
+    }
+}
+
+test_bindgen! {
+    bindgen_rewritten_module_origin
+
+    py: r#"
+    import json
+
+    # Mimic a C-extension type that reports a module which does not expose it
+    class Vector:
+        def norm(self) -> float:
+            return 5.0
+
+    Vector.__module__ = "json"
+
+    def make_vector() -> Vector:
+        return Vector()
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_rewritten_module_origin {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import json\n\n# Mimic a C-extension type that reports a module which does not expose it\nclass Vector:\n    def norm(self) -> float:\n        return 5.0\n\nVector.__module__ = \"json\"\n\ndef make_vector() -> Vector:\n    return Vector()\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_rewritten_module_origin",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_rewritten_module_origin",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_rewritten_module_origin/__init__.py",
+                        "mod_bindgen_rewritten_module_origin",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Vector(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Vector);
+            ::pyo3::pyobject_native_type_info!(
+                Vector,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_rewritten_module_origin.Vector")
+            );
+            impl Vector {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Vector")]
+            pub trait VectorMethods {
+                fn norm(&self) -> ::pyo3::PyResult<f64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl VectorMethods for ::pyo3::Bound<'_, Vector> {
+                fn norm(&self) -> ::pyo3::PyResult<f64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "norm"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn make_vector<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Vector>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_rewritten_module_origin"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "make_vector"),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_rewritten_module_origin::VectorMethods;
+        mod_bindgen_rewritten_module_origin::pyo3_embed_python_source_code(py, false).unwrap();
+        let vector = mod_bindgen_rewritten_module_origin::make_vector(py).unwrap();
+        assert_eq!(vector.norm().unwrap(), 5.0);
+    }
+}
+
+test_bindgen! {
+    bindgen_rewritten_module_origin_aliases
+
+    py: r#"
+    import json
+
+    # Mimic C-extension types that are exposed under multiple names
+    class Vector:
+        def norm(self) -> float:
+            return 5.0
+
+    class _Impl:
+        pass
+
+    Vector.__module__ = "json"
+    _Impl.__module__ = "json"
+    Arrow = Vector
+    Primary = _Impl
+    Secondary = _Impl
+
+    def make_arrow() -> Arrow:
+        return Arrow()
+
+    def make_secondary() -> Secondary:
+        return Secondary()
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_rewritten_module_origin_aliases {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import json\n\n# Mimic C-extension types that are exposed under multiple names\nclass Vector:\n    def norm(self) -> float:\n        return 5.0\n\nclass _Impl:\n    pass\n\nVector.__module__ = \"json\"\n_Impl.__module__ = \"json\"\nArrow = Vector\nPrimary = _Impl\nSecondary = _Impl\n\ndef make_arrow() -> Arrow:\n    return Arrow()\n\ndef make_secondary() -> Secondary:\n    return Secondary()\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_rewritten_module_origin_aliases")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_rewritten_module_origin_aliases",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_rewritten_module_origin_aliases/__init__.py",
+                        "mod_bindgen_rewritten_module_origin_aliases",
+                    )?,
+                )
+            }
+            pub use self::Vector as Arrow;
+            pub use self::Primary as Secondary;
+            #[repr(transparent)]
+            pub struct Primary(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Primary);
+            ::pyo3::pyobject_native_type_info!(
+                Primary,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_rewritten_module_origin_aliases.Primary")
+            );
+            impl Primary {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Primary")]
+            pub trait PrimaryMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl PrimaryMethods for ::pyo3::Bound<'_, Primary> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[repr(transparent)]
+            pub struct Vector(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Vector);
+            ::pyo3::pyobject_native_type_info!(
+                Vector,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_rewritten_module_origin_aliases.Vector")
+            );
+            impl Vector {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Vector")]
+            pub trait VectorMethods {
+                fn norm(&self) -> ::pyo3::PyResult<f64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl VectorMethods for ::pyo3::Bound<'_, Vector> {
+                fn norm(&self) -> ::pyo3::PyResult<f64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "norm"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn make_arrow<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Vector>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_rewritten_module_origin_aliases"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "make_arrow"),
+                    )?,
+                )
+            }
+            pub fn make_secondary<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Primary>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_rewritten_module_origin_aliases"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "make_secondary"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_rewritten_module_origin_aliases::{Arrow, Primary, Secondary, Vector, VectorMethods};
+        mod_bindgen_rewritten_module_origin_aliases::pyo3_embed_python_source_code(py, false).unwrap();
+        let arrow: pyo3::Bound<'_, Arrow> = mod_bindgen_rewritten_module_origin_aliases::make_arrow(py).unwrap();
+        let vector: &pyo3::Bound<'_, Vector> = &arrow;
+        assert_eq!(vector.norm().unwrap(), 5.0);
+        let secondary: pyo3::Bound<'_, Secondary> = mod_bindgen_rewritten_module_origin_aliases::make_secondary(py).unwrap();
+        let _primary: &pyo3::Bound<'_, Primary> = &secondary;
+    }
+}
+
+test_bindgen! {
+    bindgen_main_module_origin
+
+    py: r#"
+    class Counter:
+        def value(self) -> int:
+            return 42
+
+    Counter.__module__ = "__main__"
+
+    def make_counter() -> Counter:
+        return Counter()
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_main_module_origin {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Counter:\n    def value(self) -> int:\n        return 42\n\nCounter.__module__ = \"__main__\"\n\ndef make_counter() -> Counter:\n    return Counter()\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_main_module_origin",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_main_module_origin",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_main_module_origin/__init__.py",
+                        "mod_bindgen_main_module_origin",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_main_module_origin.Counter")
+            );
+            impl Counter {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn value(&self) -> ::pyo3::PyResult<i64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                fn value(&self) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "value"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn make_counter<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Counter>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_main_module_origin"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "make_counter"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_main_module_origin::CounterMethods;
+        mod_bindgen_main_module_origin::pyo3_embed_python_source_code(py, false).unwrap();
+        let counter = mod_bindgen_main_module_origin::make_counter(py).unwrap();
+        assert_eq!(counter.value().unwrap(), 42);
+    }
+}
+
+#[test]
+fn bindgen_output_edition() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def gen(count: int) -> int:
+        return count
+    "# };
+    let generate = |edition| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .output_edition(edition)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_output_edition")
+        .unwrap()
+        .generate()
+        .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+
+    // Act
+    let bindings_2021 = generate(pyo3_bindgen_engine::Edition::E2021);
+    let bindings_2024 = generate(pyo3_bindgen_engine::Edition::E2024);
+
+    // Assert
+    assert!(bindings_2021.contains("pub fn gen("));
+    assert!(bindings_2024.contains("pub fn r#gen("));
+    assert!(bindings_2024.contains("::pyo3::intern!(py, \"gen\")"));
+    assert!(bindings_2024.contains("py: ::pyo3::marker::Python<'_>"));
+}
+
+#[test]
+fn bindgen_generate_root_prelude() {
+    // Arrange
+    const CODE_PY_GEOMETRY: &str = indoc::indoc! { r#"
+    class Point:
+        def norm(self) -> float:
+            return 0.0
+
+    def area(width: float, height: float) -> float:
+        return width * height
+
+    def version() -> str:
+        return "geometry"
+    "# };
+    const CODE_PY_UNITS: &str = indoc::indoc! { r#"
+    def to_meters(feet: float) -> float:
+        return feet * 0.3048
+
+    def version() -> str:
+        return "units"
+    "# };
+
+    // Act
+    let codegen = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .generate_root_prelude(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY_GEOMETRY, "mod_bindgen_root_prelude_geometry")
+    .unwrap()
+    .module_from_str(CODE_PY_UNITS, "mod_bindgen_root_prelude_units")
+    .unwrap();
+    let (bindings, report) = codegen.generate_with_report().unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    let prelude = bindings.split("pub mod prelude {").nth(1).unwrap();
+    assert!(prelude.contains("pub use super::mod_bindgen_root_prelude_geometry::Point;"));
+    assert!(prelude.contains("pub use super::mod_bindgen_root_prelude_geometry::PointMethods;"));
+    assert!(prelude.contains("pub use super::mod_bindgen_root_prelude_geometry::area;"));
+    assert!(prelude.contains("pub use super::mod_bindgen_root_prelude_units::to_meters;"));
+    assert!(!prelude.contains("version"));
+    assert!(report.warnings.contains(&pyo3_bindgen_engine::Warning {
+        kind: pyo3_bindgen_engine::WarningKind::Other,
+        path: "version".to_owned(),
+        message: "Name 'version' is defined in multiple modules (mod_bindgen_root_prelude_geometry.version, mod_bindgen_root_prelude_units.version). It is skipped in the combined prelude.".to_owned(),
+    }));
+}
+
+#[test]
+fn bindgen_collision_policy() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Widget:
+        def __init__(self, size: int):
+            self.size = size
+
+        def new(self) -> "Widget":
+            return Widget(self.size)
+    "# };
+    const CODE_PY_REORDERED: &str = indoc::indoc! { r#"
+    class Widget:
+        def clone(self) -> "Widget":
+            return Widget(self.size)
+
+        def new(self) -> "Widget":
+            return Widget(self.size)
+
+        def __init__(self, size: int):
+            self.size = size
+    "# };
+    let generate = |code: &str, on_collision| {
+        pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .on_collision(on_collision)
+                .build(),
+        )
+        .module_from_str(code, "mod_bindgen_collision_policy")
+        .unwrap()
+        .generate_with_report()
+    };
+
+    // Act
+    let (bindings, report) =
+        generate(CODE_PY, pyo3_bindgen_engine::CollisionPolicy::Suffix).unwrap();
+    let (bindings_reordered, report_reordered) = generate(
+        CODE_PY_REORDERED,
+        pyo3_bindgen_engine::CollisionPolicy::Suffix,
+    )
+    .unwrap();
+    let result_error = generate(CODE_PY, pyo3_bindgen_engine::CollisionPolicy::Error);
+
+    // Assert
+    for bindings in [bindings, bindings_reordered] {
+        let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+        assert!(bindings.contains("pub fn new_from_init<'py>("));
+        assert!(bindings.contains("fn new<'py>("));
+    }
+    let expected_renamed = vec![pyo3_bindgen_engine::RenamedItem {
+        path: "mod_bindgen_collision_policy.Widget.__init__".to_owned(),
+        intended_name: "new".to_owned(),
+        final_name: "new_from_init".to_owned(),
+    }];
+    assert_eq!(report.collisions.renamed, expected_renamed);
+    assert_eq!(report_reordered.collisions.renamed, expected_renamed);
+    assert!(matches!(
+        result_error,
+        Err(pyo3_bindgen_engine::PyBindgenError::CodegenError(_))
+    ));
+}
+
+#[test]
+fn bindgen_rename_conflicting_getters() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Widget:
+        def __init__(self):
+            pass
+
+        @property
+        def new(self) -> bool:
+            return False
+    "# };
+    let generate = |rename_conflicting_getters| {
+        pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .rename_conflicting_getters(rename_conflicting_getters)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_rename_conflicting_getters")
+        .unwrap()
+        .generate()
+        .map(|bindings| prettyplease::unparse(&syn::parse2(bindings).unwrap()))
+    };
+
+    // Act
+    let bindings_prefix = generate(pyo3_bindgen_engine::GetterRenaming::Prefix).unwrap();
+    let bindings_suffix = generate(pyo3_bindgen_engine::GetterRenaming::Suffix).unwrap();
+    let result_error = generate(pyo3_bindgen_engine::GetterRenaming::Error);
+
+    // Assert
+    assert!(bindings_prefix.contains("fn get_new(&self) -> ::pyo3::PyResult<bool>"));
+    assert!(!bindings_prefix.contains("fn new_prop"));
+    assert!(bindings_suffix.contains("fn new_prop(&self) -> ::pyo3::PyResult<bool>"));
+    assert!(!bindings_suffix.contains("fn get_new"));
+    for bindings in [bindings_prefix, bindings_suffix] {
+        assert!(bindings.contains("pub fn new<'py>(\n            py: ::pyo3::marker::Python<'py>,"));
+    }
+    assert!(matches!(
+        result_error,
+        Err(pyo3_bindgen_engine::PyBindgenError::CodegenError(_))
+    ));
+}
+
+#[test]
+fn bindgen_constructor_name() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Widget:
+        def __init__(self, size: int):
+            self.size = size
+
+        def resize(self, size: int) -> None:
+            self.size = size
+
+    class Gadget:
+        def __init__(self):
+            pass
+
+        def create(self) -> "Gadget":
+            return Gadget()
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .constructor_name("create")
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_constructor_name")
+    .unwrap()
+    .generate()
+    .unwrap();
+
+    // Assert
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    assert!(bindings.contains("pub fn create<'py>(\n            py: ::pyo3::marker::Python<'py>,\n            p_size: i64,"));
+    assert!(bindings.contains("pub fn create_from_init<'py>("));
+    assert!(bindings
+        .contains("fn create<'py>(&'py self) -> ::pyo3::PyResult<::pyo3::Bound<'py, Gadget>>"));
+    assert!(!bindings.contains("pub fn new<'py>("));
+}
+
+#[test]
+fn bindgen_name_sanitization() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    def crate(x: int) -> int:
+        return x
+
+    Self = type("Self", (), {"__module__": __name__})
+    "# };
+    let generate = |sanitization| {
+        pyo3_bindgen_engine::Codegen::new(
+            pyo3_bindgen_engine::Config::builder()
+                .sanitization(sanitization)
+                .build(),
+        )
+        .module_from_str(CODE_PY, "mod_bindgen_name_sanitization")
+        .unwrap()
+        .generate()
+    };
+
+    // Act
+    let bindings_warn = generate(pyo3_bindgen_engine::NameSanitization::Warn).unwrap();
+    let bindings_silent = generate(pyo3_bindgen_engine::NameSanitization::Silent).unwrap();
+    let result_error = generate(pyo3_bindgen_engine::NameSanitization::Error);
+
+    // Assert
+    assert_eq!(bindings_warn.to_string(), bindings_silent.to_string());
+    let bindings = prettyplease::unparse(&syn::parse2(bindings_warn).unwrap());
+    assert!(bindings.contains("pub fn f_crate("));
+    assert!(bindings.contains("pub struct s_Self("));
+    assert!(matches!(
+        result_error,
+        Err(pyo3_bindgen_engine::PyBindgenError::CodegenError(message))
+            if message.contains("is an invalid Rust ident")
+    ));
+}
+
+test_bindgen! {
+    bindgen_collection_helpers
+
+    py: r#"
+    from collections.abc import Iterator, Mapping
+
+    class Inventory(Mapping):
+        def __init__(self):
+            self._items = {"apple": 3, "pear": 5}
+
+        def __getitem__(self, key: str) -> int:
+            return self._items[key]
+
+        def __iter__(self) -> Iterator[str]:
+            return iter(self._items)
+
+        def __len__(self) -> int:
+            return len(self._items)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_collection_helpers {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from collections.abc import Iterator, Mapping\n\nclass Inventory(Mapping):\n    def __init__(self):\n        self._items = {\"apple\": 3, \"pear\": 5}\n\n    def __getitem__(self, key: str) -> int:\n        return self._items[key]\n\n    def __iter__(self) -> Iterator[str]:\n        return iter(self._items)\n\n    def __len__(self) -> int:\n        return len(self._items)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_collection_helpers",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_collection_helpers",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_collection_helpers/__init__.py",
+                        "mod_bindgen_collection_helpers",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Inventory(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Inventory);
+            ::pyo3::pyobject_native_type_info!(
+                Inventory,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_collection_helpers.Inventory")
+            );
+            impl Inventory {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_collection_helpers"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Inventory"),
+                                )?
+                                .as_any(),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Inventory")]
+            pub trait InventoryMethods {
+                fn get(&self, p_key: &str) -> ::pyo3::PyResult<::std::option::Option<i64>>;
+                fn keys(&self) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>>;
+                fn values(&self) -> ::pyo3::PyResult<::std::vec::Vec<i64>>;
+                fn items(
+                    &self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<(::std::string::String, i64)>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl InventoryMethods for ::pyo3::Bound<'_, Inventory> {
+                /// Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).
+                fn get(&self, p_key: &str) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    let py = self.py();
+                    match ::pyo3::types::PyAnyMethods::get_item(self.as_any(), p_key) {
+                        ::std::result::Result::Ok(value) => {
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                                .map(::std::option::Option::Some)
+                        }
+                        ::std::result::Result::Err(
+                            err,
+                        ) if err.is_instance_of::<::pyo3::exceptions::PyKeyError>(py) => {
+                            ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                    }
+                }
+                /// Collect all keys of the mapping via `keys()`.
+                fn keys(&self) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>> {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "keys"),
+                            )?,
+                        )?
+                        .map(|key| ::pyo3::types::PyAnyMethods::extract(&key?))
+                        .collect()
+                }
+                /// Collect all values of the mapping via `values()`.
+                fn values(&self) -> ::pyo3::PyResult<::std::vec::Vec<i64>> {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "values"),
+                            )?,
+                        )?
+                        .map(|value| ::pyo3::types::PyAnyMethods::extract(&value?))
+                        .collect()
+                }
+                /// Collect all key-value pairs of the mapping via `items()`.
+                fn items(
+                    &self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<(::std::string::String, i64)>> {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "items"),
+                            )?,
+                        )?
+                        .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                        .collect()
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_collection_helpers::InventoryMethods;
+        mod_bindgen_collection_helpers::pyo3_embed_python_source_code(py, false).unwrap();
+        let inventory = mod_bindgen_collection_helpers::Inventory::new(py).unwrap();
+        assert_eq!(inventory.get("pear").unwrap(), Some(5));
+        assert_eq!(inventory.get("plum").unwrap(), None);
+        assert_eq!(inventory.keys().unwrap(), vec!["apple", "pear"]);
+        assert_eq!(inventory.values().unwrap(), vec![3, 5]);
+        assert_eq!(
+            inventory.items().unwrap(),
+            vec![("apple".to_owned(), 3), ("pear".to_owned(), 5)]
+        );
+    }
+}
+
+test_bindgen! {
+    bindgen_getattr_fallback
+
+    cfg: pyo3_bindgen_engine::Config::builder().emit_getattr_fallback(true).build(),
+
+    py: r#"
+    class Record:
+        def __getattr__(self, name: str):
+            return name.upper()
+
+    def make_record() -> Record:
+        return Record()
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_getattr_fallback {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Record:\n    def __getattr__(self, name: str):\n        return name.upper()\n\ndef make_record() -> Record:\n    return Record()\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_getattr_fallback",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_getattr_fallback",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_getattr_fallback/__init__.py",
+                        "mod_bindgen_getattr_fallback",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Record(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Record);
+            ::pyo3::pyobject_native_type_info!(
+                Record,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_getattr_fallback.Record")
+            );
+            impl Record {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Record")]
+            pub trait RecordMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+                fn get_attr<'py>(
+                    &'py self,
+                    p_name: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn set_attr(
+                    &self,
+                    p_name: &str,
+                    p_value: impl ::pyo3::ToPyObject,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl RecordMethods for ::pyo3::Bound<'_, Record> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+                /// Get an attribute of the object by its name, including attributes that are not bound explicitly.
+                fn get_attr<'py>(
+                    &'py self,
+                    p_name: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::getattr(self.as_any(), p_name)
+                }
+                /// Set an attribute of the object by its name, including attributes that are not bound explicitly.
+                fn set_attr(
+                    &self,
+                    p_name: &str,
+                    p_value: impl ::pyo3::ToPyObject,
+                ) -> ::pyo3::PyResult<()> {
+                    ::pyo3::types::PyAnyMethods::setattr(self.as_any(), p_name, p_value)
+                }
+            }
+            pub fn make_record<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Record>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_getattr_fallback"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "make_record"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_getattr_fallback::RecordMethods;
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_getattr_fallback::pyo3_embed_python_source_code(py, false).unwrap();
+        let record = mod_bindgen_getattr_fallback::make_record(py).unwrap();
+        let dynamic: String = record.get_attr("dynamic").unwrap().extract().unwrap();
+        assert_eq!(dynamic, "DYNAMIC");
+        record.set_attr("answer", 42).unwrap();
+        let answer: i64 = record.get_attr("answer").unwrap().extract().unwrap();
+        assert_eq!(answer, 42);
+    }
+}
+
+test_bindgen! {
+    bindgen_dict_helpers
+
+    py: r#"
+    from dataclasses import dataclass
+
+    class Point:
+        def __init__(self, x: int, y: int):
+            self.x = x
+            self.y = y
+
+    @dataclass
+    class Size:
+        width: int
+        height: int
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_dict_helpers {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from dataclasses import dataclass\n\nclass Point:\n    def __init__(self, x: int, y: int):\n        self.x = x\n        self.y = y\n\n@dataclass\nclass Size:\n    width: int\n    height: int\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_dict_helpers")?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_dict_helpers",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_dict_helpers/__init__.py",
+                        "mod_bindgen_dict_helpers",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Point(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Point);
+            ::pyo3::pyobject_native_type_info!(
+                Point,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_dict_helpers.Point")
+            );
+            impl Point {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_x: i64,
+                    p_y: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_dict_helpers"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Point"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_x, py),
+                                    ::pyo3::ToPyObject::to_object(&p_y, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Point")]
+            pub trait PointMethods {
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl PointMethods for ::pyo3::Bound<'_, Point> {
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// Size(width: int, height: int)
+            #[repr(transparent)]
+            pub struct Size(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Size);
+            ::pyo3::pyobject_native_type_info!(
+                Size,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_dict_helpers.Size")
+            );
+            impl Size {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_width: i64,
+                    p_height: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_dict_helpers"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Size"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_width, py),
+                                    ::pyo3::ToPyObject::to_object(&p_height, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Size")]
+            pub trait SizeMethods {
+                fn width(&self) -> ::pyo3::PyResult<i64>;
+                fn set_width(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn height(&self) -> ::pyo3::PyResult<i64>;
+                fn set_height(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl SizeMethods for ::pyo3::Bound<'_, Size> {
+                fn width(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "width"),
+                        )?,
+                    )
+                }
+                fn set_width(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "width"),
+                        p_value,
+                    )
+                }
+                fn height(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "height"),
+                        )?,
+                    )
+                }
+                fn set_height(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "height"),
+                        p_value,
+                    )
+                }
+                /// Convert the dataclass into a dict via `dataclasses.asdict()`, which also converts nested dataclasses.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "asdict"),
+                        (self.as_any(),),
+                    )?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the fields of the dataclass (`dataclasses.fields()`) to the values of the given dict. Keys that are not fields are ignored.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let fields = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "fields"),
+                        (self.as_any(),),
+                    )?;
+                    for field in ::pyo3::types::PyAnyMethods::iter(&fields)? {
+                        let name = ::pyo3::types::PyAnyMethods::getattr(
+                            &field?,
+                            ::pyo3::intern!(py, "name"),
+                        )?;
+                        if let ::std::option::Option::Some(value) = ::pyo3::types::PyDictMethods::get_item(
+                            p_dict,
+                            &name,
+                        )? {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_dict_helpers::{Point, PointMethods, Size, SizeMethods};
+        use pyo3::types::{PyAnyMethods, PyDictMethods};
+        mod_bindgen_dict_helpers::pyo3_embed_python_source_code(py, false).unwrap();
+
+        let point = Point::new(py, 1, 2).unwrap();
+        let dict = point.to_dict().unwrap();
+        assert_eq!(dict.get_item("x").unwrap().unwrap().extract::<i64>().unwrap(), 1);
+        dict.set_item("y", 3).unwrap();
+        point.update_from_dict(&dict).unwrap();
+        assert_eq!(point.getattr("y").unwrap().extract::<i64>().unwrap(), 3);
+
+        let size = Size::new(py, 4, 5).unwrap();
+        let dict = size.to_dict().unwrap();
+        assert_eq!(dict.len(), 2);
+        dict.set_item("height", 6).unwrap();
+        dict.set_item("depth", 7).unwrap();
+        size.update_from_dict(&dict).unwrap();
+        assert_eq!(size.getattr("height").unwrap().extract::<i64>().unwrap(), 6);
+        assert!(!size.hasattr("depth").unwrap());
+    }
+}
+
+test_bindgen! {
+    bindgen_python_version_requirements
+
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .min_python_version((3, 8))
+        .require_python_version("mod_bindgen_python_version_requirements.new_function", (3, 12))
+        .require_python_version("mod_bindgen_python_version_requirements.old_function", (3, 8))
+        .require_python_version("mod_bindgen_python_version_requirements.Thing.new_method", (99, 0))
+        .build(),
+
+    py: r#"
+    def new_function() -> int:
+        return 1
+
+    def old_function() -> int:
+        return 2
+
+    class Thing:
+        def new_method(self) -> int:
+            return 3
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_python_version_requirements {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def new_function() -> int:\n    return 1\n\ndef old_function() -> int:\n    return 2\n\nclass Thing:\n    def new_method(self) -> int:\n        return 3\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_python_version_requirements",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_python_version_requirements",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_python_version_requirements/__init__.py",
+                        "mod_bindgen_python_version_requirements",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Thing(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Thing);
+            ::pyo3::pyobject_native_type_info!(
+                Thing,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_python_version_requirements.Thing")
+            );
+            impl Thing {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Thing")]
+            pub trait ThingMethods {
+                fn new_method(&self) -> ::pyo3::PyResult<i64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl ThingMethods for ::pyo3::Bound<'_, Thing> {
+                /// Note: Requires Python 99.0+, which is checked at runtime.
+                fn new_method(&self) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    static IS_PYTHON_VERSION_SUPPORTED: ::pyo3::sync::GILOnceCell<bool> = ::pyo3::sync::GILOnceCell::new();
+                    if !*IS_PYTHON_VERSION_SUPPORTED
+                        .get_or_init(py, || py.version_info() >= (99u8, 0u8))
+                    {
+                        return ::std::result::Result::Err(
+                            ::pyo3::exceptions::PyRuntimeError::new_err(
+                                "mod_bindgen_python_version_requirements.Thing.new_method requires Python 99.0+",
+                            ),
+                        );
+                    }
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "new_method"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// Note: Requires Python 3.12+, which is checked at runtime.
+            pub fn new_function(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                static IS_PYTHON_VERSION_SUPPORTED: ::pyo3::sync::GILOnceCell<bool> = ::pyo3::sync::GILOnceCell::new();
+                if !*IS_PYTHON_VERSION_SUPPORTED
+                    .get_or_init(py, || py.version_info() >= (3u8, 12u8))
+                {
+                    return ::std::result::Result::Err(
+                        ::pyo3::exceptions::PyRuntimeError::new_err(
+                            "mod_bindgen_python_version_requirements.new_function requires Python 3.12+",
+                        ),
+                    );
+                }
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_python_version_requirements"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "new_function"),
+                    )?,
+                )
+            }
+            pub fn old_function(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_python_version_requirements"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "old_function"),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_python_version_requirements::{new_function, old_function, Thing, ThingMethods};
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_python_version_requirements::pyo3_embed_python_source_code(py, false).unwrap();
+
+        let result = new_function(py);
+        if py.version_info() >= (3, 12) {
+            assert_eq!(result.unwrap(), 1);
+        } else {
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "RuntimeError: mod_bindgen_python_version_requirements.new_function requires Python 3.12+"
+            );
+        }
+        assert_eq!(old_function(py).unwrap(), 2);
+
+        let thing = py
+            .import_bound("mod_bindgen_python_version_requirements")
+            .unwrap()
+            .getattr("Thing")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .downcast_into::<Thing>()
+            .unwrap();
+        assert_eq!(
+            thing.new_method().unwrap_err().to_string(),
+            "RuntimeError: mod_bindgen_python_version_requirements.Thing.new_method requires Python 99.0+"
+        );
+        // The result of the version check is cached
+        assert!(thing.new_method().is_err());
+    }
+}
+
+test_bindgen! {
+    bindgen_params_with_default_preprocessed
+
+    py: r#"
+    import datetime
+    from typing import Any, Optional, Tuple, Union
+
+    def union(p: Union[int, str] = 0, q: Optional[Union[int, str]] = None) -> str:
+        return repr((p, q))
+
+    def unknown(p=1, q: Optional[Any] = None) -> str:
+        return repr((p, q))
+
+    def tuple_(p: Tuple[int] = (1,), q: Optional[Tuple[int]] = None) -> str:
+        return repr((p, q))
+
+    def delta(p: datetime.timedelta = datetime.timedelta(0), q: Optional[datetime.timedelta] = None) -> str:
+        return repr((p, q))
+
+    def other(p: "Unresolved" = None, q: Optional["Unresolved"] = None) -> str:
+        return repr((p, q))
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_params_with_default_preprocessed {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import datetime\nfrom typing import Any, Optional, Tuple, Union\n\ndef union(p: Union[int, str] = 0, q: Optional[Union[int, str]] = None) -> str:\n    return repr((p, q))\n\ndef unknown(p=1, q: Optional[Any] = None) -> str:\n    return repr((p, q))\n\ndef tuple_(p: Tuple[int] = (1,), q: Optional[Tuple[int]] = None) -> str:\n    return repr((p, q))\n\ndef delta(p: datetime.timedelta = datetime.timedelta(0), q: Optional[datetime.timedelta] = None) -> str:\n    return repr((p, q))\n\ndef other(p: \"Unresolved\" = None, q: Optional[\"Unresolved\"] = None) -> str:\n    return repr((p, q))\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_params_with_default_preprocessed",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_params_with_default_preprocessed",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_params_with_default_preprocessed/__init__.py",
+                        "mod_bindgen_params_with_default_preprocessed",
+                    )?,
+                )
+            }
+            pub fn delta<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_p: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_q: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_p = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_p, py);
+                let p_p = p_p.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_params_with_default_preprocessed"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "delta"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_p,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "p"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_q,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "q"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn other<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_p: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_q: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_p = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_p, py);
+                let p_p = p_p.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_params_with_default_preprocessed"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "other"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_p,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "p"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_q,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "q"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn tuple_<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_p: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyTuple>>,
+                p_q: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyTuple>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_p = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyTuple>>::into_py(p_p, py);
+                let p_p = p_p.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_params_with_default_preprocessed"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "tuple_"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_p,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "p"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_q,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "q"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn union(
+                py: ::pyo3::marker::Python<'_>,
+                p_p: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_q: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_p = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_p, py);
+                let p_p = p_p.bind(py);
+                let p_q = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_q, py);
+                let p_q = p_q.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_params_with_default_preprocessed"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "union"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_p,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "p"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_q,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "q"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn unknown<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_p: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                p_q: ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                let p_p = ::pyo3::IntoPy::<::pyo3::Py<::pyo3::types::PyAny>>::into_py(p_p, py);
+                let p_p = p_p.bind(py);
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_params_with_default_preprocessed"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "unknown"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_p,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "p"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_q,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "q"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_params_with_default_preprocessed::*;
+        pyo3_embed_python_source_code(py, false).unwrap();
+
+        // Arguments are passed as keyword arguments unless they are None
+        assert_eq!(union(py, "a", 2).unwrap(), "('a', 2)");
+        assert_eq!(union(py, py.None(), py.None()).unwrap(), "(0, None)");
+        assert_eq!(unknown(py, 2, None).unwrap(), "(2, None)");
+        assert_eq!(unknown(py, py.None(), None).unwrap(), "(1, None)");
+        assert_eq!(tuple_(py, (2,), None).unwrap(), "((2,), None)");
+        assert_eq!(
+            tuple_(py, (3,), Some(pyo3::types::PyTuple::new_bound(py, [4]))).unwrap(),
+            "((3,), (4,))"
+        );
+        assert_eq!(
+            delta(py, py.None(), None).unwrap(),
+            "(datetime.timedelta(0), None)"
+        );
+        assert_eq!(other(py, py.None(), None).unwrap(), "(None, None)");
+        assert_eq!(other(py, 1, None).unwrap(), "(1, None)");
+    }
+}
+
+#[test]
+fn bindgen_emit_doctest_examples() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import Callable
+
+    def greet(name: str, times: int = 1) -> str:
+        return name * times
+
+    def apply(callback: Callable[[int], int]) -> int:
+        return callback(0)
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .emit_doctest_examples(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_emit_doctest_examples")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert!(bindings.contains("/// ```no_run"));
+    assert!(bindings
+        .contains("///     let result = mod_bindgen_emit_doctest_examples::greet(py, \"\", 0);"));
+    assert!(!bindings.contains("mod_bindgen_emit_doctest_examples::apply(py"));
+}
+
+#[test]
+fn bindgen_generate_usage_examples() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from typing import Dict, Optional
+
+    class MyClass:
+        def __init__(self, my_arg1: str, my_arg2: Optional[int] = None):
+            ...
+        def my_method(self, my_arg1: Dict[str, int]) -> int:
+            ...
+
+    def my_function(flag: bool, ratio: float) -> None:
+        ...
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .generate_usage_examples(true)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_generate_usage_examples")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    assert_eq!(bindings.matches("/// ```rust,ignore").count(), 3);
+    assert!(!bindings.contains("/// ```no_run"));
+    assert!(bindings
+        .contains("///     let result = mod_bindgen_generate_usage_examples::MyClass::new(py,"));
+    assert!(bindings.contains("///         p_my_arg1: \"...\","));
+    assert!(bindings.contains("///         p_my_arg2: None,"));
+    assert!(bindings.contains("///     let instance: &pyo3::Bound<'_, mod_bindgen_generate_usage_examples::MyClass> = todo!();"));
+    assert!(bindings.contains("///     let result = instance.my_method("));
+    assert!(bindings.contains("///         p_my_arg1: todo!(),"));
+    assert!(bindings
+        .contains("///     let result = mod_bindgen_generate_usage_examples::my_function(py,"));
+    assert!(bindings.contains("///         p_ratio: 0.0,"));
+}
+
+#[test]
+fn bindgen_sibling_modules() {
+    // Arrange
+    const CODE_PY_A: &str = indoc::indoc! { r#"
+    def foo() -> "pkg_bindgen_sibling_modules.b.Thing":
+        import sys
+        return sys.modules["pkg_bindgen_sibling_modules.b"].Thing()
+    "# };
+    const CODE_PY_B: &str = indoc::indoc! { r#"
+    class Thing:
+        def value(self) -> int:
+            return 42
+    "# };
+    // The expected bindings are also compiled, so that their behaviour can be verified at runtime
+    macro_rules! expected_bindings {
+        ($($code_rs:tt)*) => {
+            const CODE_RS: &str = stringify! { $($code_rs)* };
+            $($code_rs)*
+        };
+    }
+    expected_bindings! {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod pkg_bindgen_sibling_modules {
+            pub mod a {
+                pub fn foo<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, super::b::Thing>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "pkg_bindgen_sibling_modules"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "a"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "foo"),
+                        )?,
+                    )
+                }
+            }
+            pub mod b {
+                #[repr(transparent)]
+                pub struct Thing(::pyo3::PyAny);
+                ::pyo3::pyobject_native_type_named!(Thing);
+                ::pyo3::pyobject_native_type_info!(
+                    Thing,
+                    ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                    ::std::option::Option::Some("pkg_bindgen_sibling_modules.b.Thing")
+                );
+                impl Thing {}
+                /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+                /// method call syntax these methods are separated into a trait, because stable
+                /// Rust does not yet support `arbitrary_self_types`.
+                #[doc(alias = "Thing")]
+                pub trait ThingMethods {
+                    fn value(&self) -> ::pyo3::PyResult<i64>;
+                    fn __init__<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()>;
+                }
+                #[automatically_derived]
+                impl ThingMethods for ::pyo3::Bound<'_, Thing> {
+                    fn value(&self) -> ::pyo3::PyResult<i64> {
+                        let py = self.py();
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "value"),
+                            )?,
+                        )
+                    }
+                    fn __init__<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                        ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "__init__"),
+                            )?,
+                        )
+                    }
+                    /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                    fn to_dict<'py>(
+                        &'py self,
+                    ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                        let py = self.py();
+                        let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                        let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "vars"),
+                                (self.as_any(),),
+                            )
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                                builtins.as_any(),
+                                ::pyo3::intern!(py, "dict"),
+                                (self.as_any(),),
+                            ))
+                            .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                                self.as_any(),
+                                ::pyo3::intern!(py, "__dict__"),
+                            ))?;
+                        ::std::result::Result::Ok(
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyDict,
+                            >(dict)?,
+                        )
+                    }
+                    /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                    fn update_from_dict<'py>(
+                        &'py self,
+                        p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                    ) -> ::pyo3::PyResult<()> {
+                        for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                        ::std::result::Result::Ok(())
+                    }
+                }
+            }
+        }
+    }
+    let generate = |sibling_modules: [(&str, &str); 2]| {
+        sibling_modules
+            .into_iter()
+            .fold(
+                pyo3_bindgen_engine::Codegen::default(),
+                |codegen, (code_py, module_name)| {
+                    codegen.module_from_str(code_py, module_name).unwrap()
+                },
+            )
+            .generate()
+            .unwrap()
+    };
+
+    // Act
+    let bindings = generate([
+        (CODE_PY_A, "pkg_bindgen_sibling_modules.a"),
+        (CODE_PY_B, "pkg_bindgen_sibling_modules.b"),
+    ]);
+    let bindings_reordered = generate([
+        (CODE_PY_B, "pkg_bindgen_sibling_modules.b"),
+        (CODE_PY_A, "pkg_bindgen_sibling_modules.a"),
+    ]);
+
+    // Assert
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    let bindings_reordered = prettyplease::unparse(&syn::parse2(bindings_reordered).unwrap());
+    let expected = prettyplease::unparse(&syn::parse_str(CODE_RS).unwrap());
+    assert_eq!(bindings, bindings_reordered);
+    assert!(bindings.contains("::pyo3::PyResult<::pyo3::Bound<'py, super::b::Thing>>"));
+    assert_eq!(bindings, expected, "\nGenerated:\n\n{bindings}");
+    pyo3::Python::with_gil(|py| {
+        use pkg_bindgen_sibling_modules::b::ThingMethods;
+        // The parent package does not exist, so it is registered manually alongside its submodules
+        py.run_bound(
+            indoc::indoc! { r#"
+            import sys, types
+            package = types.ModuleType("pkg_bindgen_sibling_modules")
+            package.a = sys.modules["pkg_bindgen_sibling_modules.a"]
+            package.b = sys.modules["pkg_bindgen_sibling_modules.b"]
+            sys.modules["pkg_bindgen_sibling_modules"] = package
+            "# },
+            None,
+            None,
+        )
+        .unwrap();
+        let thing = pkg_bindgen_sibling_modules::a::foo(py).unwrap();
+        assert_eq!(thing.value().unwrap(), 42);
+    });
+}
+
+test_bindgen! {
+    bindgen_mapping_helpers
+
+    cfg: pyo3_bindgen_engine::Config::builder().mapping_helpers(true).build(),
+
+    py: r#"
+    class Registry:
+        def __init__(self):
+            self._entries = {}
+
+        def __getitem__(self, key: str) -> int:
+            return self._entries[key]
+
+        def __setitem__(self, key: str, value: int):
+            self._entries[key] = value
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_mapping_helpers {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Registry:\n    def __init__(self):\n        self._entries = {}\n\n    def __getitem__(self, key: str) -> int:\n        return self._entries[key]\n\n    def __setitem__(self, key: str, value: int):\n        self._entries[key] = value\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_mapping_helpers",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_mapping_helpers",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_mapping_helpers/__init__.py",
+                        "mod_bindgen_mapping_helpers",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Registry(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Registry);
+            ::pyo3::pyobject_native_type_info!(
+                Registry,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_mapping_helpers.Registry")
+            );
+            impl Registry {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_mapping_helpers"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Registry"),
+                                )?
+                                .as_any(),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Registry")]
+            pub trait RegistryMethods {
+                fn get(&self, p_key: &str) -> ::pyo3::PyResult<::std::option::Option<i64>>;
+                fn insert(&self, p_key: &str, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl RegistryMethods for ::pyo3::Bound<'_, Registry> {
+                /// Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).
+                fn get(&self, p_key: &str) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    let py = self.py();
+                    match ::pyo3::types::PyAnyMethods::get_item(self.as_any(), p_key) {
+                        ::std::result::Result::Ok(value) => {
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                                .map(::std::option::Option::Some)
+                        }
+                        ::std::result::Result::Err(
+                            err,
+                        ) if err.is_instance_of::<::pyo3::exceptions::PyKeyError>(py) => {
+                            ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                    }
+                }
+                /// Insert the value for the given key via `__setitem__`.
+                fn insert(&self, p_key: &str, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::set_item(self.as_any(), p_key, p_value)
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_mapping_helpers::RegistryMethods;
+        mod_bindgen_mapping_helpers::pyo3_embed_python_source_code(py, false).unwrap();
+        let registry = mod_bindgen_mapping_helpers::Registry::new(py).unwrap();
+        assert_eq!(registry.get("answer").unwrap(), None);
+        registry.insert("answer", 42).unwrap();
+        assert_eq!(registry.get("answer").unwrap(), Some(42));
+    }
+}
+
+#[test]
+fn bindgen_visibility_tiers() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Vault:
+        def _helper(self) -> int:
+            return 1
+
+        def __secret(self) -> int:
+            return 2
+
+        def __len__(self) -> int:
+            return 3
+    "# };
+    let generate = |cfg: pyo3_bindgen_engine::Config| {
+        let bindings = pyo3_bindgen_engine::Codegen::new(cfg)
+            .module_from_str(CODE_PY, "mod_bindgen_visibility_tiers")
+            .unwrap()
+            .generate()
+            .unwrap();
+        prettyplease::unparse(&syn::parse2(bindings).unwrap())
+    };
+
+    // Act
+    let bindings_default = generate(pyo3_bindgen_engine::Config::default());
+    let bindings_underscore = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .include_underscore_attrs(true)
+            .build(),
+    );
+    let bindings_name_mangled = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .include_name_mangled(true)
+            .build(),
+    );
+    let bindings_dunder = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .include_dunder(vec!["__len__".to_owned()])
+            .build(),
+    );
+    let bindings_private = generate(
+        pyo3_bindgen_engine::Config::builder()
+            .include_private(true)
+            .build(),
+    );
+
+    // Assert
+    let has_methods = |bindings: &str| {
+        [
+            bindings.contains("fn _helper(&self)"),
+            bindings.contains("fn _Vault__secret(&self)"),
+            bindings.contains("fn __len__(&self)"),
+        ]
+    };
+    assert_eq!(has_methods(&bindings_default), [false, false, false]);
+    assert_eq!(has_methods(&bindings_underscore), [true, false, false]);
+    assert_eq!(has_methods(&bindings_name_mangled), [false, true, false]);
+    assert_eq!(has_methods(&bindings_dunder), [false, false, true]);
+    assert_eq!(has_methods(&bindings_private), [true, false, true]);
+}
+
+#[test]
+fn bindgen_dataclass_constructor() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from dataclasses import InitVar, dataclass, field
+
+    @dataclass
+    class Account:
+        owner: str
+        seed: InitVar[int]
+        balance: int = field(init=False, default=0)
+        currency: str = field(default="EUR", kw_only=True)
+
+        def __post_init__(self, seed: int):
+            self.balance = seed
+
+    class SavingsAccount(Account):
+        def __init__(self, *args, **kwargs):
+            super().__init__(*args, **kwargs)
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(
+        pyo3_bindgen_engine::Config::builder()
+            .dict_helpers(false)
+            .build(),
+    )
+    .module_from_str(CODE_PY, "mod_bindgen_dataclass_constructor")
+    .unwrap()
+    .generate()
+    .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    let constructor = "pub fn new<'py>(
+            py: ::pyo3::marker::Python<'py>,
+            p_owner: &str,
+            p_seed: i64,
+            p_currency: &str,
+        ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>>";
+    assert_eq!(bindings.matches(constructor).count(), 2, "{bindings}");
+    assert!(!bindings.contains("p_balance"));
+    assert!(!bindings.contains("p_args"));
+    assert!(!bindings.contains("fn seed"));
+}
+
+test_bindgen! {
+    bindgen_validate_annotated
+
+    cfg: pyo3_bindgen_engine::Config::builder().validate_annotated(true).build(),
+
+    py: r#"
+    from dataclasses import dataclass
+    from typing import Annotated
+
+    @dataclass(frozen=True)
+    class Ge:
+        ge: int
+
+    @dataclass(frozen=True)
+    class MaxLen:
+        max_length: int
+
+    def sleep_for(duration: Annotated[int, "units: seconds"]) -> int:
+        return duration
+
+    def label(count: Annotated[int, Ge(0)], name: Annotated[str, MaxLen(8)]) -> str:
+        return name * count
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_validate_annotated {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from dataclasses import dataclass\nfrom typing import Annotated\n\n@dataclass(frozen=True)\nclass Ge:\n    ge: int\n\n@dataclass(frozen=True)\nclass MaxLen:\n    max_length: int\n\ndef sleep_for(duration: Annotated[int, \"units: seconds\"]) -> int:\n    return duration\n\ndef label(count: Annotated[int, Ge(0)], name: Annotated[str, MaxLen(8)]) -> str:\n    return name * count\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_validate_annotated")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_validate_annotated",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_validate_annotated/__init__.py",
+                        "mod_bindgen_validate_annotated",
+                    )?,
+                )
+            }
+            /// Ge(ge: int)
+            #[repr(transparent)]
+            pub struct Ge(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Ge);
+            ::pyo3::pyobject_native_type_info!(
+                Ge, ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_validate_annotated.Ge")
+            );
+            impl Ge {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_ge: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_validate_annotated"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Ge"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_ge, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Ge")]
+            pub trait GeMethods {
+                fn ge(&self) -> ::pyo3::PyResult<i64>;
+                fn set_ge(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl GeMethods for ::pyo3::Bound<'_, Ge> {
+                fn ge(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "ge"),
+                        )?,
+                    )
+                }
+                fn set_ge(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "ge"),
+                        p_value,
+                    )
+                }
+                /// Convert the dataclass into a dict via `dataclasses.asdict()`, which also converts nested dataclasses.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "asdict"),
+                        (self.as_any(),),
+                    )?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the fields of the dataclass (`dataclasses.fields()`) to the values of the given dict. Keys that are not fields are ignored.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let fields = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "fields"),
+                        (self.as_any(),),
+                    )?;
+                    for field in ::pyo3::types::PyAnyMethods::iter(&fields)? {
+                        let name = ::pyo3::types::PyAnyMethods::getattr(
+                            &field?,
+                            ::pyo3::intern!(py, "name"),
+                        )?;
+                        if let ::std::option::Option::Some(value) = ::pyo3::types::PyDictMethods::get_item(
+                            p_dict,
+                            &name,
+                        )? {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// MaxLen(max_length: int)
+            #[repr(transparent)]
+            pub struct MaxLen(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(MaxLen);
+            ::pyo3::pyobject_native_type_info!(
+                MaxLen,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_validate_annotated.MaxLen")
+            );
+            impl MaxLen {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_max_length: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_validate_annotated"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "MaxLen"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_max_length, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "MaxLen")]
+            pub trait MaxLenMethods {
+                fn max_length(&self) -> ::pyo3::PyResult<i64>;
+                fn set_max_length(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl MaxLenMethods for ::pyo3::Bound<'_, MaxLen> {
+                fn max_length(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "max_length"),
+                        )?,
+                    )
+                }
+                fn set_max_length(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "max_length"),
+                        p_value,
+                    )
+                }
+                /// Convert the dataclass into a dict via `dataclasses.asdict()`, which also converts nested dataclasses.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "asdict"),
+                        (self.as_any(),),
+                    )?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the fields of the dataclass (`dataclasses.fields()`) to the values of the given dict. Keys that are not fields are ignored.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let fields = ::pyo3::types::PyAnyMethods::call_method1(
+                        py.import_bound(::pyo3::intern!(py, "dataclasses"))?.as_any(),
+                        ::pyo3::intern!(py, "fields"),
+                        (self.as_any(),),
+                    )?;
+                    for field in ::pyo3::types::PyAnyMethods::iter(&fields)? {
+                        let name = ::pyo3::types::PyAnyMethods::getattr(
+                            &field?,
+                            ::pyo3::intern!(py, "name"),
+                        )?;
+                        if let ::std::option::Option::Some(value) = ::pyo3::types::PyDictMethods::get_item(
+                            p_dict,
+                            &name,
+                        )? {
+                            ::pyo3::types::PyAnyMethods::setattr(
+                                self.as_any(),
+                                ::pyo3::types::PyAnyMethods::downcast_into::<
+                                    ::pyo3::types::PyString,
+                                >(name)?,
+                                value,
+                            )?;
+                        }
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[doc = " Parameter `count` annotated with: `Ge(ge=0)`\n\nParameter `name` annotated with: `MaxLen(max_length=8)`\n"]
+            pub fn label(
+                py: ::pyo3::marker::Python<'_>,
+                p_count: i64,
+                p_name: &str,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                debug_assert!(p_count >= 0, "{}", "Parameter `count` must satisfy `Ge(ge=0)`");
+                debug_assert!(
+                    p_name.chars().count() <= 8, "{}",
+                    "Parameter `name` must satisfy `MaxLen(max_length=8)`"
+                );
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_validate_annotated"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "label"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_count, py),
+                                ::pyo3::ToPyObject::to_object(&p_name, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+            /// Parameter `duration` annotated with: `'units: seconds'`
+            pub fn sleep_for(
+                py: ::pyo3::marker::Python<'_>,
+                p_duration: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_validate_annotated"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "sleep_for"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_duration, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_validate_annotated::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_validate_annotated::label(py, 2, "ab").unwrap(), "abab");
+        let result = std::panic::catch_unwind(|| {
+            pyo3::Python::with_gil(|py| mod_bindgen_validate_annotated::label(py, -1, "ab"))
+        });
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn bindgen_validate_annotated_disabled() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    from dataclasses import dataclass
+    from typing import Annotated
+
+    @dataclass(frozen=True)
+    class Ge:
+        ge: int
+
+    @dataclass(frozen=True)
+    class MaxLen:
+        max_length: int
+
+    def sleep_for(duration: Annotated[int, "units: seconds"]) -> int:
+        return duration
+
+    def label(count: Annotated[int, Ge(0)], name: Annotated[str, MaxLen(8)]) -> str:
+        return name * count
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_validate_annotated_disabled")
+        .unwrap()
+        .generate()
+        .unwrap();
+
+    // Assert
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    assert!(bindings.contains("p_duration: i64"));
+    assert!(bindings.contains("p_count: i64"));
+    assert!(bindings.contains("p_name: &str"));
+    assert!(bindings.contains("Parameter `duration` annotated with: `'units: seconds'`"));
+    assert!(bindings.contains("Parameter `count` annotated with: `Ge(ge=0)`"));
+    assert!(!bindings.contains("debug_assert!"));
+}
+
+test_bindgen! {
+    bindgen_runtime_signature_guards
+
+    cfg: pyo3_bindgen_engine::Config::builder().runtime_signature_guards(true).build(),
+
+    py: r#"
+    def scale(value: int, factor: int = 2) -> int:
+        return value * factor
+
+    class Scaler:
+        def apply(self, value: int) -> int:
+            return value
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_runtime_signature_guards {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def scale(value: int, factor: int = 2) -> int:\n    return value * factor\n\nclass Scaler:\n    def apply(self, value: int) -> int:\n        return value\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_runtime_signature_guards")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_runtime_signature_guards",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_runtime_signature_guards/__init__.py",
+                        "mod_bindgen_runtime_signature_guards",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Scaler(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Scaler);
+            ::pyo3::pyobject_native_type_info!(
+                Scaler,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_runtime_signature_guards.Scaler")
+            );
+            impl Scaler {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Scaler")]
+            pub trait ScalerMethods {
+                fn apply(&self, p_value: i64) -> ::pyo3::PyResult<i64>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl ScalerMethods for ::pyo3::Bound<'_, Scaler> {
+                fn apply(&self, p_value: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    const EXPECTED_SIGNATURE: &str = "value:1";
+                    static SIGNATURE_GUARD: ::pyo3::sync::GILOnceCell<()> = ::pyo3::sync::GILOnceCell::new();
+                    SIGNATURE_GUARD
+                        .get_or_init(
+                            py,
+                            || {
+                                let _ = (|| -> ::pyo3::PyResult<()> {
+                                    let __internal_signature = ::pyo3::types::PyAnyMethods::call_method1(
+                                        py.import_bound(::pyo3::intern!(py, "inspect"))?.as_any(),
+                                        ::pyo3::intern!(py, "signature"),
+                                        (
+                                            ::pyo3::types::PyAnyMethods::getattr(
+                                                self.as_any(),
+                                                ::pyo3::intern!(py, "apply"),
+                                            )?,
+                                        ),
+                                    )?;
+                                    let mut __internal_params = ::std::vec::Vec::new();
+                                    for __internal_param in ::pyo3::types::PyAnyMethods::iter(
+                                        &::pyo3::types::PyAnyMethods::call_method0(
+                                            &::pyo3::types::PyAnyMethods::getattr(
+                                                &__internal_signature,
+                                                ::pyo3::intern!(py, "parameters"),
+                                            )?,
+                                            ::pyo3::intern!(py, "values"),
+                                        )?,
+                                    )? {
+                                        let __internal_param = __internal_param?;
+                                        let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(
+                                            &::pyo3::types::PyAnyMethods::getattr(
+                                                &__internal_param,
+                                                ::pyo3::intern!(py, "name"),
+                                            )?,
+                                        )?;
+                                        let kind: u8 = ::pyo3::types::PyAnyMethods::extract(
+                                            &::pyo3::types::PyAnyMethods::getattr(
+                                                &__internal_param,
+                                                ::pyo3::intern!(py, "kind"),
+                                            )?,
+                                        )?;
+                                        match (name.as_str(), kind) {
+                                            ("_", _) => {}
+                                            (_, 2) => __internal_params.push(::std::format!("*{name}")),
+                                            (_, 4) => __internal_params.push(::std::format!("**{name}")),
+                                            _ => __internal_params.push(::std::format!("{name}:{kind}")),
+                                        }
+                                    }
+                                    let __internal_live_signature = __internal_params.join(",");
+                                    if __internal_live_signature != EXPECTED_SIGNATURE {
+                                        ::pyo3::types::PyAnyMethods::call_method1(
+                                            py.import_bound(::pyo3::intern!(py, "warnings"))?.as_any(),
+                                            ::pyo3::intern!(py, "warn"),
+                                            (
+                                                ::std::format!(
+                                                    "Signature of '{}' changed since its bindings were generated (expected '{}', found '{}')",
+                                                    "mod_bindgen_runtime_signature_guards.Scaler.apply",
+                                                    EXPECTED_SIGNATURE, __internal_live_signature,
+                                                ),
+                                                py.get_type_bound::<::pyo3::exceptions::PyRuntimeWarning>(),
+                                            ),
+                                        )?;
+                                    }
+                                    Ok(())
+                                })();
+                            },
+                        );
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "apply"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_value, py)],
+                            ),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn scale(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: i64,
+                p_factor: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                const EXPECTED_SIGNATURE: &str = "value:1,factor:1";
+                static SIGNATURE_GUARD: ::pyo3::sync::GILOnceCell<()> = ::pyo3::sync::GILOnceCell::new();
+                SIGNATURE_GUARD
+                    .get_or_init(
+                        py,
+                        || {
+                            let _ = (|| -> ::pyo3::PyResult<()> {
+                                let __internal_signature = ::pyo3::types::PyAnyMethods::call_method1(
+                                    py.import_bound(::pyo3::intern!(py, "inspect"))?.as_any(),
+                                    ::pyo3::intern!(py, "signature"),
+                                    (
+                                        ::pyo3::types::PyAnyMethods::getattr(
+                                            py
+                                                .import_bound(
+                                                    ::pyo3::intern!(py, "mod_bindgen_runtime_signature_guards"),
+                                                )?
+                                                .as_any(),
+                                            ::pyo3::intern!(py, "scale"),
+                                        )?,
+                                    ),
+                                )?;
+                                let mut __internal_params = ::std::vec::Vec::new();
+                                for __internal_param in ::pyo3::types::PyAnyMethods::iter(
+                                    &::pyo3::types::PyAnyMethods::call_method0(
+                                        &::pyo3::types::PyAnyMethods::getattr(
+                                            &__internal_signature,
+                                            ::pyo3::intern!(py, "parameters"),
+                                        )?,
+                                        ::pyo3::intern!(py, "values"),
+                                    )?,
+                                )? {
+                                    let __internal_param = __internal_param?;
+                                    let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(
+                                        &::pyo3::types::PyAnyMethods::getattr(
+                                            &__internal_param,
+                                            ::pyo3::intern!(py, "name"),
+                                        )?,
+                                    )?;
+                                    let kind: u8 = ::pyo3::types::PyAnyMethods::extract(
+                                        &::pyo3::types::PyAnyMethods::getattr(
+                                            &__internal_param,
+                                            ::pyo3::intern!(py, "kind"),
+                                        )?,
+                                    )?;
+                                    match (name.as_str(), kind) {
+                                        ("_", _) => {}
+                                        (_, 2) => __internal_params.push(::std::format!("*{name}")),
+                                        (_, 4) => __internal_params.push(::std::format!("**{name}")),
+                                        _ => __internal_params.push(::std::format!("{name}:{kind}")),
+                                    }
+                                }
+                                let __internal_live_signature = __internal_params.join(",");
+                                if __internal_live_signature != EXPECTED_SIGNATURE {
+                                    ::pyo3::types::PyAnyMethods::call_method1(
+                                        py.import_bound(::pyo3::intern!(py, "warnings"))?.as_any(),
+                                        ::pyo3::intern!(py, "warn"),
+                                        (
+                                            ::std::format!(
+                                                "Signature of '{}' changed since its bindings were generated (expected '{}', found '{}')",
+                                                "mod_bindgen_runtime_signature_guards.scale",
+                                                EXPECTED_SIGNATURE, __internal_live_signature,
+                                            ),
+                                            py.get_type_bound::<::pyo3::exceptions::PyRuntimeWarning>(),
+                                        ),
+                                    )?;
+                                }
+                                Ok(())
+                            })();
+                        },
+                    );
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_runtime_signature_guards"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scale"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_factor,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "factor"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::{IntoPyDict, PyAnyMethods, PyModuleMethods};
+        mod_bindgen_runtime_signature_guards::pyo3_embed_python_source_code(py, false).unwrap();
+        let module = py.import_bound("mod_bindgen_runtime_signature_guards").unwrap();
+        let warnings = py.import_bound("warnings").unwrap();
+        let catcher = warnings
+            .call_method("catch_warnings", (), Some(&[("record", true)].into_py_dict_bound(py)))
+            .unwrap();
+        let recorded = catcher.call_method0("__enter__").unwrap();
+        warnings.call_method1("simplefilter", ("always",)).unwrap();
+
+        // The live function gained a parameter since the bindings were generated
+        py.run_bound(
+            "def scale(value, factor=2, offset=0):\n    return value * factor + offset",
+            Some(&module.dict()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(mod_bindgen_runtime_signature_guards::scale(py, 3, 2).unwrap(), 6);
+        assert_eq!(mod_bindgen_runtime_signature_guards::scale(py, 4, 2).unwrap(), 8);
+        let scaler = module.getattr("Scaler").unwrap().call0().unwrap();
+        let scaler = scaler.downcast::<mod_bindgen_runtime_signature_guards::Scaler>().unwrap();
+        assert_eq!(mod_bindgen_runtime_signature_guards::ScalerMethods::apply(scaler, 5).unwrap(), 5);
+
+        catcher.call_method1("__exit__", (py.None(), py.None(), py.None())).unwrap();
+        assert_eq!(recorded.len().unwrap(), 1);
+        let warning = recorded.get_item(0).unwrap();
+        assert!(warning
+            .getattr("category")
+            .unwrap()
+            .is(&py.get_type_bound::<pyo3::exceptions::PyRuntimeWarning>()));
+        assert!(warning
+            .getattr("message")
+            .unwrap()
+            .to_string()
+            .contains("expected 'value:1,factor:1', found 'value:1,factor:1,offset:1'"));
+    }
+}
+
+test_bindgen! {
+    bindgen_module_handle_injection
+
+    cfg: pyo3_bindgen_engine::Config::builder().module_handle_injection(true).build(),
+
+    py: r#"
+    def greet(name: str) -> str:
+        return f"Hello, {name}!"
+
+    class Counter:
+        def __init__(self, start: int):
+            self.value = start
+
+        @staticmethod
+        def limit() -> int:
+            return 10
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_module_handle_injection {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def greet(name: str) -> str:\n    return f\"Hello, {name}!\"\n\nclass Counter:\n    def __init__(self, start: int):\n        self.value = start\n\n    @staticmethod\n    def limit() -> int:\n        return 10\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_module_handle_injection")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_module_handle_injection",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_module_handle_injection/__init__.py",
+                        "mod_bindgen_module_handle_injection",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_module_handle_injection.Counter")
+            );
+            impl Counter {
+                pub fn new<'py>(
+                    handle: &ModBindgenModuleHandleInjectionHandle,
+                    py: ::pyo3::marker::Python<'py>,
+                    p_start: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            handle.pyo3_class_Counter(py)?.as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_start, py)],
+                            ),
+                        )?,
+                    )
+                }
+                pub fn limit(
+                    handle: &ModBindgenModuleHandleInjectionHandle,
+                    py: ::pyo3::marker::Python<'_>,
+                ) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            handle.pyo3_class_Counter(py)?.as_any(),
+                            ::pyo3::intern!(py, "limit"),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// Handle of the `mod_bindgen_module_handle_injection` module, through which its functions are called and its classes are looked up.
+            pub struct ModBindgenModuleHandleInjectionHandle(
+                ::pyo3::Py<::pyo3::types::PyModule>,
+            );
+            impl ModBindgenModuleHandleInjectionHandle {
+                /// Import the `mod_bindgen_module_handle_injection` module by its name.
+                pub fn import(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<Self> {
+                    ::std::result::Result::Ok(
+                        Self(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_module_handle_injection"),
+                                )?
+                                .unbind(),
+                        ),
+                    )
+                }
+                /// Wrap the given module object, which is used in place of the `mod_bindgen_module_handle_injection` module (e.g. a mock in tests).
+                pub fn from_module(module: ::pyo3::Py<::pyo3::types::PyModule>) -> Self {
+                    Self(module)
+                }
+                /// Get the class object of `Counter` from the module.
+                pub fn pyo3_class_Counter<'py>(
+                    &self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::getattr(
+                        self.0.bind(py).as_any(),
+                        ::pyo3::intern!(py, "Counter"),
+                    )
+                }
+                pub fn greet(
+                    &self,
+                    py: ::pyo3::marker::Python<'_>,
+                    p_name: &str,
+                ) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.0.bind(py).as_any(),
+                            ::pyo3::intern!(py, "greet"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_name, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::PyAnyMethods;
+        use mod_bindgen_module_handle_injection::ModBindgenModuleHandleInjectionHandle as Handle;
+
+        // The real module is used by a handle that imports it by name
+        mod_bindgen_module_handle_injection::pyo3_embed_python_source_code(py, false).unwrap();
+        let handle = Handle::import(py).unwrap();
+        assert_eq!(handle.greet(py, "Rust").unwrap(), "Hello, Rust!");
+        assert_eq!(mod_bindgen_module_handle_injection::Counter::limit(&handle, py).unwrap(), 10);
+
+        // A substitute module is used by a handle that is constructed from it
+        let stub = pyo3::types::PyModule::from_code_bound(
+            py,
+            "def greet(name):\n    return 'stub'\n\nclass Counter:\n    def __init__(self, start):\n        self.value = start * 2\n\n    @staticmethod\n    def limit():\n        return -1\n",
+            "stub.py",
+            "stub",
+        )
+        .unwrap();
+        let handle = Handle::from_module(stub.unbind());
+        assert_eq!(handle.greet(py, "Rust").unwrap(), "stub");
+        assert_eq!(mod_bindgen_module_handle_injection::Counter::limit(&handle, py).unwrap(), -1);
+        let counter = mod_bindgen_module_handle_injection::Counter::new(&handle, py, 3).unwrap();
+        assert_eq!(counter.getattr("value").unwrap().extract::<i64>().unwrap(), 6);
+    }
+}
+
+test_bindgen! {
+    bindgen_emit_len_method
+
+    cfg: pyo3_bindgen_engine::Config::builder().emit_len_method(true).build(),
+
+    py: r#"
+    class Bag:
+        def __init__(self):
+            self._items = []
+
+        def add(self, item: str):
+            self._items.append(item)
+
+        def __len__(self) -> int:
+            return len(self._items)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_emit_len_method {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Bag:\n    def __init__(self):\n        self._items = []\n\n    def add(self, item: str):\n        self._items.append(item)\n\n    def __len__(self) -> int:\n        return len(self._items)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_emit_len_method",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_emit_len_method",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_emit_len_method/__init__.py",
+                        "mod_bindgen_emit_len_method",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Bag(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Bag);
+            ::pyo3::pyobject_native_type_info!(
+                Bag, ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_len_method.Bag")
+            );
+            impl Bag {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_len_method"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Bag"),
+                                )?
+                                .as_any(),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Bag")]
+            pub trait BagMethods {
+                fn add<'py>(
+                    &'py self,
+                    p_item: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn len(&self) -> ::pyo3::PyResult<usize>;
+                fn is_empty(&self) -> ::pyo3::PyResult<bool>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl BagMethods for ::pyo3::Bound<'_, Bag> {
+                fn add<'py>(
+                    &'py self,
+                    p_item: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "add"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_item, py)],
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the number of items via `__len__`.
+                fn len(&self) -> ::pyo3::PyResult<usize> {
+                    ::pyo3::types::PyAnyMethods::len(self.as_any())
+                }
+                /// Determine whether the object contains no items via `__len__`.
+                fn is_empty(&self) -> ::pyo3::PyResult<bool> {
+                    ::pyo3::types::PyAnyMethods::is_empty(self.as_any())
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_emit_len_method::BagMethods;
+        mod_bindgen_emit_len_method::pyo3_embed_python_source_code(py, false).unwrap();
+        let bag = mod_bindgen_emit_len_method::Bag::new(py).unwrap();
+        assert!(bag.is_empty().unwrap());
+        bag.add("apple").unwrap();
+        bag.add("pear").unwrap();
+        assert_eq!(bag.len().unwrap(), 2);
+        assert!(!bag.is_empty().unwrap());
+    }
+}
+
+test_bindgen! {
+    bindgen_emit_into_py_object
+
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .emit_into_py_object(true)
+        .dict_helpers(false)
+        .build(),
+
+    py: r#"
+    import collections
+    import enum
+    import typing
+
+
+    class Color(enum.Enum):
+        RED = 1
+        GREEN = 2
+
+
+    class Movie(typing.TypedDict):
+        title: str
+        year: typing.Optional[int]
+
+
+    class Review(Movie, total=False):
+        rating: float
+
+
+    class Point(typing.NamedTuple):
+        x: int
+        y: int
+
+
+    Pair = collections.namedtuple("Pair", ["first", "second"])
+
+
+    def paint(color: Color) -> Color:
+        return Color.GREEN if color is Color.RED else Color.RED
+
+
+    def rate(movie: Movie, rating: float) -> Review:
+        return Review(title=movie["title"], year=movie["year"], rating=rating)
+
+
+    def stars(review: typing.Optional[Review] = None) -> int:
+        return 0 if review is None or "rating" not in review else round(review["rating"])
+
+
+    def shift(point: Point, dx: int) -> Point:
+        return point._replace(x=point.x + dx)
+
+
+    def swap(pair: Pair) -> Pair:
+        return Pair(pair.second, pair.first)
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_emit_into_py_object {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import collections\nimport enum\nimport typing\n\n\nclass Color(enum.Enum):\n    RED = 1\n    GREEN = 2\n\n\nclass Movie(typing.TypedDict):\n    title: str\n    year: typing.Optional[int]\n\n\nclass Review(Movie, total=False):\n    rating: float\n\n\nclass Point(typing.NamedTuple):\n    x: int\n    y: int\n\n\nPair = collections.namedtuple(\"Pair\", [\"first\", \"second\"])\n\n\ndef paint(color: Color) -> Color:\n    return Color.GREEN if color is Color.RED else Color.RED\n\n\ndef rate(movie: Movie, rating: float) -> Review:\n    return Review(title=movie[\"title\"], year=movie[\"year\"], rating=rating)\n\n\ndef stars(review: typing.Optional[Review] = None) -> int:\n    return 0 if review is None or \"rating\" not in review else round(review[\"rating\"])\n\n\ndef shift(point: Point, dx: int) -> Point:\n    return point._replace(x=point.x + dx)\n\n\ndef swap(pair: Pair) -> Pair:\n    return Pair(pair.second, pair.first)\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_emit_into_py_object",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_emit_into_py_object",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_emit_into_py_object/__init__.py",
+                        "mod_bindgen_emit_into_py_object",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Color(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Color);
+            ::pyo3::pyobject_native_type_info!(
+                Color,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_into_py_object.Color")
+            );
+            impl Color {
+                /// Get the value of the class attribute `GREEN` from the class itself.
+                pub fn GREEN(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<ColorMember> {
+                    {
+                        let value = ::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Color"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "GREEN"),
+                        )?;
+                        ::pyo3::types::PyAnyMethods::extract(&value)
+                    }
+                }
+                /// Get the value of the class attribute `RED` from the class itself.
+                pub fn RED(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<ColorMember> {
+                    {
+                        let value = ::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Color"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "RED"),
+                        )?;
+                        ::pyo3::types::PyAnyMethods::extract(&value)
+                    }
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Color")]
+            pub trait ColorMethods {
+                fn GREEN(&self) -> ::pyo3::PyResult<ColorMember>;
+                fn RED(&self) -> ::pyo3::PyResult<ColorMember>;
+            }
+            #[automatically_derived]
+            impl ColorMethods for ::pyo3::Bound<'_, Color> {
+                fn GREEN(&self) -> ::pyo3::PyResult<ColorMember> {
+                    {
+                        let value = ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "GREEN"),
+                        )?;
+                        ::pyo3::types::PyAnyMethods::extract(&value)
+                    }
+                }
+                fn RED(&self) -> ::pyo3::PyResult<ColorMember> {
+                    {
+                        let value = ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "RED"),
+                        )?;
+                        ::pyo3::types::PyAnyMethods::extract(&value)
+                    }
+                }
+            }
+            /// Members of the Python enum [`Color`], which can be passed to and returned from Python by value.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum ColorMember {
+                RED,
+                GREEN,
+            }
+            impl ColorMember {
+                /// Get the corresponding member of the Python enum.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Color>> {
+                    let name = match self {
+                        Self::RED => "RED",
+                        Self::GREEN => "GREEN",
+                    };
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Color"),
+                                )?
+                                .as_any(),
+                            name,
+                        )?,
+                    )
+                }
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for ColorMember {
+                fn extract_bound(
+                    object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                ) -> ::pyo3::PyResult<Self> {
+                    let py = object.py();
+                    let name: ::std::string::String = ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            object,
+                            ::pyo3::intern!(py, "name"),
+                        )?,
+                    )?;
+                    let member = match name.as_str() {
+                        "RED" => Self::RED,
+                        "GREEN" => Self::GREEN,
+                        _ => {
+                            return ::std::result::Result::Err(
+                                ::pyo3::exceptions::PyValueError::new_err(
+                                    ::std::format!(
+                                        "`{name}` is not a member of `mod_bindgen_emit_into_py_object.Color`"
+                                    ),
+                                ),
+                            );
+                        }
+                    };
+                    if !::pyo3::types::PyAnyMethods::is(
+                        member.into_bound(py)?.as_any(),
+                        object,
+                    ) {
+                        return ::std::result::Result::Err(
+                            ::pyo3::exceptions::PyValueError::new_err(
+                                "the object is not a member of `mod_bindgen_emit_into_py_object.Color`",
+                            ),
+                        );
+                    }
+                    ::std::result::Result::Ok(member)
+                }
+            }
+            #[repr(transparent)]
+            pub struct Movie(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Movie);
+            ::pyo3::pyobject_native_type_info!(
+                Movie,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_into_py_object.Movie")
+            );
+            impl Movie {
+                /// Create a new dictionary with keys from iterable and values set to value.
+                pub fn fromkeys<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_iterable: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    let p_iterable = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_iterable, py);
+                    let p_iterable = p_iterable.bind(py);
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Movie"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "fromkeys"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_iterable, py),
+                                    ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Movie")]
+            pub trait MovieMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn clear<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn copy<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn get<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn items<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn keys<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn pop<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn popitem<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn setdefault<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn update<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn values<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn title(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn set_title(&self, p_value: &str) -> ::pyo3::PyResult<()>;
+                fn year(&self) -> ::pyo3::PyResult<::std::option::Option<i64>>;
+                fn set_year(&self, p_value: ::std::option::Option<i64>) -> ::pyo3::PyResult<()>;
+                fn get_from_mapping<'py>(
+                    &'py self,
+                    p_key: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<
+                    ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                >;
+                fn keys_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                fn values_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                fn items_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<
+                        (
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                        ),
+                    >,
+                >;
+            }
+            #[automatically_derived]
+            impl MovieMethods for ::pyo3::Bound<'_, Movie> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn clear<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "clear"),
+                        )?,
+                    )
+                }
+                fn copy<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "copy"),
+                        )?,
+                    )
+                }
+                fn get<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "get"),
+                        )?,
+                    )
+                }
+                fn items<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "items"),
+                        )?,
+                    )
+                }
+                fn keys<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "keys"),
+                        )?,
+                    )
+                }
+                fn pop<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "pop"),
+                        )?,
+                    )
+                }
+                fn popitem<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "popitem"),
+                        )?,
+                    )
+                }
+                fn setdefault<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "setdefault"),
+                        )?,
+                    )
+                }
+                fn update<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "update"),
+                        )?,
+                    )
+                }
+                fn values<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "values"),
+                        )?,
+                    )
+                }
+                fn title(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "title"),
+                        )?,
+                    )
+                }
+                fn set_title(&self, p_value: &str) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "title"),
+                        p_value,
+                    )
+                }
+                fn year(&self) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "year"),
+                        )?,
+                    )
+                }
+                fn set_year(&self, p_value: ::std::option::Option<i64>) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "year"),
+                        p_value,
+                    )
+                }
+                /// Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).
+                fn get_from_mapping<'py>(
+                    &'py self,
+                    p_key: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<
+                    ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    let py = self.py();
+                    let p_key = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_key, py);
+                    let p_key = p_key.bind(py);
+                    match ::pyo3::types::PyAnyMethods::get_item(self.as_any(), p_key) {
+                        ::std::result::Result::Ok(value) => {
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                                .map(::std::option::Option::Some)
+                        }
+                        ::std::result::Result::Err(
+                            err,
+                        ) if err.is_instance_of::<::pyo3::exceptions::PyKeyError>(py) => {
+                            ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                    }
+                }
+                /// Collect all keys of the mapping via `keys()`.
+                fn keys_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "keys"),
+                            )?,
+                        )?
+                        .map(|key| ::pyo3::types::PyAnyMethods::extract(&key?))
+                        .collect()
+                }
+                /// Collect all values of the mapping via `values()`.
+                fn values_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "values"),
+                            )?,
+                        )?
+                        .map(|value| ::pyo3::types::PyAnyMethods::extract(&value?))
+                        .collect()
+                }
+                /// Collect all key-value pairs of the mapping via `items()`.
+                fn items_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<
+                        (
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                        ),
+                    >,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "items"),
+                            )?,
+                        )?
+                        .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                        .collect()
+                }
+            }
+            /// Record of the Python class [`Movie`], which can be passed to and returned from Python by value.
+            #[derive(Debug, Clone)]
+            pub struct MovieRecord {
+                pub title: ::std::string::String,
+                pub year: ::std::option::Option<i64>,
+            }
+            impl MovieRecord {
+                /// Convert the record into its Python representation.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let dict = ::pyo3::types::PyDict::new_bound(py);
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &dict,
+                        ::pyo3::intern!(py, "title"),
+                        self.title,
+                    )?;
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &dict,
+                        ::pyo3::intern!(py, "year"),
+                        self.year,
+                    )?;
+                    ::std::result::Result::Ok(dict)
+                }
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for MovieRecord {
+                fn extract_bound(
+                    object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                ) -> ::pyo3::PyResult<Self> {
+                    let py = object.py();
+                    ::std::result::Result::Ok(Self {
+                        title: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::get_item(
+                                object,
+                                ::pyo3::intern!(py, "title"),
+                            )?,
+                        )?,
+                        year: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::get_item(
+                                object,
+                                ::pyo3::intern!(py, "year"),
+                            )?,
+                        )?,
+                    })
+                }
+            }
+            /// Pair(first, second)
+            #[repr(transparent)]
+            pub struct Pair(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Pair);
+            ::pyo3::pyobject_native_type_info!(
+                Pair,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_into_py_object.Pair")
+            );
+            impl Pair {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Pair")]
+            pub trait PairMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn count<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn first<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn set_first(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()>;
+                fn index<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn second<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn set_second(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()>;
+                fn get_index<'py>(
+                    &'py self,
+                    index: usize,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn len(&self) -> ::pyo3::PyResult<usize>;
+                fn to_vec<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+            }
+            #[automatically_derived]
+            impl PairMethods for ::pyo3::Bound<'_, Pair> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn count<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "count"),
+                        )?,
+                    )
+                }
+                fn first<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "first"),
+                        )?,
+                    )
+                }
+                fn set_first(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "first"),
+                        p_value,
+                    )
+                }
+                fn index<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "index"),
+                        )?,
+                    )
+                }
+                fn second<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "second"),
+                        )?,
+                    )
+                }
+                fn set_second(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "second"),
+                        p_value,
+                    )
+                }
+                /// Get the item at the given index via `__getitem__`.
+                fn get_index<'py>(
+                    &'py self,
+                    index: usize,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::get_item(self.as_any(), index)?,
+                    )
+                }
+                /// Get the number of items via `__len__`.
+                fn len(&self) -> ::pyo3::PyResult<usize> {
+                    ::pyo3::types::PyAnyMethods::len(self.as_any())
+                }
+                /// Collect all items of the sequence via `__iter__`.
+                fn to_vec<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(self.as_any())?
+                        .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                        .collect()
+                }
+            }
+            /// Record of the Python class [`Pair`], which can be passed to and returned from Python by value.
+            #[derive(Debug, Clone)]
+            pub struct PairRecord {
+                pub first: ::pyo3::PyObject,
+                pub second: ::pyo3::PyObject,
+            }
+            impl PairRecord {
+                /// Convert the record into its Python representation.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Pair>> {
+                    let kwargs = ::pyo3::types::PyDict::new_bound(py);
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &kwargs,
+                        ::pyo3::intern!(py, "first"),
+                        self.first,
+                    )?;
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &kwargs,
+                        ::pyo3::intern!(py, "second"),
+                        self.second,
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Pair"),
+                                )?
+                                .as_any(),
+                            (),
+                            ::std::option::Option::Some(&kwargs),
+                        )?,
+                    )
+                }
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for PairRecord {
+                fn extract_bound(
+                    object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                ) -> ::pyo3::PyResult<Self> {
+                    let py = object.py();
+                    ::std::result::Result::Ok(Self {
+                        first: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                object,
+                                ::pyo3::intern!(py, "first"),
+                            )?,
+                        )?,
+                        second: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                object,
+                                ::pyo3::intern!(py, "second"),
+                            )?,
+                        )?,
+                    })
+                }
+            }
+            /// Point(x, y)
+            #[repr(transparent)]
+            pub struct Point(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Point);
+            ::pyo3::pyobject_native_type_info!(
+                Point,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_into_py_object.Point")
+            );
+            impl Point {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Point")]
+            pub trait PointMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn count<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn index<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn x<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn set_x(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()>;
+                fn y<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn set_y(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()>;
+                fn get_index<'py>(
+                    &'py self,
+                    index: usize,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn len(&self) -> ::pyo3::PyResult<usize>;
+                fn to_vec<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+            }
+            #[automatically_derived]
+            impl PointMethods for ::pyo3::Bound<'_, Point> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn count<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "count"),
+                        )?,
+                    )
+                }
+                fn index<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "index"),
+                        )?,
+                    )
+                }
+                fn x<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "x"),
+                        )?,
+                    )
+                }
+                fn set_x(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "x"),
+                        p_value,
+                    )
+                }
+                fn y<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "y"),
+                        )?,
+                    )
+                }
+                fn set_y(
+                    &self,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "y"),
+                        p_value,
+                    )
+                }
+                /// Get the item at the given index via `__getitem__`.
+                fn get_index<'py>(
+                    &'py self,
+                    index: usize,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::get_item(self.as_any(), index)?,
+                    )
+                }
+                /// Get the number of items via `__len__`.
+                fn len(&self) -> ::pyo3::PyResult<usize> {
+                    ::pyo3::types::PyAnyMethods::len(self.as_any())
+                }
+                /// Collect all items of the sequence via `__iter__`.
+                fn to_vec<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(self.as_any())?
+                        .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                        .collect()
+                }
+            }
+            /// Record of the Python class [`Point`], which can be passed to and returned from Python by value.
+            #[derive(Debug, Clone)]
+            pub struct PointRecord {
+                pub x: i64,
+                pub y: i64,
+            }
+            impl PointRecord {
+                /// Convert the record into its Python representation.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Point>> {
+                    let kwargs = ::pyo3::types::PyDict::new_bound(py);
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &kwargs,
+                        ::pyo3::intern!(py, "x"),
+                        self.x,
+                    )?;
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &kwargs,
+                        ::pyo3::intern!(py, "y"),
+                        self.y,
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Point"),
+                                )?
+                                .as_any(),
+                            (),
+                            ::std::option::Option::Some(&kwargs),
+                        )?,
+                    )
+                }
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for PointRecord {
+                fn extract_bound(
+                    object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                ) -> ::pyo3::PyResult<Self> {
+                    let py = object.py();
+                    ::std::result::Result::Ok(Self {
+                        x: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                object,
+                                ::pyo3::intern!(py, "x"),
+                            )?,
+                        )?,
+                        y: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::getattr(
+                                object,
+                                ::pyo3::intern!(py, "y"),
+                            )?,
+                        )?,
+                    })
+                }
+            }
+            #[repr(transparent)]
+            pub struct Review(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Review);
+            ::pyo3::pyobject_native_type_info!(
+                Review,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_emit_into_py_object.Review")
+            );
+            impl Review {
+                /// Create a new dictionary with keys from iterable and values set to value.
+                pub fn fromkeys<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_iterable: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                    p_value: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    let p_iterable = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_iterable, py);
+                    let p_iterable = p_iterable.bind(py);
+                    let p_value = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_value, py);
+                    let p_value = p_value.bind(py);
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Review"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "fromkeys"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_iterable, py),
+                                    ::pyo3::ToPyObject::to_object(&p_value, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Review")]
+            pub trait ReviewMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn clear<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn copy<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn get<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn items<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn keys<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn pop<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn popitem<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn setdefault<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn update<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn values<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn title(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn set_title(&self, p_value: &str) -> ::pyo3::PyResult<()>;
+                fn year(&self) -> ::pyo3::PyResult<::std::option::Option<i64>>;
+                fn set_year(&self, p_value: ::std::option::Option<i64>) -> ::pyo3::PyResult<()>;
+                fn rating(&self) -> ::pyo3::PyResult<f64>;
+                fn set_rating(&self, p_value: f64) -> ::pyo3::PyResult<()>;
+                fn get_from_mapping<'py>(
+                    &'py self,
+                    p_key: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<
+                    ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                >;
+                fn keys_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                fn values_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>;
+                fn items_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<
+                        (
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                        ),
+                    >,
+                >;
+            }
+            #[automatically_derived]
+            impl ReviewMethods for ::pyo3::Bound<'_, Review> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn clear<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "clear"),
+                        )?,
+                    )
+                }
+                fn copy<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "copy"),
+                        )?,
+                    )
+                }
+                fn get<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "get"),
+                        )?,
+                    )
+                }
+                fn items<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "items"),
+                        )?,
+                    )
+                }
+                fn keys<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "keys"),
+                        )?,
+                    )
+                }
+                fn pop<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "pop"),
+                        )?,
+                    )
+                }
+                fn popitem<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "popitem"),
+                        )?,
+                    )
+                }
+                fn setdefault<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "setdefault"),
+                        )?,
+                    )
+                }
+                fn update<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "update"),
+                        )?,
+                    )
+                }
+                fn values<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "values"),
+                        )?,
+                    )
+                }
+                fn title(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "title"),
+                        )?,
+                    )
+                }
+                fn set_title(&self, p_value: &str) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "title"),
+                        p_value,
+                    )
+                }
+                fn year(&self) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "year"),
+                        )?,
+                    )
+                }
+                fn set_year(&self, p_value: ::std::option::Option<i64>) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "year"),
+                        p_value,
+                    )
+                }
+                fn rating(&self) -> ::pyo3::PyResult<f64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "rating"),
+                        )?,
+                    )
+                }
+                fn set_rating(&self, p_value: f64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "rating"),
+                        p_value,
+                    )
+                }
+                /// Get the value for the given key via `__getitem__`, or `None` if the key is missing (`KeyError`).
+                fn get_from_mapping<'py>(
+                    &'py self,
+                    p_key: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<
+                    ::std::option::Option<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    let py = self.py();
+                    let p_key = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_key, py);
+                    let p_key = p_key.bind(py);
+                    match ::pyo3::types::PyAnyMethods::get_item(self.as_any(), p_key) {
+                        ::std::result::Result::Ok(value) => {
+                            ::pyo3::types::PyAnyMethods::extract(&value)
+                                .map(::std::option::Option::Some)
+                        }
+                        ::std::result::Result::Err(
+                            err,
+                        ) if err.is_instance_of::<::pyo3::exceptions::PyKeyError>(py) => {
+                            ::std::result::Result::Ok(::std::option::Option::None)
+                        }
+                        ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+                    }
+                }
+                /// Collect all keys of the mapping via `keys()`.
+                fn keys_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "keys"),
+                            )?,
+                        )?
+                        .map(|key| ::pyo3::types::PyAnyMethods::extract(&key?))
+                        .collect()
+                }
+                /// Collect all values of the mapping via `values()`.
+                fn values_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "values"),
+                            )?,
+                        )?
+                        .map(|value| ::pyo3::types::PyAnyMethods::extract(&value?))
+                        .collect()
+                }
+                /// Collect all key-value pairs of the mapping via `items()`.
+                fn items_from_mapping<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<
+                    ::std::vec::Vec<
+                        (
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                            ::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                        ),
+                    >,
+                > {
+                    ::pyo3::types::PyAnyMethods::iter(
+                            &::pyo3::types::PyAnyMethods::call_method0(
+                                self.as_any(),
+                                ::pyo3::intern!(self.py(), "items"),
+                            )?,
+                        )?
+                        .map(|item| ::pyo3::types::PyAnyMethods::extract(&item?))
+                        .collect()
+                }
+            }
+            /// Record of the Python class [`Review`], which can be passed to and returned from Python by value.
+            #[derive(Debug, Clone)]
+            pub struct ReviewRecord {
+                pub title: ::std::string::String,
+                pub year: ::std::option::Option<i64>,
+                pub rating: ::std::option::Option<f64>,
+            }
+            impl ReviewRecord {
+                /// Convert the record into its Python representation.
+                pub fn into_bound<'py>(
+                    self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let dict = ::pyo3::types::PyDict::new_bound(py);
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &dict,
+                        ::pyo3::intern!(py, "title"),
+                        self.title,
+                    )?;
+                    ::pyo3::types::PyDictMethods::set_item(
+                        &dict,
+                        ::pyo3::intern!(py, "year"),
+                        self.year,
+                    )?;
+                    if let ::std::option::Option::Some(value) = self.rating {
+                        ::pyo3::types::PyDictMethods::set_item(
+                            &dict,
+                            ::pyo3::intern!(py, "rating"),
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(dict)
+                }
+            }
+            impl<'py> ::pyo3::FromPyObject<'py> for ReviewRecord {
+                fn extract_bound(
+                    object: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+                ) -> ::pyo3::PyResult<Self> {
+                    let py = object.py();
+                    ::std::result::Result::Ok(Self {
+                        title: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::get_item(
+                                object,
+                                ::pyo3::intern!(py, "title"),
+                            )?,
+                        )?,
+                        year: ::pyo3::types::PyAnyMethods::extract(
+                            &::pyo3::types::PyAnyMethods::get_item(
+                                object,
+                                ::pyo3::intern!(py, "year"),
+                            )?,
+                        )?,
+                        rating: if ::pyo3::types::PyAnyMethods::contains(
+                            object,
+                            ::pyo3::intern!(py, "rating"),
+                        )? {
+                            ::pyo3::types::PyAnyMethods::extract(
+                                &::pyo3::types::PyAnyMethods::get_item(
+                                    object,
+                                    ::pyo3::intern!(py, "rating"),
+                                )?,
+                            )?
+                        } else {
+                            ::std::option::Option::None
+                        },
+                    })
+                }
+            }
+            pub fn paint(
+                py: ::pyo3::marker::Python<'_>,
+                p_color: ColorMember,
+            ) -> ::pyo3::PyResult<ColorMember> {
+                let p_color = p_color.into_bound(py)?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "paint"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_color, py)],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(&value)
+                }
+            }
+            pub fn rate(
+                py: ::pyo3::marker::Python<'_>,
+                p_movie: MovieRecord,
+                p_rating: f64,
+            ) -> ::pyo3::PyResult<ReviewRecord> {
+                let p_movie = p_movie.into_bound(py)?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "rate"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_movie, py),
+                                ::pyo3::ToPyObject::to_object(&p_rating, py),
+                            ],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(&value)
+                }
+            }
+            pub fn shift(
+                py: ::pyo3::marker::Python<'_>,
+                p_point: PointRecord,
+                p_dx: i64,
+            ) -> ::pyo3::PyResult<PointRecord> {
+                let p_point = p_point.into_bound(py)?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "shift"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_point, py),
+                                ::pyo3::ToPyObject::to_object(&p_dx, py),
+                            ],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(&value)
+                }
+            }
+            pub fn stars(
+                py: ::pyo3::marker::Python<'_>,
+                p_review: ::std::option::Option<ReviewRecord>,
+            ) -> ::pyo3::PyResult<i64> {
+                let p_review = match p_review {
+                    ::std::option::Option::Some(p_review) => {
+                        ::std::option::Option::Some(p_review.into_bound(py)?)
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                };
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "stars"),
+                        (),
+                        Some(
+                            &{
+                                let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                {
+                                    let __internal_value = ::pyo3::ToPyObject::to_object(
+                                        &p_review,
+                                        py,
+                                    );
+                                    if !__internal_value.is_none(py) {
+                                        ::pyo3::types::PyDictMethods::set_item(
+                                            &__internal_kwargs,
+                                            ::pyo3::intern!(py, "review"),
+                                            __internal_value,
+                                        )?;
+                                    }
+                                }
+                                __internal_kwargs
+                            },
+                        ),
+                    )?,
+                )
+            }
+            pub fn swap(
+                py: ::pyo3::marker::Python<'_>,
+                p_pair: PairRecord,
+            ) -> ::pyo3::PyResult<PairRecord> {
+                let p_pair = p_pair.into_bound(py)?;
+                {
+                    let value = ::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_emit_into_py_object"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "swap"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_pair, py)],
+                        ),
+                    )?;
+                    ::pyo3::types::PyAnyMethods::extract(&value)
+                }
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_emit_into_py_object::{
+            ColorMember, MovieRecord, PairRecord, PointRecord, ReviewRecord,
+        };
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_emit_into_py_object::pyo3_embed_python_source_code(py, false).unwrap();
+
+        // Enum members
+        let painted = mod_bindgen_emit_into_py_object::paint(py, ColorMember::RED).unwrap();
+        assert_eq!(painted, ColorMember::GREEN);
+        let member = ColorMember::GREEN.into_bound(py).unwrap();
+        assert_eq!(member.getattr("value").unwrap().extract::<i64>().unwrap(), 2);
+        assert!(pyo3::types::PyString::new_bound(py, "RED").extract::<ColorMember>().is_err());
+
+        // Typed dicts
+        let movie = MovieRecord {
+            title: "Up".to_owned(),
+            year: Some(2009),
+        };
+        let review = mod_bindgen_emit_into_py_object::rate(py, movie, 4.6).unwrap();
+        assert_eq!(review.title, "Up");
+        assert_eq!(review.year, Some(2009));
+        assert_eq!(review.rating, Some(4.6));
+        assert_eq!(mod_bindgen_emit_into_py_object::stars(py, Some(review)).unwrap(), 5);
+        let unrated = ReviewRecord {
+            title: "Up".to_owned(),
+            year: None,
+            rating: None,
+        };
+        let unrated = unrated.into_bound(py).unwrap();
+        assert!(!unrated.contains("rating").unwrap());
+        assert_eq!(
+            mod_bindgen_emit_into_py_object::stars(py, Some(unrated.extract().unwrap())).unwrap(),
+            0
+        );
+        assert_eq!(mod_bindgen_emit_into_py_object::stars(py, None).unwrap(), 0);
+        assert!(pyo3::types::PyDict::new_bound(py).extract::<MovieRecord>().is_err());
+
+        // Named tuples
+        let point = PointRecord { x: 1, y: 2 };
+        let shifted = mod_bindgen_emit_into_py_object::shift(py, point, 3).unwrap();
+        assert_eq!((shifted.x, shifted.y), (4, 2));
+        let pair = PairRecord {
+            first: pyo3::IntoPy::into_py(1, py),
+            second: pyo3::IntoPy::into_py("one", py),
+        };
+        let swapped = mod_bindgen_emit_into_py_object::swap(py, pair).unwrap();
+        assert_eq!(swapped.first.extract::<String>(py).unwrap(), "one");
+        assert_eq!(swapped.second.extract::<i64>(py).unwrap(), 1);
+        let bound = PointRecord { x: 5, y: 6 }.into_bound(py).unwrap();
+        assert_eq!(bound.getattr("y").unwrap().extract::<i64>().unwrap(), 6);
+    }
+}
+
+test_bindgen! {
+    bindgen_string_return_type
+
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .string_return_type(pyo3_bindgen_engine::StringReturnType::BoxStr)
+        .build(),
+
+    py: r#"
+    from typing import Optional
+
+    def greet(name: str) -> str:
+        return f"Hello, {name}!"
+
+    def nickname(name: str) -> Optional[str]:
+        return name[:3] if len(name) > 3 else None
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_string_return_type {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from typing import Optional\n\ndef greet(name: str) -> str:\n    return f\"Hello, {name}!\"\n\ndef nickname(name: str) -> Optional[str]:\n    return name[:3] if len(name) > 3 else None\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_string_return_type")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_string_return_type",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_string_return_type/__init__.py",
+                        "mod_bindgen_string_return_type",
+                    )?,
+                )
+            }
+            pub fn greet(
+                py: ::pyo3::marker::Python<'_>,
+                p_name: &str,
+            ) -> ::pyo3::PyResult<::std::boxed::Box<str>> {
+                ::pyo3::types::PyAnyMethods::extract::<
+                    ::std::string::String,
+                >(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_string_return_type"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "greet"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_name, py)],
+                            ),
+                        )?,
+                    )
+                    .map(::std::convert::Into::into)
+            }
+            pub fn nickname(
+                py: ::pyo3::marker::Python<'_>,
+                p_name: &str,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::boxed::Box<str>>> {
+                ::pyo3::types::PyAnyMethods::extract::<
+                    ::std::option::Option<::std::string::String>,
+                >(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            py
+                                .import_bound(
+                                    ::pyo3::intern!(py, "mod_bindgen_string_return_type"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "nickname"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_name, py)],
+                            ),
+                        )?,
+                    )
+                    .map(|value| value.map(::std::convert::Into::into))
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_string_return_type::pyo3_embed_python_source_code(py, false).unwrap();
+        let greeting: Box<str> = mod_bindgen_string_return_type::greet(py, "World").unwrap();
+        assert_eq!(&*greeting, "Hello, World!");
+        let nickname: Option<Box<str>> =
+            mod_bindgen_string_return_type::nickname(py, "Robert").unwrap();
+        assert_eq!(nickname.as_deref(), Some("Rob"));
+    }
+}
+
+test_bindgen! {
+    bindgen_embed_python_source_code_idempotent
+
+    py: r#"
+    def answer() -> int:
+        return 42
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_embed_python_source_code_idempotent {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def answer() -> int:\n    return 42\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_embed_python_source_code_idempotent",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_embed_python_source_code_idempotent",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_embed_python_source_code_idempotent/__init__.py",
+                        "mod_bindgen_embed_python_source_code_idempotent",
+                    )?,
+                )
+            }
+            pub fn answer(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_embed_python_source_code_idempotent"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "answer"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_embed_python_source_code_idempotent::pyo3_embed_python_source_code(py, false)
+            .unwrap();
+        py.import_bound("mod_bindgen_embed_python_source_code_idempotent")
+            .unwrap()
+            .setattr("answer", py.eval_bound("lambda: 0", None, None).unwrap())
+            .unwrap();
+
+        // Embedding the source code again keeps the existing module
+        mod_bindgen_embed_python_source_code_idempotent::pyo3_embed_python_source_code(py, false)
+            .unwrap();
+        assert_eq!(
+            mod_bindgen_embed_python_source_code_idempotent::answer(py).unwrap(),
+            0
+        );
+
+        // Forced embedding executes the source code again
+        mod_bindgen_embed_python_source_code_idempotent::pyo3_embed_python_source_code(py, true)
+            .unwrap();
+        assert_eq!(
+            mod_bindgen_embed_python_source_code_idempotent::answer(py).unwrap(),
+            42
+        );
+    }
+}
+
+test_bindgen! {
+    bindgen_dict_key_accessors
+
+    cfg: pyo3_bindgen_engine::Config::builder().dict_key_accessors(true).build(),
+
+    py: r#"
+    from typing import Any
+
+    def stats() -> dict[str, Any]:
+        return {"count": 3, "name": "widgets"}
+
+    def scores() -> dict[str, float]:
+        return {"alice": 1.5}
+
+    class Inventory:
+        def __init__(self):
+            pass
+
+        def counts(self) -> dict[str, int]:
+            return {"apples": 2}
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_dict_key_accessors {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from typing import Any\n\ndef stats() -> dict[str, Any]:\n    return {\"count\": 3, \"name\": \"widgets\"}\n\ndef scores() -> dict[str, float]:\n    return {\"alice\": 1.5}\n\nclass Inventory:\n    def __init__(self):\n        pass\n\n    def counts(self) -> dict[str, int]:\n        return {\"apples\": 2}\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_dict_key_accessors",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_dict_key_accessors",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_dict_key_accessors/__init__.py",
+                        "mod_bindgen_dict_key_accessors",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Inventory(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Inventory);
+            ::pyo3::pyobject_native_type_info!(
+                Inventory,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_dict_key_accessors.Inventory")
+            );
+            impl Inventory {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call0(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_dict_key_accessors"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Inventory"),
+                                )?
+                                .as_any(),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Inventory")]
+            pub trait InventoryMethods {
+                fn counts<'py>(&'py self) -> ::pyo3::PyResult<super::StrKeyedDict<'py, i64>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl InventoryMethods for ::pyo3::Bound<'_, Inventory> {
+                /// The values of the returned dictionary can be extracted by key via `StrKeyedDict::get_str`.
+                fn counts<'py>(&'py self) -> ::pyo3::PyResult<super::StrKeyedDict<'py, i64>> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "counts"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            /// The values of the returned dictionary can be extracted by key via `StrKeyedDict::get_str`.
+            pub fn scores<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<super::StrKeyedDict<'py, f64>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_dict_key_accessors"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "scores"),
+                    )?,
+                )
+            }
+            /// The values of the returned dictionary can be extracted by key via `DictKeyAccessors::get_str`.
+            pub fn stats<'py>(
+                py: ::pyo3::marker::Python<'py>,
+            ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_dict_key_accessors"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "stats"),
+                    )?,
+                )
+            }
+        }
+        /// Typed access to single keys of the dictionaries returned for `dict[str, Any]`, without
+        /// converting the whole dictionary.
+        pub trait DictKeyAccessors<'py> {
+            /// Extract the value of the given key (or `None` if the key is missing).
+            fn get_str<T>(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<T>>
+            where
+                T: ::pyo3::FromPyObject<'py>;
+        }
+        #[automatically_derived]
+        impl<'py> DictKeyAccessors<'py> for ::pyo3::Bound<'py, ::pyo3::types::PyDict> {
+            fn get_str<T>(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<T>>
+            where
+                T: ::pyo3::FromPyObject<'py>,
+            {
+                ::pyo3::types::PyDictMethods::get_item(self, key)?
+                    .map(|value| ::pyo3::types::PyAnyMethods::extract(&value))
+                    .transpose()
+            }
+        }
+        /// Dictionary returned for `dict[str, V]`, whose values can be extracted by key as `V` without
+        /// converting the whole dictionary.
+        #[repr(transparent)]
+        pub struct StrKeyedDict<'py, V>(
+            ::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+            ::std::marker::PhantomData<V>,
+        );
+        impl<'py, V> StrKeyedDict<'py, V>
+        where
+            V: ::pyo3::FromPyObject<'py>,
+        {
+            /// Extract the value of the given key (or `None` if the key is missing).
+            pub fn get_str(&self, key: &str) -> ::pyo3::PyResult<::std::option::Option<V>> {
+                DictKeyAccessors::get_str(&self.0, key)
+            }
+        }
+        impl<'py, V> StrKeyedDict<'py, V> {
+            /// Get the underlying Python dictionary.
+            pub fn into_inner(self) -> ::pyo3::Bound<'py, ::pyo3::types::PyDict> {
+                self.0
+            }
+        }
+        #[automatically_derived]
+        impl<'py, V> ::std::ops::Deref for StrKeyedDict<'py, V> {
+            type Target = ::pyo3::Bound<'py, ::pyo3::types::PyDict>;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        #[automatically_derived]
+        impl<'py, V> ::pyo3::FromPyObject<'py> for StrKeyedDict<'py, V> {
+            fn extract_bound(
+                ob: &::pyo3::Bound<'py, ::pyo3::types::PyAny>,
+            ) -> ::pyo3::PyResult<Self> {
+                ::std::result::Result::Ok(
+                    Self(
+                        ::pyo3::types::PyAnyMethods::downcast::<::pyo3::types::PyDict>(ob)?
+                            .clone(),
+                        ::std::marker::PhantomData,
+                    ),
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_dict_key_accessors::InventoryMethods;
+        mod_bindgen_dict_key_accessors::pyo3_embed_python_source_code(py, false).unwrap();
+        let stats = mod_bindgen_dict_key_accessors::stats(py).unwrap();
+        let count: Option<i64> = stats.get_str("count").unwrap();
+        let name: Option<String> = stats.get_str("name").unwrap();
+        let missing: Option<i64> = stats.get_str("missing").unwrap();
+        assert_eq!(count, Some(3));
+        assert_eq!(name.as_deref(), Some("widgets"));
+        assert_eq!(missing, None);
+        let scores = mod_bindgen_dict_key_accessors::scores(py).unwrap();
+        assert_eq!(scores.get_str("alice").unwrap(), Some(1.5));
+        assert_eq!(scores.get_str("missing").unwrap(), None);
+        assert_eq!(pyo3::types::PyDictMethods::len(&scores.into_inner()), 1);
+        let inventory = mod_bindgen_dict_key_accessors::Inventory::new(py).unwrap();
+        let counts = inventory.counts().unwrap();
+        assert_eq!(counts.get_str("apples").unwrap(), Some(2));
+    }
+}
+
+#[test]
+fn bindgen_dict_key_accessors_shared() {
+    // Arrange
+    let cfg = pyo3_bindgen_engine::Config::builder()
+        .dict_key_accessors(true)
+        .build();
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::new(cfg)
+        .module_from_str(
+            "def counts() -> dict[str, int]:\n    return {}\n",
+            "mod_bindgen_dict_key_accessors_shared_a",
+        )
+        .unwrap()
+        .module_from_str(
+            "def names() -> dict[str, str]:\n    return {}\n",
+            "mod_bindgen_dict_key_accessors_shared_b",
+        )
+        .unwrap()
+        .generate()
+        .unwrap()
+        .to_string();
+
+    // Assert
+    assert_eq!(bindings.matches("pub trait DictKeyAccessors").count(), 1);
+    assert_eq!(bindings.matches("pub struct StrKeyedDict").count(), 1);
+    assert!(bindings.contains("super :: StrKeyedDict < 'py , i64 >"));
+    assert!(bindings.contains("super :: StrKeyedDict < 'py , :: std :: string :: String >"));
+}
+
+test_bindgen! {
+    bindgen_non_ascii_identifiers
+
+    py: r#"
+    import inspect
+
+    def 数据(población: int) -> int:
+        return población + 1
+
+    class クラス:
+        def café(self) -> str:
+            return "café"
+
+    def _double(x: int) -> int:
+        return x * 2
+
+    _double.__name__ = _double.__qualname__ = "double😀"
+    globals()["double😀"] = _double
+
+    def double_u1F600_(x: int) -> int:
+        return x * 3
+
+    def _identity(value: int) -> int:
+        return value
+
+    _identity.__signature__ = inspect.Signature(
+        [inspect.Parameter("valué", inspect.Parameter.POSITIONAL_OR_KEYWORD, annotation=int)],
+        return_annotation=int,
+    )
+    _identity.__name__ = _identity.__qualname__ = "identitý"
+    globals()["identitý"] = _identity
+
+    globals()["Box😀"] = type("Box😀", (), {"__module__": __name__})
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_non_ascii_identifiers {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import inspect\n\ndef 数据(población: int) -> int:\n    return población + 1\n\nclass クラス:\n    def café(self) -> str:\n        return \"café\"\n\ndef _double(x: int) -> int:\n    return x * 2\n\n_double.__name__ = _double.__qualname__ = \"double😀\"\nglobals()[\"double😀\"] = _double\n\ndef double_u1F600_(x: int) -> int:\n    return x * 3\n\ndef _identity(value: int) -> int:\n    return value\n\n_identity.__signature__ = inspect.Signature(\n    [inspect.Parameter(\"value\u{301}\", inspect.Parameter.POSITIONAL_OR_KEYWORD, annotation=int)],\n    return_annotation=int,\n)\n_identity.__name__ = _identity.__qualname__ = \"identity\u{301}\"\nglobals()[\"identity\u{301}\"] = _identity\n\nglobals()[\"Box😀\"] = type(\"Box😀\", (), {\"__module__\": __name__})\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_non_ascii_identifiers",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_non_ascii_identifiers",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_non_ascii_identifiers/__init__.py",
+                        "mod_bindgen_non_ascii_identifiers",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Box_u1F600_(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Box_u1F600_);
+            ::pyo3::pyobject_native_type_info!(
+                Box_u1F600_,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_non_ascii_identifiers.Box😀")
+            );
+            impl Box_u1F600_ {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Box_u1F600_")]
+            pub trait Box_u1F600_Methods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl Box_u1F600_Methods for ::pyo3::Bound<'_, Box_u1F600_> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[repr(transparent)]
+            pub struct クラス(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(クラス);
+            ::pyo3::pyobject_native_type_info!(
+                クラス,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_non_ascii_identifiers.クラス")
+            );
+            impl クラス {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "クラス")]
+            pub trait クラスMethods {
+                fn café(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl クラスMethods for ::pyo3::Bound<'_, クラス> {
+                fn café(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "café"),
+                        )?,
+                    )
+                }
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn double_u1F600_(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_non_ascii_identifiers"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "double_u1F600_"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_x, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn double_u1F600__2(
+                py: ::pyo3::marker::Python<'_>,
+                p_x: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_non_ascii_identifiers"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "double😀"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_x, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn identitý(
+                py: ::pyo3::marker::Python<'_>,
+                p_valué: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_non_ascii_identifiers"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "identity\u{301}"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_valué, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn 数据(
+                py: ::pyo3::marker::Python<'_>,
+                p_población: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_non_ascii_identifiers"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "数据"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_población, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_non_ascii_identifiers::クラスMethods;
+        use pyo3::prelude::*;
+        mod_bindgen_non_ascii_identifiers::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_non_ascii_identifiers::数据(py, 1).unwrap(), 2);
+        assert_eq!(mod_bindgen_non_ascii_identifiers::double_u1F600_(py, 2).unwrap(), 6);
+        assert_eq!(mod_bindgen_non_ascii_identifiers::double_u1F600__2(py, 2).unwrap(), 4);
+        assert_eq!(mod_bindgen_non_ascii_identifiers::identitý(py, 7).unwrap(), 7);
+        let instance = py
+            .import_bound("mod_bindgen_non_ascii_identifiers")
+            .unwrap()
+            .getattr("クラス")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .downcast_into::<mod_bindgen_non_ascii_identifiers::クラス>()
+            .unwrap();
+        assert_eq!(instance.café().unwrap(), "café");
+    }
+}
+
+test_bindgen! {
+    bindgen_custom_descriptor
+
+    py: r#"
+    import functools
+
+    class Field:
+        def __set_name__(self, owner, name):
+            self.name = "_" + name
+
+        def __get__(self, instance, owner=None) -> int:
+            if instance is None:
+                return self
+            return getattr(instance, self.name, 0)
+
+        def __set__(self, instance, value: int):
+            setattr(instance, self.name, value)
+
+    class Untyped:
+        def __get__(self, instance, owner=None):
+            return self if instance is None else "untyped"
+
+    class memoized(functools.cached_property):
+        pass
+
+    class Model:
+        count = Field()
+        untyped = Untyped()
+
+        @memoized
+        def total(self) -> float:
+            """The total of the model."""
+            return 1.5
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_custom_descriptor {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import functools\n\nclass Field:\n    def __set_name__(self, owner, name):\n        self.name = \"_\" + name\n\n    def __get__(self, instance, owner=None) -> int:\n        if instance is None:\n            return self\n        return getattr(instance, self.name, 0)\n\n    def __set__(self, instance, value: int):\n        setattr(instance, self.name, value)\n\nclass Untyped:\n    def __get__(self, instance, owner=None):\n        return self if instance is None else \"untyped\"\n\nclass memoized(functools.cached_property):\n    pass\n\nclass Model:\n    count = Field()\n    untyped = Untyped()\n\n    @memoized\n    def total(self) -> float:\n        \"\"\"The total of the model.\"\"\"\n        return 1.5\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_custom_descriptor",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_custom_descriptor",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_custom_descriptor/__init__.py",
+                        "mod_bindgen_custom_descriptor",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Field(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Field);
+            ::pyo3::pyobject_native_type_info!(
+                Field,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_custom_descriptor.Field")
+            );
+            impl Field {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Field")]
+            pub trait FieldMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl FieldMethods for ::pyo3::Bound<'_, Field> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[repr(transparent)]
+            pub struct Model(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Model);
+            ::pyo3::pyobject_native_type_info!(
+                Model,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_custom_descriptor.Model")
+            );
+            impl Model {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Model")]
+            pub trait ModelMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn count(&self) -> ::pyo3::PyResult<i64>;
+                fn set_count(&self, p_value: i64) -> ::pyo3::PyResult<()>;
+                fn total(&self) -> ::pyo3::PyResult<f64>;
+                fn untyped<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl ModelMethods for ::pyo3::Bound<'_, Model> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                fn count(&self) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "count"),
+                        )?,
+                    )
+                }
+                fn set_count(&self, p_value: i64) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "count"),
+                        p_value,
+                    )
+                }
+                /// The total of the model.
+                fn total(&self) -> ::pyo3::PyResult<f64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "total"),
+                        )?,
+                    )
+                }
+                fn untyped<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "untyped"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[repr(transparent)]
+            pub struct Untyped(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Untyped);
+            ::pyo3::pyobject_native_type_info!(
+                Untyped,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_custom_descriptor.Untyped")
+            );
+            impl Untyped {}
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Untyped")]
+            pub trait UntypedMethods {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl UntypedMethods for ::pyo3::Bound<'_, Untyped> {
+                fn __init__<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "__init__"),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            #[repr(transparent)]
+            pub struct memoized(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(memoized);
+            ::pyo3::pyobject_native_type_info!(
+                memoized,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_custom_descriptor.memoized")
+            );
+            impl memoized {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_func: impl ::pyo3::IntoPy<::pyo3::Py<::pyo3::types::PyAny>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    let p_func = ::pyo3::IntoPy::<
+                        ::pyo3::Py<::pyo3::types::PyAny>,
+                    >::into_py(p_func, py);
+                    let p_func = p_func.bind(py);
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_custom_descriptor"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "memoized"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_func, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "memoized")]
+            pub trait memoizedMethods {
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl memoizedMethods for ::pyo3::Bound<'_, memoized> {
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_custom_descriptor::{Model, ModelMethods};
+        use pyo3::types::PyAnyMethods;
+        mod_bindgen_custom_descriptor::pyo3_embed_python_source_code(py, false).unwrap();
+        let model = py
+            .import_bound("mod_bindgen_custom_descriptor")
+            .unwrap()
+            .getattr("Model")
+            .unwrap()
+            .call0()
+            .unwrap()
+            .downcast_into::<Model>()
+            .unwrap();
+        assert_eq!(model.count().unwrap(), 0);
+        model.set_count(7).unwrap();
+        assert_eq!(model.count().unwrap(), 7);
+        assert_eq!(model.total().unwrap(), 1.5);
+        assert_eq!(model.untyped().unwrap().to_string(), "untyped");
+    }
+}
+
+#[test]
+fn bindgen_custom_descriptor_variadic_setter() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    class Variadic:
+        def __get__(self, instance, owner=None) -> int:
+            return self if instance is None else 0
+
+        def __set__(self, *args):
+            pass
+
+    class Model:
+        value = Variadic()
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_custom_descriptor_variadic_setter")
+        .unwrap()
+        .generate()
+        .unwrap();
+
+    // Assert
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+    assert!(bindings.contains("fn value(&self) -> ::pyo3::PyResult<i64>;"));
+    assert!(bindings.contains("fn set_value("));
+}
+
+test_bindgen! {
+    bindgen_send_wrappers
+
+    cfg: pyo3_bindgen_engine::Config::builder().emit_send_wrappers(true).build(),
+
+    py: r#"
+    class Counter:
+        def __init__(self, start: int = 0):
+            self._count = start
+
+        def increment(self, step: int) -> int:
+            self._count += step
+            return self._count
+
+        def copy(self) -> "Counter":
+            return Counter(self._count)
+
+        def merge(self, other: "Counter") -> None:
+            self._count += other._count
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_send_wrappers {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Counter:\n    def __init__(self, start: int = 0):\n        self._count = start\n\n    def increment(self, step: int) -> int:\n        self._count += step\n        return self._count\n\n    def copy(self) -> \"Counter\":\n        return Counter(self._count)\n\n    def merge(self, other: \"Counter\") -> None:\n        self._count += other._count\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_send_wrappers",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_send_wrappers",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_send_wrappers/__init__.py",
+                        "mod_bindgen_send_wrappers",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_send_wrappers.Counter")
+            );
+            impl Counter {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_start: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_send_wrappers"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Counter"),
+                                )?
+                                .as_any(),
+                            (),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_start,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "start"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// `Send` handle to an object of `Counter`, which can be moved across threads. Its methods acquire the GIL internally.
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct CounterHandle(pub ::pyo3::Py<Counter>);
+            impl CounterHandle {
+                /// Create a handle to the given object.
+                pub fn new(object: &::pyo3::Bound<'_, Counter>) -> Self {
+                    Self(::pyo3::Bound::clone(object).unbind())
+                }
+                /// Borrow the object for the lifetime of the GIL.
+                pub fn bind<'py>(
+                    &self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> &::pyo3::Bound<'py, Counter> {
+                    self.0.bind(py)
+                }
+                /// Call `CounterMethods::copy` while holding the GIL.
+                pub fn copy(&self) -> ::pyo3::PyResult<::pyo3::Py<Counter>> {
+                    ::pyo3::Python::with_gil(|py| {
+                        CounterMethods::copy(self.0.bind(py)).map(::pyo3::Bound::unbind)
+                    })
+                }
+                /// Call `CounterMethods::increment` while holding the GIL.
+                pub fn increment(&self, p_step: i64) -> ::pyo3::PyResult<i64> {
+                    ::pyo3::Python::with_gil(|py| {
+                        CounterMethods::increment(self.0.bind(py), p_step)
+                    })
+                }
+                /// Call `CounterMethods::to_dict` while holding the GIL.
+                pub fn to_dict(&self) -> ::pyo3::PyResult<::pyo3::Py<::pyo3::types::PyDict>> {
+                    ::pyo3::Python::with_gil(|py| {
+                        CounterMethods::to_dict(self.0.bind(py)).map(::pyo3::Bound::unbind)
+                    })
+                }
+            }
+            #[automatically_derived]
+            impl ::std::convert::From<::pyo3::Bound<'_, Counter>> for CounterHandle {
+                fn from(object: ::pyo3::Bound<'_, Counter>) -> Self {
+                    Self(object.unbind())
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn copy<'py>(&'py self) -> ::pyo3::PyResult<::pyo3::Bound<'py, Counter>>;
+                fn increment(&self, p_step: i64) -> ::pyo3::PyResult<i64>;
+                fn merge<'py>(
+                    &'py self,
+                    p_other: impl ::std::borrow::Borrow<::pyo3::Bound<'py, Counter>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                fn copy<'py>(&'py self) -> ::pyo3::PyResult<::pyo3::Bound<'py, Counter>> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method0(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "copy"),
+                        )?,
+                    )
+                }
+                fn increment(&self, p_step: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "increment"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_step, py)],
+                            ),
+                        )?,
+                    )
+                }
+                fn merge<'py>(
+                    &'py self,
+                    p_other: impl ::std::borrow::Borrow<::pyo3::Bound<'py, Counter>>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyAny>> {
+                    let py = self.py();
+                    let p_other = ::std::borrow::Borrow::<
+                        ::pyo3::Bound<'py, Counter>,
+                    >::borrow(&p_other);
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "merge"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_other, py)],
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_send_wrappers::{Counter, CounterHandle, CounterMethods};
+        mod_bindgen_send_wrappers::pyo3_embed_python_source_code(py, false).unwrap();
+        let counter = Counter::new(py, 1).unwrap();
+        let handle = CounterHandle::new(&counter);
+
+        // The handle is moved to another thread, which acquires the GIL for each call
+        let (count, copy) = py.allow_threads(move || {
+            std::thread::spawn(move || {
+                let count = handle.increment(2).unwrap();
+                (count, handle.copy().unwrap())
+            })
+            .join()
+            .unwrap()
+        });
+        assert_eq!(count, 3);
+        assert_eq!(counter.increment(0).unwrap(), 3);
+        let copy = CounterHandle::from(copy.into_bound(py));
+        assert_eq!(copy.increment(1).unwrap(), 4);
+        assert_eq!(counter.increment(0).unwrap(), 3);
+    }
+}
+
+#[test]
+fn bindgen_collections_abc_params() {
+    // Arrange
+    const CODE_PY: &str = indoc::indoc! { r#"
+    import typing
+    from collections.abc import (
+        Collection,
+        Container,
+        ItemsView,
+        KeysView,
+        MutableMapping,
+        MutableSequence,
+        MutableSet,
+        Reversible,
+        ValuesView,
+    )
+
+    def consume(
+        mutable_sequence: MutableSequence[int],
+        mutable_mapping: MutableMapping[str, float],
+        abstract_set: typing.AbstractSet[int],
+        mutable_set: MutableSet[str],
+        collection: Collection[int],
+        container: Container[str],
+        reversible: Reversible[float],
+        keys_view: KeysView[str],
+        values_view: ValuesView[int],
+        items_view: ItemsView[str, int],
+        typing_items_view: typing.ItemsView[str, int],
+        bare_sequence: typing.MutableSequence,
+        bare_mapping: typing.MutableMapping,
+        bare_set: typing.AbstractSet,
+    ) -> None:
+        ...
+    "# };
+
+    // Act
+    let bindings = pyo3_bindgen_engine::Codegen::default()
+        .module_from_str(CODE_PY, "mod_bindgen_collections_abc_params")
+        .unwrap()
+        .generate()
+        .unwrap();
+    let bindings = prettyplease::unparse(&syn::parse2(bindings).unwrap());
+
+    // Assert
+    for param in [
+        "p_mutable_sequence: &[i64],",
+        "p_mutable_mapping: &::std::collections::HashMap<::std::string::String, f64>,",
+        "p_abstract_set: &::std::collections::HashSet<i64>,",
+        "p_mutable_set: &::std::collections::HashSet<::std::string::String>,",
+        "p_collection: &[i64],",
+        "p_container: &[::std::string::String],",
+        "p_reversible: &[f64],",
+        "p_keys_view: &[::std::string::String],",
+        "p_values_view: &[i64],",
+        "p_items_view: &[(::std::string::String, i64)],",
+        "p_typing_items_view: &[(::std::string::String, i64)],",
+        "p_bare_sequence: &[::pyo3::Bound<'py, ::pyo3::types::PyAny>],",
+        "p_bare_mapping: impl ::pyo3::types::IntoPyDict,",
+        "p_bare_set: &::pyo3::Bound<'py, ::pyo3::types::PySet>,",
+    ] {
+        assert!(bindings.contains(param), "{param}");
+    }
+}
+
+test_bindgen! {
+    bindgen_shared_methods
+
+    cfg: pyo3_bindgen_engine::Config::builder()
+        .emit_shared_methods(true)
+        .thread_docs(true)
+        .build(),
+
+    py: r#"
+    class Counter:
+        """Thread-safe counter."""
+
+        def __init__(self, start: int = 0):
+            self._count = start
+
+        def increment(self, step: int) -> int:
+            self._count += step
+            return self._count
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_shared_methods {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "class Counter:\n    \"\"\"Thread-safe counter.\"\"\"\n\n    def __init__(self, start: int = 0):\n        self._count = start\n\n    def increment(self, step: int) -> int:\n        self._count += step\n        return self._count\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_shared_methods")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_shared_methods",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_shared_methods/__init__.py",
+                        "mod_bindgen_shared_methods",
+                    )?,
+                )
+            }
+            /// Thread-safe counter.
+            ///
+            /// # Threading
+            ///
+            /// `Bound<'py, Counter>` is tied to the lifetime of the GIL, so it cannot be stored beyond `Python::with_gil` or sent to other threads. Unbind it into `Py<Counter>` (`Send + Sync`) to store it and re-bind it with `Py::bind` to use it. `Counter::into_py_shared` and `CounterSharedMethods` provide typed helpers for both steps. Note that all calls into Python still acquire the GIL, so they are serialized across threads.
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_shared_methods.Counter")
+            );
+            impl Counter {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_start: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_shared_methods"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Counter"),
+                                )?
+                                .as_any(),
+                            (),
+                            Some(
+                                &{
+                                    let __internal_kwargs = ::pyo3::types::PyDict::new_bound(py);
+                                    {
+                                        let __internal_value = ::pyo3::ToPyObject::to_object(
+                                            &p_start,
+                                            py,
+                                        );
+                                        if !__internal_value.is_none(py) {
+                                            ::pyo3::types::PyDictMethods::set_item(
+                                                &__internal_kwargs,
+                                                ::pyo3::intern!(py, "start"),
+                                                __internal_value,
+                                            )?;
+                                        }
+                                    }
+                                    __internal_kwargs
+                                },
+                            ),
+                        )?,
+                    )
+                }
+                /// Unbind the object from the lifetime of the GIL, so that it can be stored and sent across threads.
+                pub fn into_py_shared(self_: ::pyo3::Bound<'_, Self>) -> ::pyo3::Py<Self> {
+                    ::pyo3::Bound::unbind(self_)
+                }
+            }
+            /// Methods of `Counter` for the `Py<T>` smart pointer, which can be stored and sent across threads. Each method binds the object to the GIL token `py`.
+            pub trait CounterSharedMethods {
+                fn rebind<'py>(
+                    &self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::Bound<'py, Counter>;
+                fn increment<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                    p_step: i64,
+                ) -> ::pyo3::PyResult<i64>;
+                fn to_dict<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterSharedMethods for ::pyo3::Py<Counter> {
+                /// Bind the object to the lifetime of the GIL token `py`.
+                fn rebind<'py>(
+                    &self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::Bound<'py, Counter> {
+                    ::pyo3::Bound::clone(self.bind(py))
+                }
+                /// Call `CounterMethods::increment` on the object bound to the GIL token `py`.
+                fn increment<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                    p_step: i64,
+                ) -> ::pyo3::PyResult<i64> {
+                    CounterMethods::increment(self.bind(py), p_step)
+                }
+                /// Call `CounterMethods::to_dict` on the object bound to the GIL token `py`.
+                fn to_dict<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    CounterMethods::to_dict(self.bind(py))
+                }
+                /// Call `CounterMethods::update_from_dict` on the object bound to the GIL token `py`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    py: ::pyo3::marker::Python<'py>,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    CounterMethods::update_from_dict(self.bind(py), p_dict)
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn increment(&self, p_step: i64) -> ::pyo3::PyResult<i64>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                fn increment(&self, p_step: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "increment"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_step, py)],
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_shared_methods::{Counter, CounterMethods, CounterSharedMethods};
+        mod_bindgen_shared_methods::pyo3_embed_python_source_code(py, false).unwrap();
+        let counter = Counter::into_py_shared(Counter::new(py, 1).unwrap());
+        let counter = py.allow_threads(|| {
+            std::thread::spawn(move || {
+                pyo3::Python::with_gil(|py| {
+                    assert_eq!(counter.increment(py, 2).unwrap(), 3);
+                });
+                counter
+            })
+            .join()
+            .unwrap()
+        });
+        assert_eq!(counter.rebind(py).increment(4).unwrap(), 7);
+    }
+}
+
+test_bindgen! {
+    bindgen_recursive_type_annotations
+    cfg: pyo3_bindgen_engine::Config::builder().max_type_depth(8).build(),
+
+    py: r#"
+    import typing
+    from typing import Dict, List, Optional, Union
+
+    JSON = Union[str, int, float, bool, None, List["JSON"], Dict[str, "JSON"]]
+
+    def dump(value: Optional[List["JSON"]]) -> str:
+        return str(value)
+
+    # Evaluating the forward references makes them refer back to the alias itself
+    typing.get_type_hints(dump)
+
+    def depth(value: eval("List[" * 100 + "int" + "]" * 100)) -> int:
+        depth = 0
+        while isinstance(value, list):
+            value = value[0]
+            depth += 1
+        return depth
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_recursive_type_annotations {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "import typing\nfrom typing import Dict, List, Optional, Union\n\nJSON = Union[str, int, float, bool, None, List[\"JSON\"], Dict[str, \"JSON\"]]\n\ndef dump(value: Optional[List[\"JSON\"]]) -> str:\n    return str(value)\n\n# Evaluating the forward references makes them refer back to the alias itself\ntyping.get_type_hints(dump)\n\ndef depth(value: eval(\"List[\" * 100 + \"int\" + \"]\" * 100)) -> int:\n    depth = 0\n    while isinstance(value, list):\n        value = value[0]\n        depth += 1\n    return depth\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force
+                    && pyo3::types::PyAnyMethods::contains(
+                        &modules,
+                        "mod_bindgen_recursive_type_annotations",
+                    )?
+                {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_recursive_type_annotations",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_recursive_type_annotations/__init__.py",
+                        "mod_bindgen_recursive_type_annotations",
+                    )?,
+                )
+            }
+            pub fn depth<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: &[Vec<
+                    Vec<Vec<Vec<Vec<Vec<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>>>>>,
+                >],
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_recursive_type_annotations"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "depth"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?,
+                )
+            }
+            pub fn dump<'py>(
+                py: ::pyo3::marker::Python<'py>,
+                p_value: ::std::option::Option<Vec<::pyo3::Bound<'py, ::pyo3::types::PyAny>>>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_recursive_type_annotations"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "dump"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+    run: |py| {
+        use mod_bindgen_recursive_type_annotations::{depth, dump};
+        use pyo3::IntoPy;
+        mod_bindgen_recursive_type_annotations::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(dump(py, Some(vec![1_i64.into_py(py).into_bound(py)])).unwrap(), "[1]");
+        let leaf = 1_i64.into_py(py).into_bound(py);
+        let value = [vec![vec![vec![vec![vec![vec![vec![leaf]]]]]]]];
+        assert_eq!(depth(py, &value).unwrap(), 8);
+    }
+}
+
+test_bindgen! {
+    bindgen_metadata_accessors
+
+    cfg: pyo3_bindgen_engine::Config::builder().generate_metadata_accessors(true).build(),
+
+    py: r#"
+    __version__ = "1.2.3"
+    __all__ = ["version", "add"]
+
+    def version() -> str:
+        return "own"
+
+    def add(a: int, b: int) -> int:
+        return a + b
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_metadata_accessors {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "__version__ = \"1.2.3\"\n__all__ = [\"version\", \"add\"]\n\ndef version() -> str:\n    return \"own\"\n\ndef add(a: int, b: int) -> int:\n    return a + b\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_metadata_accessors")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_metadata_accessors",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_metadata_accessors/__init__.py",
+                        "mod_bindgen_metadata_accessors",
+                    )?,
+                )
+            }
+            /// Get the version of the module from its `__version__` attribute (if any).
+            pub fn version_from_metadata(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::string::String>> {
+                let module = py
+                    .import_bound(::pyo3::intern!(py, "mod_bindgen_metadata_accessors"))?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                let version = ::pyo3::types::PyAnyMethods::getattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )?;
+                let version = ::pyo3::types::PyAnyMethods::str(&version)?;
+                ::std::result::Result::Ok(
+                    ::std::option::Option::Some(
+                        ::pyo3::types::PyStringMethods::to_cow(&version)?.into_owned(),
+                    ),
+                )
+            }
+            /// Get the path of the file from which the module was loaded via its `__file__` attribute, which is
+            /// missing (or `None`) for namespace packages and built-in modules.
+            pub fn module_path(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::path::PathBuf>> {
+                let module = py
+                    .import_bound(::pyo3::intern!(py, "mod_bindgen_metadata_accessors"))?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__file__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        module.as_any(),
+                        ::pyo3::intern!(py, "__file__"),
+                    )?,
+                )
+            }
+            /// Get the names exported by the module from its `__all__` attribute, falling back to the public
+            /// names (without a leading underscore) listed by `dir()`.
+            pub fn exported_names(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>> {
+                let module = py
+                    .import_bound(::pyo3::intern!(py, "mod_bindgen_metadata_accessors"))?;
+                if ::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__all__"),
+                )? {
+                    return ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            module.as_any(),
+                            ::pyo3::intern!(py, "__all__"),
+                        )?,
+                    );
+                }
+                ::std::result::Result::Ok(
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::vec::Vec<::std::string::String>,
+                    >(::pyo3::types::PyAnyMethods::dir(module.as_any()).as_any())?
+                        .into_iter()
+                        .filter(|name| !name.starts_with('_'))
+                        .collect(),
+                )
+            }
+            pub fn add(
+                py: ::pyo3::marker::Python<'_>,
+                p_a: i64,
+                p_b: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_metadata_accessors"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "add"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_a, py),
+                                ::pyo3::ToPyObject::to_object(&p_b, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+            pub fn version(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_metadata_accessors"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "version"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_metadata_accessors::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_metadata_accessors::version(py).unwrap(), "own");
+        assert_eq!(
+            mod_bindgen_metadata_accessors::version_from_metadata(py).unwrap(),
+            Some("1.2.3".to_owned())
+        );
+        assert_eq!(
+            mod_bindgen_metadata_accessors::module_path(py).unwrap(),
+            Some(std::path::PathBuf::from("mod_bindgen_metadata_accessors/__init__.py"))
+        );
+        assert_eq!(
+            mod_bindgen_metadata_accessors::exported_names(py).unwrap(),
+            ["version", "add"]
+        );
+    }
+}
+
+test_bindgen! {
+    bindgen_metadata_accessors_missing
+
+    cfg: pyo3_bindgen_engine::Config::builder().generate_metadata_accessors(true).build(),
+
+    py: r#"
+    def _private() -> None:
+        pass
+
+    def add(a: int, b: int) -> int:
+        return a + b
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_metadata_accessors_missing {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def _private() -> None:\n    pass\n\ndef add(a: int, b: int) -> int:\n    return a + b\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_metadata_accessors_missing")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_metadata_accessors_missing",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_metadata_accessors_missing/__init__.py",
+                        "mod_bindgen_metadata_accessors_missing",
+                    )?,
+                )
+            }
+            /// Get the version of the module from its `__version__` attribute (if any).
+            pub fn version(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::string::String>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_missing"),
+                    )?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                let version = ::pyo3::types::PyAnyMethods::getattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )?;
+                let version = ::pyo3::types::PyAnyMethods::str(&version)?;
+                ::std::result::Result::Ok(
+                    ::std::option::Option::Some(
+                        ::pyo3::types::PyStringMethods::to_cow(&version)?.into_owned(),
+                    ),
+                )
+            }
+            /// Get the path of the file from which the module was loaded via its `__file__` attribute, which is
+            /// missing (or `None`) for namespace packages and built-in modules.
+            pub fn module_path(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::path::PathBuf>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_missing"),
+                    )?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__file__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        module.as_any(),
+                        ::pyo3::intern!(py, "__file__"),
+                    )?,
+                )
+            }
+            /// Get the names exported by the module from its `__all__` attribute, falling back to the public
+            /// names (without a leading underscore) listed by `dir()`.
+            pub fn exported_names(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_missing"),
+                    )?;
+                if ::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__all__"),
+                )? {
+                    return ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            module.as_any(),
+                            ::pyo3::intern!(py, "__all__"),
+                        )?,
+                    );
+                }
+                ::std::result::Result::Ok(
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::vec::Vec<::std::string::String>,
+                    >(::pyo3::types::PyAnyMethods::dir(module.as_any()).as_any())?
+                        .into_iter()
+                        .filter(|name| !name.starts_with('_'))
+                        .collect(),
+                )
+            }
+            pub fn add(
+                py: ::pyo3::marker::Python<'_>,
+                p_a: i64,
+                p_b: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_missing"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "add"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [
+                                ::pyo3::ToPyObject::to_object(&p_a, py),
+                                ::pyo3::ToPyObject::to_object(&p_b, py),
+                            ],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        mod_bindgen_metadata_accessors_missing::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_metadata_accessors_missing::version(py).unwrap(), None);
+        assert_eq!(
+            mod_bindgen_metadata_accessors_missing::exported_names(py).unwrap(),
+            ["add"]
+        );
+    }
+}
+
+test_bindgen! {
+    bindgen_metadata_accessors_namespace_package
+
+    cfg: pyo3_bindgen_engine::Config::builder().generate_metadata_accessors(true).build(),
+
+    py: r#"
+    def answer() -> int:
+        return 42
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_metadata_accessors_namespace_package {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def answer() -> int:\n    return 42\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_metadata_accessors_namespace_package")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_metadata_accessors_namespace_package",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_metadata_accessors_namespace_package/__init__.py",
+                        "mod_bindgen_metadata_accessors_namespace_package",
+                    )?,
+                )
+            }
+            /// Get the version of the module from its `__version__` attribute (if any).
+            pub fn version(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::string::String>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_namespace_package"),
+                    )?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                let version = ::pyo3::types::PyAnyMethods::getattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__version__"),
+                )?;
+                let version = ::pyo3::types::PyAnyMethods::str(&version)?;
+                ::std::result::Result::Ok(
+                    ::std::option::Option::Some(
+                        ::pyo3::types::PyStringMethods::to_cow(&version)?.into_owned(),
+                    ),
+                )
+            }
+            /// Get the path of the file from which the module was loaded via its `__file__` attribute, which is
+            /// missing (or `None`) for namespace packages and built-in modules.
+            pub fn module_path(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::option::Option<::std::path::PathBuf>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_namespace_package"),
+                    )?;
+                if !::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__file__"),
+                )? {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        module.as_any(),
+                        ::pyo3::intern!(py, "__file__"),
+                    )?,
+                )
+            }
+            /// Get the names exported by the module from its `__all__` attribute, falling back to the public
+            /// names (without a leading underscore) listed by `dir()`.
+            pub fn exported_names(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::vec::Vec<::std::string::String>> {
+                let module = py
+                    .import_bound(
+                        ::pyo3::intern!(py, "mod_bindgen_metadata_accessors_namespace_package"),
+                    )?;
+                if ::pyo3::types::PyAnyMethods::hasattr(
+                    module.as_any(),
+                    ::pyo3::intern!(py, "__all__"),
+                )? {
+                    return ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            module.as_any(),
+                            ::pyo3::intern!(py, "__all__"),
+                        )?,
+                    );
+                }
+                ::std::result::Result::Ok(
+                    ::pyo3::types::PyAnyMethods::extract::<
+                        ::std::vec::Vec<::std::string::String>,
+                    >(::pyo3::types::PyAnyMethods::dir(module.as_any()).as_any())?
+                        .into_iter()
+                        .filter(|name| !name.starts_with('_'))
+                        .collect(),
+                )
+            }
+            pub fn answer(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method0(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(
+                                    py, "mod_bindgen_metadata_accessors_namespace_package"
+                                ),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "answer"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use pyo3::types::{PyAnyMethods, PyDictMethods, PyListMethods};
+        // Import a namespace package (directory without `__init__.py`) under the name of the module instead of
+        // the module that was parsed from the source code
+        let dir = std::env::temp_dir()
+            .join(format!("pyo3_bindgen_{}", std::process::id()))
+            .join("namespace_package");
+        std::fs::create_dir_all(dir.join("mod_bindgen_metadata_accessors_namespace_package")).unwrap();
+        let sys = py.import_bound("sys").unwrap();
+        let path = sys.getattr("path").unwrap().downcast_into::<pyo3::types::PyList>().unwrap();
+        let modules = sys.getattr("modules").unwrap().downcast_into::<pyo3::types::PyDict>().unwrap();
+        modules.del_item("mod_bindgen_metadata_accessors_namespace_package").unwrap();
+        path.insert(0, &dir).unwrap();
+        let module = py
+            .import_bound("mod_bindgen_metadata_accessors_namespace_package")
+            .unwrap();
+        assert!(module.getattr("__file__").map_or(true, |file| file.is_none()));
+        assert_eq!(
+            mod_bindgen_metadata_accessors_namespace_package::module_path(py).unwrap(),
+            None
+        );
+        assert_eq!(
+            mod_bindgen_metadata_accessors_namespace_package::version(py).unwrap(),
+            None
+        );
+        assert!(mod_bindgen_metadata_accessors_namespace_package::exported_names(py)
+            .unwrap()
+            .is_empty());
+        path.del_item(0).unwrap();
+        modules.del_item("mod_bindgen_metadata_accessors_namespace_package").unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+test_bindgen! {
+    bindgen_owned_only_signatures
+
+    py: r#"
+    def increment(value: int) -> int:
+        return value + 1
+
+    class Counter:
+        def __init__(self, start: int):
+            self.count = start
+
+        def add(self, value: int) -> int:
+            self.count += value
+            return self.count
+
+        def __call__(self, value: int) -> int:
+            return self.count * value
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_owned_only_signatures {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "def increment(value: int) -> int:\n    return value + 1\n\nclass Counter:\n    def __init__(self, start: int):\n        self.count = start\n\n    def add(self, value: int) -> int:\n        self.count += value\n        return self.count\n\n    def __call__(self, value: int) -> int:\n        return self.count * value\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_owned_only_signatures")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_owned_only_signatures",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_owned_only_signatures/__init__.py",
+                        "mod_bindgen_owned_only_signatures",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Counter(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Counter);
+            ::pyo3::pyobject_native_type_info!(
+                Counter,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_owned_only_signatures.Counter")
+            );
+            impl Counter {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_start: i64,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_owned_only_signatures"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Counter"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_start, py)],
+                            ),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Counter")]
+            pub trait CounterMethods {
+                fn call(&self, p_value: i64) -> ::pyo3::PyResult<i64>;
+                fn add(&self, p_value: i64) -> ::pyo3::PyResult<i64>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl CounterMethods for ::pyo3::Bound<'_, Counter> {
+                fn call(&self, p_value: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            self.as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_value, py)],
+                            ),
+                        )?,
+                    )
+                }
+                fn add(&self, p_value: i64) -> ::pyo3::PyResult<i64> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call_method1(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "add"),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [::pyo3::ToPyObject::to_object(&p_value, py)],
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn increment(
+                py: ::pyo3::marker::Python<'_>,
+                p_value: i64,
+            ) -> ::pyo3::PyResult<i64> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::call_method1(
+                        py
+                            .import_bound(
+                                ::pyo3::intern!(py, "mod_bindgen_owned_only_signatures"),
+                            )?
+                            .as_any(),
+                        ::pyo3::intern!(py, "increment"),
+                        ::pyo3::types::PyTuple::new_bound(
+                            py,
+                            [::pyo3::ToPyObject::to_object(&p_value, py)],
+                        ),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_owned_only_signatures::{increment, Counter, CounterMethods};
+        mod_bindgen_owned_only_signatures::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(increment(py, 41).unwrap(), 42);
+        let counter = Counter::new(py, 1).unwrap();
+        assert_eq!(counter.add(2).unwrap(), 3);
+        assert_eq!(counter.call(2).unwrap(), 6);
+    }
+}
+
+test_bindgen! {
+    bindgen_class_var_final
+
+    py: r#"
+    from typing import ClassVar, Final, Optional
+
+    VERSION: Final[str] = "1.0"
+
+    class Settings:
+        limit: ClassVar[Optional[int]] = None
+        label: Final[str]
+        ratio: Final = 0.5
+        name: str
+
+        def __init__(self, label: str, name: str):
+            self.label = label
+            self.name = name
+    "#
+
+    rs: {
+        #[allow(
+            clippy::all,
+            clippy::nursery,
+            clippy::pedantic,
+            non_camel_case_types,
+            non_snake_case,
+            non_upper_case_globals,
+            unused
+        )]
+        pub mod mod_bindgen_class_var_final {
+            /// Embed the Python source code of the module into the Python interpreter
+            /// in order to enable the use of the generated Rust bindings.
+            ///
+            /// An existing module of the same name in `sys.modules` (e.g. from a previous call or from
+            /// the host application of an extension module) is kept, unless `force` is set.
+            pub fn pyo3_embed_python_source_code(
+                py: ::pyo3::marker::Python<'_>,
+                force: bool,
+            ) -> ::pyo3::PyResult<()> {
+                const SOURCE_CODE: &str = "from typing import ClassVar, Final, Optional\n\nVERSION: Final[str] = \"1.0\"\n\nclass Settings:\n    limit: ClassVar[Optional[int]] = None\n    label: Final[str]\n    ratio: Final = 0.5\n    name: str\n\n    def __init__(self, label: str, name: str):\n        self.label = label\n        self.name = name\n";
+                let modules = pyo3::types::PyAnyMethods::getattr(
+                    py.import_bound(pyo3::intern!(py, "sys"))?.as_any(),
+                    pyo3::intern!(py, "modules"),
+                )?;
+                if !force && pyo3::types::PyAnyMethods::contains(&modules, "mod_bindgen_class_var_final")? {
+                    return Ok(());
+                }
+                pyo3::types::PyAnyMethods::set_item(
+                    &modules,
+                    "mod_bindgen_class_var_final",
+                    pyo3::types::PyModule::from_code_bound(
+                        py,
+                        SOURCE_CODE,
+                        "mod_bindgen_class_var_final/__init__.py",
+                        "mod_bindgen_class_var_final",
+                    )?,
+                )
+            }
+            #[repr(transparent)]
+            pub struct Settings(::pyo3::PyAny);
+            ::pyo3::pyobject_native_type_named!(Settings);
+            ::pyo3::pyobject_native_type_info!(
+                Settings,
+                ::pyo3::pyobject_native_static_type_object!(::pyo3::ffi::PyBaseObject_Type),
+                ::std::option::Option::Some("mod_bindgen_class_var_final.Settings")
+            );
+            impl Settings {
+                pub fn new<'py>(
+                    py: ::pyo3::marker::Python<'py>,
+                    p_label: &str,
+                    p_name: &str,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, Self>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::call1(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_var_final"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Settings"),
+                                )?
+                                .as_any(),
+                            ::pyo3::types::PyTuple::new_bound(
+                                py,
+                                [
+                                    ::pyo3::ToPyObject::to_object(&p_label, py),
+                                    ::pyo3::ToPyObject::to_object(&p_name, py),
+                                ],
+                            ),
+                        )?,
+                    )
+                }
+                /// Get the value of the class attribute `limit` from the class itself.
+                pub fn limit(
+                    py: ::pyo3::marker::Python<'_>,
+                ) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_var_final"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Settings"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "limit"),
+                        )?,
+                    )
+                }
+                /// Get the value of the class attribute `ratio` from the class itself.
+                pub fn ratio(py: ::pyo3::marker::Python<'_>) -> ::pyo3::PyResult<f64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            ::pyo3::types::PyAnyMethods::getattr(
+                                    py
+                                        .import_bound(
+                                            ::pyo3::intern!(py, "mod_bindgen_class_var_final"),
+                                        )?
+                                        .as_any(),
+                                    ::pyo3::intern!(py, "Settings"),
+                                )?
+                                .as_any(),
+                            ::pyo3::intern!(py, "ratio"),
+                        )?,
+                    )
+                }
+            }
+            /// These methods are defined for the `Bound<'py, T>` smart pointer, so to use
+            /// method call syntax these methods are separated into a trait, because stable
+            /// Rust does not yet support `arbitrary_self_types`.
+            #[doc(alias = "Settings")]
+            pub trait SettingsMethods {
+                fn limit(&self) -> ::pyo3::PyResult<::std::option::Option<i64>>;
+                fn ratio(&self) -> ::pyo3::PyResult<f64>;
+                fn label(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn name(&self) -> ::pyo3::PyResult<::std::string::String>;
+                fn set_name(&self, p_value: &str) -> ::pyo3::PyResult<()>;
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>>;
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()>;
+            }
+            #[automatically_derived]
+            impl SettingsMethods for ::pyo3::Bound<'_, Settings> {
+                fn limit(&self) -> ::pyo3::PyResult<::std::option::Option<i64>> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "limit"),
+                        )?,
+                    )
+                }
+                fn ratio(&self) -> ::pyo3::PyResult<f64> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "ratio"),
+                        )?,
+                    )
+                }
+                fn label(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "label"),
+                        )?,
+                    )
+                }
+                fn name(&self) -> ::pyo3::PyResult<::std::string::String> {
+                    ::pyo3::types::PyAnyMethods::extract(
+                        &::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(self.py(), "name"),
+                        )?,
+                    )
+                }
+                fn set_name(&self, p_value: &str) -> ::pyo3::PyResult<()> {
+                    let py = self.py();
+                    ::pyo3::types::PyAnyMethods::setattr(
+                        self.as_any(),
+                        ::pyo3::intern!(py, "name"),
+                        p_value,
+                    )
+                }
+                /// Get the attributes of the object as a dict via `vars()`, falling back to `dict()` and `__dict__`.
+                fn to_dict<'py>(
+                    &'py self,
+                ) -> ::pyo3::PyResult<::pyo3::Bound<'py, ::pyo3::types::PyDict>> {
+                    let py = self.py();
+                    let builtins = py.import_bound(::pyo3::intern!(py, "builtins"))?;
+                    let dict = ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "vars"),
+                            (self.as_any(),),
+                        )
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::call_method1(
+                            builtins.as_any(),
+                            ::pyo3::intern!(py, "dict"),
+                            (self.as_any(),),
+                        ))
+                        .or_else(|_| ::pyo3::types::PyAnyMethods::getattr(
+                            self.as_any(),
+                            ::pyo3::intern!(py, "__dict__"),
+                        ))?;
+                    ::std::result::Result::Ok(
+                        ::pyo3::types::PyAnyMethods::downcast_into::<
+                            ::pyo3::types::PyDict,
+                        >(dict)?,
+                    )
+                }
+                /// Set the attributes of the object to the values of the given dict via `setattr()`.
+                fn update_from_dict<'py>(
+                    &'py self,
+                    p_dict: &::pyo3::Bound<'py, ::pyo3::types::PyDict>,
+                ) -> ::pyo3::PyResult<()> {
+                    for (name, value) in ::pyo3::types::PyDictMethods::iter(p_dict) {
+                        ::pyo3::types::PyAnyMethods::setattr(
+                            self.as_any(),
+                            ::pyo3::types::PyAnyMethods::downcast_into::<
+                                ::pyo3::types::PyString,
+                            >(name)?,
+                            value,
+                        )?;
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            pub fn VERSION(
+                py: ::pyo3::marker::Python<'_>,
+            ) -> ::pyo3::PyResult<::std::string::String> {
+                ::pyo3::types::PyAnyMethods::extract(
+                    &::pyo3::types::PyAnyMethods::getattr(
+                        py
+                            .import_bound(::pyo3::intern!(py, "mod_bindgen_class_var_final"))?
+                            .as_any(),
+                        ::pyo3::intern!(py, "VERSION"),
+                    )?,
+                )
+            }
+        }
+    }
+
+    run: |py| {
+        use mod_bindgen_class_var_final::{Settings, SettingsMethods};
+        mod_bindgen_class_var_final::pyo3_embed_python_source_code(py, false).unwrap();
+        assert_eq!(mod_bindgen_class_var_final::VERSION(py).unwrap(), "1.0");
+        assert_eq!(Settings::limit(py).unwrap(), None);
+        assert_eq!(Settings::ratio(py).unwrap(), 0.5);
+        let settings = Settings::new(py, "fixed", "initial").unwrap();
+        assert_eq!(settings.limit().unwrap(), None);
+        assert_eq!(settings.label().unwrap(), "fixed");
+        settings.set_name("renamed").unwrap();
+        assert_eq!(settings.name().unwrap(), "renamed");
+    }
+}
@@ -1,4 +1,13 @@
 fn main() {
     // Expose #[cfg] flags of pyo3
     pyo3_build_config::use_pyo3_cfgs();
+
+    // Expose the Python interpreter that is embedded into the procedural macros (part of the cache key)
+    let interpreter = pyo3_build_config::get();
+    println!(
+        "cargo:rustc-env=PYO3_BINDGEN_PYTHON={} {}",
+        interpreter.executable.as_deref().unwrap_or_default(),
+        interpreter.version
+    );
+    println!("cargo:rerun-if-env-changed=PYO3_PYTHON");
 }
@@ -5,9 +5,7 @@ mod utils;
 
 /// Procedural macro for generating Rust bindings to Python modules in-place.
 ///
-/// # Panics
-///
-/// Panics if the bindings cannot be generated.
+/// If the bindings cannot be generated, a compile error is emitted at the name of the module.
 ///
 /// # Examples
 ///
@@ -19,6 +17,14 @@ mod utils;
 /// pub use sys::*;
 /// ```
 ///
+/// Multiple modules can be imported in a single invocation.
+///
+/// ```
+/// # use pyo3_bindgen_macros::import_python;
+/// import_python!("os", "sys", "math");
+/// pub use math::pi;
+/// ```
+///
 /// For consistency, the top-level package is always included in the generated bindings.
 ///
 /// ```
@@ -34,10 +40,36 @@ mod utils;
 /// import_python!("os.path");
 /// pub use posixpath::*;
 /// ```
+///
+/// The code generation can be customized via optional `name = value` flags, which correspond to the boolean,
+/// integer and list fields of `Config` (e.g. `include_private = true`, `max_type_depth = 8` or
+/// `include_dunder = ["__len__"]`). As the macro is experimental, the generated bindings can also warn about it on
+/// each use (see `Config::emit_experimental_warnings`).
+///
+/// ```
+/// # use pyo3_bindgen_macros::import_python;
+/// import_python!("math", generate_docs = false, emit_experimental_warnings = true);
+/// ```
+///
+/// The generated bindings are cached across expansions (e.g. for `cargo check` and IDEs) until the source files of
+/// the module, the Python interpreter or the configuration change. Set `PYO3_BINDGEN_NO_CACHE=1` to disable the cache.
+///
+/// Modules that cannot be imported result in a compile error (see `tests/ui`).
 #[proc_macro]
 pub fn import_python(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the macro arguments
-    let parser::Args { module_name } = syn::parse_macro_input!(input as parser::Args);
+    let parser::Args { module_names, cfg } = syn::parse_macro_input!(input as parser::Args);
+    let module_name_values = module_names
+        .iter()
+        .map(syn::LitStr::value)
+        .collect::<Vec<_>>();
+
+    // Reuse the bindings of a previous expansion if neither the modules nor the configuration changed
+    let cache_path =
+        utils::is_cache_enabled().then(|| utils::cache_path(&module_name_values, &cfg));
+    if let Some(bindings) = cache_path.as_deref().and_then(utils::load_cached_bindings) {
+        return bindings.into();
+    }
 
     // On Unix systems, ensure that the symbols of the libpython shared library are loaded globally
     #[cfg(unix)]
@@ -47,15 +79,74 @@ pub fn import_python(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         );
     });
 
-    // Generate the bindings
-    pyo3_bindgen_engine::Codegen::default()
-        .module_name(&module_name)
-        .unwrap_or_else(|err| {
-            panic!("Failed to parse the content of '{module_name}' Python module:\n{err}")
-        })
-        .generate()
-        .unwrap_or_else(|err| {
-            panic!("Failed to generate bindings for '{module_name}' Python module:\n{err}")
-        })
-        .into()
+    match expand(&module_names, cfg) {
+        Ok(bindings) => {
+            if let Some(cache_path) = cache_path {
+                utils::save_cached_bindings(&cache_path, &module_name_values, &bindings);
+            }
+            bindings.into()
+        }
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Generate the bindings for Python modules, or an error at the name of the first module that fails to parse
+/// (the first module if the bindings cannot be generated).
+fn expand(
+    module_names: &[syn::LitStr],
+    cfg: pyo3_bindgen_engine::Config,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let codegen = module_names.iter().try_fold(
+        pyo3_bindgen_engine::Codegen::new(cfg),
+        |codegen, module_name| {
+            codegen.module_name(&module_name.value()).map_err(|err| {
+                syn::Error::new(
+                    module_name.span(),
+                    format!(
+                        "Failed to parse the content of '{}' Python module:\n{err}",
+                        module_name.value()
+                    ),
+                )
+            })
+        },
+    )?;
+    codegen.generate().map_err(|err| {
+        syn::Error::new(
+            module_names[0].span(),
+            format!(
+                "Failed to generate bindings for '{}' Python module:\n{err}",
+                module_names
+                    .iter()
+                    .map(syn::LitStr::value)
+                    .collect::<Vec<_>>()
+                    .join("', '")
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_nonexistent_module() {
+        let module_names: [syn::LitStr; 2] = [
+            syn::parse_quote!("math"),
+            syn::parse_quote!("module_that_does_not_exist"),
+        ];
+        let expanded = expand(&module_names, pyo3_bindgen_engine::Config::default())
+            .unwrap_err()
+            .into_compile_error();
+        let mac: syn::Macro = syn::parse2(expanded).unwrap();
+        let message: syn::LitStr = mac.parse_body().unwrap();
+        assert_eq!(
+            mac.path,
+            syn::parse_quote!(::core::compile_error),
+            "expected a `compile_error!` invocation"
+        );
+        assert!(message
+            .value()
+            .contains("Failed to parse the content of 'module_that_does_not_exist' Python module"));
+    }
 }
@@ -2,19 +2,215 @@
 
 use syn::{
     parse::{Parse, ParseStream, Result},
-    LitStr,
+    punctuated::Punctuated,
+    Ident, LitBool, LitInt, LitStr, Token,
 };
 
 /// Arguments for the `import_python` procedural macro.
 pub struct Args {
-    /// Name of the Python module for which to generate the bindings.
-    pub module_name: String,
+    /// Names of the Python modules for which to generate the bindings.
+    pub module_names: Vec<LitStr>,
+    /// Configuration of the code generation, which is customized via `name = value` flags.
+    pub cfg: pyo3_bindgen_engine::Config,
+}
+
+/// Assign the value of a flag to the field of the configuration with the same name.
+macro_rules! parse_flag {
+    ($cfg:ident, $flag:ident, $input:ident, { $($parse:ident => [$($field:ident),* $(,)?]),* $(,)? }) => {
+        match $flag.to_string().as_str() {
+            $($(stringify!($field) => $cfg.$field = $parse($input)?,)*)*
+            _ => {
+                return Err(syn::Error::new(
+                    $flag.span(),
+                    format!(
+                        "unknown flag `{}`, expected a boolean, integer, string or list field of `pyo3_bindgen::Config`",
+                        $flag
+                    ),
+                ))
+            }
+        }
+    };
 }
 
 impl Parse for Args {
     fn parse(input: ParseStream) -> Result<Self> {
-        // Python module name might contain dots, so it is parsed as a string literal
-        let module_name = input.parse::<LitStr>()?.value();
-        Ok(Args { module_name })
+        // Python module names might contain dots, so they are parsed as string literals
+        let mut module_names = vec![input.parse::<LitStr>()?];
+        while input.peek(Token![,]) && input.peek2(LitStr) {
+            input.parse::<Token![,]>()?;
+            module_names.push(input.parse::<LitStr>()?);
+        }
+
+        // Optional flags are passed as `name = value` pairs after the module names
+        let mut cfg = pyo3_bindgen_engine::Config::default();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let flag = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            parse_flag!(cfg, flag, input, {
+                parse_bool => [
+                    traverse_submodules,
+                    generate_preludes,
+                    generate_root_prelude,
+                    flatten_single_item_modules,
+                    generate_imports,
+                    generate_classes,
+                    generate_type_vars,
+                    generate_functions,
+                    generate_properties,
+                    generate_docs,
+                    preserve_default_repr_in_docs,
+                    default_helpers,
+                    emit_doctest_examples,
+                    generate_usage_examples,
+                    validate_annotated,
+                    runtime_signature_guards,
+                    module_handle_injection,
+                    inline_accessors,
+                    impl_comparisons,
+                    mapping_helpers,
+                    emit_len_method,
+                    dict_key_accessors,
+                    logging_helpers,
+                    emit_getattr_fallback,
+                    dict_helpers,
+                    emit_experimental_warnings,
+                    infallible_setters,
+                    emit_builder_for_constructors,
+                    emit_into_py_object,
+                    emit_send_wrappers,
+                    emit_shared_methods,
+                    thread_docs,
+                    callable_class_params_accept_closures,
+                    flexible_primitives,
+                    portable_property_types,
+                    include_private,
+                    include_underscore_attrs,
+                    include_name_mangled,
+                    platform_stubs,
+                    dynamic_module_attributes,
+                    generate_metadata_accessors,
+                    generate_dependencies,
+                    collect_all_types_from_stubs,
+                    fuzzy_type_resolution,
+                    suppress_python_stdout,
+                    suppress_python_stderr,
+                ],
+                parse_usize => [max_type_depth],
+                parse_optional_usize => [compact_leaf_modules, docstring_max_width],
+                parse_string => [constructor_name],
+                parse_strings => [skip_modules, blocklist_names, include_dunder],
+            });
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+
+        Ok(Args { module_names, cfg })
+    }
+}
+
+fn parse_bool(input: ParseStream) -> Result<bool> {
+    Ok(input.parse::<LitBool>()?.value())
+}
+
+fn parse_usize(input: ParseStream) -> Result<usize> {
+    input.parse::<LitInt>()?.base10_parse()
+}
+
+fn parse_optional_usize(input: ParseStream) -> Result<Option<usize>> {
+    parse_usize(input).map(Some)
+}
+
+fn parse_string(input: ParseStream) -> Result<String> {
+    Ok(input.parse::<LitStr>()?.value())
+}
+
+/// Parse a list of strings, e.g. `["a", "b"]`.
+fn parse_strings(input: ParseStream) -> Result<Vec<String>> {
+    let content;
+    syn::bracketed!(content in input);
+    Ok(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+        .iter()
+        .map(LitStr::value)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module_name() {
+        // Act
+        let args = syn::parse_str::<Args>(r#""os.path""#).unwrap();
+
+        // Assert
+        assert_eq!(module_names(&args), ["os.path"]);
+        assert_eq!(args.cfg, pyo3_bindgen_engine::Config::default());
+    }
+
+    #[test]
+    fn test_parse_module_names() {
+        // Act
+        let args = syn::parse_str::<Args>(r#""os", "sys", "math","#).unwrap();
+
+        // Assert
+        assert_eq!(module_names(&args), ["os", "sys", "math"]);
+        assert_eq!(args.cfg, pyo3_bindgen_engine::Config::default());
+    }
+
+    #[test]
+    fn test_parse_module_names_with_flags() {
+        // Act
+        let args = syn::parse_str::<Args>(r#""os", "sys", include_private = true"#).unwrap();
+
+        // Assert
+        assert_eq!(module_names(&args), ["os", "sys"]);
+        assert!(args.cfg.include_private);
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        // Act
+        let args = syn::parse_str::<Args>(
+            r#""numpy", include_private = true, generate_docs = false, max_type_depth = 1,
+            docstring_max_width = 80, include_dunder = ["__len__", "__iter__"], constructor_name = "create","#,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(module_names(&args), ["numpy"]);
+        assert_eq!(
+            args.cfg,
+            pyo3_bindgen_engine::Config::builder()
+                .include_private(true)
+                .generate_docs(false)
+                .max_type_depth(1)
+                .docstring_max_width(80)
+                .include_dunder(vec!["__len__".to_owned(), "__iter__".to_owned()])
+                .constructor_name("create")
+                .build()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "",
+            "numpy",
+            r#""numpy" include_private = true"#,
+            r#""numpy", unknown_flag = true"#,
+            r#""numpy", include_private = 1"#,
+            r#""numpy", max_type_depth = -1"#,
+            r#""numpy", include_dunder = "__len__""#,
+            r#""numpy", include_private = true, "sys""#,
+            r#""numpy" "sys""#,
+        ] {
+            assert!(syn::parse_str::<Args>(input).is_err(), "{input}");
+        }
+    }
+
+    fn module_names(args: &Args) -> Vec<String> {
+        args.module_names.iter().map(LitStr::value).collect()
     }
 }
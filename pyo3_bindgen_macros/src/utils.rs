@@ -1,5 +1,13 @@
-#[cfg(unix)]
 use pyo3::prelude::*;
+use rustc_hash::FxHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that disables the caching of generated bindings if set to `1`.
+const NO_CACHE_ENV: &str = "PYO3_BINDGEN_NO_CACHE";
+
+/// Header of the cache files (invalidated with each version of the procedural macros).
+const CACHE_FILE_HEADER: &str = concat!("pyo3_bindgen_macros ", env!("CARGO_PKG_VERSION"));
 
 /// Ensure that the symbols of the libpython shared library are loaded globally.
 ///
@@ -36,3 +44,291 @@ pub fn try_load_libpython_symbols() -> pyo3::PyResult<()> {
         Ok(())
     })
 }
+
+/// Determine whether the generated bindings are cached (opt-out via `PYO3_BINDGEN_NO_CACHE=1`).
+pub fn is_cache_enabled() -> bool {
+    !std::env::var(NO_CACHE_ENV).is_ok_and(|value| value == "1")
+}
+
+/// Get the path of the cache file for the bindings of Python modules generated with the given configuration.
+///
+/// The cache is stored under `CARGO_TARGET_DIR` if it is set, and in the temporary directory otherwise.
+pub fn cache_path(module_names: &[String], cfg: &pyo3_bindgen_engine::Config) -> PathBuf {
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(std::env::temp_dir, PathBuf::from)
+        .join("pyo3_bindgen_cache")
+        .join(cache_key(
+            &crate_name,
+            module_names,
+            env!("PYO3_BINDGEN_PYTHON"),
+            cfg,
+        ))
+}
+
+/// Compute the key under which the bindings of Python modules are cached, which identifies the consuming crate,
+/// the modules, the Python interpreter (executable and version) and the configuration.
+pub fn cache_key(
+    crate_name: &str,
+    module_names: &[String],
+    python: &str,
+    cfg: &pyo3_bindgen_engine::Config,
+) -> String {
+    let mut hasher = FxHasher::default();
+    (crate_name, module_names, python, cfg).hash(&mut hasher);
+    format!(
+        "{crate_name}-{}-{:016x}",
+        module_names.join("+"),
+        hasher.finish()
+    )
+}
+
+/// Load the cached bindings if the source files of the modules did not change since they were generated.
+pub fn load_cached_bindings(path: &Path) -> Option<proc_macro2::TokenStream> {
+    CachedBindings::load(path)
+        .filter(CachedBindings::is_fresh)
+        .and_then(|cached| cached.code.parse().ok())
+}
+
+/// Cache the bindings alongside the source files of the modules that were loaded during their generation.
+///
+/// Failures are only reported because the cache is an optimization.
+pub fn save_cached_bindings(
+    path: &Path,
+    module_names: &[String],
+    bindings: &proc_macro2::TokenStream,
+) {
+    let result = loaded_module_files(module_names)
+        .map_err(std::io::Error::other)
+        .and_then(|files| CachedBindings::new(files, bindings.to_string()).save(path));
+    if let Err(err) = result {
+        eprintln!(
+            "WARN: Failed to cache the bindings for '{}' Python module:\n{err}",
+            module_names.join("', '")
+        );
+    }
+}
+
+/// Get the source files of all loaded modules of the top-level packages that contain the given modules.
+fn loaded_module_files(module_names: &[String]) -> pyo3::PyResult<Vec<PathBuf>> {
+    let package_names = module_names
+        .iter()
+        .map(|module_name| module_name.split('.').next().unwrap_or(module_name))
+        .collect::<Vec<_>>();
+    pyo3::Python::with_gil(|py| {
+        let mut files = Vec::new();
+        let modules = py
+            .import_bound(pyo3::intern!(py, "sys"))?
+            .getattr(pyo3::intern!(py, "modules"))?
+            .call_method0(pyo3::intern!(py, "copy"))?;
+        for (name, module) in modules.downcast_into::<pyo3::types::PyDict>()? {
+            let name = name.to_string();
+            if !package_names.iter().any(|package_name| {
+                name == *package_name || name.starts_with(&format!("{package_name}."))
+            }) {
+                continue;
+            }
+            // Modules imported from zip archives are tracked via the archive because their `__file__` is not on
+            // the filesystem, while frozen modules might not have any `__file__` at all
+            let archive = module
+                .getattr(pyo3::intern!(py, "__loader__"))
+                .and_then(|loader| loader.getattr(pyo3::intern!(py, "archive")));
+            if let Ok(file) = archive.or_else(|_| module.getattr(pyo3::intern!(py, "__file__"))) {
+                if let Ok(file) = file.extract::<PathBuf>() {
+                    files.push(file);
+                }
+            }
+        }
+        files.sort_unstable();
+        files.dedup();
+        Ok(files)
+    })
+}
+
+/// Bindings generated in a previous expansion of the macro, alongside the modification times of the source files
+/// that they were generated from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CachedBindings {
+    /// Source files of the module and their modification times (in nanoseconds since the Unix epoch).
+    pub dependencies: Vec<(PathBuf, u128)>,
+    pub code: String,
+}
+
+impl CachedBindings {
+    /// Record the current modification times of the given source files alongside the code.
+    pub fn new(files: impl IntoIterator<Item = PathBuf>, code: String) -> Self {
+        let dependencies = files
+            .into_iter()
+            .filter_map(|file| modification_time(&file).map(|mtime| (file, mtime)))
+            .collect();
+        Self { dependencies, code }
+    }
+
+    /// Determine whether none of the source files changed since the bindings were generated.
+    pub fn is_fresh(&self) -> bool {
+        self.dependencies
+            .iter()
+            .all(|(file, mtime)| modification_time(file) == Some(*mtime))
+    }
+
+    /// Load the cached bindings from a file. Returns `None` if the file is missing or corrupt.
+    pub fn load(path: &Path) -> Option<Self> {
+        Self::parse(&std::fs::read_to_string(path).ok()?)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut content = format!("{CACHE_FILE_HEADER}\n");
+        for (file, mtime) in &self.dependencies {
+            content.push_str(&format!("file {mtime} {}\n", file.display()));
+        }
+        content.push_str("code\n");
+        content.push_str(&self.code);
+
+        // Concurrent expansions of the macro must not observe a partially written file
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension(format!("tmp{}", std::process::id()));
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(tmp_path, path)
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let (header, mut content) = content.split_once('\n')?;
+        if header != CACHE_FILE_HEADER {
+            return None;
+        }
+        let mut dependencies = Vec::new();
+        loop {
+            let (line, rest) = content.split_once('\n')?;
+            content = rest;
+            if line == "code" {
+                break;
+            }
+            let (mtime, file) = line.strip_prefix("file ")?.split_once(' ')?;
+            dependencies.push((PathBuf::from(file), mtime.parse().ok()?));
+        }
+        Some(Self {
+            dependencies,
+            code: content.to_owned(),
+        })
+    }
+}
+
+fn modification_time(file: &Path) -> Option<u128> {
+    std::fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key() {
+        // Arrange
+        let cfg = pyo3_bindgen_engine::Config::default();
+        let other_cfg = pyo3_bindgen_engine::Config::builder()
+            .emit_experimental_warnings(true)
+            .build();
+
+        // Act
+        let module_names = ["os.path".to_owned()];
+        let key = cache_key("my_crate", &module_names, "/usr/bin/python3 3.11", &cfg);
+
+        // Assert
+        assert!(key.starts_with("my_crate-os.path-"));
+        assert_eq!(
+            key,
+            cache_key("my_crate", &module_names, "/usr/bin/python3 3.11", &cfg)
+        );
+        for other_key in [
+            cache_key("other_crate", &module_names, "/usr/bin/python3 3.11", &cfg),
+            cache_key(
+                "my_crate",
+                &["os".to_owned()],
+                "/usr/bin/python3 3.11",
+                &cfg,
+            ),
+            cache_key(
+                "my_crate",
+                &["os.path".to_owned(), "sys".to_owned()],
+                "/usr/bin/python3 3.11",
+                &cfg,
+            ),
+            cache_key("my_crate", &module_names, "/usr/bin/python3 3.12", &cfg),
+            cache_key(
+                "my_crate",
+                &module_names,
+                "/usr/bin/python3 3.11",
+                &other_cfg,
+            ),
+        ] {
+            assert_ne!(key, other_key);
+        }
+    }
+
+    #[test]
+    fn test_cached_bindings_roundtrip() {
+        // Arrange
+        let dir = std::env::temp_dir().join(format!(
+            "pyo3_bindgen_test_cached_bindings_roundtrip_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source_file = dir.join("my module.py");
+        std::fs::write(&source_file, "x = 1\n").unwrap();
+        let cached = CachedBindings::new([source_file], "pub mod my_module {}".to_owned());
+        let path = dir.join("cache").join("my_module");
+
+        // Act
+        cached.save(&path).unwrap();
+        let loaded = CachedBindings::load(&path);
+
+        // Assert
+        assert_eq!(cached.dependencies.len(), 1);
+        assert!(cached.is_fresh());
+        assert_eq!(loaded, Some(cached));
+        assert_eq!(
+            load_cached_bindings(&path).map(|bindings| bindings.to_string()),
+            Some("pub mod my_module { }".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_cached_bindings_invalidation() {
+        // Arrange
+        let source_file = std::env::temp_dir().join(format!(
+            "pyo3_bindgen_test_cached_bindings_invalidation_{}.py",
+            std::process::id()
+        ));
+        std::fs::write(&source_file, "x = 1\n").unwrap();
+        let mut cached = CachedBindings::new([source_file.clone()], String::new());
+        assert!(cached.is_fresh());
+
+        // Act & Assert (modified source file)
+        cached.dependencies[0].1 += 1;
+        assert!(!cached.is_fresh());
+
+        // Act & Assert (removed source file)
+        cached.dependencies[0].1 -= 1;
+        std::fs::remove_file(&source_file).unwrap();
+        assert!(!cached.is_fresh());
+    }
+
+    #[test]
+    fn test_cached_bindings_corrupt() {
+        for content in [
+            "",
+            "pyo3_bindgen_macros 0.0.0\ncode\n",
+            &format!("{CACHE_FILE_HEADER}\n"),
+            &format!("{CACHE_FILE_HEADER}\nfile xyz a.py\ncode\n"),
+        ] {
+            assert_eq!(CachedBindings::parse(content), None, "{content}");
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Compile errors of the procedural macros, which are compared against the `.stderr` snapshots in `tests/ui`.
+
+#[test]
+fn test_import_python_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,3 @@
+pyo3_bindgen_macros::import_python!("module_that_does_not_exist");
+
+fn main() {}